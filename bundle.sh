@@ -13,6 +13,7 @@ cd "$SCRIPT_DIR"
 
 # Configuration
 BINARY_NAME="pthkd"
+CTL_BINARY_NAME="pthkdctl"
 APP_NAME="ProTools Hotkey Daemon"
 BUNDLE_NAME="pthkd.app"
 VERSION=$(awk '/\[package\]/ {flag=1} flag && /^version =/ {print $3; exit}' Cargo.toml | tr -d '"')
@@ -77,7 +78,7 @@ lipo -create -output "$BUILD_DIR/libPTHKDui.dylib" \
 echo "   ✓ Universal Swift library created"
 lipo -info "$BUILD_DIR/libPTHKDui.dylib"
 
-# Copy Swift dylib to Rust target dirs for linking
+# Copy Swift dylib next to the Rust binaries (loaded at runtime)
 echo ""
 echo "📋 Copying Swift library to Rust target directories..."
 mkdir -p target/aarch64-apple-darwin/release
@@ -106,6 +107,11 @@ lipo -create -output "$BUILD_DIR/$BINARY_NAME" \
 echo "   ✓ Universal Rust binary created"
 lipo -info "$BUILD_DIR/$BINARY_NAME"
 
+lipo -create -output "$BUILD_DIR/$CTL_BINARY_NAME" \
+    "target/aarch64-apple-darwin/release/$CTL_BINARY_NAME" \
+    "target/x86_64-apple-darwin/release/$CTL_BINARY_NAME"
+echo "   ✓ Universal $CTL_BINARY_NAME created"
+
 # Create .app bundle structure
 echo ""
 echo "📦 Creating .app bundle..."
@@ -117,7 +123,9 @@ mkdir -p "$APP_PATH/Contents/Resources"
 # Copy binary
 cp "$BUILD_DIR/$BINARY_NAME" "$APP_PATH/Contents/MacOS/$BINARY_NAME"
 chmod +x "$APP_PATH/Contents/MacOS/$BINARY_NAME"
-echo "   ✓ Copied Rust binary"
+cp "$BUILD_DIR/$CTL_BINARY_NAME" "$APP_PATH/Contents/MacOS/$CTL_BINARY_NAME"
+chmod +x "$APP_PATH/Contents/MacOS/$CTL_BINARY_NAME"
+echo "   ✓ Copied Rust binaries"
 
 # Copy Swift dylib to Frameworks
 cp "$BUILD_DIR/libPTHKDui.dylib" "$APP_PATH/Contents/Frameworks/libPTHKDui.dylib"
@@ -195,6 +203,11 @@ codesign --sign $CODESIGN_CERTIFICATE_ID \
     "$APP_PATH/Contents/MacOS/$BINARY_NAME"
 echo "   ✓ Signed main binary"
 
+codesign --sign $CODESIGN_CERTIFICATE_ID \
+    --force --options runtime \
+    "$APP_PATH/Contents/MacOS/$CTL_BINARY_NAME"
+echo "   ✓ Signed $CTL_BINARY_NAME"
+
 # Sign the entire app bundle
 codesign --sign $CODESIGN_CERTIFICATE_ID \
     --deep --force --options runtime \
@@ -0,0 +1,182 @@
+//! Hotkey usage statistics (`os.usage_report`)
+//!
+//! Every triggered binding's run count and run time are kept in usage.json
+//! next to config.toml, so the report can point out dead bindings and slow
+//! macros across restarts. Entries are keyed by trigger and action, so a
+//! rebound trigger starts a fresh entry.
+
+use crate::input::HOTKEYS;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How often changed stats are written to disk
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Stats by binding key (None until loaded from disk)
+static STATS: Mutex<Option<HashMap<String, Usage>>> = Mutex::new(None);
+
+/// True when STATS has changes not yet on disk
+static DIRTY: AtomicBool = AtomicBool::new(false);
+
+static SAVER: Once = Once::new();
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Usage {
+    pub count: u64,
+    pub failures: u64,
+    pub total_ms: u64,
+    pub max_ms: u64,
+    /// Unix seconds
+    pub last_used: u64,
+}
+
+impl Usage {
+    pub fn average_ms(&self) -> u64 {
+        self.total_ms / self.count.max(1)
+    }
+}
+
+/// The stats key for a binding, e.g. "cmd+f1 => pt.tracks_solo_selected"
+pub fn binding_key(trigger: &str, action_name: &str) -> String {
+    format!("{} => {}", trigger, action_name)
+}
+
+/// Count one run of a binding
+pub fn record(binding: &str, elapsed: Duration, ok: bool) {
+    let elapsed_ms = elapsed.as_millis() as u64;
+    with_stats(|stats| {
+        let usage = stats.entry(binding.to_string()).or_default();
+        usage.count += 1;
+        usage.failures += u64::from(!ok);
+        usage.total_ms += elapsed_ms;
+        usage.max_ms = usage.max_ms.max(elapsed_ms);
+        usage.last_used = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+    });
+    DIRTY.store(true, Ordering::SeqCst);
+
+    SAVER.call_once(|| {
+        std::thread::spawn(|| {
+            loop {
+                std::thread::sleep(SAVE_INTERVAL);
+                if DIRTY.swap(false, Ordering::SeqCst)
+                    && let Err(e) = save()
+                {
+                    log::warn!("Failed to save usage stats: {:#}", e);
+                }
+            }
+        });
+    });
+}
+
+/// Forget all stats
+pub fn reset() -> R<()> {
+    with_stats(HashMap::clear);
+    save()
+}
+
+/// Write the stats to usage.json
+pub fn save() -> R<()> {
+    let json = with_stats(|stats| serde_json::to_string_pretty(stats))?;
+    let path = stats_path()?;
+    std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Report lines: most/least used bindings, bindings never used, slowest actions
+pub fn report(top: usize) -> Vec<String> {
+    let bindings: Vec<String> = HOTKEYS.get().map_or_else(Vec::new, |table| {
+        table
+            .load()
+            .iter()
+            .map(|hk| binding_key(&hk.trigger.describe(), &hk.action_name))
+            .collect()
+    });
+    let stats = with_stats(|stats| stats.clone());
+
+    // Current bindings only - stats for removed bindings stay on disk but aren't shown
+    let mut used: Vec<(&String, &Usage)> = bindings
+        .iter()
+        .filter_map(|binding| stats.get_key_value(binding))
+        .collect();
+    used.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(a.0.cmp(b.0)));
+    used.dedup_by_key(|(binding, _)| *binding);
+    let mut unused: Vec<&String> = bindings
+        .iter()
+        .filter(|binding| !stats.contains_key(*binding))
+        .collect();
+    unused.sort();
+    unused.dedup();
+
+    let mut lines = vec![format!("Most used (of {} bindings):", bindings.len())];
+    lines.extend(
+        used.iter()
+            .take(top)
+            .map(|(binding, usage)| format!("  {:>6}×  {}", usage.count, binding)),
+    );
+
+    lines.push(String::new());
+    lines.push(format!("Never used ({}):", unused.len()));
+    lines.extend(unused.iter().map(|binding| format!("  {}", binding)));
+
+    lines.push(String::new());
+    lines.push("Least used:".to_string());
+    lines.extend(
+        used.iter()
+            .rev()
+            .take(top)
+            .map(|(binding, usage)| format!("  {:>6}×  {}", usage.count, binding)),
+    );
+
+    let mut slowest = used.clone();
+    slowest.sort_by_key(|(_, usage)| std::cmp::Reverse(usage.average_ms()));
+    lines.push(String::new());
+    lines.push("Slowest (average run time):".to_string());
+    lines.extend(slowest.iter().take(top).map(|(binding, usage)| {
+        format!(
+            "  {:>8} (max {}, {} failed)  {}",
+            format_ms(usage.average_ms()),
+            format_ms(usage.max_ms),
+            usage.failures,
+            binding
+        )
+    }));
+    lines
+}
+
+fn format_ms(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{} ms", ms)
+    } else {
+        format!("{:.1} s", ms as f64 / 1000.0)
+    }
+}
+
+/// Run `f` on the stats, loading them from disk on first use
+fn with_stats<T>(f: impl FnOnce(&mut HashMap<String, Usage>) -> T) -> T {
+    let mut stats = STATS.lock().unwrap();
+    f(stats.get_or_insert_with(load))
+}
+
+fn load() -> HashMap<String, Usage> {
+    let Ok(path) = stats_path() else {
+        return HashMap::new();
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            log::warn!("Ignoring unreadable {}: {}", path.display(), e);
+            HashMap::new()
+        }),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// usage.json, next to config.toml
+fn stats_path() -> R<PathBuf> {
+    Ok(crate::config::get_config_path()?.with_file_name("usage.json"))
+}
@@ -0,0 +1,30 @@
+//! pthkdctl - control a running pthkd from shell scripts and Stream Deck
+//!
+//! Sends the same requests as `pthkd <command>` over the daemon's socket:
+//!
+//! ```text
+//! pthkdctl run pt.tracks_solo_selected
+//! pthkdctl run os.keystroke 'keys=["cmd", "s"]'
+//! pthkdctl reload
+//! pthkdctl actions
+//! pthkdctl status          # one line, for tmux status bars
+//! pthkdctl status --json
+//! ```
+
+#[path = "../ipc/client.rs"]
+mod client;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || matches!(args[0].as_str(), "-h" | "--help" | "help") {
+        println!(
+            "pthkdctl - control the running pthkd daemon\n\n{}",
+            client::USAGE
+        );
+        return;
+    }
+    if let Err(e) = client::run_cli(&args) {
+        eprintln!("Error: {:#}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,139 @@
+//! Live feed of action results and log lines for remote clients
+//!
+//! WebSocket clients (and anything else that wants to watch the daemon)
+//! subscribe and receive every event published after that. With nobody
+//! subscribed, publishing costs an atomic load. The last action's outcome
+//! is kept either way, for `pthkd status`, along with the ones before it for
+//! support bundles.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::time::Instant;
+
+static SUBSCRIBERS: Mutex<Vec<Sender<Event>>> = Mutex::new(Vec::new());
+
+/// True while at least one subscriber is connected
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// How many finished actions are remembered
+const HISTORY_LEN: usize = 100;
+
+/// Recently finished actions, oldest first
+static HISTORY: Mutex<VecDeque<LastAction>> = Mutex::new(VecDeque::new());
+
+#[derive(Debug, Clone)]
+pub struct LastAction {
+    pub action: String,
+    pub error: Option<String>,
+    pub finished: Instant,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    /// An action finished (from a hotkey, IPC or the HTTP API)
+    Action {
+        action: String,
+        ok: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// A log line at info level or above (`time` in Unix seconds, as in the log file)
+    Log {
+        time: u64,
+        level: String,
+        message: String,
+    },
+}
+
+impl Event {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// A log event as the log file writes it (None for other events)
+    pub fn log_line(&self) -> Option<String> {
+        match self {
+            Event::Log {
+                time,
+                level,
+                message,
+            } => Some(format!("[{} {:5}] {}", time, level, message)),
+            _ => None,
+        }
+    }
+}
+
+/// Receive every event published from now on
+pub fn subscribe() -> Receiver<Event> {
+    let (sender, receiver) = channel();
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.push(sender);
+    ACTIVE.store(true, Ordering::Relaxed);
+    receiver
+}
+
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Send an event to every subscriber, dropping the ones that went away
+pub fn publish(event: Event) {
+    if !is_active() {
+        return;
+    }
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    ACTIVE.store(!subscribers.is_empty(), Ordering::Relaxed);
+}
+
+/// Publish an action's outcome (`error` = None on success)
+pub fn action_finished(action: &str, error: Option<String>) {
+    let mut history = HISTORY.lock().unwrap();
+    if history.len() == HISTORY_LEN {
+        history.pop_front();
+    }
+    history.push_back(LastAction {
+        action: action.to_string(),
+        error: error.clone(),
+        finished: Instant::now(),
+    });
+    drop(history);
+    publish(Event::Action {
+        action: action.to_string(),
+        ok: error.is_none(),
+        error,
+    });
+}
+
+/// The most recent action to finish, from any source
+pub fn last_action() -> Option<LastAction> {
+    HISTORY.lock().unwrap().back().cloned()
+}
+
+/// Recently finished actions, oldest first
+pub fn recent_actions() -> Vec<LastAction> {
+    HISTORY.lock().unwrap().iter().cloned().collect()
+}
+
+/// Publish a log record (called by the logger)
+pub fn log_record(record: &log::Record) {
+    // Our own socket libraries log while sending - don't feed those back in
+    if !is_active()
+        || record.level() > log::Level::Info
+        || record.target().starts_with("tungstenite")
+    {
+        return;
+    }
+    let time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    publish(Event::Log {
+        time,
+        level: record.level().to_string(),
+        message: record.args().to_string(),
+    });
+}
@@ -1,12 +1,20 @@
 pub mod params;
+pub mod roles;
+pub mod simulate;
+pub mod watch;
 pub use params::*;
+pub use roles::Role;
 
-use crate::input::{ChordPattern, Hotkey, TriggerPattern, key_name_to_codes};
+use crate::input::{
+    ChordPattern, FaderMapping, Hotkey, MidiMessageSpec, MidiPattern, TriggerPattern,
+    VelocityLayer, key_name_to_codes, wheel::WheelMapping,
+};
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Deserializer};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Deserialize either a single string or an array of strings into Vec<String>
 fn string_or_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
@@ -47,8 +55,14 @@ where
     deserializer.deserialize_any(StringOrVecVisitor)
 }
 
+/// Embedded configuration guide (comments only), the top of every generated config
+const CONFIG_GUIDE: &str = include_str!("guide.toml");
+
 /// Embedded default configuration
-const DEFAULT_CONFIG: &str = include_str!("default_config.toml");
+const DEFAULT_CONFIG: &str = concat!(
+    include_str!("guide.toml"),
+    include_str!("default_config.toml")
+);
 
 /// Get the Application Support directory path for config storage
 pub fn get_config_path() -> Result<PathBuf> {
@@ -60,16 +74,30 @@ pub fn get_config_path() -> Result<PathBuf> {
         .join("config.toml");
     Ok(path)
 }
-pub fn create_default_config(path: &Path) -> Result<()> {
+/// Get the daemon's log file path (~/Library/Logs/pthkd.log)
+pub fn get_log_path() -> Result<PathBuf> {
+    let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    Ok(PathBuf::from(home)
+        .join("Library")
+        .join("Logs")
+        .join("pthkd.log"))
+}
+
+/// Write a starter config: the generic default, or a role's bindings under the guide
+pub fn create_default_config(path: &Path, role: Option<Role>) -> Result<()> {
     if let Some(parent) = path.parent() {
-        log::warn!(
-            "Config file not found, creating from default at {}",
-            path.display()
-        );
         fs::create_dir_all(&parent).context("Failed to create Application Support directory")?;
 
-        fs::write(&path, DEFAULT_CONFIG).context("Failed to write default config file")?;
-        log::info!("Created default config at {}", path.display());
+        let contents = match role {
+            Some(role) => format!("{}{}", CONFIG_GUIDE, role.bindings()),
+            None => DEFAULT_CONFIG.to_string(),
+        };
+        fs::write(path, contents).context("Failed to write default config file")?;
+        log::info!(
+            "Created {} config at {}",
+            role.map_or("default", Role::name),
+            path.display()
+        );
     }
     Ok(())
 }
@@ -103,40 +131,530 @@ pub struct Config {
     pub hotkey: Vec<HotkeyConfig>,
     #[serde(default)]
     pub midi: Option<MidiConfig>,
+    #[serde(default)]
+    pub auto_save: Option<AutoSaveConfig>,
+    #[serde(default)]
+    pub review_mode: Option<ReviewModeConfig>,
+    #[serde(default)]
+    pub resource_meter: Option<ResourceMeterConfig>,
+    #[serde(default)]
+    pub notifications: Option<NotificationConfig>,
+    #[serde(default)]
+    pub busy_guard: Option<BusyGuardConfig>,
+    #[serde(default)]
+    pub menu_locale: Option<MenuLocaleConfig>,
+    #[serde(default)]
+    pub server: Option<ServerConfig>,
+    #[serde(default)]
+    pub osc: Option<OscConfig>,
+    #[serde(default)]
+    pub menubar: Option<MenubarConfig>,
+    #[serde(default)]
+    pub tally: Option<TallyConfig>,
+    #[serde(default)]
+    pub trigger: Option<TriggerConfig>,
+    #[serde(default)]
+    pub waits: Option<WaitsConfig>,
+    /// Hotkey groups by name (`[group.rx]`)
+    #[serde(default)]
+    pub group: HashMap<String, HotkeyGroupConfig>,
+    /// Click positions saved by `os.record_click`, by name
+    #[serde(default)]
+    pub clicks: HashMap<String, ClickConfig>,
+    /// Window layouts saved by `pt.window_layout_save`, by name
+    #[serde(default)]
+    pub layouts: HashMap<String, LayoutConfig>,
+    /// Directory `params_file` paths are relative to (None = config.toml's)
+    #[serde(skip)]
+    pub params_dir: Option<PathBuf>,
+}
+
+impl Config {
+    /// Every `params_file` the hotkeys reference, resolved
+    pub fn params_files(&self) -> Vec<PathBuf> {
+        let dir = self.resolved_params_dir();
+        self.hotkey
+            .iter()
+            .filter_map(|hk| hk.params_file.as_ref())
+            .map(|file| dir.join(file))
+            .collect()
+    }
+
+    fn resolved_params_dir(&self) -> PathBuf {
+        self.params_dir
+            .clone()
+            .or_else(|| get_config_path().ok()?.parent().map(Path::to_path_buf))
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct MidiConfig {
     #[serde(default = "default_true")]
     pub enabled: bool,
+    /// Output port for os.midi_send when the action doesn't name one (soft matched)
+    pub output: Option<String>,
+}
+
+/// Idle-aware auto-save settings (`[auto_save]` section)
+#[derive(Debug, Deserialize, Clone)]
+pub struct AutoSaveConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Seconds without keyboard/mouse input before a save is allowed
+    #[serde(default = "default_idle_seconds")]
+    pub idle_seconds: u64,
+    /// Minimum minutes between saves
+    #[serde(default = "default_interval_minutes")]
+    pub interval_minutes: u64,
+}
+
+/// Review mode settings (`[review_mode]` section)
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReviewModeConfig {
+    /// Start the daemon with review mode already on
+    #[serde(default)]
+    pub enabled: bool,
+    /// Hotkeys carrying any of these tags are ignored while review mode is on
+    #[serde(default = "crate::input::review::default_blocked_tags")]
+    pub block_tags: Vec<String>,
+}
+
+/// Menu bar CPU/memory readout during renders (`[resource_meter]` section)
+#[derive(Debug, Deserialize, Clone)]
+pub struct ResourceMeterConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How often the readout refreshes
+    #[serde(default = "default_meter_interval_ms")]
+    pub interval_ms: u64,
+}
+
+/// Notification policy (`[notifications]` section)
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotificationConfig {
+    /// Start the daemon with notifications muted
+    #[serde(default)]
+    pub quiet: bool,
+    /// Notifications within this window are coalesced into one summary (0 = off)
+    #[serde(default = "default_coalesce_ms")]
+    pub coalesce_ms: u64,
+    /// Shipped translation of the notification texts ("de", "es", "fr", "ja")
+    pub language: Option<String>,
+    /// Keep the emoji at the start of notifications
+    #[serde(default = "default_true")]
+    pub emoji: bool,
+    /// Notification texts by id (`[notifications.templates]`), over the shipped ones
+    #[serde(default)]
+    pub templates: HashMap<String, String>,
+}
+
+/// Hold Pro Tools actions while a progress dialog is up (`[busy_guard]` section)
+#[derive(Debug, Deserialize, Clone)]
+pub struct BusyGuardConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How long an action waits for the dialog to clear before it's refused
+    #[serde(default = "default_busy_wait_ms")]
+    pub wait_ms: u64,
+    /// Pro Tools window titles that mean it's busy (soft matched)
+    #[serde(default = "default_busy_windows")]
+    pub windows: Vec<String>,
+}
+
+/// Menu paths on localized apps (`[menu_locale]` section)
+#[derive(Debug, Deserialize, Clone)]
+pub struct MenuLocaleConfig {
+    /// Shipped translation map to start from ("de", "es", "fr", "ja")
+    pub language: Option<String>,
+    /// English => localized menu titles (override the shipped map)
+    #[serde(default)]
+    pub translations: HashMap<String, String>,
+    /// Match titles ignoring punctuation, and top-level menus by position
+    #[serde(default = "default_true")]
+    pub fuzzy: bool,
+}
+
+/// HTTP + WebSocket API (`[server]` section)
+#[derive(Debug, Deserialize, Clone)]
+pub struct ServerConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Address to listen on; "0.0.0.0" lets other machines (tablets) connect
+    #[serde(default = "default_server_bind")]
+    pub bind: String,
+    #[serde(default = "default_server_port")]
+    pub port: u16,
+    /// Shared secret clients must send (required when not bound to localhost)
+    pub token: Option<String>,
+    /// Browser origins allowed to call the API, e.g. "http://ipad.local:8080"
+    /// (requests without an Origin header, like curl or Companion, always pass)
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+}
+
+/// OSC input from control surfaces (`[osc]` section)
+#[derive(Debug, Deserialize, Clone)]
+pub struct OscConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Address to listen on; "0.0.0.0" lets tablets on the network send
+    #[serde(default = "default_server_bind")]
+    pub bind: String,
+    #[serde(default = "default_osc_port")]
+    pub port: u16,
+    /// Addresses under this prefix run the registry action they name
+    #[serde(default = "default_osc_prefix")]
+    pub prefix: String,
+    /// Actions the prefix may run, e.g. ["pt.tracks_solo_selected"]
+    /// (empty = no routing; os.shell_script is never routed)
+    #[serde(default)]
+    pub routes: Vec<String>,
+}
+
+/// Extra menu bar entries (`[menubar]` section)
+#[derive(Debug, Deserialize, Clone)]
+pub struct MenubarConfig {
+    #[serde(default)]
+    pub items: Vec<MenubarItemConfig>,
+}
+
+/// One `[[menubar.items]]` entry: a title that runs an action
+#[derive(Debug, Deserialize, Clone)]
+pub struct MenubarItemConfig {
+    pub title: String,
+    pub action: String,
+    #[serde(default)]
+    pub params: HashMap<String, toml::Value>,
+}
+
+/// Event triggers (`[trigger]` section): actions run when something happens
+/// rather than when a key is pressed
+#[derive(Debug, Deserialize, Clone)]
+pub struct TriggerConfig {
+    /// `[[trigger.app_launch]]` - run when an app starts
+    #[serde(default)]
+    pub app_launch: Vec<AppTriggerConfig>,
+    /// `[[trigger.app_quit]]` - run when an app quits (or crashes)
+    #[serde(default)]
+    pub app_quit: Vec<AppTriggerConfig>,
+    /// `[[trigger.window]]` - run when a window appears
+    #[serde(default)]
+    pub window: Vec<WindowTriggerConfig>,
+    /// `[[trigger.schedule]]` - run on an interval or at a time of day
+    #[serde(default)]
+    pub schedule: Vec<ScheduleTriggerConfig>,
+    /// How often apps are checked for new windows
+    #[serde(default = "default_window_poll_ms")]
+    pub window_poll_ms: u64,
+}
+
+/// One app launch/quit trigger
+#[derive(Debug, Deserialize, Clone)]
+pub struct AppTriggerConfig {
+    /// App name (fuzzy match, like `target_application`)
+    pub app: String,
+    pub action: String,
+    #[serde(default)]
+    pub params: HashMap<String, toml::Value>,
+    /// Wait this long before running, e.g. for Pro Tools to open its PTSL port
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+/// One window-appeared trigger
+#[derive(Debug, Deserialize, Clone)]
+pub struct WindowTriggerConfig {
+    /// App whose windows are watched (fuzzy match)
+    pub app: String,
+    /// Window title (fuzzy match)
+    pub title: String,
+    pub action: String,
+    #[serde(default)]
+    pub params: HashMap<String, toml::Value>,
+}
+
+/// One scheduled trigger: `every` (an interval) and/or `at` (times of day)
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScheduleTriggerConfig {
+    /// Interval such as "90s", "20m" or "1h30m"
+    pub every: Option<String>,
+    /// Local times of day, "HH:MM"
+    #[serde(default, deserialize_with = "string_or_vec")]
+    pub at: Vec<String>,
+    pub action: String,
+    #[serde(default)]
+    pub params: HashMap<String, toml::Value>,
+}
+
+/// Settle times after keystrokes, menu clicks and the like (`[waits]` section)
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct WaitsConfig {
+    /// Fixed multiplier for every wait (None = measure the machine at startup)
+    pub scale: Option<f64>,
+    /// Per-kind waits in ms before scaling, e.g. `menu = 80` (keystroke, menu,
+    /// click, focus, display, key_release)
+    #[serde(default)]
+    pub ms: HashMap<String, u64>,
+}
+
+/// Recording tally overlay (`[tally]` section)
+#[derive(Debug, Deserialize, Clone)]
+pub struct TallyConfig {
+    /// Light the tally automatically while Pro Tools records
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Display to light, 1 = the first screen (None = the main display)
+    pub display: Option<usize>,
+    /// "border" (a frame around the screen) or "corner" (a light in the top-right corner)
+    #[serde(default = "default_tally_style")]
+    pub style: String,
+    /// Border width or corner light size, in points
+    #[serde(default = "default_tally_size")]
+    pub size: f64,
+    /// "red", "orange", "yellow", "green" or "blue"
+    #[serde(default = "default_tally_color")]
+    pub color: String,
+    /// Also light while the transport's record button is armed
+    #[serde(default)]
+    pub armed: bool,
+    /// How often the transport is checked
+    #[serde(default = "default_tally_interval_ms")]
+    pub interval_ms: u64,
+}
+
+impl Default for BusyGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            wait_ms: default_busy_wait_ms(),
+            windows: default_busy_windows(),
+        }
+    }
+}
+
+impl Default for TallyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            display: None,
+            style: default_tally_style(),
+            size: default_tally_size(),
+            color: default_tally_color(),
+            armed: false,
+            interval_ms: default_tally_interval_ms(),
+        }
+    }
+}
+
+impl Default for OscConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bind: default_server_bind(),
+            port: default_osc_port(),
+            prefix: default_osc_prefix(),
+            routes: Vec::new(),
+        }
+    }
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_idle_seconds() -> u64 {
+    10
+}
+
+fn default_interval_minutes() -> u64 {
+    5
+}
+
+fn default_meter_interval_ms() -> u64 {
+    1000
+}
+
+fn default_busy_wait_ms() -> u64 {
+    5000
+}
+
+fn default_busy_windows() -> Vec<String> {
+    [
+        "Progress",
+        "Please Wait",
+        "Saving",
+        "Processing",
+        "Bouncing",
+        "Indexing",
+        "Rendering",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_tally_style() -> String {
+    "border".to_string()
+}
+
+fn default_tally_size() -> f64 {
+    12.0
+}
+
+fn default_tally_color() -> String {
+    "red".to_string()
+}
+
+fn default_tally_interval_ms() -> u64 {
+    250
+}
+
+fn default_window_poll_ms() -> u64 {
+    500
+}
+
+fn default_server_bind() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_server_port() -> u16 {
+    8420
+}
+
+fn default_osc_port() -> u16 {
+    8000
+}
+
+fn default_osc_prefix() -> String {
+    "/pthkd".to_string()
+}
+
+fn default_coalesce_ms() -> u64 {
+    crate::notify::DEFAULT_COALESCE_MS
+}
+
+/// A click position inside a window (`[clicks.<name>]`, written by `os.record_click`)
+#[derive(Debug, Deserialize, serde::Serialize, Clone)]
+pub struct ClickConfig {
+    pub app: String,
+    /// Window title, soft matched (None = the app's focused window)
+    pub window: Option<String>,
+    /// From the window's top-left corner, in points
+    pub x: f64,
+    pub y: f64,
+    /// The window's size when recorded
+    pub width: f64,
+    pub height: f64,
+    /// Scale the position with the window's size, for GUIs that stretch
+    #[serde(default)]
+    pub scale: bool,
+}
+
+/// A window layout (`[layouts.<name>]`, written by `pt.window_layout_save`)
+#[derive(Debug, Deserialize, serde::Serialize, Clone)]
+pub struct LayoutConfig {
+    /// Pro Tools Window Configuration recalled first, by number or name
+    pub configuration: Option<String>,
+    #[serde(default)]
+    pub windows: Vec<WindowPlacement>,
+}
+
+/// Where one window goes, in global points from the top-left of the main display
+#[derive(Debug, Deserialize, serde::Serialize, Clone)]
+pub struct WindowPlacement {
+    /// Window title, soft matched
+    pub window: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Defaults shared by the hotkeys that name the group (`group = "rx"`)
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct HotkeyGroupConfig {
+    /// Params each member gets unless it sets them itself (or in its `params_file`)
+    #[serde(default)]
+    pub default_params: HashMap<String, toml::Value>,
+    /// Tags added to each member's own
+    #[serde(default, deserialize_with = "string_or_vec")]
+    pub tags: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct HotkeyConfig {
     #[serde(default, deserialize_with = "string_or_vec")]
     pub keys: Vec<String>,
+    /// Key sequence: chords pressed one after another, e.g. [["ctrl", "k"], ["m"]]
+    #[serde(default)]
+    pub sequence: Vec<Vec<String>>,
+    /// Longest gap allowed between sequence steps
+    pub sequence_timeout_ms: Option<u64>,
     #[serde(default, deserialize_with = "string_or_vec")]
     pub midi: Vec<String>,
-    #[serde(default, deserialize_with = "string_or_vec")]
+    /// Only MIDI from these devices (soft matched), e.g. device = "X-Touch Mini"
+    #[serde(default, alias = "device", deserialize_with = "string_or_vec")]
     pub midi_device: Vec<String>,
+    #[serde(alias = "channel")]
     pub midi_channel: Option<u8>, // 1-16, None = all channels
+    /// Velocity ranges with their own action/params, e.g. [{ min = 100, action = "..." }]
+    #[serde(default)]
+    pub velocity_layers: Vec<toml::Table>,
+    /// Continuous CC: the value, scaled, becomes a param, e.g. { param = "number", min = 1, max = 9 }
+    pub fader: Option<toml::Table>,
+    /// Scroll while `keys` is held: the amount becomes a param, e.g. { param = "db", step = 0.5 }
+    pub wheel: Option<toml::Table>,
+    /// Keys that must be held for `midi` to fire, e.g. ["shift"]
+    #[serde(default, deserialize_with = "string_or_vec")]
+    pub held_keys: Vec<String>,
+    /// OSC address that triggers the action, e.g. "/pthkd/solo"
+    pub osc: Option<String>,
+    /// Param names for the OSC message's arguments, in order
+    #[serde(default, deserialize_with = "string_or_vec")]
+    pub osc_args: Vec<String>,
+    #[serde(default)]
     pub action: String,
+    /// Macro: several actions run in order instead of `action`
+    #[serde(default)]
+    pub actions: Vec<toml::Table>,
+    /// Stop a macro at the first failing step
+    #[serde(default = "default_true")]
+    pub abort_on_error: bool,
     #[serde(default)]
     pub params: HashMap<String, toml::Value>,
+    /// TOML file of params shared by several hotkeys, relative to config.toml;
+    /// `params` override its values
+    pub params_file: Option<String>,
+    /// `[group.<name>]` whose default params and tags this hotkey takes
+    pub group: Option<String>,
     #[serde(default)]
     pub trigger_on_release: bool,
+    /// Taps of the chord that run this binding (2 = double tap)
+    pub tap_count: Option<u8>,
+    /// Longest gap between taps
+    pub tap_window_ms: Option<u64>,
     #[serde(default)]
     pub notify: bool,
     #[serde(default)]
-    pub carbon: bool,
+    pub silent: bool,
     #[serde(default)]
+    pub restore_focus: bool,
+    #[serde(default)]
+    pub carbon: bool,
+    /// Let the key through while typing in a text field
+    #[serde(default, alias = "skip_in_text_fields")]
     pub check_for_text_field: bool,
+    #[serde(default)]
+    pub when_stopped: bool,
     #[serde(default, deserialize_with = "string_or_vec")]
+    pub tags: Vec<String>,
+    /// Layers the hotkey is active in, e.g. layer = "edit" (none = every layer)
+    #[serde(default, alias = "layers", deserialize_with = "string_or_vec")]
+    pub layer: Vec<String>,
+    #[serde(default, alias = "app", deserialize_with = "string_or_vec")]
     target_application: Vec<String>,
+    #[serde(alias = "window")]
     pub app_window: Option<String>,
 }
 
@@ -151,7 +669,11 @@ pub struct HotkeyConfig {
 pub fn load_config(_path: &str) -> Result<Config> {
     let path = get_config_path()?;
     if !path.exists() {
-        create_default_config(&path)?
+        log::warn!(
+            "Config file not found, creating from default at {}",
+            path.display()
+        );
+        create_default_config(&path, None)?
     }
     let contents = fs::read_to_string(path)?;
     Ok(toml::from_str::<Config>(&contents)?)
@@ -176,35 +698,210 @@ pub fn load_config(_path: &str) -> Result<Config> {
 /// Convert config hotkeys to runtime Hotkey structs
 /// Skips any hotkeys that fail to parse instead of failing entirely
 pub fn config_to_hotkeys(config: Config) -> Result<Vec<Hotkey>> {
+    let (hotkeys, skipped) = convert_hotkeys(config);
+    if !skipped.is_empty() {
+        log::warn!("Skipped {} invalid hotkey(s)", skipped.len());
+    }
+    Ok(hotkeys)
+}
+
+/// Convert config hotkeys, failing if any hotkey doesn't parse
+///
+/// Used by reload so a typo can't silently drop hotkeys from a working set.
+pub fn config_to_hotkeys_strict(config: Config) -> Result<Vec<Hotkey>> {
+    let (hotkeys, skipped) = convert_hotkeys(config);
+    match skipped.as_slice() {
+        [] => Ok(hotkeys),
+        [only] => bail!("Invalid {}", only),
+        [first, rest @ ..] => bail!("Invalid {} (and {} more)", first, rest.len()),
+    }
+}
+
+/// Convert config hotkeys, returning the parsed ones and a message per skipped one
+fn convert_hotkeys(config: Config) -> (Vec<Hotkey>, Vec<String>) {
     let mut hotkeys = Vec::new();
-    let mut skipped_count = 0;
+    let mut skipped = Vec::new();
+    let params_dir = config.resolved_params_dir();
+    let groups = config.group;
+
+    for mut hk_config in config.hotkey {
+        // Shared params go under the hotkey's own
+        if let Some(file) = &hk_config.params_file {
+            match load_params_file(&params_dir.join(file)) {
+                Ok(params) => {
+                    for (name, value) in params {
+                        hk_config.params.entry(name).or_insert(value);
+                    }
+                }
+                Err(e) => {
+                    skipped.push(format!("hotkey '{}': {:#}", hk_config.action, e));
+                    log::error!("Skipping {}", skipped.last().unwrap());
+                    continue;
+                }
+            }
+        }
+
+        // Group defaults go under both
+        if let Some(name) = &hk_config.group {
+            let Some(group) = groups.get(name) else {
+                skipped.push(format!(
+                    "hotkey '{}': unknown group '{}'",
+                    hk_config.action, name
+                ));
+                log::error!("Skipping {}", skipped.last().unwrap());
+                continue;
+            };
+            for (name, value) in &group.default_params {
+                hk_config
+                    .params
+                    .entry(name.clone())
+                    .or_insert_with(|| value.clone());
+            }
+            hk_config.tags = crate::input::tags::merge_tags(&group.tags, &hk_config.tags);
+        }
 
-    for hk_config in config.hotkey {
         // Look up the action function first (handles namespaces)
-        let action = match get_action(&hk_config.action) {
-            Some(action) => action,
-            None => {
-                log::error!("Skipping hotkey '{}': unknown action", hk_config.action);
-                skipped_count += 1;
+        let (action, implicit) = if hk_config.actions.is_empty() {
+            match get_action(&hk_config.action) {
+                Some(action) => (action, implicit_tags(&hk_config.action)),
+                None => {
+                    skipped.push(format!("hotkey '{}': unknown action", hk_config.action));
+                    log::error!("Skipping {}", skipped.last().unwrap());
+                    continue;
+                }
+            }
+        } else {
+            match macro_action(&mut hk_config) {
+                Ok(found) => found,
+                Err(e) => {
+                    skipped.push(format!("macro hotkey: {:#}", e));
+                    log::error!("Skipping {}", skipped.last().unwrap());
+                    continue;
+                }
+            }
+        };
+
+        if let Err(e) = check_params(&hk_config.action, &hk_config.params) {
+            skipped.push(format!("hotkey '{}': {:#}", hk_config.action, e));
+            log::error!("Skipping {}", skipped.last().unwrap());
+            continue;
+        }
+
+        // Parse trigger patterns (`sequence` is the multi-step form of `keys`)
+        let keys = describe_keys(&hk_config);
+        let midi = hk_config.midi.clone();
+        let velocity_layers = match parse_velocity_layers(&hk_config) {
+            Ok(layers) => layers,
+            Err(e) => {
+                skipped.push(format!("hotkey '{}': {:#}", hk_config.action, e));
+                log::error!("Skipping {}", skipped.last().unwrap());
+                continue;
+            }
+        };
+
+        // A layer can run another action, so the hotkey carries its tags too
+        let implicit = velocity_layers.iter().fold(implicit, |tags, layer| {
+            crate::input::tags::merge_tags(&tags, &implicit_tags(&layer.action_name))
+        });
+        let tags = crate::input::tags::merge_tags(&implicit, &hk_config.tags);
+        let (tap_count, tap_window) = match parse_taps(&hk_config) {
+            Ok(taps) => taps,
+            Err(e) => {
+                skipped.push(format!("hotkey '{}': {:#}", hk_config.action, e));
+                log::error!("Skipping {}", skipped.last().unwrap());
+                continue;
+            }
+        };
+        let fader = match parse_fader(&hk_config) {
+            Ok(fader) => fader,
+            Err(e) => {
+                skipped.push(format!("hotkey '{}': {:#}", hk_config.action, e));
+                log::error!("Skipping {}", skipped.last().unwrap());
                 continue;
             }
         };
+        let wheel = match parse_wheel(&hk_config) {
+            Ok(wheel) => wheel,
+            Err(e) => {
+                skipped.push(format!("hotkey '{}': {:#}", hk_config.action, e));
+                log::error!("Skipping {}", skipped.last().unwrap());
+                continue;
+            }
+        };
+        if !hk_config.held_keys.is_empty() && (midi.is_empty() || !keys.is_empty()) {
+            skipped.push(format!(
+                "hotkey '{}': 'held_keys' goes with 'midi' alone (no 'keys' or 'sequence')",
+                hk_config.action
+            ));
+            log::error!("Skipping {}", skipped.last().unwrap());
+            continue;
+        }
+        if let Some(channel) = hk_config.midi_channel
+            && !(1..=16).contains(&channel)
+        {
+            skipped.push(format!(
+                "hotkey '{}': MIDI channel must be 1-16, got {}",
+                hk_config.action, channel
+            ));
+            log::error!("Skipping {}", skipped.last().unwrap());
+            continue;
+        }
 
-        // Parse trigger patterns
-        match (&hk_config.keys[..], &hk_config.midi[..]) {
+        // OSC: its own hotkey, next to any keyboard/MIDI ones
+        if let Some(address) = &hk_config.osc {
+            if !address.starts_with('/') {
+                skipped.push(format!(
+                    "hotkey '{}': OSC address '{}' must start with '/'",
+                    hk_config.action, address
+                ));
+                log::error!("Skipping {}", skipped.last().unwrap());
+                continue;
+            }
+            hotkeys.push(Hotkey {
+                trigger: TriggerPattern::Osc {
+                    address: address.clone(),
+                    args: hk_config.osc_args.clone(),
+                },
+                action_name: hk_config.action.clone(),
+                action,
+                params: Params::new(hk_config.params.clone()),
+                trigger_on_release: false,
+                notify: hk_config.notify,
+                silent: hk_config.silent,
+                restore_focus: hk_config.restore_focus,
+                carbon: false,
+                check_for_text_field: hk_config.check_for_text_field,
+                application: (!hk_config.target_application.is_empty())
+                    .then(|| hk_config.target_application.clone()),
+                app_window: hk_config.app_window.clone(),
+                midi_device: None,
+                midi_channel: None,
+                velocity_layers: Vec::new(),
+                fader: None,
+                wheel: None,
+                when_stopped: hk_config.when_stopped,
+                tags: tags.clone(),
+                layers: hk_config.layer.clone(),
+                tap_count: 1,
+                tap_window: Duration::ZERO,
+            });
+            if keys.is_empty() && midi.is_empty() {
+                continue;
+            }
+        }
+
+        match (!keys.is_empty(), !midi.is_empty()) {
             // Hybrid: both keys AND midi - create TWO separate hotkeys
-            (keys, midi) if !keys.is_empty() && !midi.is_empty() => {
-                // Parse keyboard chord
-                let keyboard_trigger = match parse_chord(keys) {
+            (true, true) => {
+                // Parse keyboard chord or sequence
+                let keyboard_trigger = match parse_keyboard(&hk_config) {
                     Ok(chord) => TriggerPattern::Keyboard(chord),
                     Err(e) => {
-                        log::error!(
-                            "Skipping keyboard part of hotkey '{}' with keys {:?}: {:#}",
-                            hk_config.action,
-                            keys,
-                            e
-                        );
-                        skipped_count += 1;
+                        skipped.push(format!(
+                            "keyboard part of hotkey '{}' with keys {}: {:#}",
+                            hk_config.action, keys, e
+                        ));
+                        log::error!("Skipping {}", skipped.last().unwrap());
                         continue;
                     }
                 };
@@ -213,13 +910,11 @@ pub fn config_to_hotkeys(config: Config) -> Result<Vec<Hotkey>> {
                 let midi_trigger = match crate::input::midi::parse_midi_pattern(midi.to_vec()) {
                     Ok(pattern) => TriggerPattern::Midi(pattern),
                     Err(e) => {
-                        log::error!(
-                            "Skipping MIDI part of hotkey '{}' with MIDI {:?}: {:#}",
-                            hk_config.action,
-                            midi,
-                            e
-                        );
-                        skipped_count += 1;
+                        skipped.push(format!(
+                            "MIDI part of hotkey '{}' with MIDI {:?}: {:#}",
+                            hk_config.action, midi, e
+                        ));
+                        log::error!("Skipping {}", skipped.last().unwrap());
                         continue;
                     }
                 };
@@ -238,12 +933,22 @@ pub fn config_to_hotkeys(config: Config) -> Result<Vec<Hotkey>> {
                     params: Params::new(hk_config.params.clone()),
                     trigger_on_release: hk_config.trigger_on_release,
                     notify: hk_config.notify,
+                    silent: hk_config.silent,
+                    restore_focus: hk_config.restore_focus,
                     carbon: hk_config.carbon,
                     check_for_text_field: hk_config.check_for_text_field,
                     application: app.clone(),
                     app_window: hk_config.app_window.clone(),
                     midi_device: None, // Keyboard hotkey - no MIDI filtering
                     midi_channel: None,
+                    velocity_layers: Vec::new(),
+                    fader: None,
+                    wheel: None,
+                    when_stopped: hk_config.when_stopped,
+                    tags: tags.clone(),
+                    layers: hk_config.layer.clone(),
+                    tap_count,
+                    tap_window,
                 });
 
                 // Create MIDI hotkey
@@ -260,23 +965,33 @@ pub fn config_to_hotkeys(config: Config) -> Result<Vec<Hotkey>> {
                     params: Params::new(hk_config.params),
                     trigger_on_release: hk_config.trigger_on_release,
                     notify: hk_config.notify,
+                    silent: hk_config.silent,
+                    restore_focus: hk_config.restore_focus,
                     carbon: false, // MIDI hotkeys cannot be Carbon hotkeys
                     check_for_text_field: hk_config.check_for_text_field,
                     application: app,
                     app_window: hk_config.app_window.clone(),
                     midi_device: midi_dev,
                     midi_channel: hk_config.midi_channel,
+                    velocity_layers: velocity_layers.clone(),
+                    fader: fader.clone(),
+                    wheel: None,
+                    when_stopped: hk_config.when_stopped,
+                    tags: tags.clone(),
+                    layers: hk_config.layer.clone(),
+                    tap_count: 1,
+                    tap_window: Duration::ZERO,
                 });
 
                 log::info!("Created keyboard + MIDI hotkeys for '{}'", hk_config.action);
             }
-            // Keyboard-only hotkey (keys provided, no MIDI)
-            (keys, midi) if !keys.is_empty() && midi.is_empty() => {
-                let trigger = match parse_chord(keys) {
+            // Keyboard-only hotkey (keys or sequence provided, no MIDI)
+            (true, false) => {
+                let trigger = match parse_keyboard(&hk_config) {
                     Ok(chord) => TriggerPattern::Keyboard(chord),
                     Err(e) => {
-                        log::error!("Skipping hotkey with keys {:?}: {:#}", keys, e);
-                        skipped_count += 1;
+                        skipped.push(format!("hotkey with keys {}: {:#}", keys, e));
+                        log::error!("Skipping {}", skipped.last().unwrap());
                         continue;
                     }
                 };
@@ -294,21 +1009,31 @@ pub fn config_to_hotkeys(config: Config) -> Result<Vec<Hotkey>> {
                     params: Params::new(hk_config.params),
                     trigger_on_release: hk_config.trigger_on_release,
                     notify: hk_config.notify,
+                    silent: hk_config.silent,
+                    restore_focus: hk_config.restore_focus,
                     carbon: hk_config.carbon,
                     check_for_text_field: hk_config.check_for_text_field,
                     application: app,
                     app_window: hk_config.app_window,
                     midi_device: None, // Keyboard hotkey - no MIDI filtering
                     midi_channel: None,
+                    velocity_layers: Vec::new(),
+                    fader: None,
+                    wheel,
+                    when_stopped: hk_config.when_stopped,
+                    tags: tags.clone(),
+                    layers: hk_config.layer.clone(),
+                    tap_count,
+                    tap_window,
                 });
             }
             // MIDI-only hotkey (MIDI provided, no keys or empty keys)
-            (keys, midi) if keys.is_empty() && !midi.is_empty() => {
-                let trigger = match crate::input::midi::parse_midi_pattern(midi.to_vec()) {
-                    Ok(pattern) => TriggerPattern::Midi(pattern),
+            (false, true) => {
+                let trigger = match parse_midi(&hk_config) {
+                    Ok(trigger) => trigger,
                     Err(e) => {
-                        log::error!("Skipping hotkey with MIDI {:?}: {:#}", midi, e);
-                        skipped_count += 1;
+                        skipped.push(format!("hotkey with MIDI {:?}: {:#}", midi, e));
+                        log::error!("Skipping {}", skipped.last().unwrap());
                         continue;
                     }
                 };
@@ -332,31 +1057,225 @@ pub fn config_to_hotkeys(config: Config) -> Result<Vec<Hotkey>> {
                     params: Params::new(hk_config.params),
                     trigger_on_release: hk_config.trigger_on_release,
                     notify: hk_config.notify,
+                    silent: hk_config.silent,
+                    restore_focus: hk_config.restore_focus,
                     carbon: false, // MIDI hotkeys cannot be Carbon hotkeys
                     check_for_text_field: hk_config.check_for_text_field,
                     application: app,
                     app_window: hk_config.app_window,
                     midi_device: midi_dev,
                     midi_channel: hk_config.midi_channel,
+                    velocity_layers: velocity_layers.clone(),
+                    fader: fader.clone(),
+                    wheel: None,
+                    when_stopped: hk_config.when_stopped,
+                    tags: tags.clone(),
+                    layers: hk_config.layer.clone(),
+                    tap_count: 1,
+                    tap_window: Duration::ZERO,
                 });
             }
             // Invalid: neither keys nor MIDI
             _ => {
-                log::error!(
-                    "Skipping hotkey '{}': must specify 'keys', 'midi', or both",
+                skipped.push(format!(
+                    "hotkey '{}': must specify 'keys' (or 'sequence'), 'midi', or 'osc'",
                     hk_config.action
-                );
-                skipped_count += 1;
+                ));
+                log::error!("Skipping {}", skipped.last().unwrap());
                 continue;
             }
         };
     }
 
-    if skipped_count > 0 {
-        log::warn!("Skipped {} invalid hotkey(s)", skipped_count);
+    (hotkeys, skipped)
+}
+
+// ============================================================================
+// Single Bindings (`pthkd bind`)
+// ============================================================================
+
+/// Build a `[[hotkey]]` table for a keyboard binding
+pub fn binding_table(
+    keys: &[String],
+    action: &str,
+    params: HashMap<String, toml::Value>,
+) -> toml::Table {
+    let mut table = toml::Table::new();
+    table.insert(
+        "keys".to_string(),
+        toml::Value::Array(keys.iter().cloned().map(toml::Value::String).collect()),
+    );
+    table.insert(
+        "action".to_string(),
+        toml::Value::String(action.to_string()),
+    );
+    if !params.is_empty() {
+        table.insert(
+            "params".to_string(),
+            toml::Value::Table(params.into_iter().collect()),
+        );
     }
+    table
+}
 
-    Ok(hotkeys)
+/// Convert a single binding table into a runtime hotkey, failing if it's invalid
+pub fn binding_to_hotkey(binding: &toml::Table) -> Result<Hotkey> {
+    let hk_config: HotkeyConfig = toml::Value::Table(binding.clone())
+        .try_into()
+        .context("Invalid binding")?;
+    let config = Config {
+        hotkey: vec![hk_config],
+        midi: None,
+        auto_save: None,
+        review_mode: None,
+        resource_meter: None,
+        notifications: None,
+        busy_guard: None,
+        menu_locale: None,
+        server: None,
+        osc: None,
+        menubar: None,
+        tally: None,
+        trigger: None,
+        waits: None,
+        group: HashMap::new(),
+        clicks: HashMap::new(),
+        layouts: HashMap::new(),
+        params_dir: None,
+    };
+    config_to_hotkeys_strict(config)?
+        .into_iter()
+        .next()
+        .context("Binding produced no hotkey")
+}
+
+/// True if two chords use the same keys, regardless of the order they were written in
+///
+/// Sequences are equal if every step is.
+pub fn same_chord(a: &ChordPattern, b: &ChordPattern) -> bool {
+    fn sorted(key_groups: &[Vec<u16>]) -> Vec<Vec<u16>> {
+        let mut groups: Vec<Vec<u16>> = key_groups
+            .iter()
+            .map(|group| {
+                let mut group = group.clone();
+                group.sort_unstable();
+                group
+            })
+            .collect();
+        groups.sort();
+        groups
+    }
+    match (a, b) {
+        (
+            ChordPattern::Simultaneous { key_groups: a },
+            ChordPattern::Simultaneous { key_groups: b },
+        ) => sorted(a) == sorted(b),
+        (ChordPattern::Sequence { steps: a, .. }, ChordPattern::Sequence { steps: b, .. }) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_chord(a, b))
+        }
+        _ => false,
+    }
+}
+
+/// Append a binding to config.toml as a new `[[hotkey]]` entry
+///
+/// Refuses if the file already binds the same keys - two entries for one
+/// chord would be ambiguous on the next load, so that one is edited by hand.
+pub fn append_binding(binding: &toml::Table) -> Result<()> {
+    use std::io::Write;
+
+    let new_hotkey = binding_to_hotkey(binding)?;
+    let TriggerPattern::Keyboard(new_chord) = &new_hotkey.trigger else {
+        bail!("Only keyboard bindings can be saved");
+    };
+
+    let config = load_config("config.toml")?;
+    for hk_config in config.hotkey {
+        if let Ok(chord) = parse_keyboard(&hk_config)
+            && same_chord(&chord, new_chord)
+        {
+            bail!(
+                "{} is already bound to '{}' in config.toml - edit it there",
+                new_chord.describe(),
+                hk_config.action
+            );
+        }
+    }
+
+    let mut doc = toml::Table::new();
+    doc.insert(
+        "hotkey".to_string(),
+        toml::Value::Array(vec![toml::Value::Table(binding.clone())]),
+    );
+    let entry = toml::to_string(&doc).context("Failed to serialize binding")?;
+
+    let path = get_config_path()?;
+    let mut file = fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    write!(file, "\n{}", entry)?;
+    log::info!(
+        "Saved binding {} to {}",
+        new_chord.describe(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Append a recorded click to config.toml as a `[clicks.<name>]` table
+///
+/// Refuses a name that's already there, as `append_binding` does for keys.
+pub fn append_click(name: &str, click: &ClickConfig) -> Result<()> {
+    let config = load_config("config.toml")?;
+    if config.clicks.contains_key(name) {
+        bail!(
+            "A click named '{}' is already in config.toml - remove it there first",
+            name
+        );
+    }
+    append_named_table("clicks", name, click)?;
+    log::info!("Saved click '{}'", name);
+    Ok(())
+}
+
+/// Append a saved window layout to config.toml as a `[layouts.<name>]` table
+///
+/// Refuses a name that's already there, like `append_click`.
+pub fn append_layout(name: &str, layout: &LayoutConfig) -> Result<()> {
+    let config = load_config("config.toml")?;
+    if config.layouts.contains_key(name) {
+        bail!(
+            "A layout named '{}' is already in config.toml - remove it there first",
+            name
+        );
+    }
+    append_named_table("layouts", name, layout)?;
+    log::info!("Saved layout '{}'", name);
+    Ok(())
+}
+
+/// Append `value` to config.toml as `[<section>.<name>]`
+fn append_named_table(section: &str, name: &str, value: &impl serde::Serialize) -> Result<()> {
+    use std::io::Write;
+
+    let mut tables = toml::Table::new();
+    tables.insert(
+        name.to_string(),
+        toml::Value::try_from(value).with_context(|| format!("Failed to serialize {}", name))?,
+    );
+    let mut doc = toml::Table::new();
+    doc.insert(section.to_string(), toml::Value::Table(tables));
+    let entry = toml::to_string(&doc).with_context(|| format!("Failed to serialize {}", name))?;
+
+    let path = get_config_path()?;
+    let mut file = fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    write!(file, "\n{}", entry)?;
+    log::info!("Appended [{}.{}] to {}", section, name, path.display());
+    Ok(())
 }
 
 /// Parse a list of key names into a ChordPattern
@@ -385,7 +1304,230 @@ fn parse_chord(key_names: &[String]) -> Result<ChordPattern> {
     Ok(ChordPattern::Simultaneous { key_groups })
 }
 
+/// Parse the keyboard trigger: `keys` (one chord) or `sequence` (chords in turn)
+fn parse_keyboard(hk_config: &HotkeyConfig) -> Result<ChordPattern> {
+    match (hk_config.keys.is_empty(), hk_config.sequence.is_empty()) {
+        (false, false) => bail!("use either 'keys' or 'sequence', not both"),
+        (false, true) => parse_chord(&hk_config.keys),
+        (true, false) => parse_sequence(
+            &hk_config.sequence,
+            hk_config
+                .sequence_timeout_ms
+                .unwrap_or(crate::input::sequence::DEFAULT_TIMEOUT_MS),
+        ),
+        (true, true) => bail!("no keys given"),
+    }
+}
+
+/// Parse a list of chords into a Sequence pattern
+///
+/// [["ctrl", "k"], ["m"]] becomes Sequence { steps: [ctrl+k, m] }
+fn parse_sequence(steps: &[Vec<String>], timeout_ms: u64) -> Result<ChordPattern> {
+    if steps.len() < 2 {
+        bail!("A sequence needs at least two steps (use 'keys' for a single chord)");
+    }
+    let steps = steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| parse_chord(step).with_context(|| format!("sequence step {}", i + 1)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ChordPattern::Sequence { steps, timeout_ms })
+}
+
+/// Parse the MIDI trigger, combined with `held_keys` if given
+fn parse_midi(hk_config: &HotkeyConfig) -> Result<TriggerPattern> {
+    let midi = crate::input::midi::parse_midi_pattern(hk_config.midi.clone())?;
+    if hk_config.held_keys.is_empty() {
+        return Ok(TriggerPattern::Midi(midi));
+    }
+    let keyboard = parse_chord(&hk_config.held_keys).context("held_keys")?;
+    Ok(TriggerPattern::Hybrid { keyboard, midi })
+}
+
+/// Parse `velocity_layers`, checking every action exists
+fn parse_velocity_layers(hk_config: &HotkeyConfig) -> Result<Vec<VelocityLayer>> {
+    if hk_config.velocity_layers.is_empty() {
+        return Ok(Vec::new());
+    }
+    if hk_config.midi.is_empty() {
+        bail!("'velocity_layers' needs a 'midi' trigger");
+    }
+    hk_config
+        .velocity_layers
+        .iter()
+        .enumerate()
+        .map(|(i, table)| {
+            parse_velocity_layer(hk_config, table)
+                .with_context(|| format!("velocity layer {}", i + 1))
+        })
+        .collect()
+}
+
+/// Read a `params_file`: a TOML table of params
+fn load_params_file(path: &Path) -> Result<toml::Table> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read params_file {}", path.display()))?;
+    toml::from_str(&contents).with_context(|| format!("Invalid params_file {}", path.display()))
+}
+
+/// Parse `tap_count` and `tap_window_ms` (multi-tap goes with a single `keys` chord)
+fn parse_taps(hk_config: &HotkeyConfig) -> Result<(u8, Duration)> {
+    let window = Duration::from_millis(
+        hk_config
+            .tap_window_ms
+            .unwrap_or(crate::input::taps::DEFAULT_WINDOW_MS),
+    );
+    let count = hk_config.tap_count.unwrap_or(1);
+    if count == 0 {
+        bail!("'tap_count' must be at least 1");
+    }
+    if count > 1 {
+        if hk_config.keys.is_empty() {
+            bail!("'tap_count' needs a 'keys' chord");
+        }
+        if hk_config.carbon || hk_config.trigger_on_release {
+            bail!("'tap_count' can't be combined with 'carbon' or 'trigger_on_release'");
+        }
+    }
+    Ok((count, window))
+}
+
+/// Parse `fader`, which needs `midi` to be a single CC
+fn parse_fader(hk_config: &HotkeyConfig) -> Result<Option<FaderMapping>> {
+    let Some(table) = &hk_config.fader else {
+        return Ok(None);
+    };
+    let single_cc = match crate::input::midi::parse_midi_pattern(hk_config.midi.clone()) {
+        Ok(MidiPattern::Simultaneous { messages }) => {
+            matches!(messages.as_slice(), [MidiMessageSpec::ControlChange { .. }])
+        }
+        Err(_) => false,
+    };
+    if !single_cc {
+        bail!("'fader' needs 'midi' to be a single CC, e.g. midi = \"cc7\"");
+    }
+    if !hk_config.velocity_layers.is_empty() {
+        bail!("'fader' and 'velocity_layers' can't be combined");
+    }
+    FaderMapping::from_table(table).map(Some).context("fader")
+}
+
+/// Parse `wheel`, which needs `keys` to be a single chord (no MIDI, OSC or taps)
+fn parse_wheel(hk_config: &HotkeyConfig) -> Result<Option<WheelMapping>> {
+    let Some(table) = &hk_config.wheel else {
+        return Ok(None);
+    };
+    if hk_config.keys.is_empty() || !hk_config.sequence.is_empty() {
+        bail!("'wheel' needs a 'keys' chord to hold while scrolling");
+    }
+    if !hk_config.midi.is_empty() || hk_config.osc.is_some() {
+        bail!("'wheel' can't be combined with 'midi' or 'osc'");
+    }
+    if hk_config.carbon || hk_config.trigger_on_release || hk_config.tap_count.unwrap_or(1) > 1 {
+        bail!("'wheel' can't be combined with 'carbon', 'trigger_on_release' or 'tap_count'");
+    }
+    WheelMapping::from_table(table).map(Some).context("wheel")
+}
+
+/// Parse one layer: { min, max, action, <params...> }
+///
+/// `action` defaults to the hotkey's own and the other keys are layered over
+/// the hotkey's params.
+fn parse_velocity_layer(hk_config: &HotkeyConfig, table: &toml::Table) -> Result<VelocityLayer> {
+    let int = |key: &str| table.get(key).and_then(|v| v.as_integer());
+    let (min, max) = crate::input::midi::parse_velocity_range(int("min"), int("max"))?;
+    let action_name = table
+        .get("action")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&hk_config.action)
+        .to_string();
+    // A macro hotkey's layers default to running its steps
+    let action = if action_name == crate::macros::ACTION_NAME {
+        crate::macros::run
+    } else {
+        get_action(&action_name).with_context(|| format!("unknown action '{}'", action_name))?
+    };
+
+    let mut params = hk_config.params.clone();
+    for (key, value) in table {
+        if !matches!(key.as_str(), "min" | "max" | "action") {
+            params.insert(key.clone(), value.clone());
+        }
+    }
+    check_params(&action_name, &params)?;
+    Ok(VelocityLayer {
+        min,
+        max,
+        action_name,
+        action,
+        params: Params::new(params),
+    })
+}
+
+/// The keyboard part of a hotkey for messages ("" if it has none)
+fn describe_keys(hk_config: &HotkeyConfig) -> String {
+    if !hk_config.sequence.is_empty() {
+        format!("{:?}", hk_config.sequence)
+    } else if !hk_config.keys.is_empty() {
+        format!("{:?}", hk_config.keys)
+    } else {
+        String::new()
+    }
+}
+
+/// A registered action
+type ActionFn = fn(&Params) -> anyhow::Result<()>;
+
+/// Turn a hotkey with `actions = [...]` into a macro
+///
+/// Checks every step up front and moves the steps into the params, where
+/// `macros::run` finds them. The macro carries the implicit tags of all its
+/// steps, so e.g. one destructive step makes the whole macro destructive.
+fn macro_action(hk_config: &mut HotkeyConfig) -> Result<(ActionFn, Vec<String>)> {
+    if !hk_config.action.is_empty() {
+        anyhow::bail!(
+            "use either 'action' or 'actions', not both ('{}')",
+            hk_config.action
+        );
+    }
+    let steps = crate::macros::parse_steps(&hk_config.actions)?;
+    let mut tags = Vec::new();
+    for step in &steps {
+        tags = crate::input::tags::merge_tags(&tags, &implicit_tags(&step.name));
+    }
+
+    let steps = std::mem::take(&mut hk_config.actions);
+    hk_config.params.insert(
+        crate::macros::STEPS_PARAM.to_string(),
+        toml::Value::Array(steps.into_iter().map(toml::Value::Table).collect()),
+    );
+    hk_config.params.insert(
+        crate::macros::ABORT_PARAM.to_string(),
+        toml::Value::Boolean(hk_config.abort_on_error),
+    );
+    hk_config.action = crate::macros::ACTION_NAME.to_string();
+    Ok((crate::macros::run, tags))
+}
+
+/// Built-in tags derived from the action name
+///
+/// Pro Tools edit actions are tagged "edit", and destructive ones also "destructive"
+pub fn implicit_tags(action: &str) -> Vec<String> {
+    let (namespace, name) = action.split_once('.').unwrap_or(("", action));
+    let mut tags = Vec::new();
+    let is_pt = matches!(namespace, "" | "pt");
+    if is_pt && name.starts_with("edit_") {
+        tags.push("edit".to_string());
+    }
+    #[cfg(target_os = "macos")]
+    if is_pt && crate::protools::transport::is_destructive(name) {
+        tags.push("destructive".to_string());
+    }
+    tags
+}
+
 /// Look up an action by name, handling namespaces
+#[cfg(target_os = "macos")]
 pub fn get_action(name: &str) -> Option<fn(&Params) -> anyhow::Result<()>> {
     // Check if action is namespaced (contains '.')
     if let Some((namespace, action_name)) = name.split_once('.') {
@@ -414,3 +1556,46 @@ pub fn get_action(name: &str) -> Option<fn(&Params) -> anyhow::Result<()>> {
             })
     }
 }
+
+/// Check an action's params where that's possible without running it
+/// (e.g. a typo'd AudioSuite plugin name)
+#[cfg(target_os = "macos")]
+pub fn check_params(action: &str, params: &HashMap<String, toml::Value>) -> Result<()> {
+    crate::protools::plugins::check_params(action, params)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn check_params(_action: &str, _params: &HashMap<String, toml::Value>) -> Result<()> {
+    Ok(())
+}
+
+/// Actions are macOS-only; elsewhere every action is unknown and skipped
+#[cfg(not(target_os = "macos"))]
+pub fn get_action(_name: &str) -> Option<fn(&Params) -> anyhow::Result<()>> {
+    None
+}
+
+/// Every registered action as "namespace.name", sorted
+#[cfg(target_os = "macos")]
+pub fn action_names() -> Vec<String> {
+    let registries = [
+        ("os", crate::macos::actions::get_action_registry()),
+        ("pt", crate::protools::get_action_registry()),
+        ("sm", crate::soundminer::actions::get_action_registry()),
+    ];
+    let mut names: Vec<String> = registries
+        .iter()
+        .flat_map(|(namespace, registry)| {
+            registry
+                .keys()
+                .map(move |name| format!("{}.{}", namespace, name))
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn action_names() -> Vec<String> {
+    Vec::new()
+}
@@ -8,7 +8,7 @@ use toml::Value;
 
 /// Wrapper for action parameters with type-safe accessor methods
 /// Uses Arc for cheap cloning when passing to async actions
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Params(Arc<HashMap<String, Value>>);
 
 impl Params {
@@ -22,6 +22,16 @@ impl Params {
         Params(Arc::new(HashMap::new()))
     }
 
+    /// A copy with `overrides` added, replacing params of the same name
+    pub fn merged(&self, overrides: HashMap<String, Value>) -> Self {
+        if overrides.is_empty() {
+            return self.clone();
+        }
+        let mut map = HashMap::clone(&self.0);
+        map.extend(overrides);
+        Params::new(map)
+    }
+
     /// Get a string parameter as a borrowed &str with a default value
     ///
     /// Use this when you don't need ownership of the string (avoids allocation).
@@ -68,6 +78,9 @@ impl Params {
             .and_then(|v| v.as_integer())
             .unwrap_or(default)
     }
+    pub fn get_oint(&self, key: &str) -> Option<i64> {
+        self.0.get(key).and_then(|v| v.as_integer())
+    }
 
     /// Get a boolean parameter with a default value
     ///
@@ -95,6 +108,22 @@ impl Params {
             .unwrap_or(default)
     }
 
+    /// Get a number written either as an integer (`-27`) or a float (`-27.5`)
+    ///
+    /// # Example
+    /// ```ignore
+    /// let target = params.get_number("target_lufs", -27.0);
+    /// ```
+    pub fn get_number(&self, key: &str, default: f64) -> f64 {
+        self.get_onumber(key).unwrap_or(default)
+    }
+
+    pub fn get_onumber(&self, key: &str) -> Option<f64> {
+        self.0
+            .get(key)
+            .and_then(|v| v.as_float().or_else(|| v.as_integer().map(|i| i as f64)))
+    }
+
     /// Get timeout in milliseconds with a default value
     ///
     /// Convenience method for getting timeout parameters as u64.
@@ -129,6 +158,15 @@ impl Params {
             .unwrap_or_default()
     }
 
+    /// Get an array of integers, e.g. `numbers = [3, 4, 7]` (empty if missing)
+    pub fn get_int_vec(&self, key: &str) -> Vec<i64> {
+        self.0
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|array| array.iter().filter_map(|v| v.as_integer()).collect())
+            .unwrap_or_default()
+    }
+
     pub fn get_str_vec<'a>(&'a self, key: &'a str) -> Vec<&'a str> {
         self.0
             .get(key)
@@ -190,6 +228,28 @@ impl Params {
             })
             .collect()
     }
+
+    /// Get an array of tables
+    ///
+    /// # Example
+    /// ```ignore
+    /// // In config.toml:
+    /// // actions = [{ action = "pt.tracks_solo_selected" }, { action = "os.keystroke", keys = ["cmd", "s"] }]
+    /// let steps = params.get_tables("actions");
+    /// // Returns: one toml::Table per step
+    /// ```
+    pub fn get_tables(&self, key: &str) -> Vec<toml::Table> {
+        self.0
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|array| array.iter().filter_map(|t| t.as_table().cloned()).collect())
+            .unwrap_or_default()
+    }
+
+    /// All params as a table, sorted by name (for display)
+    pub fn to_table(&self) -> toml::Table {
+        self.0.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
 }
 
 impl Default for Params {
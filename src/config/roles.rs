@@ -0,0 +1,107 @@
+//! Starter configs per role
+//!
+//! `pthkd init --role dialogue|mix|adr|assistant` writes config.toml with the
+//! configuration guide followed by bindings for that job, instead of the
+//! generic default. Without `--role` it writes the generic default.
+
+use super::{create_default_config, get_config_path};
+use anyhow::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Dialogue,
+    Mix,
+    Adr,
+    Assistant,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [Role::Dialogue, Role::Mix, Role::Adr, Role::Assistant];
+
+    pub fn parse(name: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|role| role.name().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown role '{}' (expected one of: {})",
+                    name,
+                    Self::names()
+                )
+            })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Dialogue => "dialogue",
+            Role::Mix => "mix",
+            Role::Adr => "adr",
+            Role::Assistant => "assistant",
+        }
+    }
+
+    /// The role's bindings (appended to the configuration guide)
+    pub fn bindings(self) -> &'static str {
+        match self {
+            Role::Dialogue => include_str!("roles/dialogue.toml"),
+            Role::Mix => include_str!("roles/mix.toml"),
+            Role::Adr => include_str!("roles/adr.toml"),
+            Role::Assistant => include_str!("roles/assistant.toml"),
+        }
+    }
+
+    fn names() -> String {
+        Self::ALL.map(Role::name).join("|")
+    }
+}
+
+/// True if the command line asks for `init` rather than the daemon
+pub fn is_init_command(args: &[String]) -> bool {
+    matches!(args.first().map(String::as_str), Some("init"))
+}
+
+/// `init [--role <role>] [--force]`: write a starter config.toml
+///
+/// An existing config is only replaced with `--force`, and is kept as
+/// config.toml.bak. A running daemon picks the new file up on its own.
+pub fn run_init(args: &[String]) -> Result<()> {
+    let usage = format!("usage: pthkd init [--role {}] [--force]", Role::names());
+
+    let mut role = None;
+    let mut force = false;
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--force" => force = true,
+            "--role" => {
+                let name = args.next().ok_or_else(|| anyhow::anyhow!("{}", usage))?;
+                role = Some(Role::parse(name)?);
+            }
+            other => match other.strip_prefix("--role=") {
+                Some(name) => role = Some(Role::parse(name)?),
+                None => anyhow::bail!("{}", usage),
+            },
+        }
+    }
+
+    let path = get_config_path()?;
+    if path.exists() {
+        if !force {
+            anyhow::bail!(
+                "{} already exists (add --force to replace it; the old one is kept as config.toml.bak)",
+                path.display()
+            );
+        }
+        let backup = path.with_file_name("config.toml.bak");
+        std::fs::rename(&path, &backup)?;
+        println!("Previous config saved as {}", backup.display());
+    }
+
+    create_default_config(&path, role)?;
+    println!(
+        "Wrote {} config to {}",
+        role.map_or("default", Role::name),
+        path.display()
+    );
+    Ok(())
+}
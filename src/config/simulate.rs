@@ -0,0 +1,242 @@
+//! Binding simulation for testing shared configs
+//!
+//! `pthkd simulate --press "cmd+shift+k"` loads a config the way the daemon
+//! does and reports which binding each key press would run, without running
+//! it. Matching goes through the daemon's own sequence engine and per-app
+//! precedence, with the frontmost app pinned to `--app`/`--window` and the
+//! hotkey layer set by `--layer`.
+//!
+//! Exits with an error if the config has invalid hotkeys or the last press
+//! doesn't run the `--expect`ed action ("none" for nothing), so a facility's
+//! config repo can run it in CI.
+
+use super::{Config, convert_hotkeys, get_config_path};
+use crate::input::sequence::{self, SequenceEvent};
+use crate::input::{Hotkey, key_name_to_codes};
+use anyhow::{Context, Result, bail};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: pthkd simulate --press <keys> [--press <keys> ...] [--config <file>] \
+                     [--app <name>] [--window <title>] [--layer <name>] [--review] \
+                     [--expect <action>|none]";
+
+/// True if the command line asks for `simulate` rather than the daemon
+pub fn is_simulate_command(args: &[String]) -> bool {
+    matches!(args.first().map(String::as_str), Some("simulate"))
+}
+
+/// `simulate --press <keys> ...`: print what each press would run
+///
+/// `<keys>` is a chord like "cmd+shift+k"; separate chords with spaces to
+/// press a sequence ("ctrl+k m") or tap a chord again ("pad_0 pad_0").
+pub fn run_simulate(args: &[String]) -> Result<()> {
+    let mut presses = Vec::new();
+    let mut config_path = None;
+    let mut app = None;
+    let mut window = None;
+    let mut layer = None;
+    let mut review = false;
+    let mut expect = None;
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().context(USAGE);
+        match arg.as_str() {
+            "--press" => presses.extend(value()?.split_whitespace().map(String::from)),
+            "--config" => config_path = Some(PathBuf::from(value()?)),
+            "--app" => app = Some(value()?),
+            "--window" => window = Some(value()?),
+            "--layer" => layer = Some(value()?),
+            "--expect" => expect = Some(value()?),
+            "--review" => review = true,
+            _ => bail!(USAGE),
+        }
+    }
+    if presses.is_empty() {
+        bail!(USAGE);
+    }
+
+    let path = match config_path {
+        Some(path) => path,
+        None => get_config_path()?,
+    };
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut config: Config =
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))?;
+    config.params_dir = path.parent().map(PathBuf::from);
+
+    if review {
+        crate::input::review::set_blocked_tags(
+            config
+                .review_mode
+                .as_ref()
+                .map_or_else(crate::input::review::default_blocked_tags, |r| {
+                    r.block_tags.clone()
+                }),
+        );
+        crate::input::review::set_enabled(true);
+    }
+    crate::input::frontmost::pin(app, window);
+    crate::input::layers::set(layer.as_deref());
+
+    let (hotkeys, invalid) = convert_hotkeys(config);
+    println!("{}: {} hotkeys", path.display(), hotkeys.len());
+    for message in &invalid {
+        println!("invalid {}", message);
+    }
+
+    let mut last = None;
+    let mut next = 0;
+    while let Some(press) = presses.get(next) {
+        let pressed = pressed_keys(press)?;
+        let repeats = presses[next..].iter().take_while(|p| *p == press).count();
+        let (action, taps) = simulate_press(press, &pressed, &hotkeys, repeats);
+        last = action;
+        next += taps;
+    }
+
+    if !invalid.is_empty() {
+        bail!("{} invalid hotkey(s) in {}", invalid.len(), path.display());
+    }
+    if let Some(expected) = expect {
+        let actual = last.unwrap_or_else(|| "none".to_string());
+        if actual != expected {
+            bail!("expected {}, got {}", expected, actual);
+        }
+    }
+    Ok(())
+}
+
+/// Key codes for a chord like "cmd+shift+k" (the first code of each key)
+fn pressed_keys(chord: &str) -> Result<HashSet<u16>> {
+    chord
+        .split('+')
+        .map(|name| {
+            key_name_to_codes(name.trim())
+                .and_then(|codes| codes.first().copied())
+                .with_context(|| format!("Unknown key name: {}", name))
+        })
+        .collect()
+}
+
+/// Print what a press does; returns the action it runs, if any, and the
+/// number of presses used
+///
+/// `repeats` is how many times the press occurs in a row from here; a chord
+/// with multi-tap bindings takes as many of those as taps.
+fn simulate_press(
+    press: &str,
+    pressed: &HashSet<u16>,
+    hotkeys: &[Hotkey],
+    repeats: usize,
+) -> (Option<String>, usize) {
+    match sequence::on_key_down(pressed, hotkeys) {
+        SequenceEvent::Completed(index) => {
+            let hotkey = &hotkeys[index];
+            let action = report(press, hotkey, &[]).then(|| hotkey.action_name.clone());
+            return (action, 1);
+        }
+        SequenceEvent::Consumed => {
+            println!("{} => (key sequence continues or is cancelled)", press);
+            return (None, 1);
+        }
+        SequenceEvent::Waiting => {
+            println!(
+                "{} => (modifiers, waiting for the next sequence step)",
+                press
+            );
+            return (None, 1);
+        }
+        SequenceEvent::Ignored => {}
+    }
+
+    let mut matching: Vec<(usize, &Hotkey)> = hotkeys
+        .iter()
+        .enumerate()
+        .filter(|(_, hotkey)| hotkey.matches_keyboard(pressed))
+        .collect();
+    let mut taps = 1;
+    let mut label = press.to_string();
+    if crate::input::taps::is_multi_tap(&matching) {
+        let most = matching
+            .iter()
+            .map(|(_, hk)| hk.tap_count)
+            .max()
+            .unwrap_or(1);
+        taps = repeats.min(usize::from(most));
+        label = format!("{} x{}", press, taps);
+        matching.retain(|(_, hotkey)| usize::from(hotkey.tap_count) == taps);
+    }
+    let Some((index, hotkey)) = crate::input::pick_binding(matching.iter().copied()) else {
+        // Taps of a multi-tap chord are consumed even with no binding for the count
+        let note = if label == press {
+            " (the key reaches the frontmost app)"
+        } else {
+            ""
+        };
+        println!("{} => nothing{}", label, note);
+        return (None, taps);
+    };
+    let shadowed: Vec<&Hotkey> = matching
+        .iter()
+        .filter(|(i, _)| *i != index)
+        .map(|(_, hotkey)| *hotkey)
+        .collect();
+    let action = report(&label, hotkey, &shadowed).then(|| hotkey.action_name.clone());
+    (action, taps)
+}
+
+/// Print the binding a press runs; returns false if review mode blocks it
+fn report(press: &str, hotkey: &Hotkey, shadowed: &[&Hotkey]) -> bool {
+    println!(
+        "{} => {} ({})",
+        press,
+        hotkey.action_name,
+        hotkey.trigger.describe()
+    );
+    if let Some(apps) = &hotkey.application {
+        println!("  only in: {}", apps.join(", "));
+    }
+    if let Some(window) = &hotkey.app_window {
+        println!("  only in window: {}", window);
+    }
+    if !hotkey.layers.is_empty() {
+        println!("  only in layer: {}", hotkey.layers.join(", "));
+    }
+    let params = hotkey.params.to_table();
+    if !params.is_empty() {
+        let params: Vec<String> = params
+            .iter()
+            .map(|(name, value)| format!("{} = {}", name, value))
+            .collect();
+        println!("  params: {}", params.join(", "));
+    }
+    if hotkey.trigger_on_release {
+        println!("  runs on key release");
+    }
+    if hotkey.check_for_text_field {
+        println!("  skipped while typing in a text field");
+    }
+    if hotkey.when_stopped {
+        println!("  only while the transport is stopped");
+    }
+    let blocked = crate::input::review::blocks(&hotkey.tags);
+    if blocked {
+        println!(
+            "  blocked by review mode - nothing runs (tags: {})",
+            hotkey.tags.join(", ")
+        );
+    }
+    for other in shadowed {
+        println!(
+            "  shadowed: {}{}",
+            other.action_name,
+            other
+                .application
+                .as_ref()
+                .map_or(String::new(), |apps| format!(" (in {})", apps.join(", ")))
+        );
+    }
+    !blocked
+}
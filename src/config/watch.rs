@@ -0,0 +1,85 @@
+//! Automatic reload when config.toml is saved
+//!
+//! Polls the file's modification time, like the other background watchers,
+//! and waits for a change to settle before calling back - an editor's save
+//! (often write-to-temp then rename) triggers one reload, not several.
+//! The `params_file`s the hotkeys reference are watched too.
+
+use super::{Config, get_config_path};
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+use std::time::{Duration, SystemTime};
+
+static START: Once = Once::new();
+
+/// How often to check the file
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the file must stay unchanged before it's reloaded
+const SETTLE: Duration = Duration::from_millis(250);
+
+/// Call `on_change` whenever config.toml is saved (safe to call more than once)
+pub fn start(on_change: fn()) {
+    START.call_once(|| {
+        let path = match get_config_path() {
+            Ok(path) => path,
+            Err(e) => {
+                log::error!("Config watcher disabled: {:#}", e);
+                return;
+            }
+        };
+        log::info!("Watching {} for changes", path.display());
+        std::thread::spawn(move || watch_loop(&path, on_change));
+    });
+}
+
+fn watch_loop(path: &Path, on_change: fn()) {
+    let mut params_files = referenced_params_files(path);
+    let mut last = modified(path, &params_files);
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        // Missing = mid-save (or deleted); keep the old config until it's back
+        let Some(mut current) = modified(path, &params_files) else {
+            continue;
+        };
+        if Some(current) == last {
+            continue;
+        }
+        loop {
+            std::thread::sleep(SETTLE);
+            match modified(path, &params_files) {
+                Some(latest) if latest != current => current = latest,
+                _ => break,
+            }
+        }
+        log::info!("config.toml (or a params file) changed on disk");
+        on_change();
+
+        // The config may now reference different params files
+        params_files = referenced_params_files(path);
+        last = modified(path, &params_files);
+    }
+}
+
+/// Latest modification time of config.toml and its params files
+///
+/// None while config.toml itself is missing; a missing params file is skipped.
+fn modified(path: &Path, params_files: &[PathBuf]) -> Option<SystemTime> {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let config = modified(path)?;
+    Some(
+        params_files
+            .iter()
+            .filter_map(|file| modified(file))
+            .fold(config, SystemTime::max),
+    )
+}
+
+/// The params files config.toml currently references (none if it doesn't parse)
+fn referenced_params_files(path: &Path) -> Vec<PathBuf> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| toml::from_str::<Config>(&contents).ok())
+        .map(|config| config.params_files())
+        .unwrap_or_default()
+}
@@ -0,0 +1,60 @@
+//! Throttling for continuous CC bindings (`fader = { ... }`)
+//!
+//! A fader sends a value for every step it moves - easily a hundred a second,
+//! far more than a Pro Tools round trip can keep up with. Each binding gets
+//! at most one run in flight and one waiting: a new value replaces the
+//! waiting one, so a fast move skips the values in between but always ends
+//! on where the fader stopped.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+#[derive(Default)]
+struct Slot {
+    /// The latest value's run, waiting for the previous one to finish
+    waiting: Option<Job>,
+    /// True while a thread is draining this slot
+    busy: bool,
+}
+
+/// Slots by binding
+static SLOTS: Mutex<Option<HashMap<String, Slot>>> = Mutex::new(None);
+
+/// Run `job` for `binding` once the previous run has finished and `interval`
+/// has passed since it started, replacing any run still waiting
+pub fn submit<F>(binding: &str, interval: Duration, job: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    let mut slots = SLOTS.lock().unwrap();
+    let slot = slots
+        .get_or_insert_with(HashMap::new)
+        .entry(binding.to_string())
+        .or_default();
+    slot.waiting = Some(Box::new(job));
+    if slot.busy {
+        return;
+    }
+    slot.busy = true;
+
+    let binding = binding.to_string();
+    std::thread::spawn(move || {
+        while let Some(job) = take_waiting(&binding) {
+            let started = Instant::now();
+            job();
+            std::thread::sleep(interval.saturating_sub(started.elapsed()));
+        }
+    });
+}
+
+/// The waiting run for `binding`, or None (marking the slot idle) if there isn't one
+fn take_waiting(binding: &str) -> Option<Job> {
+    let mut slots = SLOTS.lock().unwrap();
+    let slot = slots.as_mut()?.get_mut(binding)?;
+    let job = slot.waiting.take();
+    slot.busy = job.is_some();
+    job
+}
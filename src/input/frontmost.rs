@@ -0,0 +1,70 @@
+//! Cached frontmost app, window and text field focus for hotkey filters
+//!
+//! Every key press checks `app`/`window` filters (and `skip_in_text_fields`)
+//! on each candidate hotkey, and each lookup is an accessibility round trip.
+//! A lookup is reused for a short moment so one key press (and a burst of
+//! MIDI notes) costs at most one query per kind - well under the time it
+//! takes to switch apps or click into a field and press a hotkey.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a lookup stays valid
+const MAX_AGE: Duration = Duration::from_millis(100);
+
+static APP: Mutex<Option<(Instant, Option<String>)>> = Mutex::new(None);
+static WINDOW: Mutex<Option<(Instant, Option<String>)>> = Mutex::new(None);
+static TEXT_FIELD: Mutex<Option<(Instant, bool)>> = Mutex::new(None);
+
+/// App and window pinned by `pthkd simulate` instead of asking the system
+static PINNED: Mutex<Option<(Option<String>, Option<String>)>> = Mutex::new(None);
+
+/// Name of the frontmost application, if it can be determined
+pub fn current_app() -> Option<String> {
+    if let Some((app, _)) = PINNED.lock().unwrap().as_ref() {
+        return app.clone();
+    }
+    cached(&APP, || crate::platform::current().current_app().ok())
+}
+
+/// Title of the frontmost application's focused window, if any
+pub fn app_window() -> Option<String> {
+    if let Some((_, window)) = PINNED.lock().unwrap().as_ref() {
+        return window.clone();
+    }
+    cached(&WINDOW, || crate::platform::current().app_window().ok())
+}
+
+/// True if the focused UI element accepts text input
+pub fn in_text_field() -> bool {
+    if PINNED.lock().unwrap().is_some() {
+        return false;
+    }
+    cached(&TEXT_FIELD, || {
+        crate::platform::current().is_in_text_field()
+    })
+}
+
+/// Answer every lookup with `app` and `window` from now on (for simulation)
+pub fn pin(app: Option<String>, window: Option<String>) {
+    *PINNED.lock().unwrap() = Some((app, window));
+}
+
+/// Drop cached lookups (e.g. after an action switched apps)
+pub fn invalidate() {
+    *APP.lock().unwrap() = None;
+    *WINDOW.lock().unwrap() = None;
+    *TEXT_FIELD.lock().unwrap() = None;
+}
+
+fn cached<T: Clone>(slot: &Mutex<Option<(Instant, T)>>, lookup: impl FnOnce() -> T) -> T {
+    let mut slot = slot.lock().unwrap();
+    if let Some((at, value)) = slot.as_ref()
+        && at.elapsed() < MAX_AGE
+    {
+        return value.clone();
+    }
+    let value = lookup();
+    *slot = Some((Instant::now(), value.clone()));
+    value
+}
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use arc_swap::ArcSwap;
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex, OnceLock};
 
@@ -72,8 +73,16 @@ pub enum ChordPattern {
     ///
     /// Matches when: (55 OR 54) AND (56 OR 60) AND 1 are ALL pressed
     Simultaneous { key_groups: Vec<Vec<u16>> },
-    // Future: Sequential chords for multi-tap patterns
-    // Sequential { steps: Vec<ChordStep> },
+
+    /// Key sequence ("leader key"): simultaneous chords pressed one after another
+    ///
+    /// Example: [["ctrl", "k"], ["m"]] fires after ctrl+k followed by m, with
+    /// at most `timeout_ms` between steps. Sequences are matched step by step
+    /// by `input::sequence`, never by `matches`.
+    Sequence {
+        steps: Vec<ChordPattern>,
+        timeout_ms: u64,
+    },
 }
 
 impl ChordPattern {
@@ -107,6 +116,26 @@ impl ChordPattern {
                 // This prevents CMD+Shift+L from matching when CMD+Shift+Option+L is pressed
                 pressed_keys.len() == chord_keys_pressed
             }
+            // A sequence never matches a single key state
+            ChordPattern::Sequence { .. } => false,
+        }
+    }
+
+    /// Number of steps (1 for a simultaneous chord)
+    pub fn step_count(&self) -> usize {
+        match self {
+            ChordPattern::Simultaneous { .. } => 1,
+            ChordPattern::Sequence { steps, .. } => steps.len(),
+        }
+    }
+
+    /// Checks if the pressed keys match step `step` of a sequence
+    pub fn step_matches(&self, step: usize, pressed_keys: &HashSet<u16>) -> bool {
+        match self {
+            ChordPattern::Simultaneous { .. } => false,
+            ChordPattern::Sequence { steps, .. } => steps
+                .get(step)
+                .is_some_and(|chord| chord.matches(pressed_keys)),
         }
     }
 
@@ -128,6 +157,11 @@ impl ChordPattern {
                     .collect();
                 parts.join("+")
             }
+            ChordPattern::Sequence { steps, .. } => steps
+                .iter()
+                .map(|step| step.describe())
+                .collect::<Vec<_>>()
+                .join(", "),
         }
     }
 }
@@ -136,7 +170,7 @@ impl ChordPattern {
 // Trigger Pattern System (Keyboard + MIDI)
 // ============================================================================
 
-/// Unified trigger pattern that supports keyboard, MIDI, or hybrid triggers
+/// Unified trigger pattern that supports keyboard, MIDI, hybrid or OSC triggers
 #[derive(Debug, Clone)]
 pub enum TriggerPattern {
     /// Keyboard chord pattern
@@ -144,8 +178,16 @@ pub enum TriggerPattern {
 
     /// MIDI pattern
     Midi(crate::input::midi::MidiPattern),
-    // Future: Hybrid keyboard + MIDI triggers
-    // Hybrid { keyboard: ChordPattern, midi: MidiPattern },
+
+    /// MIDI pattern that only fires while a keyboard chord is held
+    /// (e.g. shift + a pad gives the pad a second layer)
+    Hybrid {
+        keyboard: ChordPattern,
+        midi: crate::input::midi::MidiPattern,
+    },
+
+    /// OSC address, with names for the message's arguments
+    Osc { address: String, args: Vec<String> },
 }
 
 impl TriggerPattern {
@@ -153,25 +195,39 @@ impl TriggerPattern {
     pub fn describe(&self) -> String {
         match self {
             TriggerPattern::Keyboard(chord) => chord.describe(),
-            TriggerPattern::Midi(pattern) => {
-                // Describe MIDI pattern
-                match pattern {
-                    crate::input::midi::MidiPattern::Simultaneous { messages } => {
-                        let parts: Vec<String> = messages
-                            .iter()
-                            .map(|spec| match spec {
-                                crate::input::midi::MidiMessageSpec::Note { note } => {
-                                    format!("note{}", note)
-                                }
-                                crate::input::midi::MidiMessageSpec::ControlChange { cc } => {
-                                    format!("cc{}", cc)
-                                }
-                            })
-                            .collect();
-                        parts.join("+")
-                    }
-                }
+            TriggerPattern::Midi(pattern) => describe_midi(pattern),
+            TriggerPattern::Hybrid { keyboard, midi } => {
+                format!("{} + {}", keyboard.describe(), describe_midi(midi))
             }
+            TriggerPattern::Osc { address, .. } => format!("osc {}", address),
+        }
+    }
+
+    /// True for triggers that need MIDI input
+    pub fn uses_midi(&self) -> bool {
+        matches!(
+            self,
+            TriggerPattern::Midi(_) | TriggerPattern::Hybrid { .. }
+        )
+    }
+}
+
+/// Describe a MIDI pattern, e.g. "note36+cc64"
+fn describe_midi(pattern: &crate::input::midi::MidiPattern) -> String {
+    match pattern {
+        crate::input::midi::MidiPattern::Simultaneous { messages } => {
+            let parts: Vec<String> = messages
+                .iter()
+                .map(|spec| match spec {
+                    crate::input::midi::MidiMessageSpec::Note { note } => {
+                        format!("note{}", note)
+                    }
+                    crate::input::midi::MidiMessageSpec::ControlChange { cc } => {
+                        format!("cc{}", cc)
+                    }
+                })
+                .collect();
+            parts.join("+")
         }
     }
 }
@@ -181,7 +237,7 @@ impl TriggerPattern {
 // ============================================================================
 
 /// Represents a hotkey binding
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Hotkey {
     /// The trigger pattern to match (keyboard or MIDI)
     pub trigger: TriggerPattern,
@@ -201,6 +257,12 @@ pub struct Hotkey {
     /// Whether to show notification on action completion
     pub notify: bool,
 
+    /// Whether to drop every notification the action posts (including `notify`)
+    pub silent: bool,
+
+    /// Whether to bring back the app/window that was frontmost once the action returns
+    pub restore_focus: bool,
+
     /// Whether to register as a Carbon hotkey (works during secure input)
     pub carbon: bool,
 
@@ -217,11 +279,39 @@ pub struct Hotkey {
 
     /// MIDI channel filter (only for MIDI triggers) - None = all channels
     pub midi_channel: Option<u8>,
+
+    /// Velocity ranges that run a different action/params (only for MIDI triggers)
+    pub velocity_layers: Vec<crate::input::midi::VelocityLayer>,
+
+    /// Continuous CC mapping - runs on every value change instead of as a trigger
+    pub fader: Option<crate::input::midi::FaderMapping>,
+
+    /// Scroll-wheel mapping - the chord is held while scrolling instead of triggering
+    pub wheel: Option<crate::input::wheel::WheelMapping>,
+
+    /// Only trigger when the Pro Tools transport is stopped
+    pub when_stopped: bool,
+
+    /// Tags used for bulk operations such as review mode (built-in + user tags)
+    pub tags: Vec<String>,
+
+    /// Layers this hotkey is active in (empty = every layer)
+    pub layers: Vec<String>,
+
+    /// Taps of the chord that run this hotkey (1 unless it's a double/multi-tap binding)
+    pub tap_count: u8,
+
+    /// Longest gap between taps
+    pub tap_window: Duration,
 }
 
 impl Hotkey {
     /// Checks if this hotkey's keyboard chord matches the current key state
     pub fn matches_keyboard(&self, pressed_keys: &HashSet<u16>) -> bool {
+        if self.wheel.is_some() {
+            return false; // Wheel chords are held while scrolling (`matches_wheel`)
+        }
+
         // Check if trigger is keyboard type
         let trigger_matches = match &self.trigger {
             TriggerPattern::Keyboard(chord) => chord.matches(pressed_keys),
@@ -231,21 +321,161 @@ impl Hotkey {
         trigger_matches && self.check_application_filters()
     }
 
+    /// Checks if the pressed keys match step `step` of this hotkey's key sequence
+    pub fn matches_sequence_step(&self, step: usize, pressed_keys: &HashSet<u16>) -> bool {
+        let step_matches = match &self.trigger {
+            TriggerPattern::Keyboard(chord) => chord.step_matches(step, pressed_keys),
+            _ => false,
+        };
+
+        step_matches && self.check_application_filters()
+    }
+
+    /// Number of steps in this hotkey's key sequence (0 if it isn't one)
+    pub fn sequence_len(&self) -> usize {
+        match &self.trigger {
+            TriggerPattern::Keyboard(chord @ ChordPattern::Sequence { .. }) => chord.step_count(),
+            _ => 0,
+        }
+    }
+
+    /// Longest allowed gap between sequence steps (0 if it isn't a sequence)
+    pub fn sequence_timeout(&self) -> Duration {
+        match &self.trigger {
+            TriggerPattern::Keyboard(ChordPattern::Sequence { timeout_ms, .. }) => {
+                Duration::from_millis(*timeout_ms)
+            }
+            _ => Duration::ZERO,
+        }
+    }
+
     /// Checks if this hotkey's MIDI pattern matches the current MIDI state
     pub fn matches_midi(&self, active_midi: &HashSet<crate::input::midi::MidiMessage>) -> bool {
+        if self.fader.is_some() {
+            return false; // Faders follow values (`matches_fader`), they don't trigger
+        }
+
         // Check if trigger is MIDI type
         let trigger_matches = match &self.trigger {
             TriggerPattern::Midi(pattern) => pattern.matches(active_midi),
+            TriggerPattern::Hybrid { keyboard, midi } => {
+                midi.matches(active_midi)
+                    && KEY_STATE
+                        .get()
+                        .is_some_and(|state| keyboard.matches(&state.lock().unwrap().pressed_keys))
+            }
             _ => false, // Not a MIDI trigger
         };
 
         trigger_matches && self.check_application_filters()
     }
 
-    /// Check application and window filters (shared by keyboard and MIDI)
+    /// Checks if this hotkey is a fader on `cc` (with any held keys down)
+    pub fn matches_fader(&self, cc: u8) -> bool {
+        use crate::input::midi::{MidiMessageSpec, MidiPattern};
+        let is_cc = |midi: &MidiPattern| match midi {
+            MidiPattern::Simultaneous { messages } => {
+                messages.as_slice() == [MidiMessageSpec::ControlChange { cc }]
+            }
+        };
+        let trigger_matches = match &self.trigger {
+            TriggerPattern::Midi(midi) => is_cc(midi),
+            TriggerPattern::Hybrid { keyboard, midi } => {
+                is_cc(midi)
+                    && KEY_STATE
+                        .get()
+                        .is_some_and(|state| keyboard.matches(&state.lock().unwrap().pressed_keys))
+            }
+            _ => false,
+        };
+
+        self.fader.is_some() && trigger_matches && self.check_application_filters()
+    }
+
+    /// Checks if this hotkey is a wheel binding whose chord is held
+    pub fn matches_wheel(&self, pressed_keys: &HashSet<u16>) -> bool {
+        let trigger_matches = match &self.trigger {
+            TriggerPattern::Keyboard(chord) => chord.matches(pressed_keys),
+            _ => false,
+        };
+
+        self.wheel.is_some() && trigger_matches && self.check_application_filters()
+    }
+
+    /// Checks if this hotkey is triggered by an OSC message to `address`
+    pub fn matches_osc(&self, address: &str) -> bool {
+        matches!(&self.trigger, TriggerPattern::Osc { address: osc, .. } if osc == address)
+            && self.check_application_filters()
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Check runtime conditions (disabled tags, review mode, transport state) before triggering
+    pub fn check_conditions(&self) -> bool {
+        if crate::input::tags::any_disabled(&self.tags) {
+            log::info!(
+                "Hotkey '{}' not triggered - one of its tags is disabled",
+                self.action_name
+            );
+            return false;
+        }
+        if crate::input::review::blocks(&self.tags) {
+            log::info!(
+                "Hotkey '{}' not triggered - blocked by review mode",
+                self.action_name
+            );
+            return false;
+        }
+        self.check_transport_condition()
+    }
+
+    /// Check the `when_stopped` condition against the Pro Tools transport
+    ///
+    /// Reads the background-refreshed cache only (this runs on the event
+    /// tap) and passes while the state is unknown, e.g. Pro Tools not running
+    #[cfg(target_os = "macos")]
+    pub fn check_transport_condition(&self) -> bool {
+        if !self.when_stopped {
+            return true;
+        }
+        match crate::protools::transport::transport_state_cached() {
+            Some(state) if crate::protools::transport::is_stopped(&state) => true,
+            Some(state) => {
+                log::debug!(
+                    "Hotkey '{}' not triggered - transport is not stopped ({})",
+                    self.action_name,
+                    state
+                );
+                false
+            }
+            None => {
+                log::debug!(
+                    "Hotkey '{}': transport state unknown, allowing",
+                    self.action_name
+                );
+                true
+            }
+        }
+    }
+
+    /// There is no Pro Tools transport off macOS, so `when_stopped` always passes
+    #[cfg(not(target_os = "macos"))]
+    pub fn check_transport_condition(&self) -> bool {
+        true
+    }
+
+    /// True if this hotkey only fires in certain apps or windows
+    pub fn has_app_filter(&self) -> bool {
+        self.application.is_some() || self.app_window.is_some()
+    }
+
+    /// Check application, window and layer filters and suspension (shared by
+    /// every trigger type)
     fn check_application_filters(&self) -> bool {
         (self.application.is_none()
-            || match (&self.application, OS::get_current_app().ok()) {
+            || match (&self.application, super::frontmost::current_app()) {
                 (Some(config_apps), Some(current_app)) => {
                     // Check if any of the configured apps match the current app
                     config_apps
@@ -256,16 +486,31 @@ impl Hotkey {
             })
             && match &self.app_window {
                 None => true,
-                Some(config_window) => match OS::get_app_window().ok() {
+                Some(config_window) => match super::frontmost::app_window() {
                     None => false,
                     Some(app_window) => crate::soft_match(&app_window, config_window),
                 },
             }
+            && super::layers::is_active(&self.layers)
+            && super::suspend::allows(&self.action_name)
     }
 }
 
+/// The binding that runs when several match the same chord: one limited to
+/// the frontmost app/window wins over a global one, then one limited to the
+/// current layer, then config order
+pub fn pick_binding<'a>(
+    matching: impl Iterator<Item = (usize, &'a Hotkey)>,
+) -> Option<(usize, &'a Hotkey)> {
+    matching.min_by_key(|(_, hotkey)| (!hotkey.has_app_filter(), hotkey.layers.is_empty()))
+}
+
 /// Global hotkey registry accessible from C callback
-pub static HOTKEYS: OnceLock<Mutex<Vec<Hotkey>>> = OnceLock::new();
+///
+/// The event tap reads an immutable snapshot (`HOTKEYS.get()?.load()`) without
+/// taking a lock; reload and `pthkd bind` build a new table and swap it in, so
+/// a slow writer can never stall keyboard input.
+pub static HOTKEYS: OnceLock<ArcSwap<Vec<Hotkey>>> = OnceLock::new();
 
 // ============================================================================
 // Pending Hotkey Tracking (for trigger_on_release)
@@ -167,6 +167,21 @@ pub const KEY_OPTION_RIGHT: u16 = 61;
 pub const KEY_CONTROL_LEFT: u16 = 59;
 pub const KEY_CONTROL_RIGHT: u16 = 62;
 
+/// True for the physical modifier keys (cmd, shift, option, control - either side)
+pub fn is_modifier(code: u16) -> bool {
+    matches!(
+        code,
+        KEY_CMD_LEFT
+            | KEY_CMD_RIGHT
+            | KEY_SHIFT_LEFT
+            | KEY_SHIFT_RIGHT
+            | KEY_OPTION_LEFT
+            | KEY_OPTION_RIGHT
+            | KEY_CONTROL_LEFT
+            | KEY_CONTROL_RIGHT
+    )
+}
+
 // ====== Key Name Mapping ======
 
 /// Maps a key name string to its corresponding key code(s).
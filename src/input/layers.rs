@@ -0,0 +1,52 @@
+//! Hotkey layers (modes)
+//!
+//! `os.set_layer` / `os.toggle_layer` switch the daemon into a named layer,
+//! like a keyboard's Fn layer: hotkeys with `layer = "edit"` only fire while
+//! the "edit" layer is on, hotkeys without a layer fire in every layer. The
+//! base layer (no name) is where the daemon starts.
+//!
+//! The current layer is shown next to the menu bar icon.
+
+use std::sync::Mutex;
+
+/// The current layer (None = base)
+static CURRENT: Mutex<Option<String>> = Mutex::new(None);
+
+/// The current layer's name, or None in the base layer
+pub fn current() -> Option<String> {
+    CURRENT.lock().unwrap().clone()
+}
+
+/// Switch to a layer (None, "" or "base" = the base layer)
+pub fn set(layer: Option<&str>) {
+    let layer = layer
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && !name.eq_ignore_ascii_case("base"))
+        .map(str::to_string);
+    log::info!("Layer: {}", layer.as_deref().unwrap_or("base"));
+    #[cfg(target_os = "macos")]
+    crate::macos::menubar::set_layer_label(layer.as_deref());
+    *CURRENT.lock().unwrap() = layer;
+}
+
+/// Switch to `layer`, or back to the base layer if it's already on;
+/// returns true if `layer` is now on
+pub fn toggle(layer: &str) -> bool {
+    let on = !is_current(layer);
+    set(on.then_some(layer));
+    on
+}
+
+/// True if `layer` is the current layer
+fn is_current(layer: &str) -> bool {
+    CURRENT
+        .lock()
+        .unwrap()
+        .as_deref()
+        .is_some_and(|current| current.eq_ignore_ascii_case(layer))
+}
+
+/// True if a hotkey limited to `layers` may fire (empty = every layer)
+pub fn is_active(layers: &[String]) -> bool {
+    layers.is_empty() || layers.iter().any(|layer| is_current(layer))
+}
@@ -67,6 +67,8 @@ impl MidiPattern {
 pub struct MidiState {
     active_notes: Arc<HashSet<u8>>,
     active_ccs: Arc<HashMap<u8, u8>>, // CC number -> value
+    /// Last value seen per CC (kept after auto-release, for fader change detection)
+    cc_values: HashMap<u8, u8>,
 }
 
 impl MidiState {
@@ -74,6 +76,7 @@ impl MidiState {
         Self {
             active_notes: Arc::new(HashSet::new()),
             active_ccs: Arc::new(HashMap::new()),
+            cc_values: HashMap::new(),
         }
     }
 
@@ -92,7 +95,10 @@ impl MidiState {
     }
 
     /// Register a control change event (auto-releases after 50ms)
-    pub fn cc(&mut self, cc: u8, value: u8) {
+    ///
+    /// Returns true if the value differs from the last one seen for this CC.
+    pub fn cc(&mut self, cc: u8, value: u8) -> bool {
+        let changed = self.cc_values.insert(cc, value) != Some(value);
         let mut new_ccs = (*self.active_ccs).clone();
         new_ccs.insert(cc, value);
         self.active_ccs = Arc::new(new_ccs);
@@ -109,6 +115,7 @@ impl MidiState {
                 s.active_ccs = Arc::new(new_ccs);
             }
         });
+        changed
     }
 
     /// Get all currently active MIDI messages
@@ -171,6 +178,136 @@ pub fn parse_midi_pattern(specs: Vec<String>) -> Result<MidiPattern> {
     Ok(MidiPattern::Simultaneous { messages })
 }
 
+/// A velocity range of a MIDI binding with its own action and params
+///
+/// Configured as `velocity_layers = [{ min = 100, action = "...", ... }]`;
+/// velocities outside every layer run the binding's own action.
+#[derive(Debug, Clone)]
+pub struct VelocityLayer {
+    pub min: u8,
+    pub max: u8,
+    pub action_name: String,
+    pub action: fn(&crate::config::Params) -> Result<()>,
+    pub params: crate::config::Params,
+}
+
+impl VelocityLayer {
+    pub fn contains(&self, velocity: u8) -> bool {
+        (self.min..=self.max).contains(&velocity)
+    }
+}
+
+/// The first layer whose range holds `velocity`
+pub fn select_layer(layers: &[VelocityLayer], velocity: u8) -> Option<&VelocityLayer> {
+    layers.iter().find(|layer| layer.contains(velocity))
+}
+
+/// Parse a layer's velocity range (`min` defaults to 1, `max` to 127)
+pub fn parse_velocity_range(min: Option<i64>, max: Option<i64>) -> Result<(u8, u8)> {
+    let min = min.unwrap_or(1);
+    let max = max.unwrap_or(127);
+    if !(1..=127).contains(&min) || !(1..=127).contains(&max) {
+        bail!("velocity must be 1-127, got {}-{}", min, max);
+    }
+    if min > max {
+        bail!("velocity range {}-{} is empty", min, max);
+    }
+    Ok((min as u8, max as u8))
+}
+
+/// How a fader's 0-127 travel is spread over its range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaderCurve {
+    Linear,
+    /// Fast at the bottom, fine control at the top
+    Log,
+    /// Fine control at the bottom, fast at the top
+    Exp,
+}
+
+/// A continuous CC binding: every new CC value, scaled into `min..=max`,
+/// runs the action with that value as param `param`
+///
+/// Configured as `fader = { param = "number", min = 1, max = 9 }` on a
+/// hotkey whose `midi` is a single CC.
+#[derive(Debug, Clone)]
+pub struct FaderMapping {
+    pub param: String,
+    pub min: f64,
+    pub max: f64,
+    pub curve: FaderCurve,
+    /// Round to whole numbers (when `min` and `max` are both integers)
+    pub integer: bool,
+    /// Shortest gap between runs; moves in between collapse into the latest value
+    pub interval: Duration,
+}
+
+/// Default gap between fader runs
+pub const DEFAULT_FADER_INTERVAL_MS: u64 = 50;
+
+impl FaderMapping {
+    /// Parse `{ param, min = 0, max = 127, curve = "linear", interval_ms = 50 }`
+    pub fn from_table(table: &toml::Table) -> Result<Self> {
+        let Some(param) = table.get("param").and_then(|v| v.as_str()) else {
+            bail!("'param' (the param that gets the value) is required");
+        };
+        let number = |key: &str, default: i64| match table.get(key) {
+            None => Ok((default as f64, true)),
+            Some(toml::Value::Integer(i)) => Ok((*i as f64, true)),
+            Some(toml::Value::Float(f)) => Ok((*f, false)),
+            Some(other) => Err(anyhow::anyhow!("'{}' must be a number, got {}", key, other)),
+        };
+        let (min, min_is_int) = number("min", 0)?;
+        let (max, max_is_int) = number("max", 127)?;
+        let curve = match table.get("curve").and_then(|v| v.as_str()) {
+            None | Some("linear") => FaderCurve::Linear,
+            Some("log") => FaderCurve::Log,
+            Some("exp") => FaderCurve::Exp,
+            Some(other) => bail!(
+                "curve must be \"linear\", \"log\" or \"exp\", got '{}'",
+                other
+            ),
+        };
+        let interval_ms = table
+            .get("interval_ms")
+            .and_then(|v| v.as_integer())
+            .unwrap_or(DEFAULT_FADER_INTERVAL_MS as i64);
+        if let Some(key) = table.keys().find(|key| {
+            !matches!(
+                key.as_str(),
+                "param" | "min" | "max" | "curve" | "interval_ms"
+            )
+        }) {
+            bail!("unknown fader setting '{}'", key);
+        }
+
+        Ok(Self {
+            param: param.to_string(),
+            min,
+            max,
+            curve,
+            integer: min_is_int && max_is_int,
+            interval: Duration::from_millis(interval_ms.max(0) as u64),
+        })
+    }
+
+    /// The param value for a CC value (0 => `min`, 127 => `max`)
+    pub fn scale(&self, value: u8) -> toml::Value {
+        let position = f64::from(value.min(127)) / 127.0;
+        let position = match self.curve {
+            FaderCurve::Linear => position,
+            FaderCurve::Log => position.sqrt(),
+            FaderCurve::Exp => position * position,
+        };
+        let scaled = self.min + (self.max - self.min) * position;
+        if self.integer {
+            toml::Value::Integer(scaled.round() as i64)
+        } else {
+            toml::Value::Float(scaled)
+        }
+    }
+}
+
 /// Parse raw MIDI bytes into MidiMessage
 fn parse_raw_midi(data: &[u8]) -> Option<MidiMessage> {
     if data.len() < 2 {
@@ -280,7 +417,9 @@ where
                 }
             },
             (),
-        )?;
+        )
+        // ConnectError isn't Sync on every backend
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
 
         connections.push(connection);
     }
@@ -309,6 +448,68 @@ mod tests {
         assert!(!state.active_notes.contains(&60));
     }
 
+    #[test]
+    fn test_velocity_layers() {
+        fn noop(_: &crate::config::Params) -> Result<()> {
+            Ok(())
+        }
+        let layer = |min, max, name: &str| VelocityLayer {
+            min,
+            max,
+            action_name: name.to_string(),
+            action: noop,
+            params: crate::config::Params::empty(),
+        };
+        let layers = vec![layer(1, 60, "soft"), layer(100, 127, "hard")];
+
+        assert_eq!(select_layer(&layers, 1).unwrap().action_name, "soft");
+        assert_eq!(select_layer(&layers, 60).unwrap().action_name, "soft");
+        assert!(select_layer(&layers, 80).is_none());
+        assert_eq!(select_layer(&layers, 127).unwrap().action_name, "hard");
+
+        assert_eq!(parse_velocity_range(None, None).unwrap(), (1, 127));
+        assert_eq!(parse_velocity_range(Some(100), None).unwrap(), (100, 127));
+        assert!(parse_velocity_range(Some(0), Some(10)).is_err());
+        assert!(parse_velocity_range(Some(90), Some(80)).is_err());
+    }
+
+    #[test]
+    fn test_fader_mapping() {
+        let table = |text: &str| text.parse::<toml::Table>().unwrap();
+
+        let markers =
+            FaderMapping::from_table(&table("param = 'number'\nmin = 1\nmax = 9")).unwrap();
+        assert_eq!(markers.scale(0), toml::Value::Integer(1));
+        assert_eq!(markers.scale(64), toml::Value::Integer(5));
+        assert_eq!(markers.scale(127), toml::Value::Integer(9));
+
+        let gain = FaderMapping::from_table(&table(
+            "param = 'gain'\nmin = -60.0\nmax = 0\ncurve = 'log'",
+        ))
+        .unwrap();
+        assert_eq!(gain.scale(0), toml::Value::Float(-60.0));
+        assert_eq!(gain.scale(127), toml::Value::Float(0.0));
+        let halfway = gain.scale(32).as_float().unwrap();
+        assert!(
+            halfway > -31.0,
+            "log curve rises fast at the bottom: {}",
+            halfway
+        );
+
+        assert!(FaderMapping::from_table(&table("min = 1")).is_err());
+        assert!(FaderMapping::from_table(&table("param = 'x'\ncurve = 'wavy'")).is_err());
+        assert!(FaderMapping::from_table(&table("param = 'x'\nmaxx = 3")).is_err());
+    }
+
+    #[test]
+    fn test_midi_cc_value_changes() {
+        let mut state = MidiState::new();
+        assert!(state.cc(7, 10));
+        assert!(!state.cc(7, 10));
+        assert!(state.cc(7, 11));
+        assert!(state.cc(8, 11));
+    }
+
     #[test]
     fn test_midi_pattern_exact_match() {
         let pattern = MidiPattern::Simultaneous {
@@ -0,0 +1,115 @@
+//! MIDI output for controller feedback
+//!
+//! `input::midi` only listens; this sends notes and CCs back out, so actions
+//! can light a button while a mode is on or mirror solo states onto pads.
+//!
+//! A port is opened the first time something is sent to it and kept open.
+//! The device is picked by name (soft matched, like app filters): the
+//! `device` param, else `[midi] output`, else the only output port if there
+//! is just one.
+
+use super::midi::MidiMessage;
+use anyhow::{Result, bail};
+use log::{info, warn};
+use midir::{MidiOutput as MidirOutput, MidiOutputConnection};
+use std::sync::Mutex;
+
+/// Open output ports, by port name
+pub struct MidiOutput {
+    connections: Vec<(String, MidiOutputConnection)>,
+    default_device: Option<String>,
+}
+
+static OUTPUT: Mutex<MidiOutput> = Mutex::new(MidiOutput {
+    connections: Vec::new(),
+    default_device: None,
+});
+
+impl MidiOutput {
+    /// Send raw bytes to a device, opening its port on first use
+    fn send_bytes(&mut self, device: Option<&str>, bytes: &[u8]) -> Result<()> {
+        let wanted = device
+            .map(str::to_string)
+            .or_else(|| self.default_device.clone());
+        let index = self.connect(wanted.as_deref())?;
+        if self.connections[index].1.send(bytes).is_ok() {
+            return Ok(());
+        }
+
+        // The device may have been unplugged and come back - reopen once
+        let (name, _) = self.connections.remove(index);
+        warn!("MIDI output to '{}' failed, reconnecting", name);
+        let index = self.connect(Some(&name))?;
+        self.connections[index]
+            .1
+            .send(bytes)
+            .map_err(|e| anyhow::anyhow!("MIDI output to '{}' failed: {}", name, e))
+    }
+
+    /// Index of the open connection for `device`, opening it if needed
+    fn connect(&mut self, device: Option<&str>) -> Result<usize> {
+        if let Some(device) = device
+            && let Some(index) = self
+                .connections
+                .iter()
+                .position(|(name, _)| crate::soft_match(name, device))
+        {
+            return Ok(index);
+        }
+
+        let midi_out = MidirOutput::new("pthkd")?;
+        let ports: Vec<_> = midi_out
+            .ports()
+            .into_iter()
+            .map(|port| (midi_out.port_name(&port).unwrap_or_default(), port))
+            .collect();
+        let port = match device {
+            Some(device) => ports
+                .into_iter()
+                .find(|(name, _)| crate::soft_match(name, device)),
+            None if ports.len() == 1 => ports.into_iter().next(),
+            None if ports.is_empty() => bail!("No MIDI output ports found"),
+            None => bail!(
+                "Several MIDI outputs ({}) - pick one with `device` or [midi] output",
+                ports
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let Some((name, port)) = port else {
+            bail!("No MIDI output matching '{}'", device.unwrap_or_default());
+        };
+
+        info!("Opening MIDI output: {}", name);
+        let connection = midi_out
+            .connect(&port, &format!("pthkd-{}", name))
+            .map_err(|e| anyhow::anyhow!("Failed to open MIDI output '{}': {}", name, e))?;
+        self.connections.push((name, connection));
+        Ok(self.connections.len() - 1)
+    }
+}
+
+/// Set the device used when an action doesn't name one (`[midi] output`)
+pub fn set_default_device(device: Option<String>) {
+    OUTPUT.lock().unwrap().default_device = device;
+}
+
+/// Send a note or CC on `channel` (1-16)
+pub fn send(device: Option<&str>, channel: u8, message: MidiMessage) -> Result<()> {
+    if !(1..=16).contains(&channel) {
+        bail!("MIDI channel must be 1-16, got {}", channel);
+    }
+    let bytes = to_bytes(channel - 1, message);
+    OUTPUT.lock().unwrap().send_bytes(device, &bytes)
+}
+
+/// Raw bytes for a message on a 0-based channel
+fn to_bytes(channel: u8, message: MidiMessage) -> [u8; 3] {
+    match message {
+        MidiMessage::NoteOn { note, velocity } => [0x90 | channel, note, velocity],
+        MidiMessage::NoteOff { note } => [0x80 | channel, note, 0],
+        MidiMessage::ControlChange { cc, value } => [0xB0 | channel, cc, value],
+    }
+}
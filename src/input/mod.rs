@@ -1,6 +1,17 @@
+pub mod fader;
+pub mod frontmost;
 pub mod hotkey;
 pub mod keycodes;
+pub mod layers;
 pub mod midi;
+pub mod midi_out;
+pub mod osc;
+pub mod review;
+pub mod sequence;
+pub mod suspend;
+pub mod tags;
+pub mod taps;
+pub mod wheel;
 
 pub use hotkey::*;
 pub use keycodes::*;
@@ -0,0 +1,384 @@
+//! OSC input for control surfaces (`[osc]` section)
+//!
+//! TouchOSC, Lemur, QLab and friends send OSC messages over UDP. Two ways to
+//! turn them into actions:
+//!
+//! - a hotkey with `osc = "/address"` runs its action when that address
+//!   arrives; `osc_args = ["name", ...]` names the message's arguments, which
+//!   become params (overriding the hotkey's own)
+//! - any other address under `prefix` (default "/pthkd") runs the registry
+//!   action it spells: `/pthkd/tracks/solo_selected` => `pt.tracks_solo_selected`,
+//!   `/pthkd/os/toggle_review_mode` => `os.toggle_review_mode`. Arguments are name/value pairs:
+//!   `"number", 3`. OSC has no authentication, so only actions listed in
+//!   `routes` are routed, and never `os.shell_script`
+//!
+//! Messages in bundles are handled one by one, in order; bundle time tags are
+//! ignored.
+
+use crate::config::OscConfig;
+use crate::prelude::*;
+use anyhow::bail;
+use std::collections::HashMap;
+use std::net::UdpSocket;
+use std::sync::{Mutex, Once};
+
+static START: Once = Once::new();
+
+/// Prefix routing to registry actions (None = no routing)
+static ROUTING: Mutex<Option<Routing>> = Mutex::new(None);
+
+/// Actions prefix routing never runs, listed in `routes` or not
+const NEVER_ROUTED: &[&str] = &["os.shell_script"];
+
+struct Routing {
+    prefix: String,
+    /// Actions addresses may run (`routes`)
+    actions: Vec<String>,
+}
+
+/// Namespaces an address may start with; anything else is a Pro Tools action
+const NAMESPACES: &[&str] = &["os", "pt", "sm"];
+
+/// One argument of an OSC message
+#[derive(Debug, Clone, PartialEq)]
+pub enum OscArg {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bool(bool),
+    /// Nil, impulse, blob and other types with no param equivalent
+    Other,
+}
+
+impl OscArg {
+    /// The param value for this argument (None for `Other`)
+    pub fn to_toml(&self) -> Option<toml::Value> {
+        match self {
+            OscArg::Int(i) => Some(toml::Value::Integer(*i)),
+            OscArg::Float(f) => Some(toml::Value::Float(*f)),
+            OscArg::String(s) => Some(toml::Value::String(s.clone())),
+            OscArg::Bool(b) => Some(toml::Value::Boolean(*b)),
+            OscArg::Other => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscMessage {
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+/// Apply `[osc]` routing settings (None = defaults; also called on config reload)
+pub fn set_config(config: Option<&OscConfig>) {
+    let default = OscConfig::default();
+    let config = config.unwrap_or(&default);
+    let prefix = config.prefix.trim_end_matches('/');
+    let actions: Vec<String> = config
+        .routes
+        .iter()
+        .filter(|action| {
+            let never = NEVER_ROUTED.contains(&action.as_str());
+            if never {
+                log::warn!(
+                    "OSC: '{}' can't be routed - bind it to an osc hotkey",
+                    action
+                );
+            }
+            !never
+        })
+        .cloned()
+        .collect();
+    *ROUTING.lock().unwrap() =
+        (config.enabled && !prefix.is_empty() && !actions.is_empty()).then(|| Routing {
+            prefix: prefix.to_string(),
+            actions,
+        });
+}
+
+/// Listen for OSC on `bind:port` and call `callback` for every message
+///
+/// Safe to call more than once; the address is read once, so changing it
+/// needs a restart.
+pub fn start(bind: &str, port: u16, callback: fn(OscMessage)) {
+    let address = format!("{}:{}", bind, port);
+    START.call_once(|| {
+        let socket = match UdpSocket::bind(&address) {
+            Ok(socket) => socket,
+            Err(e) => {
+                log::error!("OSC disabled - can't listen on {}: {}", address, e);
+                return;
+            }
+        };
+        log::info!("OSC listening on udp://{}", address);
+        std::thread::spawn(move || {
+            let mut buffer = [0u8; 65536];
+            loop {
+                let (length, from) = match socket.recv_from(&mut buffer) {
+                    Ok(received) => received,
+                    Err(e) => {
+                        log::warn!("OSC receive failed: {}", e);
+                        continue;
+                    }
+                };
+                match decode(&buffer[..length]) {
+                    Ok(messages) => messages.into_iter().for_each(callback),
+                    Err(e) => log::warn!("Ignoring bad OSC packet from {}: {:#}", from, e),
+                }
+            }
+        });
+    });
+}
+
+/// The registry action an address routes to, if it's under the prefix and
+/// listed in `routes`
+pub fn route(address: &str) -> Option<String> {
+    ROUTING.lock().unwrap().as_ref()?.route(address)
+}
+
+impl Routing {
+    fn route(&self, address: &str) -> Option<String> {
+        let rest = address.strip_prefix(&self.prefix)?.strip_prefix('/')?;
+        let mut segments: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+        let namespace = match segments.first() {
+            Some(first) if NAMESPACES.contains(first) && segments.len() > 1 => segments.remove(0),
+            Some(_) => "pt",
+            None => return None,
+        };
+        let action = format!("{}.{}", namespace, segments.join("_"));
+        if !self.actions.contains(&action) {
+            log::info!("OSC: {} isn't in [osc] routes - not running it", action);
+            return None;
+        }
+        Some(action)
+    }
+}
+
+/// Params from arguments named by a hotkey's `osc_args` (extra arguments are ignored)
+pub fn named_params(names: &[String], args: &[OscArg]) -> HashMap<String, toml::Value> {
+    names
+        .iter()
+        .zip(args)
+        .filter_map(|(name, arg)| Some((name.clone(), arg.to_toml()?)))
+        .collect()
+}
+
+/// Params from name/value argument pairs (routed addresses)
+pub fn paired_params(args: &[OscArg]) -> R<HashMap<String, toml::Value>> {
+    if !args.len().is_multiple_of(2) {
+        bail!("arguments must be name/value pairs");
+    }
+    args.chunks(2)
+        .filter_map(|pair| match (&pair[0], pair[1].to_toml()) {
+            (OscArg::String(name), Some(value)) => Some(Ok((name.clone(), value))),
+            (OscArg::String(_), None) => None,
+            (other, _) => Some(Err(anyhow::anyhow!(
+                "expected a param name, got {:?}",
+                other
+            ))),
+        })
+        .collect()
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+/// Decode a packet (a message or a bundle) into its messages
+pub fn decode(packet: &[u8]) -> R<Vec<OscMessage>> {
+    let mut messages = Vec::new();
+    decode_into(packet, &mut messages)?;
+    Ok(messages)
+}
+
+fn decode_into(packet: &[u8], messages: &mut Vec<OscMessage>) -> R<()> {
+    let mut reader = Reader {
+        data: packet,
+        pos: 0,
+    };
+    let address = reader.string().context("address")?;
+
+    if address == "#bundle" {
+        reader.take(8).context("bundle time tag")?;
+        while !reader.is_done() {
+            let size = reader.i32().context("bundle element size")?;
+            let element = reader
+                .take(usize::try_from(size).context("bundle element size")?)
+                .context("bundle element")?;
+            decode_into(element, messages)?;
+        }
+        return Ok(());
+    }
+    if !address.starts_with('/') {
+        bail!("address '{}' doesn't start with '/'", address);
+    }
+
+    // Type tags are optional in old senders - no tags means no arguments
+    let tags = if reader.is_done() {
+        String::new()
+    } else {
+        reader.string().context("type tags")?
+    };
+    let mut args = Vec::new();
+    for tag in tags.trim_start_matches(',').chars() {
+        let arg = match tag {
+            'i' => OscArg::Int(reader.i32()? as i64),
+            'h' => OscArg::Int(i64::from_be_bytes(reader.array()?)),
+            'f' => OscArg::Float(f32::from_be_bytes(reader.array()?) as f64),
+            'd' => OscArg::Float(f64::from_be_bytes(reader.array()?)),
+            's' | 'S' => OscArg::String(reader.string()?),
+            'T' => OscArg::Bool(true),
+            'F' => OscArg::Bool(false),
+            'N' | 'I' => OscArg::Other,
+            'c' | 'r' | 'm' => {
+                reader.take(4)?;
+                OscArg::Other
+            }
+            't' => {
+                reader.take(8)?;
+                OscArg::Other
+            }
+            'b' => {
+                let size = usize::try_from(reader.i32()?).context("blob size")?;
+                reader.take(size.next_multiple_of(4))?;
+                OscArg::Other
+            }
+            // Array brackets - the values inside are read as plain arguments
+            '[' | ']' => continue,
+            other => bail!("unsupported argument type '{}'", other),
+        };
+        args.push(arg);
+    }
+    messages.push(OscMessage { address, args });
+    Ok(())
+}
+
+/// Reads 4-byte aligned OSC fields
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn is_done(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn take(&mut self, length: usize) -> R<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(length)
+            .filter(|end| *end <= self.data.len())
+            .context("packet too short")?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> R<[u8; N]> {
+        Ok(self.take(N)?.try_into()?)
+    }
+
+    fn i32(&mut self) -> R<i32> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    /// NUL-terminated string, padded to a multiple of 4 bytes
+    fn string(&mut self) -> R<String> {
+        let rest = &self.data[self.pos.min(self.data.len())..];
+        let length = rest
+            .iter()
+            .position(|b| *b == 0)
+            .context("unterminated string")?;
+        let text = std::str::from_utf8(&rest[..length])
+            .context("string isn't UTF-8")?
+            .to_string();
+        self.take((length + 1).next_multiple_of(4))?;
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode a string the OSC way (NUL-terminated, padded to 4 bytes)
+    fn osc_string(text: &str) -> Vec<u8> {
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.resize((text.len() + 1).next_multiple_of(4), 0);
+        bytes
+    }
+
+    #[test]
+    fn test_decode_message_and_bundle() {
+        let mut message = osc_string("/pthkd/markers/go_to_quick_marker");
+        message.extend(osc_string(",sif"));
+        message.extend(osc_string("number"));
+        message.extend(3i32.to_be_bytes());
+        message.extend(0.5f32.to_be_bytes());
+
+        let expected = OscMessage {
+            address: "/pthkd/markers/go_to_quick_marker".to_string(),
+            args: vec![
+                OscArg::String("number".to_string()),
+                OscArg::Int(3),
+                OscArg::Float(0.5),
+            ],
+        };
+        assert_eq!(decode(&message).unwrap(), vec![expected.clone()]);
+
+        let mut bundle = osc_string("#bundle");
+        bundle.extend([0, 0, 0, 0, 0, 0, 0, 1]);
+        for _ in 0..2 {
+            bundle.extend((message.len() as i32).to_be_bytes());
+            bundle.extend(&message);
+        }
+        assert_eq!(decode(&bundle).unwrap(), vec![expected.clone(), expected]);
+
+        assert!(decode(&message[..message.len() - 2]).is_err());
+    }
+
+    #[test]
+    fn test_route() {
+        let routing = Routing {
+            prefix: "/pthkd".to_string(),
+            actions: vec![
+                "pt.tracks_solo_selected".to_string(),
+                "os.toggle_review_mode".to_string(),
+            ],
+        };
+        assert_eq!(
+            routing.route("/pthkd/tracks/solo_selected").as_deref(),
+            Some("pt.tracks_solo_selected")
+        );
+        assert_eq!(
+            routing.route("/pthkd/os/toggle_review_mode").as_deref(),
+            Some("os.toggle_review_mode")
+        );
+        // Not listed in routes
+        assert_eq!(routing.route("/pthkd/os/shell_script"), None);
+        assert_eq!(routing.route("/pthkd/tracks/mute_selected"), None);
+        // Not under the prefix
+        assert_eq!(routing.route("/other/tracks/solo_selected"), None);
+        assert_eq!(routing.route("/pthkd"), None);
+    }
+
+    #[test]
+    fn test_params_from_args() {
+        let args = vec![
+            OscArg::String("number".to_string()),
+            OscArg::Int(3),
+            OscArg::String("gain".to_string()),
+            OscArg::Float(-2.5),
+        ];
+        let params = paired_params(&args).unwrap();
+        assert_eq!(params["number"], toml::Value::Integer(3));
+        assert_eq!(params["gain"], toml::Value::Float(-2.5));
+        assert!(paired_params(&args[..3]).is_err());
+
+        let names = vec!["value".to_string()];
+        let params = named_params(&names, &[OscArg::Float(0.25), OscArg::Int(1)]);
+        assert_eq!(params.len(), 1);
+        assert_eq!(params["value"], toml::Value::Float(0.25));
+    }
+}
@@ -0,0 +1,55 @@
+//! Review mode (session lock)
+//!
+//! When review mode is on, hotkeys carrying any of the blocked tags are
+//! ignored. By default that's every Pro Tools edit action and every
+//! destructive action - useful when a client is driving playback.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static REVIEW_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Tags that are blocked while review mode is on
+static BLOCKED_TAGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Default blocked tags when the config doesn't specify any
+pub fn default_blocked_tags() -> Vec<String> {
+    vec!["edit".to_string(), "destructive".to_string()]
+}
+
+pub fn is_enabled() -> bool {
+    REVIEW_MODE.load(Ordering::SeqCst)
+}
+
+pub fn set_enabled(enabled: bool) {
+    REVIEW_MODE.store(enabled, Ordering::SeqCst);
+    log::info!(
+        "Review mode {}",
+        if enabled { "ENABLED" } else { "DISABLED" }
+    );
+}
+
+/// Toggle review mode, returning the new state
+pub fn toggle() -> bool {
+    let enabled = !REVIEW_MODE.fetch_xor(true, Ordering::SeqCst);
+    log::info!(
+        "Review mode {}",
+        if enabled { "ENABLED" } else { "DISABLED" }
+    );
+    enabled
+}
+
+pub fn set_blocked_tags(tags: Vec<String>) {
+    log::info!("Review mode blocks tags: {:?}", tags);
+    *BLOCKED_TAGS.lock().unwrap() = tags;
+}
+
+/// Returns true if review mode is on and any of the given tags are blocked
+pub fn blocks(tags: &[String]) -> bool {
+    if !is_enabled() {
+        return false;
+    }
+    let blocked = BLOCKED_TAGS.lock().unwrap();
+    tags.iter()
+        .any(|tag| blocked.iter().any(|b| b.eq_ignore_ascii_case(tag)))
+}
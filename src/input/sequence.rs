@@ -0,0 +1,346 @@
+//! Key sequences ("leader keys")
+//!
+//! A sequence hotkey fires after its chords are pressed one after another,
+//! e.g. `ctrl+k` then `m`. Once the first step matches, the following key
+//! presses belong to the sequence: they advance it, complete it or cancel
+//! it, and never reach the frontmost app. Modifiers pressed on their own are
+//! part of the next step rather than a step, and waiting longer than the
+//! hotkey's `sequence_timeout_ms` between steps cancels the sequence.
+//!
+//! If one sequence is a prefix of another (`ctrl+k, m` and `ctrl+k, m, x`),
+//! the shorter one fires as soon as it completes.
+
+use super::hotkey::Hotkey;
+use super::keycodes::is_modifier;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Default gap allowed between sequence steps
+pub const DEFAULT_TIMEOUT_MS: u64 = 1000;
+
+/// The sequence being typed, if any
+static PROGRESS: Mutex<Option<Progress>> = Mutex::new(None);
+
+struct Progress {
+    /// Indices into HOTKEYS of the sequences that still match
+    candidates: Vec<usize>,
+    /// Steps matched so far
+    matched: usize,
+    last_step: Instant,
+}
+
+/// What a key press meant for the sequence engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceEvent {
+    /// Not part of a sequence - handle the key as usual
+    Ignored,
+    /// A modifier pressed mid-sequence - let it through but don't match other hotkeys
+    Waiting,
+    /// Started, advanced or cancelled a sequence - consume the key
+    Consumed,
+    /// Completed the sequence of this hotkey index - trigger it and consume the key
+    Completed(usize),
+}
+
+/// Forget any sequence in progress (the hotkey table is about to change)
+pub fn reset() {
+    *PROGRESS.lock().unwrap() = None;
+}
+
+/// Feed a key-down (with the keys now held) to the sequence engine
+pub fn on_key_down(pressed_keys: &HashSet<u16>, hotkeys: &[Hotkey]) -> SequenceEvent {
+    on_key_down_at(pressed_keys, hotkeys, Instant::now())
+}
+
+/// `on_key_down` for a key pressed at `now` (tests pass their own clock)
+fn on_key_down_at(pressed_keys: &HashSet<u16>, hotkeys: &[Hotkey], now: Instant) -> SequenceEvent {
+    let mut progress = PROGRESS.lock().unwrap();
+
+    if let Some(current) = progress.as_ref() {
+        let timeout = current
+            .candidates
+            .iter()
+            .filter_map(|&index| hotkeys.get(index))
+            .map(Hotkey::sequence_timeout)
+            .max()
+            .unwrap_or_default();
+        if now.saturating_duration_since(current.last_step) > timeout {
+            log::info!("Key sequence timed out");
+            *progress = None;
+        }
+    }
+
+    // Modifiers on their own are the start of a step, not a step
+    let modifiers_only = pressed_keys.iter().all(|&key| is_modifier(key));
+
+    let Some(current) = progress.take() else {
+        if modifiers_only {
+            return SequenceEvent::Ignored;
+        }
+        return start(&mut progress, pressed_keys, hotkeys, now);
+    };
+
+    if modifiers_only {
+        *progress = Some(current);
+        return SequenceEvent::Waiting;
+    }
+
+    let candidates: Vec<usize> = current
+        .candidates
+        .iter()
+        .copied()
+        .filter(|&index| {
+            hotkeys
+                .get(index)
+                .is_some_and(|hk| hk.matches_sequence_step(current.matched, pressed_keys))
+        })
+        .collect();
+    if candidates.is_empty() {
+        log::info!("Key sequence cancelled - no sequence continues that way");
+        return SequenceEvent::Consumed;
+    }
+
+    advance(&mut progress, candidates, current.matched + 1, hotkeys, now)
+}
+
+/// Begin a sequence if the pressed keys match any sequence's first step
+fn start(
+    progress: &mut Option<Progress>,
+    pressed_keys: &HashSet<u16>,
+    hotkeys: &[Hotkey],
+    now: Instant,
+) -> SequenceEvent {
+    let mut candidates: Vec<usize> = hotkeys
+        .iter()
+        .enumerate()
+        .filter(|(_, hk)| hk.matches_sequence_step(0, pressed_keys))
+        .map(|(index, _)| index)
+        .collect();
+    if candidates.is_empty() {
+        return SequenceEvent::Ignored;
+    }
+
+    // Don't start sequences that ask to stay out of text fields
+    if candidates
+        .iter()
+        .any(|&index| hotkeys[index].check_for_text_field)
+        && super::frontmost::in_text_field()
+    {
+        candidates.retain(|&index| !hotkeys[index].check_for_text_field);
+        if candidates.is_empty() {
+            log::debug!("Key sequence not started - cursor is in a text field");
+            return SequenceEvent::Ignored;
+        }
+    }
+
+    advance(progress, candidates, 1, hotkeys, now)
+}
+
+/// Record `matched` steps for the remaining candidates, completing the shortest
+fn advance(
+    progress: &mut Option<Progress>,
+    candidates: Vec<usize>,
+    matched: usize,
+    hotkeys: &[Hotkey],
+    now: Instant,
+) -> SequenceEvent {
+    if let Some(&done) = candidates
+        .iter()
+        .find(|&&index| hotkeys[index].sequence_len() == matched)
+    {
+        log::info!("Key sequence {} complete", hotkeys[done].trigger.describe());
+        return SequenceEvent::Completed(done);
+    }
+
+    log::info!(
+        "Key sequence step {} matched ({} candidate{})",
+        matched,
+        candidates.len(),
+        if candidates.len() == 1 { "" } else { "s" }
+    );
+    *progress = Some(Progress {
+        candidates,
+        matched,
+        last_step: now,
+    });
+    SequenceEvent::Consumed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::hotkey::{ChordPattern, TriggerPattern};
+    use crate::input::keycodes::key_name_to_codes;
+    use std::time::Duration;
+
+    /// The engine keeps its progress in a static, so the tests take turns
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    /// Take the test lock, even if an earlier test failed while holding it
+    fn serial() -> std::sync::MutexGuard<'static, ()> {
+        SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn noop(_: &crate::config::Params) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// A sequence hotkey, one "ctrl+k" style chord per step
+    fn sequence(steps: &[&str], timeout_ms: u64) -> Hotkey {
+        let steps = steps
+            .iter()
+            .map(|chord| ChordPattern::Simultaneous {
+                key_groups: chord
+                    .split('+')
+                    .map(|name| key_name_to_codes(name).unwrap())
+                    .collect(),
+            })
+            .collect();
+        Hotkey {
+            trigger: TriggerPattern::Keyboard(ChordPattern::Sequence { steps, timeout_ms }),
+            action_name: "test".to_string(),
+            action: noop,
+            params: crate::config::Params::empty(),
+            trigger_on_release: false,
+            notify: false,
+            silent: false,
+            restore_focus: false,
+            carbon: false,
+            check_for_text_field: false,
+            application: None,
+            app_window: None,
+            midi_device: None,
+            midi_channel: None,
+            velocity_layers: Vec::new(),
+            fader: None,
+            wheel: None,
+            when_stopped: false,
+            tags: Vec::new(),
+            layers: Vec::new(),
+            tap_count: 1,
+            tap_window: Duration::ZERO,
+        }
+    }
+
+    /// The keys held for a chord ("ctrl+k")
+    fn keys(chord: &str) -> HashSet<u16> {
+        chord
+            .split('+')
+            .map(|name| key_name_to_codes(name).unwrap()[0])
+            .collect()
+    }
+
+    /// ctrl+k, m and ctrl+k, x, y (the second with a 50ms step timeout)
+    fn leaders() -> Vec<Hotkey> {
+        vec![
+            sequence(&["ctrl+k", "m"], DEFAULT_TIMEOUT_MS),
+            sequence(&["ctrl+k", "x", "y"], 50),
+        ]
+    }
+
+    #[test]
+    fn test_step_order() {
+        let _serial = serial();
+        reset();
+        let hotkeys = leaders();
+        // One instant for every press: these steps are never too slow
+        let now = Instant::now();
+
+        // Steps in order complete the sequence
+        assert_eq!(
+            on_key_down_at(&keys("ctrl+k"), &hotkeys, now),
+            SequenceEvent::Consumed
+        );
+        assert_eq!(
+            on_key_down_at(&keys("m"), &hotkeys, now),
+            SequenceEvent::Completed(0)
+        );
+
+        // A later step on its own isn't a sequence
+        assert_eq!(
+            on_key_down_at(&keys("m"), &hotkeys, now),
+            SequenceEvent::Ignored
+        );
+
+        // Modifiers held for the next step keep it going
+        assert_eq!(
+            on_key_down_at(&keys("ctrl+k"), &hotkeys, now),
+            SequenceEvent::Consumed
+        );
+        assert_eq!(
+            on_key_down_at(&keys("shift"), &hotkeys, now),
+            SequenceEvent::Waiting
+        );
+        assert_eq!(
+            on_key_down_at(&keys("x"), &hotkeys, now),
+            SequenceEvent::Consumed
+        );
+        assert_eq!(
+            on_key_down_at(&keys("y"), &hotkeys, now),
+            SequenceEvent::Completed(1)
+        );
+    }
+
+    #[test]
+    fn test_wrong_step_cancels() {
+        let _serial = serial();
+        reset();
+        let hotkeys = leaders();
+        // One instant for every press: these steps are never too slow
+        let now = Instant::now();
+
+        assert_eq!(
+            on_key_down_at(&keys("ctrl+k"), &hotkeys, now),
+            SequenceEvent::Consumed
+        );
+        assert_eq!(
+            on_key_down_at(&keys("x"), &hotkeys, now),
+            SequenceEvent::Consumed
+        );
+        // No sequence goes ctrl+k, x, m: swallowed, and the sequence is over
+        assert_eq!(
+            on_key_down_at(&keys("m"), &hotkeys, now),
+            SequenceEvent::Consumed
+        );
+        assert_eq!(
+            on_key_down_at(&keys("y"), &hotkeys, now),
+            SequenceEvent::Ignored
+        );
+    }
+
+    #[test]
+    fn test_timeout_between_steps() {
+        let _serial = serial();
+        reset();
+        let hotkeys = leaders();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        // Within the (longest candidate's) timeout
+        assert_eq!(
+            on_key_down_at(&keys("ctrl+k"), &hotkeys, at(0)),
+            SequenceEvent::Consumed
+        );
+        assert_eq!(
+            on_key_down_at(&keys("x"), &hotkeys, at(80)),
+            SequenceEvent::Consumed
+        );
+
+        // Only the 50ms sequence is left, and it has run out
+        assert_eq!(
+            on_key_down_at(&keys("y"), &hotkeys, at(160)),
+            SequenceEvent::Ignored
+        );
+
+        // The next first step starts over
+        assert_eq!(
+            on_key_down_at(&keys("ctrl+k"), &hotkeys, at(170)),
+            SequenceEvent::Consumed
+        );
+        assert_eq!(
+            on_key_down_at(&keys("m"), &hotkeys, at(180)),
+            SequenceEvent::Completed(0)
+        );
+    }
+}
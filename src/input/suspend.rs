@@ -0,0 +1,63 @@
+//! Suspending all hotkeys (`os.toggle_suspend`)
+//!
+//! While suspended no binding fires or consumes its key, so typing in an
+//! unusual app or during a screen share reaches the app untouched. Only
+//! bindings to `os.toggle_suspend` itself still work, so the same chord
+//! resumes. An optional timer resumes on its own in case it's forgotten.
+//!
+//! The menu bar icon is dimmed while suspended, and Carbon hotkeys other than
+//! the resume chord are unregistered until resumed.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+/// Bumped on every change so a stale auto-resume timer knows it was overtaken
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// The action that keeps working while suspended (registry name, without namespace)
+const RESUME_ACTION: &str = "toggle_suspend";
+
+pub fn is_suspended() -> bool {
+    SUSPENDED.load(Ordering::SeqCst)
+}
+
+/// Suspend or resume; a suspension ends on its own after `auto_resume`, if given
+pub fn set(suspended: bool, auto_resume: Option<Duration>) {
+    SUSPENDED.store(suspended, Ordering::SeqCst);
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    log::info!(
+        "Hotkeys {}",
+        if suspended { "SUSPENDED" } else { "RESUMED" }
+    );
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::menubar::set_suspended(suspended);
+        crate::macos::carbon_hotkeys::refresh_for_suspend();
+    }
+
+    if let Some(delay) = auto_resume.filter(|_| suspended) {
+        std::thread::spawn(move || {
+            std::thread::sleep(delay);
+            if GENERATION.load(Ordering::SeqCst) == generation {
+                log::info!("Auto-resuming hotkeys after {}s", delay.as_secs());
+                set(false, None);
+                #[cfg(target_os = "macos")]
+                crate::macos::show_notification(&crate::notify::text("hotkeys_resumed", &[]));
+            }
+        });
+    }
+}
+
+/// Toggle suspension, returning true if now suspended
+pub fn toggle(auto_resume: Option<Duration>) -> bool {
+    let suspended = !is_suspended();
+    set(suspended, auto_resume);
+    suspended
+}
+
+/// True if a binding to `action_name` may fire (namespaced or not)
+pub fn allows(action_name: &str) -> bool {
+    !is_suspended() || action_name.rsplit('.').next() == Some(RESUME_ACTION)
+}
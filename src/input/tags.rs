@@ -0,0 +1,66 @@
+//! Hotkey tags
+//!
+//! Hotkeys can carry arbitrary tags (`tags = ["destructive", "adr", "mix"]`)
+//! on top of the built-in "edit"/"destructive" tags. Tags can be disabled at
+//! runtime to switch off whole groups of hotkeys at once, and are what review
+//! mode keys off.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// Tags that are currently disabled (stored lowercase)
+static DISABLED_TAGS: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+pub fn disable(tag: &str) {
+    log::info!("Disabling hotkeys tagged '{}'", tag);
+    DISABLED_TAGS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashSet::new)
+        .insert(tag.to_lowercase());
+}
+
+pub fn enable(tag: &str) {
+    log::info!("Enabling hotkeys tagged '{}'", tag);
+    if let Some(disabled) = DISABLED_TAGS.lock().unwrap().as_mut() {
+        disabled.remove(&tag.to_lowercase());
+    }
+}
+
+pub fn is_tag_disabled(tag: &str) -> bool {
+    DISABLED_TAGS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|disabled| disabled.contains(&tag.to_lowercase()))
+}
+
+/// Returns true if any of the given tags is disabled
+pub fn any_disabled(tags: &[String]) -> bool {
+    tags.iter().any(|tag| is_tag_disabled(tag))
+}
+
+/// Refuse an action run without a hotkey (IPC, HTTP, OSC routing) the way
+/// `Hotkey::check_conditions` would: its built-in tags are disabled or
+/// blocked by review mode
+pub fn check_action(action_name: &str) -> anyhow::Result<()> {
+    let tags = crate::config::implicit_tags(action_name);
+    if any_disabled(&tags) {
+        anyhow::bail!("'{}' not run - one of its tags is disabled", action_name);
+    }
+    if super::review::blocks(&tags) {
+        anyhow::bail!("'{}' not run - blocked by review mode", action_name);
+    }
+    Ok(())
+}
+
+/// Merge tags, dropping case-insensitive duplicates (first spelling wins)
+pub fn merge_tags(a: &[String], b: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::new();
+    for tag in a.iter().chain(b) {
+        if !merged.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            merged.push(tag.clone());
+        }
+    }
+    merged
+}
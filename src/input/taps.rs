@@ -0,0 +1,125 @@
+//! Multi-tap bindings (`tap_count = 2`)
+//!
+//! The same chord can run one action on a single tap and another on a double
+//! (or triple) tap. When a chord has multi-tap bindings, each press is
+//! counted instead of run: reaching the highest tap count bound runs that
+//! binding at once, otherwise the count's binding runs when the tap window
+//! passes without another press. Chords without multi-tap bindings are not
+//! delayed at all.
+
+use super::hotkey::{Hotkey, pick_binding};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Default time allowed between taps
+pub const DEFAULT_WINDOW_MS: u64 = 300;
+
+/// The chord being tapped, if any
+static PROGRESS: Mutex<Option<Progress>> = Mutex::new(None);
+
+/// Bumped on every tap and never reset, so a stale timer can't mistake a
+/// later tap's progress for its own
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+struct Progress {
+    /// Indices into HOTKEYS of the bindings on the tapped chord
+    bindings: Vec<usize>,
+    count: u8,
+    last_tap: Instant,
+    /// The tap's `GENERATION`, so a stale timer knows it was overtaken
+    generation: u64,
+}
+
+/// What a press of a multi-tap chord means
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TapEvent {
+    /// Run this hotkey index now
+    Fire(usize),
+    /// Counted - a later tap or the window running out decides
+    Counted,
+}
+
+/// Forget any taps being counted (the hotkey table is about to change)
+///
+/// Also moves the generation on, so a timer already waiting out its window
+/// doesn't fire an index that now belongs to another binding.
+pub fn reset() {
+    let mut progress = PROGRESS.lock().unwrap();
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    *progress = None;
+}
+
+/// True if any of the bindings on a chord wants more than one tap
+pub fn is_multi_tap(matching: &[(usize, &Hotkey)]) -> bool {
+    matching.iter().any(|(_, hotkey)| hotkey.tap_count > 1)
+}
+
+/// Count a press of the chord whose bindings are `matching`
+///
+/// When the window passes without another tap, `fire` is called (on a timer
+/// thread) with the index of the binding for the count reached, if there is one.
+pub fn on_tap(matching: &[(usize, &Hotkey)], fire: fn(usize)) -> TapEvent {
+    let bindings: Vec<usize> = matching.iter().map(|(index, _)| *index).collect();
+    let window = matching
+        .iter()
+        .map(|(_, hotkey)| hotkey.tap_window)
+        .max()
+        .unwrap_or(Duration::from_millis(DEFAULT_WINDOW_MS));
+    let most = matching
+        .iter()
+        .map(|(_, hotkey)| hotkey.tap_count)
+        .max()
+        .unwrap_or(1);
+
+    let mut progress = PROGRESS.lock().unwrap();
+    let count = match progress.as_ref() {
+        Some(current) if current.bindings == bindings && current.last_tap.elapsed() <= window => {
+            current.count + 1
+        }
+        _ => 1,
+    };
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let for_count = |count: u8| {
+        pick_binding(
+            matching
+                .iter()
+                .copied()
+                .filter(|(_, hotkey)| hotkey.tap_count == count),
+        )
+        .map(|(index, _)| index)
+    };
+
+    if count >= most {
+        *progress = None;
+        return match for_count(count) {
+            Some(index) => TapEvent::Fire(index),
+            None => TapEvent::Counted,
+        };
+    }
+
+    *progress = Some(Progress {
+        bindings,
+        count,
+        last_tap: Instant::now(),
+        generation,
+    });
+    let pending = for_count(count);
+    std::thread::spawn(move || {
+        std::thread::sleep(window);
+        let mut progress = PROGRESS.lock().unwrap();
+        if progress
+            .as_ref()
+            .is_some_and(|p| p.generation == generation)
+        {
+            *progress = None;
+            drop(progress);
+            if let Some(index) = pending {
+                fire(index);
+            } else {
+                log::debug!("No binding for {} tap(s) - ignored", count);
+            }
+        }
+    });
+    TapEvent::Counted
+}
@@ -0,0 +1,159 @@
+//! Scroll-wheel bindings (`wheel = { ... }`)
+//!
+//! A chord held down turns the scroll wheel (or a two-finger trackpad swipe)
+//! into an encoder: each scroll event while the chord is held adds to an
+//! amount, and the action runs with that amount as a param - clip gain,
+//! zoom, nudge. Runs are throttled like faders (`input::fader`), but scroll
+//! deltas add up between runs instead of replacing each other, so a fast
+//! spin lands exactly as far as it was turned.
+
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// A continuous scroll binding: each scroll line moves `param` by `step`,
+/// more per line the faster the wheel turns
+///
+/// Configured as `wheel = { param = "db", step = 0.5 }` on a hotkey whose
+/// `keys` is a single chord.
+#[derive(Debug, Clone)]
+pub struct WheelMapping {
+    pub param: String,
+    pub step: f64,
+    /// Extra amount per line for each line in the same scroll event (0 = none)
+    pub acceleration: f64,
+    /// Flip the direction (scrolling down counts up)
+    pub invert: bool,
+    /// Whole numbers only (when `step` is an integer)
+    pub integer: bool,
+    /// Shortest gap between runs; scrolls in between add up
+    pub interval: Duration,
+}
+
+/// Default gap between wheel runs
+pub const DEFAULT_WHEEL_INTERVAL_MS: u64 = 50;
+
+impl WheelMapping {
+    /// Parse `{ param, step = 1, acceleration = 0, invert = false, interval_ms = 50 }`
+    pub fn from_table(table: &toml::Table) -> Result<Self> {
+        let Some(param) = table.get("param").and_then(|v| v.as_str()) else {
+            bail!("'param' (the param that gets the amount) is required");
+        };
+        let (step, integer) = match table.get("step") {
+            None => (1.0, true),
+            Some(toml::Value::Integer(i)) => (*i as f64, true),
+            Some(toml::Value::Float(f)) => (*f, false),
+            Some(other) => bail!("'step' must be a number, got {}", other),
+        };
+        let acceleration = match table.get("acceleration") {
+            None => 0.0,
+            Some(toml::Value::Integer(i)) => *i as f64,
+            Some(toml::Value::Float(f)) => *f,
+            Some(other) => bail!("'acceleration' must be a number, got {}", other),
+        };
+        if acceleration < 0.0 {
+            bail!("'acceleration' can't be negative");
+        }
+        let invert = table
+            .get("invert")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let interval_ms = table
+            .get("interval_ms")
+            .and_then(|v| v.as_integer())
+            .unwrap_or(DEFAULT_WHEEL_INTERVAL_MS as i64);
+        if let Some(key) = table.keys().find(|key| {
+            !matches!(
+                key.as_str(),
+                "param" | "step" | "acceleration" | "invert" | "interval_ms"
+            )
+        }) {
+            bail!("unknown wheel setting '{}'", key);
+        }
+
+        Ok(Self {
+            param: param.to_string(),
+            step,
+            acceleration,
+            invert,
+            integer,
+            interval: Duration::from_millis(interval_ms.max(0) as u64),
+        })
+    }
+
+    /// The amount one scroll event of `lines` (positive = up) is worth
+    pub fn amount(&self, lines: f64) -> f64 {
+        let amount = lines * self.step * (1.0 + self.acceleration * (lines.abs() - 1.0).max(0.0));
+        if self.invert { -amount } else { amount }
+    }
+}
+
+/// Amounts scrolled but not yet run, by binding
+static PENDING: Mutex<Option<HashMap<String, f64>>> = Mutex::new(None);
+
+/// Add a scroll event's amount to `binding`'s pending total
+pub fn add(binding: &str, amount: f64) {
+    *PENDING
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .entry(binding.to_string())
+        .or_default() += amount;
+}
+
+/// Take `binding`'s pending total as a param value, or None if there's
+/// nothing to run. Whole-number mappings keep the fraction for next time.
+pub fn take(binding: &str, integer: bool) -> Option<toml::Value> {
+    let mut pending = PENDING.lock().unwrap();
+    let total = pending.as_mut()?.get_mut(binding)?;
+    if integer {
+        let whole = total.trunc();
+        *total -= whole;
+        (whole != 0.0).then_some(toml::Value::Integer(whole as i64))
+    } else {
+        let amount = std::mem::take(total);
+        (amount != 0.0).then_some(toml::Value::Float(amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(toml: &str) -> toml::Table {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_wheel_mapping() {
+        let zoom = WheelMapping::from_table(&table("param = 'steps'")).unwrap();
+        assert!(zoom.integer);
+        assert_eq!(zoom.amount(3.0), 3.0);
+        assert_eq!(zoom.amount(-1.0), -1.0);
+
+        let gain = WheelMapping::from_table(&table(
+            "param = 'db'\nstep = 0.5\nacceleration = 1\ninvert = true",
+        ))
+        .unwrap();
+        assert!(!gain.integer);
+        assert_eq!(gain.amount(1.0), -0.5);
+        // 3 lines at once: each is worth 3x a single line
+        assert_eq!(gain.amount(3.0), -4.5);
+
+        assert!(WheelMapping::from_table(&table("step = 2")).is_err());
+        assert!(WheelMapping::from_table(&table("param = 'x'\nacceleration = -1")).is_err());
+        assert!(WheelMapping::from_table(&table("param = 'x'\nspeed = 3")).is_err());
+    }
+
+    #[test]
+    fn test_pending_amounts() {
+        add("test wheel", 0.6);
+        add("test wheel", 0.6);
+        assert_eq!(take("test wheel", true), Some(toml::Value::Integer(1)));
+        // The leftover 0.2 isn't a whole step yet
+        assert_eq!(take("test wheel", true), None);
+        add("test wheel", -1.2);
+        assert_eq!(take("test wheel", true), Some(toml::Value::Integer(-1)));
+    }
+}
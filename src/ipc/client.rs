@@ -0,0 +1,207 @@
+//! IPC protocol and CLI client
+//!
+//! Shared by `pthkd <command>` and the standalone `pthkdctl` binary, so this
+//! file only depends on external crates.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+usage:
+  bind <keys> <action> [name=value ...] [--save]   add or replace a keyboard binding
+  run <action> [name=value ...]                    run an action and wait for its result
+  reload                                           reload config.toml
+  actions                                          list registered actions
+  status [--json]                                  show daemon status (one line, or JSON)
+  logs [-n <lines>] [--follow]                     show the daemon log, and keep streaming it";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Request {
+    /// Add or replace a keyboard binding, optionally saving it to config.toml
+    Bind {
+        keys: Vec<String>,
+        action: String,
+        #[serde(default)]
+        params: HashMap<String, toml::Value>,
+        #[serde(default)]
+        save: bool,
+    },
+    /// Run a registered action by name, e.g. "pt.tracks_solo_selected"
+    Run {
+        action: String,
+        #[serde(default)]
+        params: HashMap<String, toml::Value>,
+    },
+    /// Reload config.toml
+    Reload,
+    /// List registered action names
+    Actions,
+    /// Daemon status: one line of text, or a JSON object with `json`
+    Status {
+        #[serde(default)]
+        json: bool,
+    },
+    /// The last `lines` of the log; with `follow`, then every new line (info and above)
+    Logs {
+        #[serde(default = "default_log_lines")]
+        lines: usize,
+        #[serde(default)]
+        follow: bool,
+    },
+}
+
+fn default_log_lines() -> usize {
+    50
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Response {
+    pub ok: bool,
+    pub message: String,
+}
+
+/// Socket path: ~/Library/Application Support/pthkd/pthkd.sock (next to config.toml)
+pub fn socket_path() -> Result<PathBuf> {
+    let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    Ok(PathBuf::from(home)
+        .join("Library")
+        .join("Application Support")
+        .join("pthkd")
+        .join("pthkd.sock"))
+}
+
+/// Run a CLI command against the running daemon
+pub fn run_cli(args: &[String]) -> Result<()> {
+    let request = match args.first().map(String::as_str) {
+        Some("bind") => parse_bind(&args[1..])?,
+        Some("run") => parse_run(&args[1..])?,
+        Some("reload") => Request::Reload,
+        Some("actions") => Request::Actions,
+        Some("status") => parse_status(&args[1..])?,
+        Some("logs") => parse_logs(&args[1..])?,
+        _ => anyhow::bail!("Unknown command\n{}", USAGE),
+    };
+    if let Request::Logs { follow: true, .. } = request {
+        return follow(&request);
+    }
+    let response = send(&request)?;
+    if response.ok {
+        println!("{}", response.message);
+        Ok(())
+    } else {
+        anyhow::bail!("{}", response.message)
+    }
+}
+
+/// `bind <keys> <action> [name=value ...] [--save]`
+fn parse_bind(args: &[String]) -> Result<Request> {
+    const USAGE: &str = "usage: pthkd bind <keys> <action> [name=value ...] [--save]";
+
+    let mut save = false;
+    let mut positional = Vec::new();
+    let mut params = HashMap::new();
+    for arg in args {
+        if arg == "--save" {
+            save = true;
+        } else if let Some((name, value)) = arg.split_once('=') {
+            params.insert(name.to_string(), parse_param_value(value));
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+    let [keys, action] = positional[..] else {
+        anyhow::bail!(USAGE);
+    };
+
+    Ok(Request::Bind {
+        keys: keys.split('+').map(|k| k.trim().to_string()).collect(),
+        action: action.to_string(),
+        params,
+        save,
+    })
+}
+
+/// `run <action> [name=value ...]`
+fn parse_run(args: &[String]) -> Result<Request> {
+    const USAGE: &str = "usage: pthkd run <action> [name=value ...]";
+
+    let (action, rest) = args.split_first().context(USAGE)?;
+    let mut params = HashMap::new();
+    for arg in rest {
+        let (name, value) = arg.split_once('=').context(USAGE)?;
+        params.insert(name.to_string(), parse_param_value(value));
+    }
+    Ok(Request::Run {
+        action: action.clone(),
+        params,
+    })
+}
+
+/// `status [--json]`
+fn parse_status(args: &[String]) -> Result<Request> {
+    match args {
+        [] => Ok(Request::Status { json: false }),
+        [flag] if flag == "--json" => Ok(Request::Status { json: true }),
+        _ => anyhow::bail!("usage: pthkd status [--json]"),
+    }
+}
+
+/// `logs [-n <lines>] [--follow]`
+fn parse_logs(args: &[String]) -> Result<Request> {
+    const USAGE: &str = "usage: pthkd logs [-n <lines>] [--follow]";
+
+    let mut lines = default_log_lines();
+    let mut follow = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-f" | "--follow" => follow = true,
+            "-n" | "--lines" => {
+                lines = args.next().and_then(|n| n.parse().ok()).context(USAGE)?;
+            }
+            _ => anyhow::bail!(USAGE),
+        }
+    }
+    Ok(Request::Logs { lines, follow })
+}
+
+/// Parse `value` as a TOML value (number, bool, array...), falling back to a string
+fn parse_param_value(value: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("v = {}", value))
+        .ok()
+        .and_then(|mut table| table.remove("v"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()))
+}
+
+fn send(request: &Request) -> Result<Response> {
+    let mut line = String::new();
+    connect(request)?.read_line(&mut line)?;
+    serde_json::from_str(&line).context("Invalid response from pthkd")
+}
+
+/// Print every response line until the daemon closes the connection (or Ctrl-C)
+fn follow(request: &Request) -> Result<()> {
+    for line in connect(request)?.lines() {
+        let response: Response =
+            serde_json::from_str(&line?).context("Invalid response from pthkd")?;
+        if !response.ok {
+            anyhow::bail!("{}", response.message);
+        }
+        println!("{}", response.message);
+    }
+    Ok(())
+}
+
+/// Connect to the daemon and send a request, ready to read the response
+fn connect(request: &Request) -> Result<BufReader<UnixStream>> {
+    let path = socket_path()?;
+    let mut stream = UnixStream::connect(&path)
+        .with_context(|| format!("pthkd is not running (no socket at {})", path.display()))?;
+    writeln!(stream, "{}", serde_json::to_string(request)?)?;
+    Ok(BufReader::new(stream))
+}
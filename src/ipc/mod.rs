@@ -0,0 +1,413 @@
+//! Local IPC with the running daemon
+//!
+//! The daemon listens on a Unix socket next to config.toml. Each connection
+//! sends one JSON request line and gets one JSON response line back. The
+//! `pthkd <command>` CLI and the `pthkdctl` binary are the client side
+//! (see `client`), e.g.
+//!
+//! ```text
+//! pthkd bind "cmd+f14" edit_crossfade preset=Short --save
+//! pthkdctl run pt.tracks_solo_selected
+//! pthkdctl run os.keystroke 'keys=["cmd", "s"]'
+//! pthkdctl reload
+//! pthkdctl status --json
+//! pthkdctl logs --follow
+//! ```
+
+pub mod client;
+
+pub use client::{Request, Response, run_cli, socket_path};
+
+use crate::config::{append_binding, binding_table, binding_to_hotkey, same_chord};
+use crate::input::{HOTKEYS, TriggerPattern};
+use crate::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::Once;
+
+static START: Once = Once::new();
+
+/// True if the command line asks for a CLI command rather than the daemon
+pub fn is_cli_command(args: &[String]) -> bool {
+    matches!(
+        args.first().map(String::as_str),
+        Some("bind" | "run" | "reload" | "actions" | "status" | "logs")
+    )
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+/// Start listening for IPC requests (safe to call more than once)
+pub fn start() {
+    START.call_once(|| {
+        let listener = match bind_listener() {
+            Ok(listener) => listener,
+            Err(e) => {
+                log::error!("IPC disabled: {:#}", e);
+                return;
+            }
+        };
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // A `run` waits for its action, so don't make other clients queue behind it
+                std::thread::spawn(move || {
+                    if let Err(e) = serve(stream) {
+                        log::warn!("IPC connection failed: {:#}", e);
+                    }
+                });
+            }
+        });
+    });
+}
+
+fn bind_listener() -> R<UnixListener> {
+    let path = socket_path()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    // A socket left behind by a previous run would make bind fail
+    if path.exists() && UnixStream::connect(&path).is_err() {
+        std::fs::remove_file(&path).ok();
+    }
+    let listener = UnixListener::bind(&path).with_context(|| {
+        format!(
+            "Failed to bind {} (is pthkd already running?)",
+            path.display()
+        )
+    })?;
+    log::info!("IPC listening on {}", path.display());
+    Ok(listener)
+}
+
+fn serve(stream: UnixStream) -> R<()> {
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+
+    let request = serde_json::from_str::<Request>(&line);
+    let follow = matches!(request, Ok(Request::Logs { follow: true, .. }));
+    let response = match request {
+        Ok(request) => match handle(request) {
+            Ok(message) => Response { ok: true, message },
+            Err(e) => Response {
+                ok: false,
+                message: format!("{:#}", e),
+            },
+        },
+        Err(e) => Response {
+            ok: false,
+            message: format!("Invalid request: {}", e),
+        },
+    };
+
+    let mut stream = stream;
+    writeln!(stream, "{}", serde_json::to_string(&response)?)?;
+    if follow && response.ok {
+        follow_logs(stream)?;
+    }
+    Ok(())
+}
+
+/// How often a quiet `logs --follow` checks that its client is still there
+const FOLLOW_CHECK: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Send every new log line (info and above) until the client disconnects
+fn follow_logs(mut stream: UnixStream) -> R<()> {
+    let events = crate::broadcast::subscribe();
+    loop {
+        match events.recv_timeout(FOLLOW_CHECK) {
+            Ok(event) => {
+                let Some(line) = event.log_line() else {
+                    continue;
+                };
+                let response = Response {
+                    ok: true,
+                    message: line,
+                };
+                if writeln!(stream, "{}", serde_json::to_string(&response)?).is_err() {
+                    return Ok(());
+                }
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) if client_gone(&stream) => {
+                return Ok(());
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+    }
+}
+
+/// True once the client has closed its end (it never sends after the request)
+fn client_gone(stream: &UnixStream) -> bool {
+    let mut byte = [0u8; 1];
+    if stream.set_nonblocking(true).is_err() {
+        return true;
+    }
+    let gone = match (&*stream).read(&mut byte) {
+        Ok(0) => true,
+        Ok(_) => false,
+        Err(e) => e.kind() != std::io::ErrorKind::WouldBlock,
+    };
+    stream.set_nonblocking(false).is_err() || gone
+}
+
+/// The last `count` lines of the log file
+pub fn recent_log_lines(count: usize) -> R<String> {
+    /// Only the end of the file is read - it grows across runs
+    const TAIL_BYTES: u64 = 256 * 1024;
+
+    let path = crate::config::get_log_path()?;
+    let mut file =
+        std::fs::File::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let start = file.metadata()?.len().saturating_sub(TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+
+    let text = String::from_utf8_lossy(&bytes);
+    // Starting mid-file, the first line is probably cut off
+    let lines: Vec<&str> = text.lines().skip(usize::from(start > 0)).collect();
+    Ok(lines[lines.len().saturating_sub(count)..].join("\n"))
+}
+
+fn handle(request: Request) -> R<String> {
+    match request {
+        Request::Bind {
+            keys,
+            action,
+            params,
+            save,
+        } => bind(&keys, &action, params, save),
+        Request::Run { action, params } => run(&action, params),
+        Request::Reload => reload(),
+        Request::Actions => Ok(list_actions()),
+        Request::Status { json: false } => Ok(status()),
+        Request::Status { json: true } => Ok(serde_json::to_string(&Status::current())?),
+        Request::Logs { lines, .. } => recent_log_lines(lines),
+    }
+}
+
+/// Registered actions, then the AudioSuite plugins `pt.plugins_audiosuite` can open
+fn list_actions() -> String {
+    let text = crate::config::action_names().join("\n");
+    #[cfg(target_os = "macos")]
+    if let Some(plugins) = crate::protools::plugins::known_plugins() {
+        return format!(
+            "{}\n\nAudioSuite plugins (pt.plugins_audiosuite plugin = \"...\"):\n  {}",
+            text,
+            plugins.join("\n  ")
+        );
+    }
+    text
+}
+
+/// Run a registered action and wait for its result
+fn run(action_name: &str, params: HashMap<String, toml::Value>) -> R<String> {
+    log::info!("IPC: running {}", action_name);
+    run_action(action_name, Params::new(params))
+}
+
+/// Run a registered action by name and wait for it (IPC, HTTP API, menu bar items)
+pub fn run_action(action_name: &str, params: Params) -> R<String> {
+    let action = crate::config::get_action(action_name)
+        .with_context(|| format!("Unknown action '{}'", action_name))?;
+    crate::platform::current().check_action_permissions(action_name)?;
+
+    // Same gate as a hotkey (the Pro Tools wrapper refuses destructive edits
+    // during record on its own)
+    crate::input::tags::check_action(action_name)?;
+
+    // Same rule as hotkeys: these belong on the main thread
+    let result = if crate::workers::is_inline(action_name) {
+        on_main_thread(move || action(&params)).and_then(|result| result)
+    } else {
+        action(&params)
+    };
+    crate::input::frontmost::invalidate();
+    crate::broadcast::action_finished(
+        action_name,
+        result.as_ref().err().map(|e| format!("{:#}", e)),
+    );
+    result.map(|_| format!("{} done", action_name))
+}
+
+#[cfg(target_os = "macos")]
+fn reload() -> R<String> {
+    on_main_thread(crate::macos::commands::reload_config_checked)?
+}
+
+#[cfg(not(target_os = "macos"))]
+fn reload() -> R<String> {
+    anyhow::bail!("Reloading is only supported on macOS")
+}
+
+/// Daemon status, as `pthkd status` reports it
+#[derive(Debug, Serialize)]
+pub struct Status {
+    pub version: &'static str,
+    /// "suspended" while no hotkeys fire, "review" while review mode blocks
+    /// tagged hotkeys, otherwise "normal"
+    pub mode: &'static str,
+    /// The current hotkey layer (None = base)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    pub hotkeys: usize,
+    /// "connected", "disconnected" or "unavailable" (not macOS)
+    pub ptsl: &'static str,
+    pub notifications: &'static str,
+    pub last_action: Option<LastActionStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LastActionStatus {
+    pub action: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub seconds_ago: u64,
+}
+
+impl Status {
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            mode: if crate::input::suspend::is_suspended() {
+                "suspended"
+            } else if crate::input::review::is_enabled() {
+                "review"
+            } else {
+                "normal"
+            },
+            layer: crate::input::layers::current(),
+            hotkeys: HOTKEYS.get().map_or(0, |table| table.load().len()),
+            ptsl: ptsl_state(),
+            notifications: if crate::notify::is_quiet() {
+                "quiet"
+            } else {
+                "on"
+            },
+            last_action: crate::broadcast::last_action().map(|last| LastActionStatus {
+                action: last.action,
+                ok: last.error.is_none(),
+                error: last.error,
+                seconds_ago: last.finished.elapsed().as_secs(),
+            }),
+        }
+    }
+
+    /// Compact single line for tmux status bars and monitoring scripts
+    pub fn line(&self) -> String {
+        let mut line = format!(
+            "pthkd {} | {} | {} hotkeys | PTSL {}",
+            self.version, self.mode, self.hotkeys, self.ptsl
+        );
+        if let Some(layer) = &self.layer {
+            line.push_str(&format!(" | layer {}", layer));
+        }
+        if self.notifications == "quiet" {
+            line.push_str(" | quiet");
+        }
+        match &self.last_action {
+            Some(last) => line.push_str(&format!(
+                " | last: {} {} {}s ago",
+                last.action,
+                if last.ok { "ok" } else { "failed" },
+                last.seconds_ago
+            )),
+            None => line.push_str(" | last: -"),
+        }
+        line
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn ptsl_state() -> &'static str {
+    if crate::protools::client::is_connected() {
+        "connected"
+    } else {
+        "disconnected"
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn ptsl_state() -> &'static str {
+    "unavailable"
+}
+
+/// One-line daemon status
+pub fn status() -> String {
+    Status::current().line()
+}
+
+/// Run `f` on the main thread and wait for its result
+#[cfg(target_os = "macos")]
+fn on_main_thread<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> R<T> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    unsafe {
+        OS::dispatch_to_main_queue(move || {
+            sender.send(f()).ok();
+        });
+    }
+    receiver.recv().context("Main thread dropped the request")
+}
+
+#[cfg(not(target_os = "macos"))]
+fn on_main_thread<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> R<T> {
+    Ok(f())
+}
+
+/// Add a keyboard binding, replacing any existing hotkey on the same keys
+fn bind(
+    keys: &[String],
+    action: &str,
+    params: HashMap<String, toml::Value>,
+    save: bool,
+) -> R<String> {
+    let binding = binding_table(keys, action, params);
+    let hotkey = binding_to_hotkey(&binding)?;
+    let TriggerPattern::Keyboard(chord) = &hotkey.trigger else {
+        anyhow::bail!("Only keyboard bindings are supported");
+    };
+    let description = chord.describe();
+
+    // Save first so a refused save leaves the running set untouched
+    if save {
+        append_binding(&binding)?;
+    }
+
+    // Copy-modify-swap so the event tap never waits on this edit
+    let table = HOTKEYS.get().context("HOTKEYS not initialized")?;
+    let mut message = String::new();
+    table.rcu(|current| {
+        let mut hotkeys = Vec::clone(current);
+        let existing = hotkeys.iter().position(|hk| {
+            matches!(&hk.trigger, TriggerPattern::Keyboard(existing) if same_chord(existing, chord))
+        });
+        message = match existing {
+            // Replace in place so any Carbon registration for these keys stays valid
+            Some(index) => {
+                let previous = std::mem::replace(&mut hotkeys[index], hotkey.clone());
+                format!(
+                    "{} rebound: {} => {}",
+                    description, previous.action_name, action
+                )
+            }
+            None => {
+                hotkeys.push(hotkey.clone());
+                format!("{} bound to {}", description, action)
+            }
+        };
+        hotkeys
+    });
+    crate::input::sequence::reset();
+    crate::input::taps::reset();
+    log::info!("IPC: {}", message);
+    Ok(if save {
+        format!("{} (saved to config.toml)", message)
+    } else {
+        message
+    })
+}
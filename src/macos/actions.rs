@@ -8,9 +8,27 @@ actions_sync!("os", {
     show_notification,
     execute_menu_item,
     reload_config,
+    reveal_config,
+    reveal_log,
+    export_support_bundle,
+    toggle_review_mode,
+    toggle_quiet,
+    toggle_suspend,
+    tally,
+    set_layer,
+    toggle_layer,
+    timer_start,
+    timer_stop,
+    enable_tag,
+    disable_tag,
+    list_tags,
+    usage_report,
+    calibrate_waits,
     dump_app_menus,
     list_running_apps,
     launch_application,
+    relaunch_protools,
+    undo_last_macro,
     list_window_buttons,
     click_window_button,
     display_window_text,
@@ -19,6 +37,12 @@ actions_sync!("os", {
     test_pw,
     list_window_titles,
     test_app_info,
+    keystroke,
+    type_text,
+    record_click,
+    click_recorded,
+    click_image,
+    midi_send,
     test_keystroke,
     shell_script,
     test_window,
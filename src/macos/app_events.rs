@@ -0,0 +1,301 @@
+//! Event triggers (`[[trigger.app_launch]]`, `[[trigger.app_quit]]`, `[[trigger.window]]`)
+//!
+//! Listens for NSWorkspace's launch and terminate notifications and runs the
+//! configured actions for the app that started or quit, e.g. preparing the
+//! window layout once Pro Tools is up.
+//!
+//! Window triggers poll the watched apps' window titles and run their action
+//! once each time a matching window appears - e.g. to dismiss Pro Tools'
+//! missing files warning. Polling rather than an AXObserver keeps it working
+//! across app relaunches without re-registering anything.
+//!
+//! Triggers are skipped while hotkeys are suspended.
+
+use super::ffi::cfstring_to_string;
+use super::session::MacOSSession;
+use crate::config::{AppTriggerConfig, TriggerConfig, WindowTriggerConfig};
+use anyhow::{Context, Result};
+use objc2::msg_send;
+use objc2::runtime::{AnyClass, AnyObject, Sel};
+use std::collections::HashSet;
+use std::ffi::c_void;
+use std::sync::{Mutex, Once};
+use std::time::Duration;
+
+/// Active triggers (None = no `[trigger]` section). Replaced on config reload.
+static TRIGGERS: Mutex<Option<TriggerConfig>> = Mutex::new(None);
+
+/// (window trigger index, title) of the matching windows open at the last poll
+static OPEN_WINDOWS: Mutex<Option<HashSet<(usize, String)>>> = Mutex::new(None);
+
+static START: Once = Once::new();
+
+/// How often the poller checks whether window triggers were added
+const IDLE_POLL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy)]
+enum AppEvent {
+    Launch,
+    Quit,
+}
+
+/// Apply triggers from config, dropping any that name an unknown action
+pub fn set_config(config: Option<TriggerConfig>) {
+    let config = config.map(|mut c| {
+        c.app_launch.retain(is_known);
+        c.app_quit.retain(is_known);
+        c.window
+            .retain(|trigger| is_known_action(&trigger.app, &trigger.action));
+        log::info!(
+            "{} app launch, {} app quit and {} window trigger(s)",
+            c.app_launch.len(),
+            c.app_quit.len(),
+            c.window.len()
+        );
+        c
+    });
+    *TRIGGERS.lock().unwrap() = config;
+    // Indexes may have changed - start over (windows open now count as new)
+    *OPEN_WINDOWS.lock().unwrap() = None;
+}
+
+fn is_known(trigger: &AppTriggerConfig) -> bool {
+    is_known_action(&trigger.app, &trigger.action)
+}
+
+fn is_known_action(app: &str, action: &str) -> bool {
+    let known = crate::config::get_action(action).is_some();
+    if !known {
+        log::error!(
+            "Skipping trigger for '{}': unknown action '{}'",
+            app,
+            action
+        );
+    }
+    known
+}
+
+/// Start listening for app launches, quits and new windows (safe to call more than once)
+pub fn start() {
+    START.call_once(|| {
+        unsafe {
+            super::dispatch_to_main_queue(|| {
+                if let Err(e) = add_observer() {
+                    log::error!("Failed to watch app launches: {:#}", e);
+                }
+            });
+        }
+        std::thread::spawn(window_loop);
+    });
+}
+
+unsafe fn add_observer() -> Result<()> {
+    use objc2::declare::ClassBuilder;
+    use objc2::sel;
+
+    unsafe {
+        let superclass = AnyClass::get("NSObject").context("Failed to get NSObject class")?;
+        let mut builder = ClassBuilder::new("AppEventObserver", superclass)
+            .context("Failed to create class builder")?;
+        builder.add_method(
+            sel!(appLaunched:),
+            app_launched as extern "C" fn(*mut AnyObject, Sel, *mut AnyObject),
+        );
+        builder.add_method(
+            sel!(appQuit:),
+            app_quit as extern "C" fn(*mut AnyObject, Sel, *mut AnyObject),
+        );
+        let class = builder.register();
+        // Never released - it observes for the life of the daemon
+        let observer: *mut AnyObject = msg_send![class, new];
+
+        let os = MacOSSession::global();
+        let workspace = os.get_workspace()?;
+        let center: *mut AnyObject = msg_send![workspace, notificationCenter];
+        for (selector, name) in [
+            (
+                sel!(appLaunched:),
+                "NSWorkspaceDidLaunchApplicationNotification",
+            ),
+            (
+                sel!(appQuit:),
+                "NSWorkspaceDidTerminateApplicationNotification",
+            ),
+        ] {
+            let name = os.create_nsstring(name)?;
+            let _: () = msg_send![
+                center,
+                addObserver: observer
+                selector: selector
+                name: name
+                object: std::ptr::null_mut::<AnyObject>()
+            ];
+        }
+        log::info!("Watching app launches and quits");
+        Ok(())
+    }
+}
+
+extern "C" fn app_launched(_this: *mut AnyObject, _cmd: Sel, notification: *mut AnyObject) {
+    handle(notification, AppEvent::Launch);
+}
+
+extern "C" fn app_quit(_this: *mut AnyObject, _cmd: Sel, notification: *mut AnyObject) {
+    handle(notification, AppEvent::Quit);
+}
+
+fn handle(notification: *mut AnyObject, event: AppEvent) {
+    let Some(app) = (unsafe { app_name(notification) }) else {
+        return;
+    };
+    match event {
+        AppEvent::Launch => crate::protools::recovery::app_launched(&app),
+        AppEvent::Quit => crate::protools::recovery::app_quit(&app),
+    }
+    let triggers: Vec<AppTriggerConfig> = {
+        let config = TRIGGERS.lock().unwrap();
+        let Some(config) = config.as_ref() else {
+            return;
+        };
+        match event {
+            AppEvent::Launch => &config.app_launch,
+            AppEvent::Quit => &config.app_quit,
+        }
+        .iter()
+        .filter(|trigger| crate::soft_match(&app, &trigger.app))
+        .cloned()
+        .collect()
+    };
+    if triggers.is_empty() {
+        return;
+    }
+    if crate::input::suspend::is_suspended() {
+        log::info!("Hotkeys suspended - skipping triggers for '{}'", app);
+        return;
+    }
+    for trigger in triggers {
+        let what = match event {
+            AppEvent::Launch => "launched",
+            AppEvent::Quit => "quit",
+        };
+        log::info!("'{}' {} - running {}", app, what, trigger.action);
+        run(
+            trigger.action,
+            crate::config::Params::new(trigger.params),
+            Duration::from_millis(trigger.delay_ms),
+        );
+    }
+}
+
+/// Run a trigger's action on its own thread after `delay`
+fn run(action: String, params: crate::config::Params, delay: Duration) {
+    // Off the main thread - Pro Tools actions block until they finish
+    std::thread::spawn(move || {
+        std::thread::sleep(delay);
+        if let Err(e) = crate::ipc::run_action(&action, params) {
+            log::error!("Trigger action {} failed: {:#}", action, e);
+            super::show_notification(&crate::notify::text(
+                "action_failed",
+                &[("action", &action), ("error", &format!("{:#}", e))],
+            ));
+        }
+    });
+}
+
+/// Poll the watched apps for new matching windows
+fn window_loop() {
+    loop {
+        let config = TRIGGERS.lock().unwrap().clone();
+        let Some(config) = config.filter(|c| !c.window.is_empty()) else {
+            std::thread::sleep(IDLE_POLL);
+            continue;
+        };
+        std::thread::sleep(Duration::from_millis(config.window_poll_ms.max(100)));
+
+        // One lookup per app, however many triggers watch it
+        let mut titles: Vec<(&str, Vec<String>)> = Vec::new();
+        for trigger in &config.window {
+            if !titles.iter().any(|(app, _)| *app == trigger.app) {
+                // App not running = no windows
+                let open = super::get_window_titles(&trigger.app).unwrap_or_default();
+                titles.push((&trigger.app, open));
+            }
+        }
+        let open: HashSet<(usize, String)> = config
+            .window
+            .iter()
+            .enumerate()
+            .flat_map(|(index, trigger)| {
+                titles
+                    .iter()
+                    .filter(|(app, _)| *app == trigger.app)
+                    .flat_map(|(_, open)| open)
+                    .filter(|title| crate::soft_match(title, &trigger.title))
+                    .map(move |title| (index, title.clone()))
+            })
+            .collect();
+
+        let appeared: Vec<(usize, String)> = {
+            let mut previous = OPEN_WINDOWS.lock().unwrap();
+            let appeared = match previous.as_ref() {
+                Some(previous) => open.difference(previous).cloned().collect(),
+                None => open.iter().cloned().collect(),
+            };
+            *previous = Some(open);
+            appeared
+        };
+        if appeared.is_empty() {
+            continue;
+        }
+        if crate::input::suspend::is_suspended() {
+            log::info!("Hotkeys suspended - skipping window triggers");
+            continue;
+        }
+        for (index, title) in appeared {
+            window_appeared(&config.window[index], &title);
+        }
+    }
+}
+
+fn window_appeared(trigger: &WindowTriggerConfig, title: &str) {
+    log::info!(
+        "'{}' window '{}' appeared - running {}",
+        trigger.app,
+        title,
+        trigger.action
+    );
+    // The action gets the app and window it was triggered by unless set
+    let mut params = trigger.params.clone();
+    params
+        .entry("app".to_string())
+        .or_insert_with(|| toml::Value::String(trigger.app.clone()));
+    params
+        .entry("window".to_string())
+        .or_insert_with(|| toml::Value::String(title.to_string()));
+    run(
+        trigger.action.clone(),
+        crate::config::Params::new(params),
+        Duration::ZERO,
+    );
+}
+
+/// The name of the app a workspace notification is about
+unsafe fn app_name(notification: *mut AnyObject) -> Option<String> {
+    unsafe {
+        let os = MacOSSession::global();
+        let user_info: *mut AnyObject = msg_send![notification, userInfo];
+        if user_info.is_null() {
+            return None;
+        }
+        let key = os.create_nsstring("NSWorkspaceApplicationKey").ok()?;
+        let app: *mut AnyObject = msg_send![user_info, objectForKey: key];
+        if app.is_null() {
+            return None;
+        }
+        let name: *mut AnyObject = msg_send![app, localizedName];
+        if name.is_null() {
+            return None;
+        }
+        cfstring_to_string(name as *mut c_void)
+    }
+}
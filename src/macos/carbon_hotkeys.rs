@@ -184,35 +184,18 @@ unsafe extern "C" fn carbon_hotkey_handler(
 pub fn register_carbon_hotkeys() -> Result<()> {
     // Wrap in catch_unwind to prevent panics from aborting
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| unsafe {
-        let hotkeys_guard = match crate::hotkey::HOTKEYS.get() {
-            Some(h) => h,
+        let hotkeys = match crate::hotkey::HOTKEYS.get() {
+            Some(table) => table.load_full(),
             None => {
                 eprintln!("Carbon: HOTKEYS not initialized");
                 return Err(anyhow::anyhow!("HOTKEYS not initialized"));
             }
         };
 
-        let hotkeys_guard = match hotkeys_guard.lock() {
-            Ok(g) => g,
-            Err(e) => {
-                eprintln!("Carbon: Failed to lock HOTKEYS: {}", e);
-                return Err(anyhow::anyhow!("Failed to lock HOTKEYS: {}", e));
-            }
-        };
-
-        let hotkeys = &*hotkeys_guard;
-
         // Initialize the hotkey map
         let mut map = HashMap::new();
         let mut refs = Vec::new();
 
-        // Install event handler (once for all hotkeys)
-        let event_types = [EventTypeSpec {
-            event_class: K_EVENT_CLASS_KEYBOARD,
-            event_kind: K_EVENT_HOT_KEY_PRESSED,
-        }];
-
-        let mut handler_ref: EventHandlerRef = ptr::null_mut();
         let target = GetApplicationEventTarget();
 
         if target.is_null() {
@@ -220,39 +203,59 @@ pub fn register_carbon_hotkeys() -> Result<()> {
             return Err(anyhow::anyhow!("GetApplicationEventTarget returned null"));
         }
 
-        let status = InstallEventHandler(
-            target,
-            carbon_hotkey_handler,
-            event_types.len() as u32,
-            event_types.as_ptr(),
-            ptr::null_mut(),
-            &mut handler_ref,
-        );
+        // Install event handler (once for all hotkeys, kept across reloads)
+        let handler_installed = CARBON_EVENT_HANDLER
+            .lock()
+            .map(|handler| handler.is_some())
+            .unwrap_or(false);
+        if !handler_installed {
+            let mut handler_ref: EventHandlerRef = ptr::null_mut();
+            let event_types = [EventTypeSpec {
+                event_class: K_EVENT_CLASS_KEYBOARD,
+                event_kind: K_EVENT_HOT_KEY_PRESSED,
+            }];
+
+            let status = InstallEventHandler(
+                target,
+                carbon_hotkey_handler,
+                event_types.len() as u32,
+                event_types.as_ptr(),
+                ptr::null_mut(),
+                &mut handler_ref,
+            );
 
-        if status != 0 {
-            eprintln!("Carbon: Failed to install event handler: status {}", status);
-            return Err(anyhow::anyhow!(
-                "Failed to install Carbon event handler: status {}",
-                status
-            ));
-        }
+            if status != 0 {
+                eprintln!("Carbon: Failed to install event handler: status {}", status);
+                return Err(anyhow::anyhow!(
+                    "Failed to install Carbon event handler: status {}",
+                    status
+                ));
+            }
 
-        match CARBON_EVENT_HANDLER.lock() {
-            Ok(mut guard) => *guard = Some(SendPtr(handler_ref)),
-            Err(e) => {
-                eprintln!("Carbon: Failed to store handler ref: {}", e);
-                return Err(anyhow::anyhow!("Failed to store handler ref: {}", e));
+            match CARBON_EVENT_HANDLER.lock() {
+                Ok(mut guard) => *guard = Some(SendPtr(handler_ref)),
+                Err(e) => {
+                    eprintln!("Carbon: Failed to store handler ref: {}", e);
+                    return Err(anyhow::anyhow!("Failed to store handler ref: {}", e));
+                }
             }
-        }
 
-        eprintln!("✓ Carbon event handler installed");
+            eprintln!("✓ Carbon event handler installed");
+        }
 
         // Register each hotkey that has carbon = true
         let mut hotkey_id = 1u32; // Start IDs at 1
+        let mut failed = Vec::new();
 
         for (index, hotkey) in hotkeys.iter().enumerate() {
-            // Skip hotkeys not marked for Carbon registration
-            if !hotkey.carbon {
+            // Skip hotkeys not marked for Carbon registration (carbon = true or "carbon" tag)
+            if !hotkey.carbon && !hotkey.has_tag("carbon") {
+                continue;
+            }
+
+            // While suspended only the resume chord is registered, so the
+            // others reach the frontmost app
+            if !crate::input::suspend::allows(&hotkey.action_name) {
                 continue;
             }
 
@@ -301,6 +304,7 @@ pub fn register_carbon_hotkeys() -> Result<()> {
                         "✗ Failed to register Carbon hotkey for '{}': status {}",
                         hotkey.action_name, status
                     );
+                    failed.push(hotkey.action_name.clone());
                 }
             } else {
                 eprintln!(
@@ -336,6 +340,13 @@ pub fn register_carbon_hotkeys() -> Result<()> {
             eprintln!("⚠️  No hotkeys marked with carbon=true");
         }
 
+        if !failed.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Failed to register Carbon hotkey(s): {}",
+                failed.join(", ")
+            ));
+        }
+
         Ok(())
     }));
 
@@ -412,15 +423,19 @@ fn chord_to_carbon_spec(chord: &crate::hotkey::ChordPattern) -> Option<(u32, u32
             // Must have exactly one non-modifier key
             key_code.map(|kc| (kc as u32, modifiers))
         }
+        // Carbon only sees single chords
+        ChordPattern::Sequence { .. } => None,
     }
 }
 
-/// Unregisters all Carbon hotkeys (for cleanup)
-#[allow(dead_code)]
+/// Unregisters all Carbon hotkeys
+///
+/// The event handler stays installed so hotkeys can be registered again
+/// (config reload re-registers against the new HOTKEYS indices).
 pub fn unregister_carbon_hotkeys() {
     unsafe {
-        if let Ok(refs) = CARBON_HOTKEY_REFS.lock() {
-            for send_ptr in refs.iter() {
+        if let Ok(mut refs) = CARBON_HOTKEY_REFS.lock() {
+            for send_ptr in refs.drain(..) {
                 let _ = UnregisterEventHotKey(send_ptr.0);
             }
         }
@@ -429,14 +444,57 @@ pub fn unregister_carbon_hotkeys() {
             *map = None;
         }
 
-        if let Ok(mut handler) = CARBON_EVENT_HANDLER.lock() {
-            *handler = None;
-        }
-
         eprintln!("Unregistered all Carbon hotkeys");
     }
 }
 
+/// Register the Carbon hotkeys again for the current suspension state
+///
+/// Carbon hotkeys take their keys before the event tap sees them, so a
+/// suspension has to give them back. Runs on the main thread.
+pub fn refresh_for_suspend() {
+    unsafe {
+        super::dispatch_to_main_queue(|| {
+            unregister_carbon_hotkeys();
+            if let Err(e) = register_carbon_hotkeys() {
+                log::error!("Failed to re-register Carbon hotkeys: {:#}", e);
+            }
+        });
+    }
+}
+
+/// Check that every Carbon-marked keyboard hotkey can be registered
+///
+/// Catches chords Carbon can't express and duplicate combinations (which
+/// RegisterEventHotKey rejects) before a reload swaps the hotkey set.
+pub fn validate_carbon_hotkeys(hotkeys: &[crate::hotkey::Hotkey]) -> Result<()> {
+    let mut seen: HashMap<(u32, u32), &str> = HashMap::new();
+    for hotkey in hotkeys {
+        if !hotkey.carbon && !hotkey.has_tag("carbon") {
+            continue;
+        }
+        let crate::hotkey::TriggerPattern::Keyboard(chord) = &hotkey.trigger else {
+            continue; // Skipped (with a warning) at registration
+        };
+        let spec = chord_to_carbon_spec(chord).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Carbon hotkey '{}' ({}) is too complex for Carbon - use modifiers plus one key",
+                hotkey.action_name,
+                hotkey.trigger.describe()
+            )
+        })?;
+        if let Some(other) = seen.insert(spec, &hotkey.action_name) {
+            anyhow::bail!(
+                "Carbon hotkeys '{}' and '{}' use the same keys ({})",
+                other,
+                hotkey.action_name,
+                hotkey.trigger.describe()
+            );
+        }
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Secure Input Detection
 // ============================================================================
@@ -0,0 +1,186 @@
+//! Recorded clicks
+//!
+//! Some controls can't be reached through accessibility - plugin windows
+//! that draw their own GUI, mostly. `os.record_click` saves where the mouse
+//! is, relative to the window under it, as a `[clicks.<name>]` entry in
+//! config.toml; `os.click_recorded` brings that window forward and clicks
+//! the same spot, wherever the window has moved to. Positions are kept in
+//! points rather than pixels, so they hold on Retina and standard displays
+//! alike.
+
+use super::ffi::*;
+use super::helpers::{AXElement, CFArray, CGEvent};
+use super::session::MacOSSession;
+use crate::config::ClickConfig;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::sync::Mutex;
+
+static CLICKS: Mutex<Option<HashMap<String, ClickConfig>>> = Mutex::new(None);
+
+/// Apply the `[clicks]` section
+pub fn set_config(clicks: HashMap<String, ClickConfig>) {
+    *CLICKS.lock().unwrap() = Some(clicks);
+}
+
+/// A window's title and frame (global points, top-left origin)
+pub(super) struct WindowFrame {
+    pub(super) title: String,
+    pub(super) origin: CGPointRaw,
+    pub(super) size: CGPointRaw,
+}
+
+impl WindowFrame {
+    fn contains(&self, point: CGPointRaw) -> bool {
+        (self.origin.x..self.origin.x + self.size.x).contains(&point.x)
+            && (self.origin.y..self.origin.y + self.size.y).contains(&point.y)
+    }
+}
+
+/// Read an AXPosition / AXSize attribute
+pub(super) unsafe fn ax_point(
+    element: *mut c_void,
+    attr: &str,
+    value_type: u32,
+) -> Result<CGPointRaw> {
+    unsafe {
+        let value = AXElement::new(MacOSSession::global().get_ax_element_attr(element, attr)?);
+        let mut point = CGPointRaw::default();
+        if !AXValueGetValue(
+            value.as_ptr(),
+            value_type,
+            &mut point as *mut CGPointRaw as *mut c_void,
+        ) {
+            anyhow::bail!("Failed to read {}", attr);
+        }
+        Ok(point)
+    }
+}
+
+/// The app's windows, front to back
+pub(super) fn windows(app: &str) -> Result<Vec<WindowFrame>> {
+    let os = MacOSSession::global();
+    let pid = super::get_pid_by_name(app)?;
+    let mut frames = Vec::new();
+    unsafe {
+        let ax_app = AXElement::new(AXUIElementCreateApplication(pid));
+        let windows = CFArray::new(os.get_ax_element_attr(ax_app.as_ptr(), "AXWindows")?);
+        for i in 0..windows.count() {
+            let window = windows.get(i);
+            let (Ok(origin), Ok(size)) = (
+                ax_point(window, "AXPosition", K_AX_VALUE_CG_POINT_TYPE),
+                ax_point(window, "AXSize", K_AX_VALUE_CG_SIZE_TYPE),
+            ) else {
+                continue;
+            };
+            frames.push(WindowFrame {
+                title: os.get_ax_string_attr(window, "AXTitle").unwrap_or_default(),
+                origin,
+                size,
+            });
+        }
+    }
+    Ok(frames)
+}
+
+/// Where the mouse is, in global points
+fn mouse_location() -> Result<CGPointRaw> {
+    unsafe {
+        let event = CGEvent::new(CGEventCreate(std::ptr::null_mut()));
+        if event.is_null() {
+            anyhow::bail!("Failed to read the mouse position");
+        }
+        Ok(CGEventGetLocation(event.as_ptr()))
+    }
+}
+
+/// Move the mouse to `point` and click there
+pub(super) fn click_at(point: CGPointRaw) -> Result<()> {
+    click_at_with_flags(point, 0)
+}
+
+/// Click at `point` with modifiers held (CGEventFlags, e.g. command-click)
+pub(super) fn click_at_with_flags(point: CGPointRaw, flags: u64) -> Result<()> {
+    for mouse_type in [
+        K_CG_EVENT_MOUSE_MOVED,
+        K_CG_EVENT_LEFT_MOUSE_DOWN,
+        K_CG_EVENT_LEFT_MOUSE_UP,
+    ] {
+        unsafe {
+            let event = CGEvent::new(CGEventCreateMouseEvent(
+                std::ptr::null_mut(),
+                mouse_type,
+                point,
+                K_CG_MOUSE_BUTTON_LEFT,
+            ));
+            if event.is_null() {
+                anyhow::bail!("Failed to create a mouse event");
+            }
+            if flags != 0 {
+                CGEventSetFlags(event.as_ptr(), flags);
+            }
+            CGEventPost(K_CG_HID_EVENT_TAP, event.as_ptr());
+        }
+        super::waits::settle(super::waits::Wait::Click);
+    }
+    Ok(())
+}
+
+/// Save the mouse position over the frontmost app's window as `name`
+pub fn record(name: &str) -> Result<ClickConfig> {
+    let app = super::native_ax::frontmost_info()?.app;
+    let mouse = mouse_location()?;
+    let window = windows(&app)?
+        .into_iter()
+        .find(|window| window.contains(mouse))
+        .with_context(|| format!("The mouse isn't over a {} window", app))?;
+    let click = ClickConfig {
+        app,
+        window: (!window.title.is_empty()).then_some(window.title),
+        x: (mouse.x - window.origin.x).round(),
+        y: (mouse.y - window.origin.y).round(),
+        width: window.size.x,
+        height: window.size.y,
+        scale: false,
+    };
+    crate::config::append_click(name, &click)?;
+    Ok(click)
+}
+
+/// Bring the recorded click's window forward and click in it
+pub fn replay(name: &str) -> Result<()> {
+    let click = CLICKS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|clicks| clicks.get(name).cloned())
+        .with_context(|| format!("No click named '{}' in config.toml", name))?;
+    let title = click.window.as_deref().unwrap_or("");
+    super::focus_app(&click.app, title, true, false, 2000)?;
+    super::waits::settle(super::waits::Wait::Focus);
+
+    let window = windows(&click.app)?
+        .into_iter()
+        .find(|window| title.is_empty() || crate::soft_match(&window.title, title))
+        .with_context(|| format!("No {} window '{}' open", click.app, title))?;
+    let (x, y) = if click.scale && click.width > 0.0 && click.height > 0.0 {
+        (
+            click.x * window.size.x / click.width,
+            click.y * window.size.y / click.height,
+        )
+    } else {
+        (click.x, click.y)
+    };
+    log::info!(
+        "Clicking '{}' at {:.0},{:.0} in '{}'",
+        name,
+        x,
+        y,
+        window.title
+    );
+    click_at(CGPointRaw {
+        x: window.origin.x + x,
+        y: window.origin.y + y,
+    })
+}
@@ -101,6 +101,139 @@ pub fn test_text_window(_params: &Params) -> R<()> {
     Ok(())
 }
 
+/// Send a note or CC to a MIDI controller (button lights, pad feedback)
+///
+/// Params:
+/// - note: note number, sent with velocity (default 127; 0 = note off)
+/// - cc: CC number instead of a note, sent with value (default 127)
+/// - channel: 1-16 (default 1)
+/// - device: output port name (default: [midi] output, or the only port)
+pub fn midi_send(params: &Params) -> R<()> {
+    let data_byte = |key: &str, default: i64| -> R<Option<u8>> {
+        match params.get_int(key, default) {
+            -1 => Ok(None),
+            value @ 0..=127 => Ok(Some(value as u8)),
+            value => anyhow::bail!("{} must be 0-127, got {}", key, value),
+        }
+    };
+    let message = match (data_byte("note", -1)?, data_byte("cc", -1)?) {
+        (Some(note), None) => match data_byte("velocity", 127)?.unwrap_or(127) {
+            0 => MidiMessage::NoteOff { note },
+            velocity => MidiMessage::NoteOn { note, velocity },
+        },
+        (None, Some(cc)) => MidiMessage::ControlChange {
+            cc,
+            value: data_byte("value", 127)?.unwrap_or(127),
+        },
+        _ => anyhow::bail!("Give either note or cc (e.g. note = 36, velocity = 127)"),
+    };
+    let channel = u8::try_from(params.get_int("channel", 1)).unwrap_or(0);
+    crate::input::midi_out::send(params.get_ostr("device"), channel, message)
+}
+
+/// Send a keystroke to the frontmost app
+///
+/// Params:
+/// - keys: key names, e.g. ["cmd", "s"]
+pub fn keystroke(params: &Params) -> R<()> {
+    let keys = params.get_str_vec("keys");
+    if keys.is_empty() {
+        anyhow::bail!("No keys given");
+    }
+    OS::keystroke(&keys)
+}
+
+/// Type text into the focused field
+///
+/// Params:
+/// - text: what to type
+/// - verify: replace the field's contents, read them back and fix dropped
+///   or mistyped characters (default false: type at the cursor, unchecked).
+///   Opt in for naming dialogs; it overwrites whatever the field held
+pub fn type_text(params: &Params) -> R<()> {
+    let text = params.get_str("text", "");
+    if text.is_empty() {
+        anyhow::bail!("No text given");
+    }
+    if params.get_bool("verify", false) {
+        OS::type_text_verified(text, true)
+    } else {
+        OS::type_text(text, true)
+    }
+}
+
+/// Save the mouse position, relative to the window under it, for `click_recorded`
+///
+/// Point at the control and press the hotkey; the position goes into
+/// config.toml as `[clicks.<name>]`.
+///
+/// Params:
+/// - name: what to save it as
+pub fn record_click(params: &Params) -> R<()> {
+    let name = params.get_str("name", "");
+    if name.is_empty() {
+        anyhow::bail!("No name given");
+    }
+    let click = OS::clicks::record(name)?;
+    OS::show_notification(&crate::notify::text(
+        "click_saved",
+        &[
+            ("name", name),
+            ("x", &format!("{:.0}", click.x)),
+            ("y", &format!("{:.0}", click.y)),
+            ("window", click.window.as_deref().unwrap_or(&click.app)),
+        ],
+    ));
+    Ok(())
+}
+
+/// Click a position saved by `record_click`, wherever its window is now
+///
+/// Params:
+/// - name: the saved click
+pub fn click_recorded(params: &Params) -> R<()> {
+    let name = params.get_str("name", "");
+    if name.is_empty() {
+        anyhow::bail!("No name given");
+    }
+    OS::clicks::replay(name)
+}
+
+/// Find a template image in an app's frontmost window and click its center
+///
+/// For plugin GUIs that expose nothing to accessibility. Needs the
+/// `image-match` build feature.
+///
+/// Params:
+/// - image: template image file, cropped from a screenshot of the window;
+///   relative paths are next to config.toml
+/// - app: app whose window to search (default "Pro Tools")
+/// - tolerance: how different a match may be, 0-1 (default 0.1)
+#[cfg(feature = "image-match")]
+pub fn click_image(params: &Params) -> R<()> {
+    let image = params.get_str("image", "");
+    if image.is_empty() {
+        anyhow::bail!("No image given");
+    }
+    let path = match std::path::PathBuf::from(image) {
+        path if path.is_relative() => crate::config::get_config_path()?
+            .parent()
+            .context("Config path has no folder")?
+            .join(path),
+        path => path,
+    };
+    OS::image_match::click_image(
+        params.get_str("app", "Pro Tools"),
+        &path,
+        params.get_number("tolerance", 0.1),
+    )
+}
+
+#[cfg(not(feature = "image-match"))]
+pub fn click_image(_params: &Params) -> R<()> {
+    anyhow::bail!("Built without the image-match feature")
+}
+
 pub fn test_keystroke(_params: &Params) -> R<()> {
     log::info!("Testing global keystroke - sending CMD+F1");
     OS::keystroke(&["cmd", "f1"])?;
@@ -159,43 +292,499 @@ pub fn test_app_info(_params: &Params) -> R<()> {
     log.display()
 }
 
+/// Show config.toml in Finder
+pub fn reveal_config(_params: &Params) -> R<()> {
+    MacOSSession::global().reveal_in_finder(&crate::config::get_config_path()?)
+}
+
+/// Show the log file in Finder
+pub fn reveal_log(_params: &Params) -> R<()> {
+    MacOSSession::global().reveal_in_finder(&crate::config::get_log_path()?)
+}
+
+/// Zip up what a bug report needs and show it in Finder
+///
+/// The bundle holds config.toml with tokens and passwords blanked out, the
+/// end of the log, a diagnostics report and the recent action history (see
+/// `crate::support`).
+///
+/// Params:
+/// - directory: where the zip goes (default ~/Desktop)
+/// - reveal: show the zip in Finder (default true)
+pub fn export_support_bundle(params: &Params) -> R<()> {
+    let directory = match params.get_ostring("directory") {
+        Some(directory) => std::path::PathBuf::from(directory),
+        None => std::path::PathBuf::from(
+            std::env::var("HOME").context("HOME environment variable not set")?,
+        )
+        .join("Desktop"),
+    };
+    let zip = crate::support::export_bundle(&directory)?;
+    log::info!("Support bundle written to {}", zip.display());
+    OS::show_notification(&crate::notify::text(
+        "support_bundle_saved",
+        &[("path", &zip.display().to_string())],
+    ));
+    if params.get_bool("reveal", true) {
+        MacOSSession::global().reveal_in_finder(&zip)?;
+    }
+    Ok(())
+}
+
+/// Reload config.toml, keeping the current hotkeys if anything in it is invalid
 pub fn reload_config(_params: &Params) -> R<()> {
-    use crate::config::{config_to_hotkeys, load_config};
-    use crate::input::HOTKEYS;
-    use anyhow::{Context, bail};
+    reload_config_checked().map(drop)
+}
+
+/// Config watcher callback: reload and show what changed
+///
+/// The watcher runs on its own thread, but reloading re-registers Carbon
+/// hotkeys, which has to happen on the main thread.
+pub fn reload_config_on_save() {
+    unsafe {
+        OS::dispatch_to_main_queue(|| {
+            if let Ok(summary) = reload_config_checked() {
+                OS::show_notification(&crate::notify::text(
+                    "config_reloaded_changes",
+                    &[("changes", &summary)],
+                ));
+            }
+        });
+    }
+}
 
-    log::info!("⚠️  reload_config STARTED");
+/// Reload, reporting a failure; returns a summary of the binding changes
+///
+/// Must run on the main thread (Carbon hotkeys are re-registered).
+pub fn reload_config_checked() -> R<String> {
     log::info!("Reloading config from config.toml...");
+    reload_config_atomic().inspect_err(|e| {
+        log::error!("Config reload failed: {:#}", e);
+        OS::show_notification(&crate::notify::text(
+            "config_not_reloaded",
+            &[("error", &e.to_string())],
+        ));
+    })
+}
+
+/// Validate the new config completely, then swap it in
+///
+/// Nothing is applied until every hotkey parses and the Carbon set can be
+/// registered. If Carbon registration still fails after the swap, the
+/// previous hotkeys (and their Carbon registrations) are restored.
+fn reload_config_atomic() -> R<String> {
+    use crate::config::{config_to_hotkeys_strict, load_config};
+    use crate::input::HOTKEYS;
+    use crate::macos::carbon_hotkeys;
 
-    // Load and parse config
-    log::info!("⚠️  About to call load_config");
     let config = load_config("config.toml").context("Failed to load config.toml")?;
-    log::info!("⚠️  load_config completed");
+    let hotkeys = config_to_hotkeys_strict(config.clone())?;
+    carbon_hotkeys::validate_carbon_hotkeys(&hotkeys)?;
+
+    let table = HOTKEYS.get().context("HOTKEYS not initialized")?;
+    let count = hotkeys.len();
+    let previous = table.swap(std::sync::Arc::new(hotkeys));
+    crate::input::sequence::reset();
+    crate::input::taps::reset();
+
+    carbon_hotkeys::unregister_carbon_hotkeys();
+    if let Err(e) = carbon_hotkeys::register_carbon_hotkeys() {
+        log::warn!("Rolling back to the previous hotkeys");
+        table.store(previous);
+        carbon_hotkeys::unregister_carbon_hotkeys();
+        if let Err(restore_err) = carbon_hotkeys::register_carbon_hotkeys() {
+            log::error!("Failed to restore Carbon hotkeys: {:#}", restore_err);
+        }
+        return Err(e.context("Carbon registration failed"));
+    }
 
-    // Convert to hotkeys
-    log::info!("⚠️  About to call config_to_hotkeys");
-    let hotkeys = config_to_hotkeys(config).context("Failed to parse config")?;
-    log::info!("⚠️  config_to_hotkeys completed");
+    // Hotkeys are live - apply the rest of the config
+    crate::protools::autosave::set_config(config.auto_save.clone());
+    crate::protools::jobs::set_config(config.resource_meter.clone());
+    crate::protools::transport::set_busy_config(config.busy_guard.clone());
+    crate::protools::tally::set_config(config.tally.clone());
+    crate::macos::menu_locale::set_config(config.menu_locale.clone());
+    crate::input::osc::set_config(config.osc.as_ref());
+    crate::input::midi_out::set_default_device(
+        config.midi.as_ref().and_then(|midi| midi.output.clone()),
+    );
+    crate::macos::menubar::set_custom_items(config.menubar.clone());
+    crate::macos::app_events::set_config(config.trigger.clone());
+    crate::macos::waits::set_config(config.waits.clone());
+    crate::macos::clicks::set_config(config.clicks.clone());
+    crate::macos::layouts::set_config(config.layouts.clone());
+    crate::schedule::set_config(
+        config
+            .trigger
+            .as_ref()
+            .map(|trigger| trigger.schedule.clone())
+            .unwrap_or_default(),
+    );
+    crate::notify::set_config(config.notifications.as_ref(), false);
+
+    // Apply review mode tags (the on/off state survives reloads)
+    crate::input::review::set_blocked_tags(
+        config
+            .review_mode
+            .as_ref()
+            .map_or_else(crate::input::review::default_blocked_tags, |r| {
+                r.block_tags.clone()
+            }),
+    );
 
-    // Log registered hotkeys
-    log::info!("Reloaded {} hotkeys:", hotkeys.len());
-    for hotkey in &hotkeys {
+    log::info!("Reloaded {} hotkeys:", count);
+    let current = table.load();
+    for hotkey in current.iter() {
         log::info!(
             "  - {} => {}",
             hotkey.trigger.describe(),
             hotkey.action_name
         );
     }
+    let summary = binding_changes(&previous, &current);
+    log::info!("✅ Config reloaded successfully! {}", summary);
+    Ok(summary)
+}
 
-    // Update the global hotkey registry
-    if let Some(hotkeys_mutex) = HOTKEYS.get() {
-        *hotkeys_mutex.lock().unwrap() = hotkeys;
-        log::info!("✅ Config reloaded successfully!");
-        Ok(())
+/// Summarize added/removed/changed bindings, e.g. "1 added, 2 changed: cmd+F14 => ..."
+///
+/// Bindings are matched by trigger and app filter; a binding whose action or
+/// params differ counts as changed.
+fn binding_changes(old: &[Hotkey], new: &[Hotkey]) -> String {
+    /// How many individual bindings to name before "and N more"
+    const MAX_LISTED: usize = 3;
+
+    let key = |hk: &Hotkey| {
+        format!(
+            "{}{}",
+            hk.trigger.describe(),
+            hk.application
+                .as_ref()
+                .map(|apps| format!(" in {}", apps.join("/")))
+                .unwrap_or_default()
+        )
+    };
+    let old_keys: std::collections::HashMap<String, &Hotkey> =
+        old.iter().map(|hk| (key(hk), hk)).collect();
+    let new_keys: std::collections::HashSet<String> = new.iter().map(key).collect();
+
+    let mut details = Vec::new();
+    let (mut added, mut removed, mut changed) = (0, 0, 0);
+    for hotkey in new {
+        let name = key(hotkey);
+        match old_keys.get(&name) {
+            None => {
+                added += 1;
+                details.push(format!("+ {} => {}", name, hotkey.action_name));
+            }
+            Some(previous)
+                if previous.action_name != hotkey.action_name
+                    || previous.params != hotkey.params =>
+            {
+                changed += 1;
+                details.push(format!("~ {} => {}", name, hotkey.action_name));
+            }
+            Some(_) => {}
+        }
+    }
+    for hotkey in old {
+        let name = key(hotkey);
+        if !new_keys.contains(&name) {
+            removed += 1;
+            details.push(format!("- {}", name));
+        }
+    }
+
+    if details.is_empty() {
+        return "no binding changes".to_string();
+    }
+    for line in &details {
+        log::info!("  {}", line);
+    }
+    let mut summary = [(added, "added"), (removed, "removed"), (changed, "changed")]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .collect::<Vec<_>>()
+        .join(", ");
+    summary.push_str(": ");
+    summary.push_str(&details[..details.len().min(MAX_LISTED)].join("; "));
+    if details.len() > MAX_LISTED {
+        summary.push_str(&format!(" and {} more", details.len() - MAX_LISTED));
+    }
+    summary
+}
+
+/// Toggle review mode (blocks edit/destructive hotkeys)
+///
+/// Params:
+/// - enabled: set explicitly instead of toggling
+pub fn toggle_review_mode(params: &Params) -> R<()> {
+    use crate::input::review;
+
+    let enabled = match params.get_obool("enabled") {
+        Some(enabled) => {
+            review::set_enabled(enabled);
+            enabled
+        }
+        None => review::toggle(),
+    };
+
+    if enabled {
+        OS::show_notification(&crate::notify::text("review_on", &[]));
+    } else {
+        OS::show_notification(&crate::notify::text("review_off", &[]));
+    }
+    Ok(())
+}
+
+/// Mute or unmute all notifications (the menu bar's Quiet Notifications)
+///
+/// Params:
+/// - enabled: true = quiet, false = notifications on (default: toggle)
+pub fn toggle_quiet(params: &Params) -> R<()> {
+    let quiet = match params.get_obool("enabled") {
+        Some(quiet) => {
+            crate::notify::set_quiet(quiet);
+            quiet
+        }
+        None => crate::notify::toggle_quiet(),
+    };
+    if !quiet {
+        OS::show_notification(&crate::notify::text("notifications_on", &[]));
+    }
+    Ok(())
+}
+
+/// Light or darken the recording tally by hand (`[tally]` sets its look)
+///
+/// Params:
+/// - enabled: true = lit, false = dark (default: toggle)
+pub fn tally(params: &Params) -> R<()> {
+    let lit = params
+        .get_obool("enabled")
+        .unwrap_or(!crate::protools::tally::is_manual());
+    crate::protools::tally::set_manual(lit);
+    Ok(())
+}
+
+/// Suspend or resume all hotkeys (the menu bar's Suspend Hotkeys)
+///
+/// While suspended, keys reach apps untouched; only this action's own
+/// bindings still fire, to resume.
+///
+/// Params:
+/// - enabled: true = suspend, false = resume (default: toggle)
+/// - auto_resume_minutes: resume on its own after this long
+pub fn toggle_suspend(params: &Params) -> R<()> {
+    use crate::input::suspend;
+
+    let minutes = params.get_int("auto_resume_minutes", 0);
+    let auto_resume = (minutes > 0).then(|| std::time::Duration::from_secs(minutes as u64 * 60));
+    let suspended = match params.get_obool("enabled") {
+        Some(suspended) => {
+            suspend::set(suspended, auto_resume);
+            suspended
+        }
+        None => suspend::toggle(auto_resume),
+    };
+
+    if !suspended {
+        OS::show_notification(&crate::notify::text("hotkeys_resumed", &[]));
+    } else if minutes > 0 {
+        OS::show_notification(&crate::notify::text(
+            "hotkeys_suspended_for",
+            &[("minutes", &minutes.to_string())],
+        ));
     } else {
-        log::error!("HOTKEYS not initialized - cannot reload");
-        bail!("HOTKEYS not initialized")
+        OS::show_notification(&crate::notify::text("hotkeys_suspended", &[]));
+    }
+    Ok(())
+}
+
+/// Switch hotkey layer (shown next to the menu bar icon)
+///
+/// Params:
+/// - layer: layer name ("base" or empty = the base layer, the default)
+pub fn set_layer(params: &Params) -> R<()> {
+    crate::input::layers::set(params.get_ostr("layer"));
+    Ok(())
+}
+
+/// Switch to a hotkey layer, or back to the base layer if it's already on
+///
+/// Params:
+/// - layer: layer name (required)
+pub fn toggle_layer(params: &Params) -> R<()> {
+    let layer = params
+        .get_ostr("layer")
+        .filter(|name| !name.trim().is_empty())
+        .context("No layer given (use layer = \"name\")")?;
+    crate::input::layers::toggle(layer);
+    Ok(())
+}
+
+/// Start a countdown or stopwatch, shown next to the menu bar icon
+///
+/// Params:
+/// - hours, minutes, seconds: countdown length (none = a stopwatch counting up)
+/// - name: identifies the timer for os.timer_stop; starting a running name
+///   restarts it (default "timer")
+/// - label: shown before the time (default: the name if one was given)
+/// - notify: show a notification when the countdown runs out (default true)
+/// - speak: text to speak when the countdown runs out, or true for "<label> time's up"
+pub fn timer_start(params: &Params) -> R<()> {
+    use super::timers::{self, Timer};
+    use std::time::{Duration, Instant};
+
+    let seconds = params.get_int("hours", 0) * 3600
+        + params.get_int("minutes", 0) * 60
+        + params.get_int("seconds", 0);
+    if seconds < 0 {
+        anyhow::bail!("Timer length can't be negative");
     }
+    let name = params.get_ostring("name");
+    let label = params
+        .get_ostring("label")
+        .or_else(|| name.clone())
+        .unwrap_or_default();
+    let speak = match params.get_obool("speak") {
+        Some(true) => Some(format!(
+            "{} time's up",
+            if label.is_empty() { "Timer" } else { &label }
+        )),
+        Some(false) => None,
+        None => params.get_ostring("speak"),
+    };
+    timers::start(Timer {
+        name: name.unwrap_or_else(|| "timer".to_string()),
+        label,
+        started: Instant::now(),
+        duration: (seconds > 0).then(|| Duration::from_secs(seconds as u64)),
+        notify: params.get_bool("notify", true),
+        speak,
+    });
+    Ok(())
+}
+
+/// Stop a timer started by os.timer_start, showing where it stopped
+///
+/// Params:
+/// - name: the timer to stop (default: all of them)
+pub fn timer_stop(params: &Params) -> R<()> {
+    let stopped = super::timers::stop(params.get_ostr("name"));
+    if stopped.is_empty() {
+        anyhow::bail!("No timer running");
+    }
+    OS::show_notification(&crate::notify::text(
+        "timers_stopped",
+        &[("timers", &stopped.join(", "))],
+    ));
+    Ok(())
+}
+
+/// Show how often each binding is used and which actions are slowest
+///
+/// Params:
+/// - top: entries per list (default 10)
+/// - reset: clear the stats instead of showing them
+pub fn usage_report(params: &Params) -> R<()> {
+    if params.get_bool("reset", false) {
+        crate::analytics::reset()?;
+        OS::show_notification(&crate::notify::text("usage_cleared", &[]));
+        return Ok(());
+    }
+    crate::analytics::save()?;
+    let top = params.get_int("top", 10).max(1) as usize;
+    let mut log = crate::MessageLog::new("=== Hotkey Usage ===");
+    for line in crate::analytics::report(top) {
+        log.append(&line);
+    }
+    log.display()
+}
+
+/// Re-measure how quickly the UI answers and show the resulting waits
+///
+/// Handy as a `[[trigger.app_launch]]` for Pro Tools, or after opening a
+/// heavy session.
+pub fn calibrate_waits(_params: &Params) -> R<()> {
+    let scale = super::waits::calibrate();
+    let mut log = crate::MessageLog::new(&format!("=== UI Waits (scale {:.2}) ===", scale));
+    for (name, wait) in super::waits::describe() {
+        log.append(&format!("  {:<12} {} ms", name, wait.as_millis()));
+    }
+    log.display()
+}
+
+/// Read `tag = "adr"` or `tags = ["adr", "mix"]` from params
+fn tags_from_params(params: &Params) -> R<Vec<String>> {
+    let mut tags = params.get_string_vec("tags");
+    if let Some(tag) = params.get_ostring("tag") {
+        tags.push(tag);
+    }
+    if tags.is_empty() {
+        anyhow::bail!("No tag given (use tag = \"name\" or tags = [\"a\", \"b\"])");
+    }
+    Ok(tags)
+}
+
+/// Re-enable all hotkeys carrying the given tag(s)
+pub fn enable_tag(params: &Params) -> R<()> {
+    let tags = tags_from_params(params)?;
+    for tag in &tags {
+        crate::input::tags::enable(tag);
+    }
+    OS::show_notification(&crate::notify::text(
+        "tags_enabled",
+        &[("tags", &tags.join(", "))],
+    ));
+    Ok(())
+}
+
+/// Disable all hotkeys carrying the given tag(s)
+pub fn disable_tag(params: &Params) -> R<()> {
+    let tags = tags_from_params(params)?;
+    for tag in &tags {
+        crate::input::tags::disable(tag);
+    }
+    OS::show_notification(&crate::notify::text(
+        "tags_disabled",
+        &[("tags", &tags.join(", "))],
+    ));
+    Ok(())
+}
+
+/// Show every tag in use, whether it's enabled, and which hotkeys carry it
+pub fn list_tags(_params: &Params) -> R<()> {
+    use crate::input::HOTKEYS;
+    use std::collections::BTreeMap;
+
+    let mut by_tag: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    if let Some(table) = HOTKEYS.get() {
+        for hotkey in table.load().iter() {
+            for tag in &hotkey.tags {
+                by_tag.entry(tag.to_lowercase()).or_default().push(format!(
+                    "{} => {}",
+                    hotkey.trigger.describe(),
+                    hotkey.action_name
+                ));
+            }
+        }
+    }
+
+    let mut log = crate::MessageLog::new(&format!("=== Hotkey Tags ({}) ===", by_tag.len()));
+    for (tag, hotkeys) in &by_tag {
+        let state = if crate::input::tags::is_tag_disabled(tag) {
+            "⛔ disabled"
+        } else {
+            "✅ enabled"
+        };
+        log.append(&format!("\n[{}] {}", tag, state));
+        for hotkey in hotkeys {
+            log.append(&format!("  - {}", hotkey));
+        }
+    }
+    log.display()
 }
 
 pub fn dump_app_menus(_params: &Params) -> R<()> {
@@ -238,6 +827,48 @@ pub fn launch_application(params: &Params) -> R<()> {
     Ok(())
 }
 
+/// Relaunch Pro Tools and reopen the last session seen by the crash watcher
+pub fn relaunch_protools(_params: &Params) -> R<()> {
+    crate::protools::recovery::relaunch()
+}
+
+/// Undo every Pro Tools operation the last macro made
+///
+/// Uses PTSL's multi-level undo, falling back to Cmd+Z presses on Pro Tools
+/// versions without it.
+pub fn undo_last_macro(_params: &Params) -> R<()> {
+    let last = crate::undo::take_last().context("No macro to undo")?;
+    let levels = last.operations();
+    log::info!("Undoing {} operation(s) of macro ({})", levels, last.name);
+
+    let undone = crate::protools::run_command(move || async move {
+        let mut pt = crate::protools::client::ProtoolsSession::shared().await?;
+        let undone = pt.undo(levels as i32).await?;
+        if undone.is_empty() {
+            anyhow::bail!("Pro Tools reported nothing undone");
+        }
+        log::info!("Undid: {}", undone.join(", "));
+        Ok(())
+    })
+    .wait(std::time::Duration::from_millis(
+        crate::protools::COMMAND_TIMEOUT_MS,
+    ));
+    if let Err(e) = undone {
+        log::warn!("Grouped undo failed ({:#}) - pressing Cmd+Z instead", e);
+        OS::focus_app("Pro Tools", "", true, false, 1000)?;
+        OS::waits::settle(OS::waits::Wait::Focus);
+        for _ in 0..levels {
+            OS::keystroke(&["cmd", "z"])?;
+            OS::waits::settle(OS::waits::Wait::Keystroke);
+        }
+    }
+    OS::show_notification(&crate::notify::text(
+        "undo_done",
+        &[("count", &levels.to_string())],
+    ));
+    Ok(())
+}
+
 pub fn list_window_buttons(params: &Params) -> R<()> {
     let current_app = OS::get_current_app().ok().unwrap_or_default();
     let app_name = params.get_string("app", &current_app);
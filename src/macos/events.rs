@@ -2,7 +2,7 @@
 //!
 //! This module handles:
 //! - Event tap creation and management
-//! - Keyboard event capture
+//! - Keyboard and scroll-wheel event capture
 //! - Run loop integration
 //!
 //! STATUS: STABLE - This code is tested and working
@@ -49,11 +49,13 @@ mod constants {
     pub const CG_EVENT_KEY_DOWN: u32 = 10;
     pub const CG_EVENT_KEY_UP: u32 = 11;
     pub const CG_EVENT_FLAGS_CHANGED: u32 = 12;
+    pub const CG_EVENT_SCROLL_WHEEL: u32 = 22;
     pub const CG_EVENT_TAP_DISABLED_BY_TIMEOUT: u32 = 14;
     pub const CG_EVENT_TAP_DISABLED_BY_USER_INPUT: u32 = 15;
 
     // Event fields
     pub const CG_EVENT_FIELD_KEYBOARD_EVENT_KEYCODE: u32 = 9;
+    pub const CG_EVENT_FIELD_SCROLL_WHEEL_FIXED_PT_DELTA_AXIS_1: u32 = 93;
 
     // Event tap locations
     pub const CG_SESSION_EVENT_TAP: u32 = 1;
@@ -65,8 +67,12 @@ mod constants {
     pub const CG_EVENT_TAP_OPTION_DEFAULT: u32 = 0;
 
     // Event source state
+    pub const CG_EVENT_SOURCE_STATE_COMBINED_SESSION_STATE: i32 = 0;
     pub const CG_EVENT_SOURCE_STATE_HID_SYSTEM_STATE: i32 = 1;
 
+    // Matches any input event (kCGAnyInputEventType)
+    pub const CG_ANY_INPUT_EVENT_TYPE: u32 = !0;
+
     // Event posting locations
     pub const CG_HID_EVENT_TAP: u32 = 0;
 
@@ -87,6 +93,7 @@ pub use constants::*;
 
 unsafe extern "C" {
     pub fn CGEventGetIntegerValueField(event: *mut c_void, field: u32) -> i64;
+    pub fn CGEventGetDoubleValueField(event: *mut c_void, field: u32) -> f64;
     pub fn CGEventGetFlags(event: *mut c_void) -> u64;
 
     pub fn CGEventTapCreate(
@@ -100,6 +107,20 @@ unsafe extern "C" {
 
     pub fn CGEventTapEnable(tap: *mut c_void, enable: bool);
     pub fn CGEventTapIsEnabled(tap: *mut c_void) -> bool;
+
+    pub fn CGEventSourceSecondsSinceLastEventType(state_id: i32, event_type: u32) -> f64;
+}
+
+/// Seconds since the last keyboard, mouse or trackpad input from the user
+///
+/// Uses the combined session state, so it covers input in every app - not just hotkeys
+pub fn seconds_since_last_input() -> f64 {
+    unsafe {
+        CGEventSourceSecondsSinceLastEventType(
+            CG_EVENT_SOURCE_STATE_COMBINED_SESSION_STATE,
+            CG_ANY_INPUT_EVENT_TYPE,
+        )
+    }
 }
 
 // ============================================================================
@@ -124,7 +145,7 @@ unsafe extern "C" {
 // Event Tap Management
 // ============================================================================
 
-/// Creates a keyboard event tap with the provided callback
+/// Creates a keyboard (and scroll wheel) event tap with the provided callback
 ///
 /// # Safety
 /// The callback must be safe to call from the event tap thread
@@ -132,9 +153,12 @@ pub unsafe fn create_keyboard_event_tap(
     callback: unsafe extern "C" fn(*mut c_void, u32, *mut c_void, *mut c_void) -> *mut c_void,
 ) -> Result<*mut c_void> {
     unsafe {
-        // Event mask for key down, key up, and flags changed (for modifiers)
-        let event_mask =
-            (1 << CG_EVENT_KEY_DOWN) | (1 << CG_EVENT_KEY_UP) | (1 << CG_EVENT_FLAGS_CHANGED);
+        // Event mask for key down, key up, flags changed (for modifiers),
+        // and scrolling (for wheel bindings)
+        let event_mask = (1 << CG_EVENT_KEY_DOWN)
+            | (1 << CG_EVENT_KEY_UP)
+            | (1 << CG_EVENT_FLAGS_CHANGED)
+            | (1 << CG_EVENT_SCROLL_WHEEL);
 
         let event_tap = CGEventTapCreate(
             CG_SESSION_EVENT_TAP,
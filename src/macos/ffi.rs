@@ -58,6 +58,12 @@ unsafe extern "C" {
     /// Get the type ID for CFString type
     pub fn CFStringGetTypeID() -> usize;
 
+    /// Look up a value in a CFDictionary
+    pub fn CFDictionaryGetValue(the_dict: *mut c_void, key: *const c_void) -> *mut c_void;
+
+    /// Read a CFNumber into `value_ptr` as `the_type`
+    pub fn CFNumberGetValue(number: *mut c_void, the_type: i32, value_ptr: *mut c_void) -> bool;
+
     /// Get the singleton kCFBooleanTrue value
     pub static kCFBooleanTrue: *mut c_void;
 }
@@ -101,13 +107,185 @@ unsafe extern "C" {
 
     /// Check if the current process is trusted for accessibility
     pub fn AXIsProcessTrusted() -> bool;
+
+    /// Unpack an AXValue (AXPosition, AXSize...) into a CGPoint/CGSize/...
+    pub fn AXValueGetValue(value: *mut c_void, value_type: u32, value_ptr: *mut c_void) -> bool;
+
+    /// Pack a CGPoint/CGSize/... into an AXValue for setting AXPosition, AXSize...
+    pub fn AXValueCreate(value_type: u32, value_ptr: *const c_void) -> *mut c_void;
 }
 
-// CoreGraphics framework for input monitoring permissions
+/// kAXValueCGPointType
+pub const K_AX_VALUE_CG_POINT_TYPE: u32 = 1;
+/// kAXValueCGSizeType
+pub const K_AX_VALUE_CG_SIZE_TYPE: u32 = 2;
+
+// CoreGraphics framework for input monitoring permissions and window capture
 #[link(name = "CoreGraphics", kind = "framework")]
 unsafe extern "C" {
     /// Check if the current process has permission to post events (Input Monitoring)
     pub fn CGRequestPostEventAccess() -> bool;
+
+    /// Describe the windows in the window server's list (CFArray of CFDictionary)
+    pub fn CGWindowListCopyWindowInfo(option: u32, relative_to_window: u32) -> *mut c_void;
+
+    /// Capture windows from the window server's list as a CGImage
+    pub fn CGWindowListCreateImage(
+        screen_bounds: CGRectRaw,
+        list_option: u32,
+        window_id: u32,
+        image_option: u32,
+    ) -> *mut c_void;
+
+    /// Release a CGImage
+    pub fn CGImageRelease(image: *mut c_void);
+
+    /// Check for Screen Recording permission without prompting
+    pub fn CGPreflightScreenCaptureAccess() -> bool;
+
+    /// Ask for Screen Recording permission (the system prompt shows once)
+    pub fn CGRequestScreenCaptureAccess() -> bool;
+
+    /// Create an event (a null source and no type gives the current mouse location)
+    pub fn CGEventCreate(source: *mut c_void) -> *mut c_void;
+
+    /// Where an event happened, in global points from the top-left of the main display
+    pub fn CGEventGetLocation(event: *mut c_void) -> CGPointRaw;
+
+    /// Create a mouse event at a global position
+    pub fn CGEventCreateMouseEvent(
+        source: *mut c_void,
+        mouse_type: u32,
+        position: CGPointRaw,
+        button: u32,
+    ) -> *mut c_void;
+
+    /// Post an event into the event stream
+    pub fn CGEventPost(tap: u32, event: *mut c_void);
+
+    /// Set an event's modifier flags (kCGEventFlagMask...)
+    pub fn CGEventSetFlags(event: *mut c_void, flags: u64);
+
+    /// Read a window's kCGWindowBounds dictionary into a CGRect
+    pub fn CGRectMakeWithDictionaryRepresentation(dict: *mut c_void, rect: *mut CGRectRaw) -> bool;
+
+    /// Width of a CGImage in pixels
+    pub fn CGImageGetWidth(image: *mut c_void) -> usize;
+
+    /// Height of a CGImage in pixels
+    pub fn CGImageGetHeight(image: *mut c_void) -> usize;
+
+    /// The device gray color space (release with CGColorSpaceRelease)
+    pub fn CGColorSpaceCreateDeviceGray() -> *mut c_void;
+
+    pub fn CGColorSpaceRelease(space: *mut c_void);
+
+    /// A drawing context over caller-owned pixel memory
+    pub fn CGBitmapContextCreate(
+        data: *mut c_void,
+        width: usize,
+        height: usize,
+        bits_per_component: usize,
+        bytes_per_row: usize,
+        space: *mut c_void,
+        bitmap_info: u32,
+    ) -> *mut c_void;
+
+    /// Draw an image into a context, scaled to `rect`
+    pub fn CGContextDrawImage(context: *mut c_void, rect: CGRectRaw, image: *mut c_void);
+
+    pub fn CGContextRelease(context: *mut c_void);
+}
+
+/// kCGImageAlphaNone
+pub const K_CG_IMAGE_ALPHA_NONE: u32 = 0;
+
+/// kCGHIDEventTap
+pub const K_CG_HID_EVENT_TAP: u32 = 0;
+/// kCGEventLeftMouseDown
+pub const K_CG_EVENT_LEFT_MOUSE_DOWN: u32 = 1;
+/// kCGEventLeftMouseUp
+pub const K_CG_EVENT_LEFT_MOUSE_UP: u32 = 2;
+/// kCGEventMouseMoved
+pub const K_CG_EVENT_MOUSE_MOVED: u32 = 5;
+/// kCGMouseButtonLeft
+pub const K_CG_MOUSE_BUTTON_LEFT: u32 = 0;
+
+/// kCGWindowListOptionOnScreenOnly
+pub const K_CG_WINDOW_LIST_ON_SCREEN_ONLY: u32 = 1 << 0;
+/// kCGWindowListOptionIncludingWindow
+pub const K_CG_WINDOW_LIST_INCLUDING_WINDOW: u32 = 1 << 3;
+/// kCGWindowListExcludeDesktopElements
+pub const K_CG_WINDOW_LIST_EXCLUDE_DESKTOP: u32 = 1 << 4;
+/// kCGWindowImageBoundsIgnoreFraming (no shadow)
+pub const K_CG_WINDOW_IMAGE_IGNORE_FRAMING: u32 = 1 << 0;
+
+/// CGPoint / CGSize as passed by value to CoreGraphics
+#[repr(C)]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct CGPointRaw {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// CGRect as passed by value to CoreGraphics
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct CGRectRaw {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl CGRectRaw {
+    /// CGRectNull - "the bounds of the captured windows"
+    pub const NULL: CGRectRaw = CGRectRaw {
+        x: f64::INFINITY,
+        y: f64::INFINITY,
+        width: 0.0,
+        height: 0.0,
+    };
+}
+
+// ============================================================================
+// Apple Events (Automation permission)
+// ============================================================================
+
+/// typeApplicationBundleID ('bund')
+pub const TYPE_APPLICATION_BUNDLE_ID: u32 = 0x62756E64;
+/// typeWildCard ('****')
+pub const TYPE_WILDCARD: u32 = 0x2A2A2A2A;
+/// procNotFound - the target app isn't running
+pub const PROC_NOT_FOUND: i32 = -600;
+
+/// An Apple Event descriptor
+#[repr(C)]
+pub struct AEDesc {
+    pub descriptor_type: u32,
+    pub data_handle: *mut c_void,
+}
+
+#[link(name = "CoreServices", kind = "framework")]
+unsafe extern "C" {
+    /// Create a descriptor holding a copy of `data_ptr`
+    pub fn AECreateDesc(
+        type_code: u32,
+        data_ptr: *const c_void,
+        data_size: isize,
+        result: *mut AEDesc,
+    ) -> i16;
+
+    /// Free a descriptor's data
+    pub fn AEDisposeDesc(desc: *mut AEDesc) -> i16;
+
+    /// Check (and optionally ask for) permission to send Apple Events to a target
+    pub fn AEDeterminePermissionToAutomateTarget(
+        target: *const AEDesc,
+        event_class: u32,
+        event_id: u32,
+        ask_user_if_needed: bool,
+    ) -> i32;
 }
 
 // ============================================================================
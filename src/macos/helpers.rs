@@ -218,3 +218,22 @@ where
 
     bail!("No running application found matching '{}'", app_name)
 }
+
+// ============================================================================
+// Clipboard / Time Helpers
+// ============================================================================
+
+/// Read plain text from the system clipboard
+pub fn clipboard_text() -> Result<String> {
+    let output = std::process::Command::new("pbpaste").output()?;
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Current local time as "YYYY-MM-DD HH:MM:SS"
+pub fn local_timestamp() -> String {
+    std::process::Command::new("date")
+        .arg("+%Y-%m-%d %H:%M:%S")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default()
+}
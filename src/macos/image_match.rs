@@ -0,0 +1,246 @@
+//! Image matching, the last resort beneath accessibility and recorded clicks
+//!
+//! Some plugin windows expose nothing to accessibility and move their
+//! controls around (resizable GUIs, tabbed pages), so neither AX lookups nor
+//! a recorded position finds them. `os.click_image` captures the window,
+//! looks for a small template image in it - cropped from a screenshot of the
+//! same window - and clicks its center. Only built with the `image-match`
+//! feature.
+//!
+//! Matching is grayscale, coarse to fine: a search over both images scaled
+//! down, then a full-resolution pass around the best candidates. Templates
+//! have to come from a display with the same scale (Retina or not) as the
+//! one the window is on.
+
+use super::ffi::*;
+use super::screenshot::{CGImageRef, capture_window_image};
+use super::session::MacOSSession;
+use anyhow::{Context, Result};
+use objc2::msg_send;
+use objc2::rc::autoreleasepool;
+use objc2::runtime::AnyObject;
+use std::ffi::c_void;
+use std::path::Path;
+
+/// 8-bit grayscale pixels, row by row from the top
+pub struct Gray {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Gray {
+    fn at(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Average each `factor` x `factor` block
+    fn downscaled(&self, factor: usize) -> Gray {
+        let width = self.width / factor;
+        let height = self.height / factor;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let sum: u32 = (0..factor)
+                    .flat_map(|dy| (0..factor).map(move |dx| (dx, dy)))
+                    .map(|(dx, dy)| self.at(x * factor + dx, y * factor + dy) as u32)
+                    .sum();
+                pixels.push((sum / (factor * factor) as u32) as u8);
+            }
+        }
+        Gray {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Mean absolute difference (0-1) of `template` placed at x, y, giving up
+    /// once it can't beat `limit`
+    fn difference(&self, template: &Gray, x: usize, y: usize, limit: f64) -> f64 {
+        let area = (template.width * template.height) as f64;
+        let budget = (limit * 255.0 * area) as u64;
+        let mut total = 0u64;
+        for ty in 0..template.height {
+            let row = &self.pixels[(y + ty) * self.width + x..][..template.width];
+            let template_row = &template.pixels[ty * template.width..][..template.width];
+            total += row
+                .iter()
+                .zip(template_row)
+                .map(|(&a, &b)| a.abs_diff(b) as u64)
+                .sum::<u64>();
+            if total > budget {
+                return f64::INFINITY;
+            }
+        }
+        total as f64 / 255.0 / area
+    }
+}
+
+/// Find `template` in `image`: its top-left corner and difference (0 = exact),
+/// if one is within `tolerance`
+pub fn find(image: &Gray, template: &Gray, tolerance: f64) -> Option<(usize, usize, f64)> {
+    if template.width == 0
+        || template.height == 0
+        || template.width > image.width
+        || template.height > image.height
+    {
+        return None;
+    }
+    // Scale down while the template keeps at least 8 pixels a side
+    let factor = [4, 2, 1]
+        .into_iter()
+        .find(|&f| template.width / f >= 8 && template.height / f >= 8)
+        .unwrap_or(1);
+    let (small_image, small_template) = (image.downscaled(factor), template.downscaled(factor));
+
+    // Coarse pass: every position of the scaled-down images, keeping the best few
+    let mut candidates: Vec<(f64, usize, usize)> = Vec::new();
+    for y in 0..=small_image.height - small_template.height {
+        for x in 0..=small_image.width - small_template.width {
+            let limit = match candidates.last() {
+                Some(&(worst, _, _)) if candidates.len() == 5 => worst,
+                _ => 1.0,
+            };
+            let difference = small_image.difference(&small_template, x, y, limit);
+            if difference < limit || (difference == limit && candidates.len() < 5) {
+                candidates.push((difference, x, y));
+                candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+                candidates.truncate(5);
+            }
+        }
+    }
+
+    // Fine pass: full resolution around each candidate
+    let mut best: Option<(usize, usize, f64)> = None;
+    for (_, cx, cy) in candidates {
+        let x_range = (cx * factor).saturating_sub(factor)
+            ..=(cx * factor + factor).min(image.width - template.width);
+        for y in (cy * factor).saturating_sub(factor)
+            ..=(cy * factor + factor).min(image.height - template.height)
+        {
+            for x in x_range.clone() {
+                let limit = best.map_or(tolerance, |(_, _, d)| d);
+                let difference = image.difference(template, x, y, limit);
+                if difference <= limit {
+                    best = Some((x, y, difference));
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Draw a CGImage into grayscale pixels
+unsafe fn to_gray(image: *mut c_void) -> Result<Gray> {
+    unsafe {
+        let width = CGImageGetWidth(image);
+        let height = CGImageGetHeight(image);
+        let mut pixels = vec![0u8; width * height];
+        let space = CGColorSpaceCreateDeviceGray();
+        let context = CGBitmapContextCreate(
+            pixels.as_mut_ptr() as *mut c_void,
+            width,
+            height,
+            8,
+            width,
+            space,
+            K_CG_IMAGE_ALPHA_NONE,
+        );
+        CGColorSpaceRelease(space);
+        if context.is_null() {
+            anyhow::bail!("Failed to read the image's pixels");
+        }
+        let rect = CGRectRaw {
+            x: 0.0,
+            y: 0.0,
+            width: width as f64,
+            height: height as f64,
+        };
+        CGContextDrawImage(context, rect, image);
+        CGContextRelease(context);
+        Ok(Gray {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
+
+/// Load an image file (PNG, TIFF, JPEG...) as grayscale
+fn load_template(path: &Path) -> Result<Gray> {
+    autoreleasepool(|_pool| unsafe {
+        let os = MacOSSession::global();
+        let path_string = os.create_nsstring(&path.to_string_lossy())?;
+        let rep: *mut AnyObject = msg_send![
+            os.get_class("NSBitmapImageRep")?,
+            imageRepWithContentsOfFile: path_string
+        ];
+        let _: () = msg_send![path_string, release];
+        if rep.is_null() {
+            anyhow::bail!("Failed to read {}", path.display());
+        }
+        let image: CGImageRef = msg_send![rep, CGImage];
+        to_gray(image.0)
+    })
+}
+
+/// Find `template` in `app`'s frontmost window and click its center
+pub fn click_image(app: &str, template: &Path, tolerance: f64) -> Result<()> {
+    let template = load_template(template)?;
+    super::focus_app(app, "", true, false, 2000)?;
+    super::waits::settle(super::waits::Wait::Focus);
+
+    let (image, bounds) = capture_window_image(app)?;
+    let image = unsafe { to_gray(image.0)? };
+    let (x, y, difference) = find(&image, &template, tolerance)
+        .with_context(|| format!("The image isn't in the {} window", app))?;
+    // Captures are in pixels, clicks in points
+    let scale = image.width as f64 / bounds.width;
+    let point = CGPointRaw {
+        x: bounds.x + (x as f64 + template.width as f64 / 2.0) / scale,
+        y: bounds.y + (y as f64 + template.height as f64 / 2.0) / scale,
+    };
+    log::info!(
+        "Image found at {},{} px (difference {:.3}), clicking {:.0},{:.0}",
+        x,
+        y,
+        difference,
+        point.x,
+        point.y
+    );
+    super::clicks::click_at(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_template_in_noise() {
+        // A deterministic speckled background with a gradient square in it
+        let (width, height) = (120, 80);
+        let mut pixels: Vec<u8> = (0..width * height)
+            .map(|i| ((i * 7919) % 251) as u8)
+            .collect();
+        let mut template = Vec::new();
+        for ty in 0..20 {
+            for tx in 0..24 {
+                let value = (tx * 10 + ty * 3) as u8;
+                pixels[(50 + ty) * width + 70 + tx] = value;
+                template.push(value);
+            }
+        }
+        let image = Gray {
+            width,
+            height,
+            pixels,
+        };
+        let template = Gray {
+            width: 24,
+            height: 20,
+            pixels: template,
+        };
+        assert_eq!(find(&image, &template, 0.05), Some((70, 50, 0.0)));
+    }
+}
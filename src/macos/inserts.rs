@@ -0,0 +1,244 @@
+//! Track insert slots
+//!
+//! PTSL can't add, remove or bypass plugins, so `pt.plugins_insert`,
+//! `pt.plugins_remove` and `pt.plugins_bypass_slot` work a track's strip in
+//! the Mix (or Edit) window the way a person would: press the slot's insert
+//! selector and pick from its menu, or command-click the insert to bypass
+//! it. The window has to be open with its inserts view showing.
+
+use super::clicks::{ax_point, click_at_with_flags};
+use super::ffi::*;
+use super::helpers::{AXElement, CFArray};
+use super::session::MacOSSession;
+use anyhow::{Context, Result};
+use std::ffi::c_void;
+
+/// How deep to look for a track's strip below the window
+const STRIP_DEPTH: usize = 8;
+
+/// How deep to look for a button inside a strip
+const BUTTON_DEPTH: usize = 4;
+
+/// One insert slot on one track
+pub struct InsertSlot<'a> {
+    /// Window title prefix ("" = the Mix window, else the Edit window)
+    pub window: &'a str,
+    pub track: &'a str,
+    /// Accessibility name of the slot's insert selector (the empty-slot popup)
+    pub selector: &'a str,
+    /// Accessibility name of the slot's insert button (the plugin's name)
+    pub insert: &'a str,
+}
+
+/// Slot "a"-"j" or 1-10 as its letter
+pub fn slot_letter(slot: &str) -> Result<char> {
+    let letter = match slot.trim().parse::<u32>() {
+        Ok(n @ 1..=10) => (b'a' + n as u8 - 1) as char,
+        Ok(_) => anyhow::bail!("Insert slot {} is out of range (1-10)", slot),
+        Err(_) => match slot.trim().to_ascii_lowercase().as_str() {
+            s @ ("a" | "b" | "c" | "d" | "e" | "f" | "g" | "h" | "i" | "j") => {
+                s.chars().next().unwrap()
+            }
+            _ => anyhow::bail!("Insert slot must be a-j or 1-10, got '{}'", slot),
+        },
+    };
+    Ok(letter)
+}
+
+/// An element's title, else its description
+unsafe fn name(element: *mut c_void) -> String {
+    let os = MacOSSession::global();
+    unsafe {
+        os.get_ax_string_attr(element, "AXTitle")
+            .ok()
+            .filter(|title| !title.is_empty())
+            .or_else(|| os.get_ax_string_attr(element, "AXDescription").ok())
+            .unwrap_or_default()
+    }
+}
+
+/// The first element at or below `element` (within `depth` levels) that
+/// `matches`, retained
+unsafe fn find(
+    element: *mut c_void,
+    depth: usize,
+    matches: &dyn Fn(*mut c_void) -> bool,
+) -> Option<AXElement> {
+    unsafe {
+        if matches(element) {
+            return Some(AXElement::new(CFRetain(element)));
+        }
+        if depth == 0 {
+            return None;
+        }
+        let children = MacOSSession::global()
+            .get_ax_element_attr(element, "AXChildren")
+            .ok()?;
+        let children = CFArray::new(children);
+        (0..children.count()).find_map(|i| find(children.get(i), depth - 1, matches))
+    }
+}
+
+/// Does a strip's name belong to `track` ("Vox", "Vox - Audio Track",
+/// "Track \"Vox\"", ...)
+fn is_track(name: &str, track: &str) -> bool {
+    name == track
+        || name.starts_with(&format!("{} - ", track))
+        || name.contains(&format!("\"{}\"", track))
+}
+
+/// The window the strips are in: `window`, else Mix, else Edit
+unsafe fn strips_window(app: &AXElement, window: &str) -> Result<AXElement> {
+    let prefixes: &[&str] = if window.is_empty() {
+        &["Mix:", "Edit:"]
+    } else {
+        &[window]
+    };
+    unsafe {
+        let os = MacOSSession::global();
+        let windows = CFArray::new(os.get_ax_element_attr(app.as_ptr(), "AXWindows")?);
+        for prefix in prefixes {
+            for i in 0..windows.count() {
+                let candidate = windows.get(i);
+                if os
+                    .get_ax_string_attr(candidate, "AXTitle")
+                    .is_ok_and(|title| title.starts_with(prefix))
+                {
+                    return Ok(AXElement::new(CFRetain(candidate)));
+                }
+            }
+        }
+    }
+    anyhow::bail!("No Pro Tools {} window open", prefixes.join(" or "))
+}
+
+/// The button named `button` on `slot.track`'s strip
+unsafe fn strip_button(app: &AXElement, slot: &InsertSlot, button: &str) -> Result<AXElement> {
+    let button = button.to_lowercase();
+    let is_button =
+        |element: *mut c_void| unsafe { name(element).to_lowercase().starts_with(&button) };
+    unsafe {
+        let window = strips_window(app, slot.window)?;
+        let strip = find(window.as_ptr(), STRIP_DEPTH, &|element| {
+            is_track(&name(element), slot.track)
+                && find(element, BUTTON_DEPTH, &is_button).is_some()
+        })
+        .with_context(|| {
+            format!(
+                "No '{}' on track '{}' - is the track shown with its inserts view?",
+                button, slot.track
+            )
+        })?;
+        find(strip.as_ptr(), BUTTON_DEPTH, &is_button)
+            .with_context(|| format!("No '{}' on track '{}'", button, slot.track))
+    }
+}
+
+/// Press `slot`'s insert selector and return its open menu
+unsafe fn open_selector(app: &AXElement, slot: &InsertSlot) -> Result<AXElement> {
+    let is_menu = |element: *mut c_void| unsafe {
+        MacOSSession::global()
+            .get_ax_string_attr(element, "AXRole")
+            .is_ok_and(|role| role == "AXMenu")
+    };
+    unsafe {
+        let selector = strip_button(app, slot, slot.selector)?;
+        MacOSSession::global().perform_ax_action(selector.as_ptr(), "AXPress")?;
+        super::waits::settle(super::waits::Wait::Menu);
+        find(selector.as_ptr(), 1, &is_menu)
+            .or_else(|| find(app.as_ptr(), 2, &is_menu))
+            .with_context(|| format!("The insert selector on '{}' didn't open", slot.track))
+    }
+}
+
+/// Run `pick` on `slot`'s open insert menu, closing the menu if it fails
+fn with_menu(slot: &InsertSlot, pick: impl FnOnce(&AXElement) -> Result<String>) -> Result<String> {
+    let pid = super::get_pid_by_name("Pro Tools")?;
+    let app = unsafe { AXElement::new(AXUIElementCreateApplication(pid)) };
+    let menu = unsafe { open_selector(&app, slot)? };
+    let picked = pick(&menu);
+    if picked.is_err() {
+        super::keystroke(&["escape"]).ok();
+    }
+    super::waits::settle(super::waits::Wait::Menu);
+    picked
+}
+
+/// The menu item at or below `menu` named `title`: exact (ignoring case)
+/// first, then a soft match
+unsafe fn menu_item(menu: *mut c_void, title: &str, depth: usize) -> Option<AXElement> {
+    let is_item = |element: *mut c_void| unsafe {
+        MacOSSession::global()
+            .get_ax_string_attr(element, "AXRole")
+            .is_ok_and(|role| role == "AXMenuItem")
+    };
+    unsafe {
+        find(menu, depth, &|element| {
+            is_item(element) && name(element).eq_ignore_ascii_case(title)
+        })
+        .or_else(|| {
+            find(menu, depth, &|element| {
+                is_item(element) && crate::soft_match(&name(element), title)
+            })
+        })
+    }
+}
+
+/// Put `plugin` in the slot, returning the plugin's name as the menu has it
+///
+/// `format` picks the menu branch: "stereo" / "multichannel" (the track's own
+/// width) or "mono" / "multi-mono".
+pub fn insert(slot: &InsertSlot, plugin: &str, format: &str) -> Result<String> {
+    let branches: &[&str] = match format.to_lowercase().as_str() {
+        "" | "stereo" | "multichannel" => &["multichannel plug-in", "plug-in"],
+        "mono" | "multi-mono" | "multimono" => &["multi-mono plug-in"],
+        _ => anyhow::bail!(
+            "format must be stereo/multichannel or mono/multi-mono, got '{}'",
+            format
+        ),
+    };
+    with_menu(slot, |menu| unsafe {
+        let branch = branches
+            .iter()
+            .find_map(|branch| {
+                find(menu.as_ptr(), 1, &|element| {
+                    name(element).to_lowercase().starts_with(branch)
+                })
+            })
+            .with_context(|| format!("No {} branch in the insert menu", branches.join(" / ")))?;
+        let item = menu_item(branch.as_ptr(), plugin, 6)
+            .with_context(|| format!("No {} plugin '{}' in the insert menu", format, plugin))?;
+        let title = name(item.as_ptr());
+        MacOSSession::global().perform_ax_action(item.as_ptr(), "AXPress")?;
+        Ok(title)
+    })
+}
+
+/// Empty the slot
+pub fn remove(slot: &InsertSlot) -> Result<()> {
+    with_menu(slot, |menu| unsafe {
+        let item = menu_item(menu.as_ptr(), "no insert", 1)
+            .context("No 'no insert' in the insert menu")?;
+        MacOSSession::global().perform_ax_action(item.as_ptr(), "AXPress")?;
+        Ok(String::new())
+    })
+    .map(|_| ())
+}
+
+/// Toggle the slot's bypass (command-click on the insert)
+pub fn toggle_bypass(slot: &InsertSlot) -> Result<()> {
+    let pid = super::get_pid_by_name("Pro Tools")?;
+    unsafe {
+        let app = AXElement::new(AXUIElementCreateApplication(pid));
+        let button = strip_button(&app, slot, slot.insert)?;
+        let origin = ax_point(button.as_ptr(), "AXPosition", K_AX_VALUE_CG_POINT_TYPE)?;
+        let size = ax_point(button.as_ptr(), "AXSize", K_AX_VALUE_CG_SIZE_TYPE)?;
+        click_at_with_flags(
+            CGPointRaw {
+                x: origin.x + size.x / 2.0,
+                y: origin.y + size.y / 2.0,
+            },
+            super::CG_EVENT_FLAG_MASK_COMMAND,
+        )
+    }
+}
@@ -0,0 +1,132 @@
+//! Saved window layouts
+//!
+//! Pro Tools' Window Configurations bring back the Edit and Mix windows but
+//! leave floating windows (plugins, I/O, Workspace, video) wherever a client
+//! session dragged them. `pt.window_layout_save` records where every Pro
+//! Tools window is as a `[layouts.<name>]` entry in config.toml;
+//! `pt.window_layout_restore` recalls its Window Configuration and moves
+//! and resizes the windows back.
+
+use super::ffi::*;
+use super::helpers::{AXElement, CFArray};
+use super::session::MacOSSession;
+use crate::config::{LayoutConfig, WindowPlacement};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::sync::Mutex;
+
+static LAYOUTS: Mutex<Option<HashMap<String, LayoutConfig>>> = Mutex::new(None);
+
+/// Apply the `[layouts]` section
+pub fn set_config(layouts: HashMap<String, LayoutConfig>) {
+    *LAYOUTS.lock().unwrap() = Some(layouts);
+}
+
+/// The layout saved as `name`
+pub fn get(name: &str) -> Result<LayoutConfig> {
+    LAYOUTS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|layouts| layouts.get(name).cloned())
+        .with_context(|| format!("No layout named '{}' in config.toml", name))
+}
+
+/// Where `app`'s titled windows are now, front to back
+pub fn capture(app: &str) -> Result<Vec<WindowPlacement>> {
+    Ok(super::clicks::windows(app)?
+        .into_iter()
+        .filter(|window| !window.title.is_empty() && window.size.x > 0.0)
+        .map(|window| WindowPlacement {
+            window: window.title,
+            x: window.origin.x.round(),
+            y: window.origin.y.round(),
+            width: window.size.x.round(),
+            height: window.size.y.round(),
+        })
+        .collect())
+}
+
+/// Set an AXPosition / AXSize attribute
+unsafe fn set_ax_point(
+    element: *mut c_void,
+    attr: &str,
+    value_type: u32,
+    point: CGPointRaw,
+) -> Result<()> {
+    unsafe {
+        let value = AXValueCreate(value_type, &point as *const CGPointRaw as *const c_void);
+        if value.is_null() {
+            anyhow::bail!("Failed to create {}", attr);
+        }
+        let result = MacOSSession::global().set_ax_attribute(element, attr, value);
+        CFRelease(value);
+        result
+    }
+}
+
+/// Move and resize `app`'s windows to `placements`, returning the titles of
+/// those that aren't open
+///
+/// Titles match exactly first, then loosely, each window taking one placement.
+pub fn place(app: &str, placements: &[WindowPlacement]) -> Result<Vec<String>> {
+    let os = MacOSSession::global();
+    let pid = super::get_pid_by_name(app)?;
+    let mut missing = Vec::new();
+    unsafe {
+        let ax_app = AXElement::new(AXUIElementCreateApplication(pid));
+        let windows = CFArray::new(os.get_ax_element_attr(ax_app.as_ptr(), "AXWindows")?);
+        let mut open: Vec<(*mut c_void, String)> = (0..windows.count())
+            .map(|i| {
+                let window = windows.get(i);
+                (
+                    window,
+                    os.get_ax_string_attr(window, "AXTitle").unwrap_or_default(),
+                )
+            })
+            .collect();
+        for placement in placements {
+            let index = open
+                .iter()
+                .position(|(_, title)| *title == placement.window)
+                .or_else(|| {
+                    open.iter()
+                        .position(|(_, title)| crate::soft_match(title, &placement.window))
+                });
+            let Some(index) = index else {
+                missing.push(placement.window.clone());
+                continue;
+            };
+            let (window, title) = open.remove(index);
+            log::info!(
+                "Placing '{}' at {:.0},{:.0} ({:.0}x{:.0})",
+                title,
+                placement.x,
+                placement.y,
+                placement.width,
+                placement.height
+            );
+            let position = CGPointRaw {
+                x: placement.x,
+                y: placement.y,
+            };
+            let size = CGPointRaw {
+                x: placement.width,
+                y: placement.height,
+            };
+            // Move first so the new size fits on the window's screen, then
+            // move again in case the size change nudged it. Plugin windows
+            // mostly can't be resized, so only the move has to work.
+            set_ax_point(window, "AXPosition", K_AX_VALUE_CG_POINT_TYPE, position)
+                .with_context(|| format!("Failed to move '{}'", title))?;
+            match set_ax_point(window, "AXSize", K_AX_VALUE_CG_SIZE_TYPE, size) {
+                Ok(()) => {
+                    set_ax_point(window, "AXPosition", K_AX_VALUE_CG_POINT_TYPE, position).ok();
+                }
+                Err(e) => log::debug!("'{}' keeps its size: {:#}", title, e),
+            }
+        }
+    }
+    Ok(missing)
+}
@@ -0,0 +1,215 @@
+//! Menu paths on localized apps
+//!
+//! Actions and configs name menu items in English ("Edit > Trim Clip > To
+//! Selection"). With a `[menu_locale]` section, each path is resolved
+//! against the app's real menu tree before it's clicked, one level at a time:
+//!
+//! 1. the English title, if the app has it
+//! 2. the translation map: the shipped one for `language`, overridden by
+//!    the config's own `translations`
+//! 3. with `fuzzy` on: a title that matches ignoring case and punctuation
+//!    ("Commit..." vs "Commit…"), and for Pro Tools' top-level menus, the
+//!    menu at the same position as in the English menu bar
+//!
+//! A resolved path is cached per app, so the menu tree is only read the
+//! first time a path is used. Without the section, paths pass through.
+
+use crate::config::MenuLocaleConfig;
+use crate::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Pro Tools' top-level menus in English, in menu bar order
+const PT_MENU_BAR: &[&str] = &[
+    "File",
+    "Edit",
+    "View",
+    "Track",
+    "Clip",
+    "Event",
+    "AudioSuite",
+    "Options",
+    "Setup",
+    "Window",
+    "Marketplace",
+    "Help",
+];
+
+/// Top-level menu that keeps its name in every language (position anchor)
+const PT_ANCHOR: &str = "AudioSuite";
+
+struct Locale {
+    /// English title => localized title
+    translations: HashMap<String, String>,
+    fuzzy: bool,
+}
+
+/// Active locale (None = paths pass through). Replaced on config reload.
+static LOCALE: Mutex<Option<Locale>> = Mutex::new(None);
+
+/// (app, English path) => localized path
+type ResolvedPaths = HashMap<(String, Vec<String>), Vec<String>>;
+
+static RESOLVED: Mutex<Option<ResolvedPaths>> = Mutex::new(None);
+
+#[derive(Deserialize)]
+struct MenuTree {
+    menus: Vec<MenuItem>,
+}
+
+#[derive(Deserialize)]
+struct MenuItem {
+    title: String,
+    children: Option<Vec<MenuItem>>,
+}
+
+/// Apply menu locale settings from config (None = use paths as written)
+pub fn set_config(config: Option<MenuLocaleConfig>) {
+    *RESOLVED.lock().unwrap() = None;
+    let Some(config) = config else {
+        *LOCALE.lock().unwrap() = None;
+        return;
+    };
+
+    let mut translations = match config.language.as_deref().map(shipped_map) {
+        Some(Ok(map)) => map,
+        Some(Err(e)) => {
+            log::warn!("Menu locale: {:#}", e);
+            HashMap::new()
+        }
+        None => HashMap::new(),
+    };
+    translations.extend(config.translations);
+    log::info!(
+        "Menu locale: {} translated title(s){}",
+        translations.len(),
+        if config.fuzzy { ", fuzzy matching" } else { "" }
+    );
+    *LOCALE.lock().unwrap() = Some(Locale {
+        translations,
+        fuzzy: config.fuzzy,
+    });
+}
+
+/// Shipped English => localized map for a language code
+fn shipped_map(language: &str) -> R<HashMap<String, String>> {
+    let text = match language.to_lowercase().as_str() {
+        "de" => include_str!("menu_locale/de.toml"),
+        "es" => include_str!("menu_locale/es.toml"),
+        "fr" => include_str!("menu_locale/fr.toml"),
+        "ja" => include_str!("menu_locale/ja.toml"),
+        other => anyhow::bail!(
+            "no shipped menu translations for '{}' (de, es, fr, ja) - use [menu_locale.translations]",
+            other
+        ),
+    };
+    Ok(toml::from_str(text)?)
+}
+
+/// The menu path to click in `app_name` for an English `menu_path`
+pub fn localize(app_name: &str, menu_path: &[&str]) -> Vec<String> {
+    let english: Vec<String> = menu_path.iter().map(|s| s.to_string()).collect();
+    let locale = LOCALE.lock().unwrap();
+    let Some(locale) = locale.as_ref() else {
+        return english;
+    };
+
+    let key = (app_name.to_string(), english);
+    if let Some(path) = RESOLVED.lock().unwrap().as_ref().and_then(|m| m.get(&key)) {
+        return path.clone();
+    }
+
+    let menus = match super::get_app_menus(app_name)
+        .and_then(|json| Ok(serde_json::from_str::<MenuTree>(&json)?))
+    {
+        Ok(tree) => tree.menus,
+        Err(e) => {
+            log::debug!("Menu locale: can't read menus of '{}': {:#}", app_name, e);
+            return key.1;
+        }
+    };
+    let path = resolve(locale, app_name, &menus, &key.1);
+    if path != key.1 {
+        log::info!("Menu path {:?} => {:?}", key.1, path);
+    }
+    RESOLVED
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(key, path.clone());
+    path
+}
+
+/// Drop cached paths for an app (a click failed - its menus may have changed)
+pub fn forget(app_name: &str) {
+    if let Some(resolved) = RESOLVED.lock().unwrap().as_mut() {
+        resolved.retain(|(app, _), _| app != app_name);
+    }
+}
+
+/// Walk the menu tree, resolving each level; unresolved levels stay English
+fn resolve(locale: &Locale, app_name: &str, menus: &[MenuItem], english: &[String]) -> Vec<String> {
+    let mut level = Some(menus);
+    let mut path = Vec::with_capacity(english.len());
+    for (depth, segment) in english.iter().enumerate() {
+        let found = level.and_then(|items| {
+            find_item(locale, items, segment).or_else(|| {
+                (locale.fuzzy && depth == 0 && crate::soft_match(app_name, "Pro Tools"))
+                    .then(|| by_position(items, segment))
+                    .flatten()
+            })
+        });
+        match found {
+            Some(item) => {
+                path.push(item.title.clone());
+                level = item.children.as_deref();
+            }
+            None => {
+                log::warn!(
+                    "Menu locale: no match for '{}' in {} - add it to [menu_locale.translations]",
+                    segment,
+                    app_name
+                );
+                path.push(segment.clone());
+                level = None;
+            }
+        }
+    }
+    path
+}
+
+fn find_item<'a>(locale: &Locale, items: &'a [MenuItem], english: &str) -> Option<&'a MenuItem> {
+    let by_title = |title: &str| items.iter().find(|item| item.title == title);
+    by_title(english)
+        .or_else(|| {
+            locale
+                .translations
+                .get(english)
+                .and_then(|title| by_title(title))
+        })
+        .or_else(|| {
+            let wanted = fuzzy_key(english);
+            locale
+                .fuzzy
+                .then(|| items.iter().find(|item| fuzzy_key(&item.title) == wanted))
+                .flatten()
+        })
+}
+
+/// The top-level menu at the English menu's position, counted from the anchor
+fn by_position<'a>(items: &'a [MenuItem], english: &str) -> Option<&'a MenuItem> {
+    let wanted = PT_MENU_BAR.iter().position(|title| *title == english)?;
+    let anchor = PT_MENU_BAR.iter().position(|title| *title == PT_ANCHOR)?;
+    let local_anchor = items.iter().position(|item| item.title == PT_ANCHOR)?;
+    items.get((local_anchor + wanted).checked_sub(anchor)?)
+}
+
+/// Lowercase letters and digits only, so punctuation and spacing don't matter
+fn fuzzy_key(title: &str) -> String {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
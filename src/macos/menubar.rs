@@ -3,18 +3,45 @@
 //! Provides functionality to display a status item (icon) in the macOS menu bar.
 //! Uses NSStatusBar and NSStatusItem APIs via objc2.
 
+use crate::config::{MenubarConfig, MenubarItemConfig};
 use anyhow::{Context, Result};
 use objc2::msg_send;
 use objc2::runtime::{AnyClass, AnyObject};
 use std::ptr;
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::{Mutex, OnceLock};
 
 // Import session
 use super::session::MacOSSession;
 
+// Status item button, kept so its title can be updated later
+static STATUS_BUTTON: AtomicPtr<AnyObject> = AtomicPtr::new(ptr::null_mut());
+
+// Status menu and its delegate, kept so config-defined items can be rebuilt on reload
+static STATUS_MENU: AtomicPtr<AnyObject> = AtomicPtr::new(ptr::null_mut());
+static MENU_DELEGATE: AtomicPtr<AnyObject> = AtomicPtr::new(ptr::null_mut());
+
 // Global callback for reload config
 static RELOAD_CALLBACK: OnceLock<Box<dyn Fn() + Send + Sync>> = OnceLock::new();
 
+// Text shown next to the icon: the current layer, running timers, then any status text
+static LAYER_LABEL: Mutex<String> = Mutex::new(String::new());
+static TIMER_TEXT: Mutex<String> = Mutex::new(String::new());
+static STATUS_TEXT: Mutex<String> = Mutex::new(String::new());
+
+// Config-defined items (`[[menubar.items]]`); an item's tag is CUSTOM_ITEM_TAG + its index
+static CUSTOM_ITEMS: Mutex<Vec<MenubarItemConfig>> = Mutex::new(Vec::new());
+
+/// Menu item tags: "Quit", the toggles, the layer readout, the separator above the custom items,
+/// the first custom item
+const QUIT_TAG: isize = 1;
+const QUIET_TAG: isize = 2;
+const REVIEW_TAG: isize = 3;
+const LAYER_TAG: isize = 4;
+const SUSPEND_TAG: isize = 5;
+const CUSTOM_SEPARATOR_TAG: isize = 1000;
+const CUSTOM_ITEM_TAG: isize = 1001;
+
 /// C callback function that can be called from Objective-C
 #[unsafe(no_mangle)]
 extern "C" fn menu_reload_config(
@@ -47,6 +74,32 @@ extern "C" fn menu_edit_config(
     }
 }
 
+extern "C" fn menu_reveal_config(
+    _this: *mut AnyObject,
+    _cmd: objc2::runtime::Sel,
+    _sender: *mut AnyObject,
+) {
+    log::info!("Reveal Config menu item clicked");
+    let result = crate::config::get_config_path()
+        .and_then(|path| MacOSSession::global().reveal_in_finder(&path));
+    if let Err(e) = result {
+        log::error!("Failed to reveal config: {:#}", e);
+    }
+}
+
+extern "C" fn menu_reveal_log(
+    _this: *mut AnyObject,
+    _cmd: objc2::runtime::Sel,
+    _sender: *mut AnyObject,
+) {
+    log::info!("Reveal Log menu item clicked");
+    let result = crate::config::get_log_path()
+        .and_then(|path| MacOSSession::global().reveal_in_finder(&path));
+    if let Err(e) = result {
+        log::error!("Failed to reveal log: {:#}", e);
+    }
+}
+
 extern "C" fn menu_restore_defaults(
     _this: *mut AnyObject,
     _cmd: objc2::runtime::Sel,
@@ -58,10 +111,121 @@ extern "C" fn menu_restore_defaults(
             std::fs::rename(&config, config.with_extension("backup")).ok();
         }
 
-        crate::config::create_default_config(&config).ok();
+        crate::config::create_default_config(&config, None).ok();
+    }
+}
+
+extern "C" fn menu_toggle_quiet(
+    _this: *mut AnyObject,
+    _cmd: objc2::runtime::Sel,
+    sender: *mut AnyObject,
+) {
+    let quiet = crate::notify::toggle_quiet();
+    log::info!("Quiet Notifications menu item clicked");
+    unsafe {
+        set_item_checked(sender, quiet);
+    }
+}
+
+extern "C" fn menu_toggle_review(
+    _this: *mut AnyObject,
+    _cmd: objc2::runtime::Sel,
+    sender: *mut AnyObject,
+) {
+    log::info!("Review Mode menu item clicked");
+    let enabled = crate::input::review::toggle();
+    unsafe {
+        set_item_checked(sender, enabled);
+    }
+}
+
+extern "C" fn menu_toggle_suspend(
+    _this: *mut AnyObject,
+    _cmd: objc2::runtime::Sel,
+    sender: *mut AnyObject,
+) {
+    log::info!("Suspend Hotkeys menu item clicked");
+    let suspended = crate::input::suspend::toggle(None);
+    unsafe {
+        set_item_checked(sender, suspended);
+    }
+}
+
+extern "C" fn menu_base_layer(
+    _this: *mut AnyObject,
+    _cmd: objc2::runtime::Sel,
+    _sender: *mut AnyObject,
+) {
+    log::info!("Layer menu item clicked");
+    crate::input::layers::set(None);
+}
+
+/// NSMenuDelegate: refresh the toggles' checkmarks and the layer readout just
+/// before the menu opens
+///
+/// Hotkeys, IPC and config reloads flip these states too, so the menu reads
+/// them fresh rather than tracking every change.
+extern "C" fn menu_needs_update(
+    _this: *mut AnyObject,
+    _cmd: objc2::runtime::Sel,
+    menu: *mut AnyObject,
+) {
+    let toggles = [
+        (QUIET_TAG, crate::notify::is_quiet()),
+        (REVIEW_TAG, crate::input::review::is_enabled()),
+        (SUSPEND_TAG, crate::input::suspend::is_suspended()),
+    ];
+    for (tag, checked) in toggles {
+        unsafe {
+            let item: *mut AnyObject = msg_send![menu, itemWithTag: tag];
+            set_item_checked(item, checked);
+        }
+    }
+
+    let layer = crate::input::layers::current();
+    unsafe {
+        let item: *mut AnyObject = msg_send![menu, itemWithTag: LAYER_TAG];
+        if !item.is_null()
+            && let Ok(title) = MacOSSession::global()
+                .create_nsstring(&format!("Layer: {}", layer.as_deref().unwrap_or("base")))
+        {
+            let _: () = msg_send![item, setTitle: title];
+        }
     }
 }
 
+extern "C" fn menu_run_item(
+    _this: *mut AnyObject,
+    _cmd: objc2::runtime::Sel,
+    sender: *mut AnyObject,
+) {
+    let tag: isize = unsafe { msg_send![sender, tag] };
+    let item = usize::try_from(tag - CUSTOM_ITEM_TAG)
+        .ok()
+        .and_then(|index| CUSTOM_ITEMS.lock().unwrap().get(index).cloned());
+    let Some(item) = item else {
+        log::error!("Menu item with tag {} has no config entry", tag);
+        return;
+    };
+    log::info!(
+        "'{}' menu item clicked - running {}",
+        item.title,
+        item.action
+    );
+
+    // Off the main thread - Pro Tools actions block until they finish
+    std::thread::spawn(move || {
+        let params = crate::config::Params::new(item.params);
+        if let Err(e) = crate::ipc::run_action(&item.action, params) {
+            log::error!("Menu item '{}' failed: {:#}", item.title, e);
+            super::show_notification(&crate::notify::text(
+                "action_failed",
+                &[("action", &item.title), ("error", &format!("{:#}", e))],
+            ));
+        }
+    });
+}
+
 extern "C" fn menu_show_about(
     _this: *mut AnyObject,
     _cmd: objc2::runtime::Sel,
@@ -165,6 +329,8 @@ where
         anyhow::bail!("Failed to get status item button");
     }
 
+    STATUS_BUTTON.store(button, Ordering::SeqCst);
+
     // Configure button to send action on left click
     let left_mouse_down_mask: u64 = 1 << 1; // NSEventMaskLeftMouseDown
     let _: i64 = msg_send![button, sendActionOn: left_mouse_down_mask];
@@ -230,6 +396,140 @@ where
     Ok(MenuBar { status_item })
 }
 
+/// Replace the config-defined menu items (None = no custom items)
+///
+/// Items naming unknown actions are skipped. Once the menu exists this must
+/// run on the main thread (config reload does).
+pub fn set_custom_items(config: Option<MenubarConfig>) {
+    let items: Vec<MenubarItemConfig> = config
+        .map(|c| c.items)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|item| {
+            let known = crate::config::get_action(&item.action).is_some();
+            if !known {
+                log::error!(
+                    "Skipping menu item '{}': unknown action '{}'",
+                    item.title,
+                    item.action
+                );
+            }
+            known
+        })
+        .collect();
+    *CUSTOM_ITEMS.lock().unwrap() = items;
+
+    let menu = STATUS_MENU.load(Ordering::SeqCst);
+    if !menu.is_null()
+        && let Err(e) = unsafe { rebuild_custom_items(menu) }
+    {
+        log::error!("Failed to update menu bar items: {:#}", e);
+    }
+}
+
+/// Remove the custom section, then insert the current items above Quit's separator
+unsafe fn rebuild_custom_items(menu: *mut AnyObject) -> Result<()> {
+    unsafe {
+        let count: isize = msg_send![menu, numberOfItems];
+        for index in (0..count).rev() {
+            let item: *mut AnyObject = msg_send![menu, itemAtIndex: index];
+            let tag: isize = msg_send![item, tag];
+            if tag >= CUSTOM_SEPARATOR_TAG {
+                let _: () = msg_send![menu, removeItemAtIndex: index];
+            }
+        }
+
+        let items = CUSTOM_ITEMS.lock().unwrap().clone();
+        if items.is_empty() {
+            return Ok(());
+        }
+        let quit_index: isize = msg_send![menu, indexOfItemWithTag: QUIT_TAG];
+        if quit_index < 1 {
+            anyhow::bail!("Quit menu item not found");
+        }
+        let start = quit_index - 1;
+
+        let separator_class =
+            AnyClass::get("NSMenuItem").context("Failed to get NSMenuItem class")?;
+        let separator: *mut AnyObject = msg_send![separator_class, separatorItem];
+        let _: () = msg_send![separator, setTag: CUSTOM_SEPARATOR_TAG];
+        let _: () = msg_send![menu, insertItem: separator atIndex: start];
+
+        let delegate = MENU_DELEGATE.load(Ordering::SeqCst);
+        for (i, config) in (1..).zip(&items) {
+            let item = create_menu_item(&config.title, "runMenuItem:", Some(delegate))?;
+            let _: () = msg_send![item, setTag: CUSTOM_ITEM_TAG + i - 1];
+            let _: () = msg_send![menu, insertItem: item atIndex: start + i];
+        }
+        log::info!("Added {} menu bar item(s) from config", items.len());
+        Ok(())
+    }
+}
+
+/// Show text next to the menu bar icon (empty string clears it)
+///
+/// The current layer and timers, if any, stay in front of the text. Safe to call from
+/// any thread - the update is performed on the main thread.
+pub fn set_status_text(text: &str) {
+    *STATUS_TEXT.lock().unwrap() = text.to_string();
+    update_title();
+}
+
+/// Show the current layer next to the menu bar icon (None = base, no label)
+pub fn set_layer_label(layer: Option<&str>) {
+    *LAYER_LABEL.lock().unwrap() =
+        layer.map_or_else(String::new, |name| format!(" {}", name.to_uppercase()));
+    update_title();
+}
+
+/// Show running timers next to the menu bar icon (empty string clears them)
+pub fn set_timer_text(text: &str) {
+    *TIMER_TEXT.lock().unwrap() = if text.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", text)
+    };
+    update_title();
+}
+
+/// Dim the menu bar icon while hotkeys are suspended
+pub fn set_suspended(suspended: bool) {
+    if STATUS_BUTTON.load(Ordering::SeqCst).is_null() {
+        return;
+    }
+    unsafe {
+        super::dispatch_to_main_queue(move || {
+            let button = STATUS_BUTTON.load(Ordering::SeqCst);
+            let _: () = msg_send![button, setAppearsDisabled: suspended];
+        });
+    }
+}
+
+/// Set the button title to the layer label, timers and status text
+fn update_title() {
+    let button = STATUS_BUTTON.load(Ordering::SeqCst);
+    if button.is_null() {
+        return;
+    }
+    let text = format!(
+        "{}{}{}",
+        LAYER_LABEL.lock().unwrap(),
+        TIMER_TEXT.lock().unwrap(),
+        STATUS_TEXT.lock().unwrap()
+    );
+    unsafe {
+        let Ok(title) = MacOSSession::global().create_nsstring(&text) else {
+            return;
+        };
+        let _: () = msg_send![
+            button,
+            performSelectorOnMainThread: objc2::sel!(setTitle:)
+            withObject: title
+            waitUntilDone: false
+        ];
+    }
+}
+
 /// Creates a delegate object to handle menu callbacks
 unsafe fn create_menu_delegate() -> Result<*mut AnyObject> {
     use objc2::declare::ClassBuilder;
@@ -266,6 +566,49 @@ unsafe fn create_menu_delegate() -> Result<*mut AnyObject> {
             menu_edit_config as extern "C" fn(*mut AnyObject, objc2::runtime::Sel, *mut AnyObject),
         );
 
+        builder.add_method(
+            sel!(revealConfig:),
+            menu_reveal_config
+                as extern "C" fn(*mut AnyObject, objc2::runtime::Sel, *mut AnyObject),
+        );
+
+        builder.add_method(
+            sel!(revealLog:),
+            menu_reveal_log as extern "C" fn(*mut AnyObject, objc2::runtime::Sel, *mut AnyObject),
+        );
+
+        builder.add_method(
+            sel!(toggleQuiet:),
+            menu_toggle_quiet as extern "C" fn(*mut AnyObject, objc2::runtime::Sel, *mut AnyObject),
+        );
+
+        builder.add_method(
+            sel!(toggleReview:),
+            menu_toggle_review
+                as extern "C" fn(*mut AnyObject, objc2::runtime::Sel, *mut AnyObject),
+        );
+
+        builder.add_method(
+            sel!(toggleSuspend:),
+            menu_toggle_suspend
+                as extern "C" fn(*mut AnyObject, objc2::runtime::Sel, *mut AnyObject),
+        );
+
+        builder.add_method(
+            sel!(baseLayer:),
+            menu_base_layer as extern "C" fn(*mut AnyObject, objc2::runtime::Sel, *mut AnyObject),
+        );
+
+        builder.add_method(
+            sel!(menuNeedsUpdate:),
+            menu_needs_update as extern "C" fn(*mut AnyObject, objc2::runtime::Sel, *mut AnyObject),
+        );
+
+        builder.add_method(
+            sel!(runMenuItem:),
+            menu_run_item as extern "C" fn(*mut AnyObject, objc2::runtime::Sel, *mut AnyObject),
+        );
+
         // Add the showAbout: method
         builder.add_method(
             sel!(showAbout:),
@@ -282,7 +625,14 @@ unsafe fn create_menu_delegate() -> Result<*mut AnyObject> {
 /// Creates the menu for the status item
 ///
 /// Menu items:
+/// - "Edit Config" - Opens config.toml in the default editor
+/// - "Reveal Config" / "Reveal Log" - Show the files in Finder
 /// - "Reload Config" - Reloads the hotkey configuration
+/// - "Quiet Notifications" - Mutes all notifications (checked while on)
+/// - "Review Mode" - Locks out editing hotkeys (checked while on)
+/// - "Layer: ..." - The current hotkey layer; click to go back to the base layer
+/// - "Suspend Hotkeys" - Lets every key through (checked while on, icon dimmed)
+/// - items from `[[menubar.items]]`, if any
 /// - "Quit" - Terminates the application
 unsafe fn create_status_menu(delegate: *mut AnyObject) -> Result<*mut AnyObject> {
     log::debug!("Getting NSMenu class...");
@@ -316,6 +666,14 @@ unsafe fn create_status_menu(delegate: *mut AnyObject) -> Result<*mut AnyObject>
     let _: () = msg_send![menu, addItem: reload_item];
     log::debug!("Added 'Edit Config' item");
 
+    // Create "Reveal Config" and "Reveal Log" menu items
+    let reveal_config_item =
+        unsafe { create_menu_item("Reveal Config", "revealConfig:", Some(delegate))? };
+    let _: () = msg_send![menu, addItem: reveal_config_item];
+    let reveal_log_item = unsafe { create_menu_item("Reveal Log", "revealLog:", Some(delegate))? };
+    let _: () = msg_send![menu, addItem: reveal_log_item];
+    log::debug!("Added 'Reveal Config' and 'Reveal Log' items");
+
     // Create "Reload Config" menu item
     log::debug!("Creating 'Reload Config' menu item...");
     let reload_item =
@@ -330,6 +688,36 @@ unsafe fn create_status_menu(delegate: *mut AnyObject) -> Result<*mut AnyObject>
     let _: () = msg_send![menu, addItem: restore_item];
     log::debug!("Added 'Restore Defaults' item");
 
+    // Create "Quiet Notifications" toggle, checked while notifications are muted
+    let quiet_item =
+        unsafe { create_menu_item("Quiet Notifications", "toggleQuiet:", Some(delegate))? };
+    let _: () = msg_send![quiet_item, setTag: QUIET_TAG];
+    let _: () = msg_send![menu, addItem: quiet_item];
+    log::debug!("Added 'Quiet Notifications' item");
+
+    // Create "Review Mode" toggle, checked while editing hotkeys are locked out
+    let review_item = unsafe { create_menu_item("Review Mode", "toggleReview:", Some(delegate))? };
+    let _: () = msg_send![review_item, setTag: REVIEW_TAG];
+    let _: () = msg_send![menu, addItem: review_item];
+    log::debug!("Added 'Review Mode' item");
+
+    // Create the current layer readout, which switches back to the base layer
+    let layer_item = unsafe { create_menu_item("Layer: base", "baseLayer:", Some(delegate))? };
+    let _: () = msg_send![layer_item, setTag: LAYER_TAG];
+    let _: () = msg_send![menu, addItem: layer_item];
+    log::debug!("Added 'Layer' item");
+
+    // Create "Suspend Hotkeys" toggle, checked while no hotkeys fire
+    let suspend_item =
+        unsafe { create_menu_item("Suspend Hotkeys", "toggleSuspend:", Some(delegate))? };
+    let _: () = msg_send![suspend_item, setTag: SUSPEND_TAG];
+    let _: () = msg_send![menu, addItem: suspend_item];
+    log::debug!("Added 'Suspend Hotkeys' item");
+
+    // Toggle checkmarks are refreshed each time the menu opens
+    let _: () = msg_send![menu, setDelegate: delegate];
+    menu_needs_update(delegate, objc2::sel!(menuNeedsUpdate:), menu);
+
     // Create separator
     log::debug!("Creating separator...");
     let separator_class = AnyClass::get("NSMenuItem").context("Failed to get NSMenuItem class")?;
@@ -340,13 +728,31 @@ unsafe fn create_status_menu(delegate: *mut AnyObject) -> Result<*mut AnyObject>
     // Create "Quit" menu item
     log::debug!("Creating 'Quit' menu item...");
     let quit_item = unsafe { create_menu_item("Quit", "terminate:", None)? };
+    let _: () = msg_send![quit_item, setTag: QUIT_TAG];
     let _: () = msg_send![menu, addItem: quit_item];
     log::debug!("Added 'Quit' item");
 
+    // Config-defined items go above Quit, and are rebuilt on reload
+    STATUS_MENU.store(menu, Ordering::SeqCst);
+    MENU_DELEGATE.store(delegate, Ordering::SeqCst);
+    if let Err(e) = unsafe { rebuild_custom_items(menu) } {
+        log::error!("Failed to add menu bar items: {:#}", e);
+    }
+
     log::debug!("Menu creation complete");
     Ok(menu)
 }
 
+/// Show or clear a menu item's checkmark
+unsafe fn set_item_checked(item: *mut AnyObject, checked: bool) {
+    if item.is_null() {
+        return;
+    }
+    // NSControlStateValueOn = 1, NSControlStateValueOff = 0
+    let state: isize = if checked { 1 } else { 0 };
+    let _: () = msg_send![item, setState: state];
+}
+
 /// Creates a menu item with title and action selector
 ///
 /// Legacy wrapper that calls the session method
@@ -489,7 +895,14 @@ impl MacOSSession {
                 "terminate:" => sel!(terminate:),
                 "restoreDefaults:" => sel!(restoreDefaults:),
                 "editConfig:" => sel!(editConfig:),
+                "revealConfig:" => sel!(revealConfig:),
+                "revealLog:" => sel!(revealLog:),
                 "reloadConfig:" => sel!(reloadConfig:),
+                "toggleQuiet:" => sel!(toggleQuiet:),
+                "toggleReview:" => sel!(toggleReview:),
+                "baseLayer:" => sel!(baseLayer:),
+                "toggleSuspend:" => sel!(toggleSuspend:),
+                "runMenuItem:" => sel!(runMenuItem:),
                 "showAbout:" => sel!(showAbout:),
                 _ => anyhow::bail!("Unknown action: {}", action),
             };
@@ -526,7 +939,8 @@ impl MacOSSession {
         }
     }
 
-    /// Show an About dialog with version information
+    /// Show an About dialog with version information and where the config
+    /// and log live
     ///
     /// # Example
     /// ```ignore
@@ -535,9 +949,14 @@ impl MacOSSession {
     /// ```
     pub unsafe fn show_about_dialog(&self) -> Result<()> {
         let version = env!("CARGO_PKG_VERSION");
+        let path = |path: Result<std::path::PathBuf>| {
+            path.map_or_else(|e| e.to_string(), |path| path.display().to_string())
+        };
         let message = format!(
-            "ProTools Hotkey Daemon\nA fast, scriptable hotkey system for Pro Tools\n\nVersion {}",
-            version
+            "ProTools Hotkey Daemon\nA fast, scriptable hotkey system for Pro Tools\n\nVersion {}\n\nConfig: {}\nLog: {}",
+            version,
+            path(crate::config::get_config_path()),
+            path(crate::config::get_log_path())
         );
 
         unsafe { self.show_alert("About pthkd", &message, &["OK"])? };
@@ -11,18 +11,31 @@ pub mod swift_bridge;
 // FFI declarations (shared across modules)
 pub mod ffi;
 pub mod helpers;
+pub mod native_ax;
 
 // Stable modules
 pub mod events;
+#[cfg(feature = "image-match")]
+pub mod image_match;
 pub mod notifications;
 pub mod permissions;
+pub mod screenshot;
+pub mod sysmon;
+pub mod tally;
+pub mod timers;
+pub mod waits;
 
 // Core abstractions
 pub mod session;
 
 // Experimental modules (work in progress)
+pub mod app_events;
 pub mod carbon_hotkeys;
+pub mod clicks;
+pub mod inserts;
 pub mod keyring;
+pub mod layouts;
+pub mod menu_locale;
 pub mod menubar;
 pub mod window;
 
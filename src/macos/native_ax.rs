@@ -0,0 +1,139 @@
+//! Native fallbacks for when the Swift UI library isn't available
+//!
+//! Covers the queries the daemon needs to keep running - app and window
+//! lookup, text-field detection, launching apps. UI automation (menus,
+//! buttons, typing) still requires PTHKDui.
+
+use super::ffi::*;
+use super::helpers::{AXElement, CFArray, with_running_app};
+use super::session::MacOSSession;
+use super::swift_bridge::FrontmostInfo;
+use anyhow::Result;
+use objc2::msg_send;
+use objc2::runtime::AnyObject;
+use std::ffi::c_void;
+
+/// Names of all running applications
+pub fn running_apps() -> Result<Vec<String>> {
+    let apps = MacOSSession::global().get_running_apps()?;
+    let mut names = Vec::new();
+    unsafe {
+        let count: usize = msg_send![apps, count];
+        for i in 0..count {
+            let app: *mut AnyObject = msg_send![apps, objectAtIndex: i];
+            if app.is_null() {
+                continue;
+            }
+            if let Some(name) = app_name(app) {
+                names.push(name);
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Frontmost app name and focused window title
+pub fn frontmost_info() -> Result<FrontmostInfo> {
+    let os = MacOSSession::global();
+    let app = os.get_frontmost_app()?;
+    unsafe {
+        let name = app_name(app).unwrap_or_default();
+        let pid: i32 = msg_send![app, processIdentifier];
+        let ax_app = AXElement::new(AXUIElementCreateApplication(pid));
+        let window = os
+            .get_ax_element_attr(ax_app.as_ptr(), "AXFocusedWindow")
+            .map(|window| AXElement::new(window))
+            .and_then(|window| os.get_ax_string_attr(window.as_ptr(), "AXTitle"))
+            .unwrap_or_default();
+        Ok(FrontmostInfo { app: name, window })
+    }
+}
+
+/// Titles of an app's windows (empty app name = frontmost app)
+pub fn window_titles(app_name: &str) -> Result<Vec<String>> {
+    let os = MacOSSession::global();
+    let pid: i32 = unsafe {
+        if app_name.is_empty() {
+            msg_send![os.get_frontmost_app()?, processIdentifier]
+        } else {
+            with_running_app(app_name, |app| Ok(msg_send![app, processIdentifier]))?
+        }
+    };
+
+    let mut titles = Vec::new();
+    unsafe {
+        let ax_app = AXElement::new(AXUIElementCreateApplication(pid));
+        let windows = CFArray::new(os.get_ax_element_attr(ax_app.as_ptr(), "AXWindows")?);
+        for i in 0..windows.count() {
+            if let Ok(title) = os.get_ax_string_attr(windows.get(i), "AXTitle")
+                && !title.is_empty()
+            {
+                titles.push(title);
+            }
+        }
+    }
+    Ok(titles)
+}
+
+/// True if the focused UI element accepts text input
+pub fn is_in_text_field() -> bool {
+    let os = MacOSSession::global();
+    unsafe {
+        let system = AXElement::new(AXUIElementCreateSystemWide());
+        let Ok(focused) = os.get_ax_element_attr(system.as_ptr(), "AXFocusedUIElement") else {
+            return false;
+        };
+        let focused = AXElement::new(focused);
+        os.get_ax_string_attr(focused.as_ptr(), "AXRole")
+            .is_ok_and(|role| {
+                matches!(
+                    role.as_str(),
+                    "AXTextField" | "AXTextArea" | "AXComboBox" | "AXSearchField"
+                )
+            })
+    }
+}
+
+/// Text of the focused UI element (its AXValue)
+pub fn focused_value() -> Result<String> {
+    let os = MacOSSession::global();
+    unsafe {
+        let system = AXElement::new(AXUIElementCreateSystemWide());
+        let focused =
+            AXElement::new(os.get_ax_element_attr(system.as_ptr(), "AXFocusedUIElement")?);
+        os.get_ax_string_attr(focused.as_ptr(), "AXValue")
+    }
+}
+
+/// Replace the focused UI element's text outright, bypassing the keyboard
+pub fn set_focused_value(text: &str) -> Result<()> {
+    let os = MacOSSession::global();
+    unsafe {
+        let system = AXElement::new(AXUIElementCreateSystemWide());
+        let focused =
+            AXElement::new(os.get_ax_element_attr(system.as_ptr(), "AXFocusedUIElement")?);
+        let value = os.create_nsstring(text)?;
+        let result = os.set_ax_attribute(focused.as_ptr(), "AXValue", value as *mut c_void);
+        let _: () = msg_send![value, release];
+        result
+    }
+}
+
+/// Launch an application by name
+pub fn launch_app(app_name: &str) -> Result<()> {
+    let status = std::process::Command::new("open")
+        .args(["-a", app_name])
+        .status()?;
+    if !status.success() {
+        anyhow::bail!("Launch app failed");
+    }
+    Ok(())
+}
+
+unsafe fn app_name(app: *mut AnyObject) -> Option<String> {
+    let name: *mut AnyObject = unsafe { msg_send![app, localizedName] };
+    if name.is_null() {
+        return None;
+    }
+    unsafe { cfstring_to_string(name as *mut c_void) }.filter(|name| !name.is_empty())
+}
@@ -3,7 +3,12 @@ use objc2::msg_send;
 use objc2::runtime::AnyObject;
 use std::process::Command;
 
+/// Show a notification, subject to quiet mode, `silent` hotkeys and coalescing
 pub fn show_notification(message: &str) {
+    crate::notify::post(message, deliver);
+}
+
+fn deliver(message: &str) {
     if show_notification_native(message).is_err() {
         show_notification_osascript(message).ok();
     }
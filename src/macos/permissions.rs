@@ -3,11 +3,18 @@
 //! Provides blocking permission dialog and checks for:
 //! - Accessibility (required for event tap creation)
 //! - Input Monitoring (required for keystroke sending)
+//!
+//! Actions that need more than that (Screen Recording for window capture,
+//! Automation to send Apple Events to an app) are checked before they run,
+//! so a missing permission gets a message naming it instead of a capture of
+//! the desktop or a bare AppleScript error.
 
-use super::ffi::{AXIsProcessTrusted, CGRequestPostEventAccess};
+use super::ffi::*;
 use super::session::MacOSSession;
 use anyhow::{Context, Result};
+use std::collections::HashSet;
 use std::process::Command;
+use std::sync::Mutex;
 use std::thread;
 use std::time::Duration;
 
@@ -270,3 +277,195 @@ pub fn ensure_permissions_granted() -> Result<()> {
         thread::sleep(Duration::from_millis(500));
     }
 }
+
+// ============================================================================
+// Per-Action Permissions
+// ============================================================================
+
+/// A permission an action needs to do its work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    Accessibility,
+    InputMonitoring,
+    ScreenRecording,
+    /// Sending Apple Events to this app
+    Automation(&'static str),
+}
+
+/// Permissions actions need beyond Accessibility, and what for
+const ACTION_PERMISSIONS: &[(&str, Permission, &str)] = &[
+    (
+        "pt.session_screenshot",
+        Permission::ScreenRecording,
+        "window capture",
+    ),
+    (
+        "os.keystroke",
+        Permission::InputMonitoring,
+        "sending keystrokes",
+    ),
+    ("os.type_text", Permission::InputMonitoring, "typing text"),
+    (
+        "os.click_recorded",
+        Permission::InputMonitoring,
+        "sending clicks",
+    ),
+    (
+        "os.click_image",
+        Permission::ScreenRecording,
+        "finding the image",
+    ),
+    (
+        "os.click_image",
+        Permission::InputMonitoring,
+        "sending clicks",
+    ),
+    (
+        "os.test_keystroke",
+        Permission::InputMonitoring,
+        "sending keystrokes",
+    ),
+    (
+        "os.undo_last_macro",
+        Permission::InputMonitoring,
+        "sending Cmd+Z",
+    ),
+    (
+        "sm.send_to_daw",
+        Permission::Automation("Soundminer v6"),
+        "controlling Soundminer",
+    ),
+];
+
+/// Permissions already prompted for this run (each is only prompted once)
+static PROMPTED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+impl Permission {
+    fn name(self) -> String {
+        match self {
+            Permission::Accessibility => "Accessibility".to_string(),
+            Permission::InputMonitoring => "Input Monitoring".to_string(),
+            Permission::ScreenRecording => "Screen Recording".to_string(),
+            Permission::Automation(app) => format!("Automation for {}", app),
+        }
+    }
+
+    /// System Settings pane to grant it in
+    fn settings_url(self) -> &'static str {
+        match self {
+            Permission::Accessibility => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility"
+            }
+            Permission::InputMonitoring => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_ListenEvent"
+            }
+            Permission::ScreenRecording => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture"
+            }
+            Permission::Automation(_) => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Automation"
+            }
+        }
+    }
+
+    pub fn is_granted(self) -> bool {
+        match self {
+            Permission::Accessibility => check_accessibility_permission(),
+            Permission::InputMonitoring => check_input_monitoring_permission(),
+            Permission::ScreenRecording => unsafe { CGPreflightScreenCaptureAccess() },
+            Permission::Automation(app) => check_automation_permission(app),
+        }
+    }
+
+    /// Ask for it: the system prompt where macOS has one, else the settings pane
+    fn request(self) {
+        // Shows the system prompt the first time, does nothing after
+        if self == Permission::ScreenRecording {
+            unsafe { CGRequestScreenCaptureAccess() };
+        }
+        if let Err(e) = Command::new("open").arg(self.settings_url()).spawn() {
+            log::warn!("Failed to open System Settings: {}", e);
+        }
+    }
+}
+
+/// True if Apple Events may be sent to `app` (asking the user if they haven't said)
+///
+/// An app that isn't running can't be checked and counts as allowed.
+fn check_automation_permission(app: &str) -> bool {
+    let bundle_id = unsafe {
+        super::helpers::with_running_app(app, |app| {
+            let bundle_id: *mut objc2::runtime::AnyObject = objc2::msg_send![app, bundleIdentifier];
+            cfstring_to_string(bundle_id as *mut libc::c_void)
+                .context("App has no bundle identifier")
+        })
+    };
+    let Ok(bundle_id) = bundle_id else {
+        return true;
+    };
+    unsafe {
+        let mut target = AEDesc {
+            descriptor_type: 0,
+            data_handle: std::ptr::null_mut(),
+        };
+        if AECreateDesc(
+            TYPE_APPLICATION_BUNDLE_ID,
+            bundle_id.as_ptr() as *const libc::c_void,
+            bundle_id.len() as isize,
+            &mut target,
+        ) != 0
+        {
+            return true;
+        }
+        let status =
+            AEDeterminePermissionToAutomateTarget(&target, TYPE_WILDCARD, TYPE_WILDCARD, true);
+        AEDisposeDesc(&mut target);
+        log::debug!("Automation permission for {}: {}", app, status);
+        // Anything else (errAEEventNotPermitted, no answer yet) is a no
+        status == 0 || status == PROC_NOT_FOUND
+    }
+}
+
+/// What `action` needs: Accessibility for any automation, plus its own entries
+pub fn required_permissions(action: &str) -> Vec<(Permission, &'static str)> {
+    let mut required = vec![(Permission::Accessibility, "controlling apps")];
+    required.extend(
+        ACTION_PERMISSIONS
+            .iter()
+            .filter(|(name, _, _)| *name == action)
+            .map(|&(_, permission, reason)| (permission, reason)),
+    );
+    required
+}
+
+/// Check `action`'s permissions before it runs
+///
+/// A missing one is an error naming it; the first time this run it's also
+/// requested (system prompt or System Settings pane) with a notification.
+pub fn check_action(action: &str) -> Result<()> {
+    for (permission, reason) in required_permissions(action) {
+        if permission.is_granted() {
+            continue;
+        }
+        let message = format!("{} needs {} for {}", action, permission.name(), reason);
+        log::warn!("{}", message);
+        let first_time = PROMPTED
+            .lock()
+            .unwrap()
+            .get_or_insert_with(HashSet::new)
+            .insert(permission.name());
+        if first_time {
+            super::show_notification(&crate::notify::text(
+                "permission_needed",
+                &[
+                    ("action", action),
+                    ("permission", &permission.name()),
+                    ("reason", reason),
+                ],
+            ));
+            permission.request();
+        }
+        anyhow::bail!(message);
+    }
+    Ok(())
+}
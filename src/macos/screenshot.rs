@@ -0,0 +1,236 @@
+//! Window screenshots
+//!
+//! Captures an app's frontmost window through the window server
+//! (CGWindowListCreateImage), optionally with a caption bar drawn along the
+//! bottom, and hands it back as PNG data for the clipboard or a file. Needs
+//! the Screen Recording permission; without it macOS returns the desktop
+//! behind the window instead of its contents.
+
+use super::ffi::*;
+use super::helpers::{CFArray, CFString};
+use super::session::{MacOSSession, NSPoint, NSSize};
+use anyhow::{Context, Result};
+use objc2::rc::autoreleasepool;
+use objc2::runtime::AnyObject;
+use objc2::{Encode, Encoding, msg_send};
+use std::ffi::c_void;
+use std::path::Path;
+
+/// kCFNumberSInt32Type
+const CF_NUMBER_SINT32: i32 = 3;
+
+/// NSBitmapImageFileTypePNG
+const PNG_FILE_TYPE: usize = 4;
+
+/// A CGImageRef, typed so the Objective-C runtime sees `^{CGImage=}`
+#[repr(transparent)]
+pub(super) struct CGImageRef(pub(super) *mut c_void);
+
+unsafe impl Encode for CGImageRef {
+    const ENCODING: Encoding = Encoding::Pointer(&Encoding::Struct("CGImage", &[]));
+}
+
+/// A captured window as PNG data (a retained NSData)
+pub struct Screenshot {
+    png: *mut AnyObject,
+}
+
+// The NSData is immutable once created
+unsafe impl Send for Screenshot {}
+
+impl Drop for Screenshot {
+    fn drop(&mut self) {
+        unsafe {
+            let _: () = msg_send![self.png, release];
+        }
+    }
+}
+
+impl Screenshot {
+    /// Write the PNG to `path`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        unsafe {
+            let os = MacOSSession::global();
+            let path_string = os.create_nsstring(&path.to_string_lossy())?;
+            let written: bool = msg_send![self.png, writeToFile: path_string atomically: true];
+            let _: () = msg_send![path_string, release];
+            if !written {
+                anyhow::bail!("Failed to write {}", path.display());
+            }
+        }
+        Ok(())
+    }
+
+    /// Put the PNG on the clipboard, replacing what's there
+    pub fn copy_to_clipboard(&self) -> Result<()> {
+        unsafe {
+            let os = MacOSSession::global();
+            let pasteboard: *mut AnyObject =
+                msg_send![os.get_class("NSPasteboard")?, generalPasteboard];
+            let _: isize = msg_send![pasteboard, clearContents];
+            let png_type = os.create_nsstring("public.png")?;
+            let copied: bool = msg_send![pasteboard, setData: self.png forType: png_type];
+            let _: () = msg_send![png_type, release];
+            if !copied {
+                anyhow::bail!("Failed to copy the screenshot to the clipboard");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A captured window image (a CGImage, released on drop)
+pub(super) struct WindowImage(pub(super) *mut c_void);
+
+impl Drop for WindowImage {
+    fn drop(&mut self) {
+        unsafe { CGImageRelease(self.0) }
+    }
+}
+
+/// Capture `app`'s frontmost window, with `caption` drawn along the bottom if set
+pub fn capture_window(app: &str, caption: Option<&str>) -> Result<Screenshot> {
+    let (image, _) = capture_window_image(app)?;
+    let png = autoreleasepool(|_pool| unsafe { encode_png(image.0, caption) })?;
+    Ok(Screenshot { png })
+}
+
+/// Capture `app`'s frontmost window, with its bounds on screen in points
+pub(super) fn capture_window_image(app: &str) -> Result<(WindowImage, CGRectRaw)> {
+    let pid = super::get_pid_by_name(app)?;
+    let (window_id, bounds) =
+        front_window(pid).with_context(|| format!("{} has no window open", app))?;
+    unsafe {
+        let image = CGWindowListCreateImage(
+            CGRectRaw::NULL,
+            K_CG_WINDOW_LIST_INCLUDING_WINDOW,
+            window_id,
+            K_CG_WINDOW_IMAGE_IGNORE_FRAMING,
+        );
+        if image.is_null() {
+            anyhow::bail!(
+                "Failed to capture the {} window (Screen Recording permission?)",
+                app
+            );
+        }
+        Ok((WindowImage(image), bounds))
+    }
+}
+
+/// The app's frontmost normal window and its bounds (the list runs front to back)
+fn front_window(pid: i32) -> Option<(u32, CGRectRaw)> {
+    unsafe {
+        let windows = CFArray::new(CGWindowListCopyWindowInfo(
+            K_CG_WINDOW_LIST_ON_SCREEN_ONLY | K_CG_WINDOW_LIST_EXCLUDE_DESKTOP,
+            0,
+        ));
+        if windows.is_null() {
+            return None;
+        }
+        let owner_key = CFString::new("kCGWindowOwnerPID");
+        let layer_key = CFString::new("kCGWindowLayer");
+        let number_key = CFString::new("kCGWindowNumber");
+        let bounds_key = CFString::new("kCGWindowBounds");
+        let number = |info: *mut c_void, key: &CFString| {
+            let value = CFDictionaryGetValue(info, key.as_ptr());
+            let mut number = 0i32;
+            (!value.is_null()
+                && CFNumberGetValue(
+                    value,
+                    CF_NUMBER_SINT32,
+                    &mut number as *mut i32 as *mut c_void,
+                ))
+            .then_some(number)
+        };
+        (0..windows.count())
+            .map(|index| windows.get(index))
+            // Layer 0 = document windows, not menus or floating palettes
+            .find(|&info| {
+                number(info, &owner_key) == Some(pid) && number(info, &layer_key) == Some(0)
+            })
+            .and_then(|info| {
+                let mut bounds = CGRectRaw::NULL;
+                let dict = CFDictionaryGetValue(info, bounds_key.as_ptr());
+                if !dict.is_null() {
+                    CGRectMakeWithDictionaryRepresentation(dict, &mut bounds);
+                }
+                Some((number(info, &number_key)? as u32, bounds))
+            })
+    }
+}
+
+/// Draw the caption onto the image and encode it, returning a retained NSData
+unsafe fn encode_png(image: *mut c_void, caption: Option<&str>) -> Result<*mut AnyObject> {
+    unsafe {
+        let os = MacOSSession::global();
+        let rep: *mut AnyObject = msg_send![os.get_class("NSBitmapImageRep")?, alloc];
+        let rep: *mut AnyObject = msg_send![rep, initWithCGImage: CGImageRef(image)];
+        if rep.is_null() {
+            anyhow::bail!("Failed to read the captured image");
+        }
+        let _: *mut AnyObject = msg_send![rep, autorelease];
+
+        if let Some(caption) = caption.filter(|caption| !caption.is_empty()) {
+            draw_caption(os, rep, caption)?;
+        }
+
+        let properties: *mut AnyObject = msg_send![os.get_class("NSDictionary")?, dictionary];
+        let png: *mut AnyObject = msg_send![
+            rep,
+            representationUsingType: PNG_FILE_TYPE
+            properties: properties
+        ];
+        if png.is_null() {
+            anyhow::bail!("Failed to encode the screenshot as PNG");
+        }
+        let _: *mut AnyObject = msg_send![png, retain];
+        Ok(png)
+    }
+}
+
+/// White text on a dark bar along the bottom of the bitmap
+unsafe fn draw_caption(os: &MacOSSession, rep: *mut AnyObject, caption: &str) -> Result<()> {
+    unsafe {
+        let size: NSSize = msg_send![rep, size];
+        // Readable on a laptop screen and a 5K display alike
+        let font_size = (size.width / 60.0).clamp(14.0, 48.0);
+        let bar_height = font_size * 1.8;
+
+        let context_class = os.get_class("NSGraphicsContext")?;
+        let context: *mut AnyObject =
+            msg_send![context_class, graphicsContextWithBitmapImageRep: rep];
+        if context.is_null() {
+            anyhow::bail!("Failed to draw on the screenshot");
+        }
+        let _: () = msg_send![context_class, saveGraphicsState];
+        let _: () = msg_send![context_class, setCurrentContext: context];
+
+        let color_class = os.get_class("NSColor")?;
+        let bar_color: *mut AnyObject =
+            msg_send![color_class, colorWithCalibratedWhite: 0.0_f64 alpha: 0.65_f64];
+        let _: () = msg_send![bar_color, set];
+        let bar = MacOSSession::rect(0.0, 0.0, size.width, bar_height);
+        let _: () = msg_send![os.get_class("NSBezierPath")?, fillRect: bar];
+
+        let attributes: *mut AnyObject =
+            msg_send![os.get_class("NSMutableDictionary")?, dictionary];
+        let font: *mut AnyObject =
+            msg_send![os.get_class("NSFont")?, boldSystemFontOfSize: font_size];
+        let white: *mut AnyObject = msg_send![color_class, whiteColor];
+        for (key, value) in [("NSFont", font), ("NSColor", white)] {
+            let key = os.create_nsstring(key)?;
+            let _: () = msg_send![attributes, setObject: value forKey: key];
+            let _: () = msg_send![key, release];
+        }
+        let text = os.create_nsstring(caption)?;
+        let origin = NSPoint {
+            x: font_size * 0.6,
+            y: (bar_height - font_size * 1.2) / 2.0,
+        };
+        let _: () = msg_send![text, drawAtPoint: origin withAttributes: attributes];
+        let _: () = msg_send![text, release];
+
+        let _: () = msg_send![context_class, restoreGraphicsState];
+        Ok(())
+    }
+}
@@ -496,6 +496,34 @@ impl MacOSSession {
         }
     }
 
+    /// Select a file in a Finder window
+    ///
+    /// # Example
+    /// ```ignore
+    /// os.reveal_in_finder(&crate::config::get_log_path()?)?;
+    /// ```
+    pub fn reveal_in_finder(&self, path: &std::path::Path) -> Result<()> {
+        if !path.exists() {
+            anyhow::bail!("{} doesn't exist", path.display());
+        }
+        unsafe {
+            let workspace = self.get_workspace()?;
+            let path_string = self.create_nsstring(&path.to_string_lossy())?;
+            let empty = self.create_nsstring("")?;
+            let revealed: bool = msg_send![
+                workspace,
+                selectFile: path_string
+                inFileViewerRootedAtPath: empty
+            ];
+            let _: () = msg_send![path_string, release];
+            let _: () = msg_send![empty, release];
+            if !revealed {
+                anyhow::bail!("Finder couldn't show {}", path.display());
+            }
+        }
+        Ok(())
+    }
+
     // ========================================================================
     // Geometry Helpers
     // ========================================================================
@@ -5,9 +5,111 @@ use anyhow::{Result as R, bail};
 use libc::c_void;
 use std::ffi::CStr;
 use std::os::raw::c_char;
+use std::sync::OnceLock;
 
-#[link(name = "PTHKDui")]
-unsafe extern "C" {
+// ============================================================================
+// Runtime Loading
+// ============================================================================
+
+/// Declare the Swift C ABI as function pointers resolved at runtime
+///
+/// The library is never linked, so the crate builds and runs without it.
+/// Each `pthkd_*` shim returns an error when the library isn't loaded.
+macro_rules! swift_abi {
+    ($( $(#[$meta:meta])* fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty; )*) => {
+        struct SwiftAbi {
+            $( $name: unsafe extern "C" fn($($ty),*) -> $ret, )*
+        }
+
+        impl SwiftAbi {
+            /// Resolve every symbol, or None if any is missing
+            unsafe fn resolve(handle: *mut c_void) -> Option<Self> {
+                Some(Self {
+                    $( $name: {
+                        let symbol = unsafe {
+                            libc::dlsym(handle, concat!(stringify!($name), "\0").as_ptr() as *const c_char)
+                        };
+                        if symbol.is_null() {
+                            log::warn!("Swift UI library is missing {}", stringify!($name));
+                            return None;
+                        }
+                        unsafe {
+                            std::mem::transmute::<*mut c_void, unsafe extern "C" fn($($ty),*) -> $ret>(symbol)
+                        }
+                    }, )*
+                })
+            }
+        }
+
+        $(
+            unsafe fn $name($($arg: $ty),*) -> R<$ret> {
+                let abi = swift_abi().ok_or_else(|| anyhow::anyhow!(SWIFT_UNAVAILABLE))?;
+                Ok(unsafe { (abi.$name)($($arg),*) })
+            }
+        )*
+    };
+}
+
+const SWIFT_LIB: &str = "libPTHKDui.dylib";
+const SWIFT_UNAVAILABLE: &str = "PTHKDui Swift library not available";
+
+static SWIFT_ABI: OnceLock<Option<SwiftAbi>> = OnceLock::new();
+
+fn swift_abi() -> Option<&'static SwiftAbi> {
+    SWIFT_ABI.get_or_init(load_swift_abi).as_ref()
+}
+
+/// True if the Swift UI library was found and loaded
+///
+/// Without it, app/window queries fall back to native accessibility calls and
+/// UI automation (menus, buttons, typing) returns an error.
+pub fn swift_available() -> bool {
+    swift_abi().is_some()
+}
+
+/// Look for the library next to the executable, in the app bundle's
+/// Frameworks folder, then in the SwiftPM build output (dev builds)
+#[cfg(feature = "swift-ui")]
+fn load_swift_abi() -> Option<SwiftAbi> {
+    use std::path::PathBuf;
+
+    let mut candidates = Vec::new();
+    if let Some(dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|d| d.to_path_buf()))
+    {
+        candidates.push(dir.join(SWIFT_LIB));
+        candidates.push(dir.join("../Frameworks").join(SWIFT_LIB));
+    }
+    if let Some(dev_dir) = option_env!("PTHKDUI_DEV_DIR") {
+        candidates.push(PathBuf::from(dev_dir).join(SWIFT_LIB));
+    }
+
+    for path in candidates {
+        let Ok(c_path) = std::ffi::CString::new(path.to_string_lossy().as_bytes()) else {
+            continue;
+        };
+        let handle = unsafe { libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+        if handle.is_null() {
+            continue;
+        }
+        if let Some(abi) = unsafe { SwiftAbi::resolve(handle) } {
+            log::info!("Loaded Swift UI library from {}", path.display());
+            return Some(abi);
+        }
+    }
+
+    log::warn!("Swift UI library not found - falling back to native accessibility where possible");
+    None
+}
+
+#[cfg(not(feature = "swift-ui"))]
+fn load_swift_abi() -> Option<SwiftAbi> {
+    log::info!("Built without the swift-ui feature - using native accessibility only");
+    None
+}
+
+swift_abi! {
     fn pthkd_get_app_menus(app_name: *const c_char) -> *const c_char;
     fn pthkd_menu_click(
         app_name: *const c_char,
@@ -24,6 +126,11 @@ unsafe extern "C" {
         menu_path: *const *const c_char,
         menu_path_count: i32,
     ) -> bool;
+    fn pthkd_menu_item_checked(
+        app_name: *const c_char,
+        menu_path: *const *const c_char,
+        menu_path_count: i32,
+    ) -> bool;
     fn pthkd_send_keystroke(
         app_name: *const c_char,
         key_char: *const c_char,
@@ -62,8 +169,25 @@ unsafe extern "C" {
         window_name: *const c_char,
         popup_name: *const c_char,
     ) -> *const c_char;
+    fn pthkd_select_popup_menu_item(
+        app_name: *const c_char,
+        window_name: *const c_char,
+        popup_name: *const c_char,
+        item_name: *const c_char,
+    ) -> bool;
+    fn pthkd_set_text_field_value(
+        app_name: *const c_char,
+        window_name: *const c_char,
+        field_name: *const c_char,
+        value: *const c_char,
+    ) -> bool;
+    fn pthkd_get_table_rows(
+        app_name: *const c_char,
+        window_name: *const c_char,
+        selected_only: bool,
+    ) -> *const c_char;
     fn pthkd_get_window_text(app_name: *const c_char, window_name: *const c_char) -> *const c_char;
-    fn pthkd_free_string(ptr: *const c_char);
+    fn pthkd_free_string(ptr: *const c_char) -> ();
 
     // App operations
     fn pthkd_get_frontmost_info() -> *const c_char;
@@ -103,7 +227,7 @@ pub fn get_app_menus(app_name: &str) -> R<String> {
         use std::ffi::CString;
 
         let app_cstr = CString::new(app_name)?;
-        let json_ptr = pthkd_get_app_menus(app_cstr.as_ptr());
+        let json_ptr = pthkd_get_app_menus(app_cstr.as_ptr())?;
 
         if json_ptr.is_null() {
             return Err(anyhow::anyhow!("Swift returned null"));
@@ -111,7 +235,7 @@ pub fn get_app_menus(app_name: &str) -> R<String> {
 
         let json = CStr::from_ptr(json_ptr).to_string_lossy().into_owned();
 
-        pthkd_free_string(json_ptr);
+        pthkd_free_string(json_ptr)?;
 
         Ok(json)
     }
@@ -123,6 +247,9 @@ pub fn get_app_menus(app_name: &str) -> R<String> {
 /// * `app_name` - Name of the app (e.g. "Pro Tools"), or empty string for frontmost app
 /// * `menu_path` - Array of menu titles to traverse (e.g. &["File", "Save"])
 pub fn menu_click(app_name: &str, menu_path: &[&str]) -> R<()> {
+    let undoable = app_name == "Pro Tools" && crate::undo::is_undoable_menu(menu_path);
+    let localized = super::menu_locale::localize(app_name, menu_path);
+    let menu_path: Vec<&str> = localized.iter().map(String::as_str).collect();
     unsafe {
         use std::ffi::CString;
 
@@ -140,11 +267,15 @@ pub fn menu_click(app_name: &str, menu_path: &[&str]) -> R<()> {
             app_cstr.as_ptr(),
             path_ptrs.as_ptr(),
             menu_path.len() as i32,
-        );
+        )?;
 
         if success {
+            if undoable {
+                crate::undo::operation(&menu_path.join(" > "));
+            }
             Ok(())
         } else {
+            super::menu_locale::forget(app_name);
             Err(anyhow::anyhow!("Menu click failed"))
         }
     }
@@ -156,6 +287,8 @@ pub fn menu_click(app_name: &str, menu_path: &[&str]) -> R<()> {
 /// * `app_name` - Name of the app (e.g. "Pro Tools"), or empty string for frontmost app
 /// * `menu_path` - Array of menu titles to traverse (e.g. &["File", "Save"])
 pub fn menu_item_exists(app_name: &str, menu_path: &[&str]) -> R<bool> {
+    let localized = super::menu_locale::localize(app_name, menu_path);
+    let menu_path: Vec<&str> = localized.iter().map(String::as_str).collect();
     unsafe {
         use std::ffi::CString;
 
@@ -166,11 +299,11 @@ pub fn menu_item_exists(app_name: &str, menu_path: &[&str]) -> R<bool> {
             .collect::<R<Vec<_>, _>>()?;
         let path_ptrs: Vec<*const c_char> = path_cstrs.iter().map(|cs| cs.as_ptr()).collect();
 
-        Ok(pthkd_menu_item_exists(
+        pthkd_menu_item_exists(
             app_cstr.as_ptr(),
             path_ptrs.as_ptr(),
             menu_path.len() as i32,
-        ))
+        )
     }
 }
 
@@ -180,6 +313,34 @@ pub fn menu_item_exists(app_name: &str, menu_path: &[&str]) -> R<bool> {
 /// * `app_name` - Name of the app (e.g. "Pro Tools"), or empty string for frontmost app
 /// * `menu_path` - Array of menu titles to traverse (e.g. &["File", "Save"])
 pub fn menu_item_enabled(app_name: &str, menu_path: &[&str]) -> R<bool> {
+    let localized = super::menu_locale::localize(app_name, menu_path);
+    let menu_path: Vec<&str> = localized.iter().map(String::as_str).collect();
+    unsafe {
+        use std::ffi::CString;
+
+        let app_cstr = CString::new(app_name)?;
+        let path_cstrs: Vec<CString> = menu_path
+            .iter()
+            .map(|s| CString::new(*s))
+            .collect::<R<Vec<_>, _>>()?;
+        let path_ptrs: Vec<*const c_char> = path_cstrs.iter().map(|cs| cs.as_ptr()).collect();
+
+        pthkd_menu_item_enabled(
+            app_cstr.as_ptr(),
+            path_ptrs.as_ptr(),
+            menu_path.len() as i32,
+        )
+    }
+}
+
+/// Check if a menu item shows a checkmark (e.g. the selected option in a menu)
+///
+/// # Arguments
+/// * `app_name` - Name of the app (e.g. "Soundminer"), or empty string for frontmost app
+/// * `menu_path` - Array of menu titles to traverse (e.g. &["DAW", "Pro Tools"])
+pub fn menu_item_checked(app_name: &str, menu_path: &[&str]) -> R<bool> {
+    let localized = super::menu_locale::localize(app_name, menu_path);
+    let menu_path: Vec<&str> = localized.iter().map(String::as_str).collect();
     unsafe {
         use std::ffi::CString;
 
@@ -190,11 +351,11 @@ pub fn menu_item_enabled(app_name: &str, menu_path: &[&str]) -> R<bool> {
             .collect::<R<Vec<_>, _>>()?;
         let path_ptrs: Vec<*const c_char> = path_cstrs.iter().map(|cs| cs.as_ptr()).collect();
 
-        Ok(pthkd_menu_item_enabled(
+        pthkd_menu_item_checked(
             app_cstr.as_ptr(),
             path_ptrs.as_ptr(),
             menu_path.len() as i32,
-        ))
+        )
     }
 }
 
@@ -211,7 +372,7 @@ pub fn send_keystroke(app_name: &str, key_char: &str, modifiers: i32) -> R<()> {
         let app_cstr = CString::new(app_name)?;
         let key_cstr = CString::new(key_char)?;
 
-        let success = pthkd_send_keystroke(app_cstr.as_ptr(), key_cstr.as_ptr(), modifiers);
+        let success = pthkd_send_keystroke(app_cstr.as_ptr(), key_cstr.as_ptr(), modifiers)?;
 
         if success {
             Ok(())
@@ -228,8 +389,11 @@ pub fn send_keystroke(app_name: &str, key_char: &str, modifiers: i32) -> R<()> {
 /// * `modifier_flags` - CGEventFlags (shift=0x20000, control=0x40000, option=0x80000, command=0x100000)
 pub fn send_global_keystroke(key_codes: &[u16], modifier_flags: u64) -> R<()> {
     unsafe {
-        let success =
-            pthkd_send_global_keystroke(key_codes.as_ptr(), key_codes.len() as i32, modifier_flags);
+        let success = pthkd_send_global_keystroke(
+            key_codes.as_ptr(),
+            key_codes.len() as i32,
+            modifier_flags,
+        )?;
 
         if success {
             Ok(())
@@ -250,7 +414,7 @@ pub fn type_text(text: &str, mark_events: bool) -> R<()> {
 
         let text_cstr = CString::new(text)?;
 
-        let success = pthkd_type_text(text_cstr.as_ptr(), mark_events);
+        let success = pthkd_type_text(text_cstr.as_ptr(), mark_events)?;
 
         if success {
             Ok(())
@@ -277,7 +441,7 @@ pub fn paste_text(text: &str) -> R<()> {
 
         let text_cstr = CString::new(text)?;
 
-        let success = pthkd_paste_text(text_cstr.as_ptr());
+        let success = pthkd_paste_text(text_cstr.as_ptr())?;
 
         if success {
             Ok(())
@@ -295,7 +459,7 @@ pub fn paste_into_focused_field(text: &str, send_enter: bool) -> R<()> {
 
         let text_cstr = CString::new(text)?;
 
-        let success = pthkd_paste_into_focused_field(text_cstr.as_ptr(), send_enter);
+        let success = pthkd_paste_into_focused_field(text_cstr.as_ptr(), send_enter)?;
 
         if success {
             Ok(())
@@ -305,12 +469,77 @@ pub fn paste_into_focused_field(text: &str, send_enter: bool) -> R<()> {
     }
 }
 
+/// Replace the focused field's text by typing, then check what landed
+///
+/// Typing sends US-layout key codes, so on another keyboard layout (or when
+/// Pro Tools drops keys typed too fast into a naming dialog) the field can
+/// end up holding something else. This reads the field's AXValue back,
+/// retypes once on a mismatch, then sets the value directly. Fields that
+/// don't expose their value are typed into unchecked.
+///
+/// # Arguments
+/// * `text` - The text the field should hold
+/// * `mark_events` - Whether to mark events with APP_MARKER (true = prevent event tap from catching them)
+pub fn type_text_verified(text: &str, mark_events: bool) -> R<()> {
+    use super::waits::{Wait, settle};
+
+    for attempt in 1..=2 {
+        keystroke(&["cmd", "a"])?;
+        if let Err(e) = type_text(text, mark_events) {
+            // Characters with no key code on the layout table
+            log::warn!(
+                "Typing {} characters failed ({}), setting them directly",
+                text.chars().count(),
+                e
+            );
+            break;
+        }
+        settle(Wait::Keystroke);
+        match super::native_ax::focused_value() {
+            Ok(value) if value == text => return Ok(()),
+            Ok(value) => log::warn!(
+                "Typed {} characters but the field differs at character {} (attempt {})",
+                text.chars().count(),
+                mismatch_at(text, &value),
+                attempt
+            ),
+            Err(e) => {
+                log::debug!("Can't read the focused field back: {}", e);
+                return Ok(());
+            }
+        }
+    }
+
+    super::native_ax::set_focused_value(text)?;
+    settle(Wait::Keystroke);
+    let value = super::native_ax::focused_value()?;
+    if value != text {
+        bail!(
+            "The field differs from the typed text at character {}",
+            mismatch_at(text, &value)
+        );
+    }
+    Ok(())
+}
+
+/// Position of the first character where the field differs from the typed text
+///
+/// Logged instead of the text itself, which can be private and ends up in
+/// support bundles.
+fn mismatch_at(expected: &str, actual: &str) -> usize {
+    expected
+        .chars()
+        .zip(actual.chars())
+        .take_while(|(a, b)| a == b)
+        .count()
+}
+
 /// Click a button in a window
 ///
 /// # Arguments
 /// * `app_name` - Name of the app (e.g. "Pro Tools"), or empty string for frontmost app
 /// * `window_name` - Name of the window, or empty string for frontmost window
-/// * `button_name` - Name of the button to click
+/// * `button_name` - Name of the button (or radio button) to click
 pub fn click_button(app_name: &str, window_name: &str, button_name: &str) -> R<()> {
     unsafe {
         use std::ffi::CString;
@@ -323,7 +552,7 @@ pub fn click_button(app_name: &str, window_name: &str, button_name: &str) -> R<(
             app_cstr.as_ptr(),
             window_cstr.as_ptr(),
             button_cstr.as_ptr(),
-        );
+        )?;
 
         if success {
             Ok(())
@@ -351,7 +580,7 @@ pub fn click_checkbox(app_name: &str, window_name: &str, checkbox_name: &str) ->
             app_cstr.as_ptr(),
             window_cstr.as_ptr(),
             checkbox_cstr.as_ptr(),
-        );
+        )?;
 
         if success {
             Ok(())
@@ -386,14 +615,14 @@ pub fn get_window_buttons(app_name: &str, window_name: &str) -> R<Vec<String>> {
         let app_cstr = CString::new(app_name)?;
         let window_cstr = CString::new(window_name)?;
 
-        let json_ptr = pthkd_get_window_buttons(app_cstr.as_ptr(), window_cstr.as_ptr());
+        let json_ptr = pthkd_get_window_buttons(app_cstr.as_ptr(), window_cstr.as_ptr())?;
 
         if json_ptr.is_null() {
             return Err(anyhow::anyhow!("Swift returned null"));
         }
 
         let json = CStr::from_ptr(json_ptr).to_string_lossy().into_owned();
-        pthkd_free_string(json_ptr);
+        pthkd_free_string(json_ptr)?;
 
         // Check for error response first
         check_swift_error(&json)?;
@@ -428,7 +657,7 @@ pub fn set_checkbox_value(
             window_cstr.as_ptr(),
             checkbox_cstr.as_ptr(),
             value,
-        );
+        )?;
 
         if success {
             Ok(())
@@ -456,14 +685,14 @@ pub fn get_popup_menu_items(app_name: &str, window_name: &str, popup_name: &str)
             app_cstr.as_ptr(),
             window_cstr.as_ptr(),
             popup_cstr.as_ptr(),
-        );
+        )?;
 
         if json_ptr.is_null() {
             return Err(anyhow::anyhow!("Swift returned null"));
         }
 
         let json = CStr::from_ptr(json_ptr).to_string_lossy().into_owned();
-        pthkd_free_string(json_ptr);
+        pthkd_free_string(json_ptr)?;
 
         // Check for error response first
         check_swift_error(&json)?;
@@ -473,6 +702,113 @@ pub fn get_popup_menu_items(app_name: &str, window_name: &str, popup_name: &str)
     }
 }
 
+/// Select an item from a popup menu
+///
+/// # Arguments
+/// * `app_name` - Name of the app (e.g. "Pro Tools"), or empty string for frontmost app
+/// * `window_name` - Name of the window, or empty string for frontmost window
+/// * `popup_name` - Name of the popup button
+/// * `item_name` - Menu item to select (soft match)
+pub fn select_popup_menu_item(
+    app_name: &str,
+    window_name: &str,
+    popup_name: &str,
+    item_name: &str,
+) -> R<()> {
+    unsafe {
+        use std::ffi::CString;
+
+        let app_cstr = CString::new(app_name)?;
+        let window_cstr = CString::new(window_name)?;
+        let popup_cstr = CString::new(popup_name)?;
+        let item_cstr = CString::new(item_name)?;
+
+        let success = pthkd_select_popup_menu_item(
+            app_cstr.as_ptr(),
+            window_cstr.as_ptr(),
+            popup_cstr.as_ptr(),
+            item_cstr.as_ptr(),
+        )?;
+
+        if success {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Select popup item failed"))
+        }
+    }
+}
+
+/// Set the value of a text field and focus it
+///
+/// # Arguments
+/// * `app_name` - Name of the app (e.g. "Pro Tools"), or empty string for frontmost app
+/// * `window_name` - Name of the window, or empty string for frontmost window
+/// * `field_name` - Title, description or placeholder of the field, or empty string for the first field
+/// * `value` - Text to set
+pub fn set_text_field_value(
+    app_name: &str,
+    window_name: &str,
+    field_name: &str,
+    value: &str,
+) -> R<()> {
+    unsafe {
+        use std::ffi::CString;
+
+        let app_cstr = CString::new(app_name)?;
+        let window_cstr = CString::new(window_name)?;
+        let field_cstr = CString::new(field_name)?;
+        let value_cstr = CString::new(value)?;
+
+        let success = pthkd_set_text_field_value(
+            app_cstr.as_ptr(),
+            window_cstr.as_ptr(),
+            field_cstr.as_ptr(),
+            value_cstr.as_ptr(),
+        )?;
+
+        if success {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Set text field failed"))
+        }
+    }
+}
+
+/// Get the cell text of each row in the first table/outline of a window
+///
+/// # Arguments
+/// * `app_name` - Name of the app (e.g. "Pro Tools"), or empty string for frontmost app
+/// * `window_name` - Name of the window, or empty string for frontmost window
+/// * `selected_only` - Only return the selected rows
+pub fn get_table_rows(
+    app_name: &str,
+    window_name: &str,
+    selected_only: bool,
+) -> R<Vec<Vec<String>>> {
+    unsafe {
+        use std::ffi::CString;
+
+        let app_cstr = CString::new(app_name)?;
+        let window_cstr = CString::new(window_name)?;
+
+        let json_ptr =
+            pthkd_get_table_rows(app_cstr.as_ptr(), window_cstr.as_ptr(), selected_only)?;
+
+        if json_ptr.is_null() {
+            return Err(anyhow::anyhow!("Swift returned null"));
+        }
+
+        let json = CStr::from_ptr(json_ptr).to_string_lossy().into_owned();
+        pthkd_free_string(json_ptr)?;
+
+        // Check for error response first
+        check_swift_error(&json)?;
+
+        let rows: Vec<Vec<String>> = serde_json::from_str(&json)?;
+        Ok(rows)
+    }
+}
+
 /// Get all text from a window
 ///
 /// # Arguments
@@ -485,14 +821,14 @@ pub fn get_window_text(app_name: &str, window_name: &str) -> R<Vec<String>> {
         let app_cstr = CString::new(app_name)?;
         let window_cstr = CString::new(window_name)?;
 
-        let json_ptr = pthkd_get_window_text(app_cstr.as_ptr(), window_cstr.as_ptr());
+        let json_ptr = pthkd_get_window_text(app_cstr.as_ptr(), window_cstr.as_ptr())?;
 
         if json_ptr.is_null() {
             return Err(anyhow::anyhow!("Swift returned null"));
         }
 
         let json = CStr::from_ptr(json_ptr).to_string_lossy().into_owned();
-        pthkd_free_string(json_ptr);
+        pthkd_free_string(json_ptr)?;
 
         // Check for error response first
         check_swift_error(&json)?;
@@ -513,15 +849,18 @@ pub struct FrontmostInfo {
 
 /// Get information about the frontmost application and window
 pub fn get_frontmost_info() -> R<FrontmostInfo> {
+    if !swift_available() {
+        return super::native_ax::frontmost_info();
+    }
     unsafe {
-        let json_ptr = pthkd_get_frontmost_info();
+        let json_ptr = pthkd_get_frontmost_info()?;
 
         if json_ptr.is_null() {
             return Err(anyhow::anyhow!("Swift returned null"));
         }
 
         let json = CStr::from_ptr(json_ptr).to_string_lossy().into_owned();
-        pthkd_free_string(json_ptr);
+        pthkd_free_string(json_ptr)?;
 
         // Check for error response first
         check_swift_error(&json)?;
@@ -533,15 +872,18 @@ pub fn get_frontmost_info() -> R<FrontmostInfo> {
 
 /// Get list of all running application names
 pub fn get_running_apps() -> R<Vec<String>> {
+    if !swift_available() {
+        return super::native_ax::running_apps();
+    }
     unsafe {
-        let json_ptr = pthkd_get_running_apps();
+        let json_ptr = pthkd_get_running_apps()?;
 
         if json_ptr.is_null() {
             return Err(anyhow::anyhow!("Swift returned null"));
         }
 
         let json = CStr::from_ptr(json_ptr).to_string_lossy().into_owned();
-        pthkd_free_string(json_ptr);
+        pthkd_free_string(json_ptr)?;
 
         // Check for error response first
         check_swift_error(&json)?;
@@ -578,7 +920,7 @@ pub fn focus_app(
             should_switch,
             should_launch,
             timeout,
-        );
+        )?;
 
         if success {
             Ok(())
@@ -593,11 +935,14 @@ pub fn focus_app(
 /// # Arguments
 /// * `app_name` - Name of the application to launch
 pub fn launch_app(app_name: &str) -> R<()> {
+    if !swift_available() {
+        return super::native_ax::launch_app(app_name);
+    }
     unsafe {
         use std::ffi::CString;
 
         let app_cstr = CString::new(app_name)?;
-        let success = pthkd_launch_app(app_cstr.as_ptr());
+        let success = pthkd_launch_app(app_cstr.as_ptr())?;
 
         if success {
             Ok(())
@@ -612,7 +957,10 @@ pub fn launch_app(app_name: &str) -> R<()> {
 /// Returns true if focused element is a text field, text area, combo box, or search field.
 /// This is useful for preventing hotkeys from triggering when the user is typing.
 pub fn is_in_text_field() -> bool {
-    unsafe { pthkd_is_in_text_field() }
+    if !swift_available() {
+        return super::native_ax::is_in_text_field();
+    }
+    unsafe { pthkd_is_in_text_field().unwrap_or(false) }
 }
 
 // MARK: - Window Operations
@@ -623,13 +971,19 @@ pub fn is_in_text_field() -> bool {
 /// * `app_name` - Name of the app (empty for frontmost)
 /// * `window_name` - Name of the window (empty for frontmost)
 pub fn window_exists(app_name: &str, window_name: &str) -> R<bool> {
+    if !swift_available() {
+        let titles = super::native_ax::window_titles(app_name).unwrap_or_default();
+        return Ok(titles
+            .iter()
+            .any(|title| window_name.is_empty() || crate::soft_match(title, window_name)));
+    }
     unsafe {
         use std::ffi::CString;
 
         let app_cstr = CString::new(app_name)?;
         let window_cstr = CString::new(window_name)?;
 
-        Ok(pthkd_window_exists(app_cstr.as_ptr(), window_cstr.as_ptr()))
+        pthkd_window_exists(app_cstr.as_ptr(), window_cstr.as_ptr())
     }
 }
 
@@ -638,18 +992,21 @@ pub fn window_exists(app_name: &str, window_name: &str) -> R<bool> {
 /// # Arguments
 /// * `app_name` - Name of the app (empty for frontmost)
 pub fn get_window_titles(app_name: &str) -> R<Vec<String>> {
+    if !swift_available() {
+        return super::native_ax::window_titles(app_name);
+    }
     unsafe {
         use std::ffi::CString;
 
         let app_cstr = CString::new(app_name)?;
-        let json_ptr = pthkd_get_window_titles(app_cstr.as_ptr());
+        let json_ptr = pthkd_get_window_titles(app_cstr.as_ptr())?;
 
         if json_ptr.is_null() {
             return Err(anyhow::anyhow!("Swift returned null"));
         }
 
         let json = CStr::from_ptr(json_ptr).to_string_lossy().into_owned();
-        pthkd_free_string(json_ptr);
+        pthkd_free_string(json_ptr)?;
 
         // Check for error response first
         check_swift_error(&json)?;
@@ -687,12 +1044,12 @@ pub fn wait_for_window(
         let app_cstr = CString::new(app_name)?;
         let window_cstr = CString::new(window_name)?;
 
-        Ok(pthkd_wait_for_window(
+        pthkd_wait_for_window(
             app_cstr.as_ptr(),
             window_cstr.as_ptr(),
             condition as i32,
             timeout,
-        ))
+        )
     }
 }
 
@@ -710,7 +1067,7 @@ pub fn close_window(app_name: &str, window_name: &str, retry_timeout: Option<i32
         let window_cstr = CString::new(window_name)?;
         let retry = retry_timeout.unwrap_or(-1);
 
-        let success = pthkd_close_window(app_cstr.as_ptr(), window_cstr.as_ptr(), retry);
+        let success = pthkd_close_window(app_cstr.as_ptr(), window_cstr.as_ptr(), retry)?;
 
         if success {
             Ok(())
@@ -0,0 +1,50 @@
+//! Lightweight CPU / memory sampling
+//!
+//! Uses `ps` rather than the Mach host APIs - one sample a second is plenty
+//! for a progress readout.
+
+use anyhow::{Context, Result};
+use std::process::Command;
+
+/// CPU and resident memory of a single process
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessStats {
+    /// Percent of one core (can exceed 100 on multi-core)
+    pub cpu_percent: f64,
+    pub mem_mb: f64,
+}
+
+/// Total CPU usage across all processes, as a percent of the whole machine
+pub fn system_cpu_percent() -> Result<f64> {
+    let output = Command::new("ps").args(["-A", "-o", "%cpu="]).output()?;
+    let total: f64 = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse::<f64>().ok())
+        .sum();
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    Ok(total / cores as f64)
+}
+
+/// CPU and memory of a running application
+pub fn process_stats(app_name: &str) -> Result<ProcessStats> {
+    let pid = super::get_pid_by_name(app_name)?;
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "%cpu=,rss="])
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut fields = stdout.split_whitespace();
+    let cpu_percent = fields
+        .next()
+        .and_then(|s| s.parse().ok())
+        .context("No CPU sample")?;
+    let rss_kb: f64 = fields
+        .next()
+        .and_then(|s| s.parse().ok())
+        .context("No memory sample")?;
+    Ok(ProcessStats {
+        cpu_percent,
+        mem_mb: rss_kb / 1024.0,
+    })
+}
@@ -0,0 +1,167 @@
+//! Recording tally overlay
+//!
+//! A coloured frame around a display (or a light in its top-right corner)
+//! that floats above every app, on every Space, and ignores the mouse - so
+//! the room can see when Pro Tools is recording. `protools::tally` decides
+//! when it's lit; this only draws it.
+//!
+//! The frame is four thin borderless windows rather than one transparent
+//! window with a drawn border, so nothing covers the screen's contents.
+
+use super::session::{MacOSSession, NSRect};
+use crate::config::TallyConfig;
+use anyhow::Result;
+use objc2::msg_send;
+use objc2::runtime::AnyObject;
+use std::sync::Mutex;
+
+/// The tally's windows while it's shown (pointers kept as usize so the static is Send)
+static WINDOWS: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+/// NSScreenSaverWindowLevel - above full-screen apps and the menu bar
+const TALLY_WINDOW_LEVEL: isize = 1000;
+
+/// canJoinAllSpaces | stationary | fullScreenAuxiliary
+const TALLY_COLLECTION_BEHAVIOR: usize = 1 | 16 | 256;
+
+/// Show the tally as `config` describes, replacing any shown now
+///
+/// Safe to call from any thread - the windows are created on the main thread.
+pub fn show(config: &TallyConfig) {
+    let config = config.clone();
+    unsafe {
+        super::dispatch_to_main_queue(move || {
+            close_windows();
+            if let Err(e) = open_windows(&config) {
+                log::error!("Failed to show the tally: {:#}", e);
+            }
+        });
+    }
+}
+
+/// Hide the tally
+pub fn hide() {
+    unsafe {
+        super::dispatch_to_main_queue(close_windows);
+    }
+}
+
+fn open_windows(config: &TallyConfig) -> Result<()> {
+    unsafe {
+        let os = MacOSSession::global();
+        let screen = screen(os, config.display)?;
+        let color = color(os, &config.color)?;
+
+        let frames = if config.style.eq_ignore_ascii_case("corner") {
+            // Below the menu bar, clear of the screen edge
+            let visible: NSRect = msg_send![screen, visibleFrame];
+            let size = config.size.max(4.0);
+            vec![MacOSSession::rect(
+                visible.origin.x + visible.size.width - size * 1.5,
+                visible.origin.y + visible.size.height - size * 1.5,
+                size,
+                size,
+            )]
+        } else {
+            let frame: NSRect = msg_send![screen, frame];
+            let (x, y) = (frame.origin.x, frame.origin.y);
+            let (width, height) = (frame.size.width, frame.size.height);
+            let edge = config.size.max(1.0);
+            vec![
+                MacOSSession::rect(x, y, width, edge),
+                MacOSSession::rect(x, y + height - edge, width, edge),
+                MacOSSession::rect(x, y, edge, height),
+                MacOSSession::rect(x + width - edge, y, edge, height),
+            ]
+        };
+
+        let mut windows = WINDOWS.lock().unwrap();
+        for frame in frames {
+            let window = overlay_window(os, frame, color)?;
+            windows.push(window as usize);
+        }
+        log::info!(
+            "Tally shown ({} on display {:?})",
+            config.style,
+            config.display
+        );
+        Ok(())
+    }
+}
+
+fn close_windows() {
+    for window in WINDOWS.lock().unwrap().drain(..) {
+        let window = window as *mut AnyObject;
+        unsafe {
+            let _: () = msg_send![window, close];
+        }
+    }
+}
+
+/// A borderless, click-through window filled with `color`
+unsafe fn overlay_window(
+    os: &MacOSSession,
+    frame: NSRect,
+    color: *mut AnyObject,
+) -> Result<*mut AnyObject> {
+    unsafe {
+        let window_class = os.get_class("NSWindow")?;
+        let window: *mut AnyObject = msg_send![window_class, alloc];
+        // styleMask 0 = NSWindowStyleMaskBorderless, backing 2 = NSBackingStoreBuffered
+        let window: *mut AnyObject = msg_send![
+            window,
+            initWithContentRect: frame
+            styleMask: 0_usize
+            backing: 2_usize
+            defer: false
+        ];
+        if window.is_null() {
+            anyhow::bail!("Failed to create tally window");
+        }
+        let _: () = msg_send![window, setReleasedWhenClosed: true];
+        let _: () = msg_send![window, setBackgroundColor: color];
+        let _: () = msg_send![window, setIgnoresMouseEvents: true];
+        let _: () = msg_send![window, setHasShadow: false];
+        let _: () = msg_send![window, setLevel: TALLY_WINDOW_LEVEL];
+        let _: () = msg_send![window, setCollectionBehavior: TALLY_COLLECTION_BEHAVIOR];
+        let _: () = msg_send![window, orderFrontRegardless];
+        Ok(window)
+    }
+}
+
+/// The NSScreen for a 1-based display number (None = the main display)
+unsafe fn screen(os: &MacOSSession, display: Option<usize>) -> Result<*mut AnyObject> {
+    unsafe {
+        let screen_class = os.get_class("NSScreen")?;
+        let screen: *mut AnyObject = match display {
+            None => msg_send![screen_class, mainScreen],
+            Some(number) => {
+                let screens: *mut AnyObject = msg_send![screen_class, screens];
+                let count: usize = msg_send![screens, count];
+                if number == 0 || number > count {
+                    anyhow::bail!("No display {} ({} connected)", number, count);
+                }
+                msg_send![screens, objectAtIndex: number - 1]
+            }
+        };
+        if screen.is_null() {
+            anyhow::bail!("No display found");
+        }
+        Ok(screen)
+    }
+}
+
+unsafe fn color(os: &MacOSSession, name: &str) -> Result<*mut AnyObject> {
+    unsafe {
+        let color_class = os.get_class("NSColor")?;
+        let color: *mut AnyObject = match name.to_lowercase().as_str() {
+            "red" => msg_send![color_class, systemRedColor],
+            "orange" => msg_send![color_class, systemOrangeColor],
+            "yellow" => msg_send![color_class, systemYellowColor],
+            "green" => msg_send![color_class, systemGreenColor],
+            "blue" => msg_send![color_class, systemBlueColor],
+            _ => anyhow::bail!("Unknown tally color '{}'", name),
+        };
+        Ok(color)
+    }
+}
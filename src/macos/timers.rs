@@ -0,0 +1,153 @@
+//! Countdown timers and stopwatches (`os.timer_start` / `os.timer_stop`)
+//!
+//! Running timers are shown next to the menu bar icon, e.g. "ADR 12:34", and
+//! updated once a second. A countdown is removed when it runs out, with a
+//! notification and/or a spoken message; a stopwatch counts up until stopped.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+const TICK: Duration = Duration::from_secs(1);
+
+/// Running timers, in start order
+static TIMERS: Mutex<Vec<Timer>> = Mutex::new(Vec::new());
+
+/// True while the tick thread runs (changed only with TIMERS locked)
+static TICKING: AtomicBool = AtomicBool::new(false);
+
+pub struct Timer {
+    /// Identifies the timer for `timer_stop`
+    pub name: String,
+    /// Shown before the time
+    pub label: String,
+    pub started: Instant,
+    /// Countdown length (None = stopwatch)
+    pub duration: Option<Duration>,
+    /// Notify when a countdown runs out
+    pub notify: bool,
+    /// Spoken when a countdown runs out
+    pub speak: Option<String>,
+}
+
+impl Timer {
+    /// "ADR 12:34": time left for a countdown, elapsed time for a stopwatch
+    fn readout(&self) -> String {
+        let time = match self.duration {
+            Some(duration) => duration.saturating_sub(self.started.elapsed()),
+            None => self.started.elapsed(),
+        };
+        if self.label.is_empty() {
+            format_time(time)
+        } else {
+            format!("{} {}", self.label, format_time(time))
+        }
+    }
+
+    fn expired(&self) -> bool {
+        self.duration
+            .is_some_and(|duration| self.started.elapsed() >= duration)
+    }
+}
+
+/// Start a timer, replacing a running one with the same name
+pub fn start(timer: Timer) {
+    log::info!(
+        "Timer '{}' started ({})",
+        timer.name,
+        timer.duration.map_or("stopwatch".to_string(), format_time)
+    );
+    let spawn = {
+        let mut timers = TIMERS.lock().unwrap();
+        timers.retain(|t| t.name != timer.name);
+        timers.push(timer);
+        !TICKING.swap(true, Ordering::SeqCst)
+    };
+    update_readout();
+    if spawn {
+        std::thread::spawn(tick_loop);
+    }
+}
+
+/// Stop the named timer (None = all of them); returns the stopped timers'
+/// final readouts
+pub fn stop(name: Option<&str>) -> Vec<String> {
+    let stopped: Vec<String> = {
+        let mut timers = TIMERS.lock().unwrap();
+        let (stopped, running): (Vec<Timer>, Vec<Timer>) = timers
+            .drain(..)
+            .partition(|t| name.is_none_or(|name| t.name == name));
+        *timers = running;
+        stopped.iter().map(Timer::readout).collect()
+    };
+    for readout in &stopped {
+        log::info!("Timer stopped at {}", readout);
+    }
+    update_readout();
+    stopped
+}
+
+/// Refresh the readout and expire countdowns until no timers are left
+fn tick_loop() {
+    loop {
+        std::thread::sleep(TICK);
+        let (expired, done) = {
+            let mut timers = TIMERS.lock().unwrap();
+            let (expired, running): (Vec<Timer>, Vec<Timer>) =
+                timers.drain(..).partition(Timer::expired);
+            *timers = running;
+            let done = timers.is_empty();
+            if done {
+                TICKING.store(false, Ordering::SeqCst);
+            }
+            (expired, done)
+        };
+        update_readout();
+        for timer in expired {
+            finish(timer);
+        }
+        if done {
+            break;
+        }
+    }
+}
+
+/// A countdown ran out: notify and/or speak
+fn finish(timer: Timer) {
+    log::info!("Timer '{}' finished", timer.name);
+    if timer.notify {
+        let label = if timer.label.is_empty() {
+            &timer.name
+        } else {
+            &timer.label
+        };
+        super::show_notification(&crate::notify::text("timer_done", &[("label", &label)]));
+    }
+    if let Some(text) = timer.speak
+        && let Err(e) = std::process::Command::new("say").arg(&text).spawn()
+    {
+        log::error!("Failed to speak timer message: {}", e);
+    }
+}
+
+fn update_readout() {
+    let readout = TIMERS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(Timer::readout)
+        .collect::<Vec<_>>()
+        .join("  ");
+    super::menubar::set_timer_text(&readout);
+}
+
+/// "1:02:03" or "12:34"
+fn format_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
@@ -0,0 +1,162 @@
+//! Settle times after UI events (`[waits]` section)
+//!
+//! Keystrokes, menu clicks and window changes need a moment to land before
+//! the next step reads or clicks the UI. Rather than fixed sleeps, each kind
+//! of wait has a base time that is scaled by how quickly this machine answers
+//! accessibility queries: measured at startup (against Pro Tools when it's
+//! running) and again by `os.calibrate_waits`, e.g. from a Pro Tools launch
+//! trigger. A heavy session that slows the UI down gets longer waits; a fast
+//! machine stops sleeping longer than it has to.
+
+use crate::config::WaitsConfig;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// What just happened, i.e. what we're waiting to settle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wait {
+    /// A keystroke sent to the frontmost app
+    Keystroke,
+    /// A menu item click
+    Menu,
+    /// A button click
+    Click,
+    /// Keyboard focus moving (app switch, focus shortcut)
+    Focus,
+    /// A window or display opening
+    Display,
+    /// The hotkey's own keys being released
+    KeyRelease,
+}
+
+impl Wait {
+    const ALL: [Wait; 6] = [
+        Wait::Keystroke,
+        Wait::Menu,
+        Wait::Click,
+        Wait::Focus,
+        Wait::Display,
+        Wait::KeyRelease,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Wait::Keystroke => "keystroke",
+            Wait::Menu => "menu",
+            Wait::Click => "click",
+            Wait::Focus => "focus",
+            Wait::Display => "display",
+            Wait::KeyRelease => "key_release",
+        }
+    }
+
+    /// Base time on a machine answering at REFERENCE speed
+    fn base_ms(self) -> u64 {
+        match self {
+            Wait::Keystroke => 35,
+            Wait::Menu => 35,
+            Wait::Click => 35,
+            Wait::Focus => 100,
+            Wait::Display => 300,
+            Wait::KeyRelease => 50,
+        }
+    }
+}
+
+/// Accessibility round trip the base times are tuned for
+const REFERENCE: Duration = Duration::from_millis(4);
+
+/// Measured scale is kept within this range
+const MIN_SCALE: f64 = 0.5;
+const MAX_SCALE: f64 = 4.0;
+
+/// Round trips timed per calibration (the median is used)
+const SAMPLES: usize = 7;
+
+/// Measured multiplier (f64 bits)
+static MEASURED: AtomicU64 = AtomicU64::new(0x3FF0_0000_0000_0000); // 1.0
+
+/// Config from `[waits]`; a fixed `scale` overrides the measured one
+static CONFIG: Mutex<Option<WaitsConfig>> = Mutex::new(None);
+
+/// Apply the `[waits]` section
+pub fn set_config(config: Option<WaitsConfig>) {
+    if let Some(config) = &config {
+        for name in config.ms.keys() {
+            if !Wait::ALL.iter().any(|wait| wait.name() == name) {
+                log::warn!("[waits] unknown wait '{}'", name);
+            }
+        }
+    }
+    *CONFIG.lock().unwrap() = config;
+}
+
+/// The multiplier in use: `[waits] scale`, or the measured one
+pub fn scale() -> f64 {
+    CONFIG
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|config| config.scale)
+        .map(|scale| scale.max(0.0))
+        .unwrap_or_else(|| f64::from_bits(MEASURED.load(Ordering::Relaxed)))
+}
+
+/// How long to wait after `wait`
+pub fn duration(wait: Wait) -> Duration {
+    let base = CONFIG
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|config| config.ms.get(wait.name()).copied())
+        .unwrap_or_else(|| wait.base_ms());
+    Duration::from_secs_f64(base as f64 * scale() / 1000.0)
+}
+
+/// Sleep long enough for `wait` to settle
+pub fn settle(wait: Wait) {
+    std::thread::sleep(duration(wait));
+}
+
+/// Time accessibility round trips and set the measured scale from them
+///
+/// Returns the scale in use (a fixed `[waits] scale` still wins).
+pub fn calibrate() -> f64 {
+    let running = super::get_running_apps().unwrap_or_default();
+    let app = if running.iter().any(|app| app == "Pro Tools") {
+        "Pro Tools"
+    } else {
+        "Finder"
+    };
+    let mut samples: Vec<Duration> = (0..SAMPLES)
+        .filter_map(|_| {
+            let started = Instant::now();
+            super::get_window_titles(app).ok()?;
+            Some(started.elapsed())
+        })
+        .collect();
+    if samples.is_empty() {
+        log::warn!("Could not time {} - keeping wait scale {:.2}", app, scale());
+        return scale();
+    }
+    samples.sort();
+    let median = samples[samples.len() / 2];
+    let measured = (median.as_secs_f64() / REFERENCE.as_secs_f64()).clamp(MIN_SCALE, MAX_SCALE);
+    MEASURED.store(measured.to_bits(), Ordering::Relaxed);
+    log::info!(
+        "{} answers in {:.1} ms - wait scale {:.2}",
+        app,
+        median.as_secs_f64() * 1000.0,
+        measured
+    );
+    scale()
+}
+
+/// Current wait for every kind, for `os.calibrate_waits`'s report
+pub fn describe() -> Vec<(&'static str, Duration)> {
+    Wait::ALL
+        .iter()
+        .map(|&wait| (wait.name(), duration(wait)))
+        .collect()
+}
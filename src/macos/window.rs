@@ -269,3 +269,61 @@ pub fn show_input_dialog(
         Ok(Some(String::new()))
     }
 }
+
+/// Show a modal yes/no dialog
+///
+/// Returns true if the user clicked `ok_button`, false if they cancelled.
+pub fn show_confirm_dialog(title: &str, message: &str, ok_button: &str) -> R<bool> {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let script = format!(
+        "display dialog \"{}\" with title \"{}\" buttons {{\"Cancel\", \"{}\"}} default button \"{}\"",
+        escape(message),
+        escape(title),
+        escape(ok_button),
+        escape(ok_button)
+    );
+
+    let output = Command::new("osascript").arg("-e").arg(&script).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("User canceled") {
+            return Ok(false);
+        }
+        anyhow::bail!("osascript failed: {}", stderr);
+    }
+    Ok(true)
+}
+
+/// Show a modal list to pick one item from
+///
+/// Typing jumps to the first item starting with the typed text. Returns the
+/// index of the chosen item, or None if the user cancelled.
+pub fn show_list_dialog(title: &str, prompt: &str, items: &[String]) -> R<Option<usize>> {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let list = items
+        .iter()
+        .map(|item| format!("\"{}\"", escape(item)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let script = format!(
+        "choose from list {{{}}} with title \"{}\" with prompt \"{}\" default items {{\"{}\"}}",
+        list,
+        escape(title),
+        escape(prompt),
+        escape(items.first().map_or("", String::as_str))
+    );
+
+    let output = Command::new("osascript").arg("-e").arg(&script).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("User canceled") {
+            return Ok(None);
+        }
+        anyhow::bail!("osascript failed: {}", stderr);
+    }
+
+    // The chosen item's text, or "false" on Cancel
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let chosen = stdout.trim_end_matches('\n');
+    Ok(items.iter().position(|item| item == chosen))
+}
@@ -0,0 +1,116 @@
+//! Action macros
+//!
+//! A hotkey with `actions = [...]` instead of `action` runs a list of
+//! registered actions in order, each with its own params:
+//!
+//! ```toml
+//! [[hotkey]]
+//! keys = ["ctrl", "shift", "s"]
+//! actions = [
+//!     { action = "pt.tracks_solo_selected" },
+//!     { action = "os.keystroke", keys = ["cmd", "s"], delay_ms = 200 },
+//! ]
+//! ```
+//!
+//! Every key in a step other than `action` and `delay_ms` is a param for that
+//! step. `delay_ms` waits before the step runs. A failing step stops the
+//! macro unless the hotkey sets `abort_on_error = false`.
+//!
+//! Steps run one after another on the macro's worker thread. Every action
+//! (Pro Tools ones included) returns once its work is done, so each step
+//! sees the state the previous one left behind.
+
+use crate::config::{Params, get_action};
+use crate::prelude::*;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Param holding the steps (the hotkey's `actions` array)
+pub const STEPS_PARAM: &str = "actions";
+
+/// Param holding the hotkey's `abort_on_error` flag
+pub const ABORT_PARAM: &str = "abort_on_error";
+
+/// Name macros run under (notifications, logs, worker dispatch)
+pub const ACTION_NAME: &str = "macro";
+
+/// One step of a macro
+pub struct Step {
+    pub name: String,
+    action: fn(&Params) -> R<()>,
+    params: Params,
+    delay: Duration,
+}
+
+/// Parse a macro's steps, checking every action exists
+pub fn parse_steps(steps: &[toml::Table]) -> R<Vec<Step>> {
+    if steps.is_empty() {
+        anyhow::bail!("'actions' has no steps");
+    }
+    steps
+        .iter()
+        .enumerate()
+        .map(|(i, table)| parse_step(table).with_context(|| format!("step {}", i + 1)))
+        .collect()
+}
+
+fn parse_step(table: &toml::Table) -> R<Step> {
+    let name = table
+        .get("action")
+        .and_then(|v| v.as_str())
+        .context("missing 'action'")?;
+    let action = get_action(name).with_context(|| format!("unknown action '{}'", name))?;
+    // These must run on the event thread, which a macro doesn't
+    if crate::workers::is_inline(name) {
+        anyhow::bail!("'{}' can't run inside a macro", name);
+    }
+    let delay_ms = table
+        .get("delay_ms")
+        .and_then(|v| v.as_integer())
+        .unwrap_or(0)
+        .max(0) as u64;
+
+    let params: HashMap<String, toml::Value> = table
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "action" | "delay_ms"))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    crate::config::check_params(name, &params)?;
+
+    Ok(Step {
+        name: name.to_string(),
+        action,
+        params: Params::new(params),
+        delay: Duration::from_millis(delay_ms),
+    })
+}
+
+/// The hotkey action for macros: run the steps stored in the params
+pub fn run(params: &Params) -> R<()> {
+    let steps = parse_steps(&params.get_tables(STEPS_PARAM))?;
+    let abort_on_error = params.get_bool(ABORT_PARAM, true);
+    let names: Vec<&str> = steps.iter().map(|step| step.name.as_str()).collect();
+    let _recording = crate::undo::record(&names.join(", "));
+
+    let mut failed = Vec::new();
+    for (i, step) in steps.iter().enumerate() {
+        if !step.delay.is_zero() {
+            std::thread::sleep(step.delay);
+        }
+        log::info!("Macro step {}/{}: {}", i + 1, steps.len(), step.name);
+        let result = crate::platform::current()
+            .check_action_permissions(&step.name)
+            .and_then(|_| (step.action)(&step.params));
+        if let Err(e) = result {
+            if abort_on_error {
+                return Err(e.context(format!("step {} ({}) failed", i + 1, step.name)));
+            }
+            log::warn!("Macro step {} ({}) failed: {:#}", i + 1, step.name, e);
+            failed.push(step.name.as_str());
+        }
+    }
+    if !failed.is_empty() {
+        anyhow::bail!("{} step(s) failed: {}", failed.len(), failed.join(", "));
+    }
+    Ok(())
+}
@@ -1,18 +1,31 @@
 #![allow(dead_code)]
+mod analytics;
+mod broadcast;
 mod config;
 mod input;
+mod ipc;
+#[cfg(target_os = "macos")]
 pub mod macos;
+mod macros;
+mod notify;
+mod platform;
 pub mod prelude;
+#[cfg(target_os = "macos")]
 mod protools;
+mod schedule;
+mod server;
+#[cfg(target_os = "macos")]
 mod soundminer;
+mod startup;
+mod support;
+mod undo;
+mod workers;
 
 pub use prelude::*;
 
 use config::{config_to_hotkeys, load_config};
 
-use libc::c_void;
 use std::io::Write;
-use std::ptr;
 use std::sync::Arc;
 
 // ============================================================================
@@ -62,57 +75,68 @@ macro_rules! actions_sync {
 ///     function_name_1,
 ///     function_name_2,
 /// });
+///
+/// // Actions that overwrite or remove session content are marked, so they're
+/// // refused during record and tagged "destructive":
+/// actions_async!("namespace", module_path {
+///     #[destructive]
+///     function_name_1,
+/// });
 /// ```
 #[macro_export]
 macro_rules! actions_async {
     // Pattern: Module identifier (e.g., tracks, markers)
     // Generates wrappers in __actions submodule with prefixed registry names
-    ($namespace:expr, $module_id:ident, { $($action_name:ident),* $(,)? }) => {
+    ($namespace:expr, $module_id:ident, { $($(#[$flag:ident])? $action_name:ident),* $(,)? }) => {
         // Generate wrapper functions in __actions submodule to avoid name collisions
         mod __actions {
 
 
             $(
                 pub fn $action_name(params: &$crate::config::Params) -> anyhow::Result<()> {
-                    use std::sync::{Arc, Mutex};
                     let params = params.clone();
                     let action_name = concat!(stringify!($module_id), "_", stringify!($action_name));
                     let notify = params.get_bool("notify", false);
-                    let _timeout_ms = params.get_int("timeout_ms", 500).max(100) as u64;
-
-                    // Create a shared error container (None = success, Some(msg) = error)
-                    let error = Arc::new(Mutex::new(None::<String>));
-                    let error_clone = error.clone();
-
-                    $crate::protools::run_command(move || async move {
-                        // Catch connection failures to prevent silent panics
-                        match $crate::protools::ProtoolsSession::new().await {
-                            Ok(mut pt) => {
-                                if let Err(e) = super::$action_name(&mut pt, &params).await {
-                                    *error_clone.lock().unwrap() = Some(format!("{:#}", e));
-                                }
-                            }
+                    let command_timeout_ms = params.get_timeout_ms(
+                        "command_timeout_ms",
+                        $crate::protools::default_timeout_ms(action_name),
+                    );
+
+                    // A second press stops a running batch - before the busy
+                    // guard, which the batch's own renders would hold up
+                    if $crate::protools::jobs::request_stop(action_name) {
+                        return Ok(());
+                    }
+
+                    // Don't click into a save/render progress dialog
+                    $crate::protools::transport::guard_busy(action_name)?;
+
+                    // Wait for the command to finish (we're on a worker thread, not the event tap)
+                    let result = $crate::protools::run_command(move || async move {
+                        let mut pt = match $crate::protools::ProtoolsSession::shared().await {
+                            Ok(pt) => pt,
                             Err(e) => {
                                 log::error!("Failed to connect to ProTools: {:#}", e);
-                                *error_clone.lock().unwrap() = Some(format!("ProTools connection failed: {:#}", e));
+                                anyhow::bail!("ProTools connection failed: {:#}", e);
                             }
-                        }
-                    });
-
-                    // Small delay to keep event consumed (50ms)
-                    std::thread::sleep(std::time::Duration::from_millis(50));
-
-                    // Check result
-                    let result = match error.lock().unwrap().as_ref() {
-                        Some(msg) => Err(anyhow::anyhow!("{}", msg)),
-                        None => Ok(()),
-                    };
+                        };
+                        // Refuse destructive edits during record
+                        $crate::protools::transport::guard_destructive(&mut pt, action_name).await?;
+                        super::$action_name(&mut pt, &params).await
+                    })
+                    .wait(std::time::Duration::from_millis(command_timeout_ms));
 
                     // Show notification if requested
                     if notify {
                         match &result {
-                            Ok(_) => $crate::macos::show_notification(&format!("✅ {}", action_name)),
-                            Err(e) => $crate::macos::show_notification(&format!("❌ {}: {}", action_name, e)),
+                            Ok(_) => $crate::macos::show_notification(&$crate::notify::text(
+                                "action_done",
+                                &[("action", action_name)],
+                            )),
+                            Err(e) => $crate::macos::show_notification(&$crate::notify::text(
+                                "action_failed",
+                                &[("action", action_name), ("error", &e.to_string())],
+                            )),
                         }
                     }
 
@@ -133,10 +157,28 @@ macro_rules! actions_async {
                 )*
                 registry
             }
+
+            /// Registry names of this module's `#[destructive]` actions
+            pub fn [<get_ $module_id _destructive>]() -> Vec<&'static str> {
+                vec![$($(
+                    $crate::action_flag!(
+                        $flag,
+                        concat!(stringify!($module_id), "_", stringify!($action_name))
+                    ),
+                )?)*]
+            }
         }
     };
 }
 
+/// Check an `actions_async!` flag (only `#[destructive]` so far), passing on the action name
+#[macro_export]
+macro_rules! action_flag {
+    (destructive, $name:expr) => {
+        $name
+    };
+}
+
 // ============================================================================
 // Hotkey Checking Helpers
 // ============================================================================
@@ -146,13 +188,13 @@ macro_rules! actions_async {
 /// This is used by both the CGEventTap callback (indirectly) and the Carbon hotkey callback.
 /// It performs app/window filtering and executes the action with proper error handling.
 pub fn trigger_hotkey_by_index(index: usize) {
-    if let Some(hotkeys_mutex) = HOTKEYS.get() {
-        let hotkeys = hotkeys_mutex.lock().unwrap();
+    if let Some(table) = HOTKEYS.get() {
+        let hotkeys = table.load();
 
         if let Some(hotkey) = hotkeys.get(index) {
             // Check app/window filters
             if let Some(config_apps) = &hotkey.application {
-                if let Ok(current_app) = macos::get_current_app() {
+                if let Ok(current_app) = platform::current().current_app() {
                     if !config_apps.iter().any(|app| soft_match(&current_app, app)) {
                         log::debug!(
                             "Hotkey '{}' not triggered - app filter doesn't match",
@@ -170,7 +212,7 @@ pub fn trigger_hotkey_by_index(index: usize) {
             }
 
             if let Some(config_window) = &hotkey.app_window {
-                if let Ok(app_window) = OS::get_app_window() {
+                if let Ok(app_window) = platform::current().app_window() {
                     if !soft_match(&app_window, config_window) {
                         log::debug!(
                             "Hotkey '{}' not triggered - window filter doesn't match",
@@ -187,8 +229,24 @@ pub fn trigger_hotkey_by_index(index: usize) {
                 }
             }
 
+            if !input::layers::is_active(&hotkey.layers) {
+                log::debug!(
+                    "Hotkey '{}' not triggered - not in its layer",
+                    hotkey.action_name
+                );
+                return;
+            }
+
+            if !input::suspend::allows(&hotkey.action_name) {
+                log::debug!(
+                    "Hotkey '{}' not triggered - hotkeys are suspended",
+                    hotkey.action_name
+                );
+                return;
+            }
+
             // Check if in text field (if enabled for this hotkey)
-            if hotkey.check_for_text_field && OS::is_in_text_field() {
+            if hotkey.check_for_text_field && input::frontmost::in_text_field() {
                 log::debug!(
                     "Hotkey '{}' not triggered - cursor is in a text field",
                     hotkey.action_name
@@ -196,36 +254,141 @@ pub fn trigger_hotkey_by_index(index: usize) {
                 return;
             }
 
+            // Check runtime conditions (review mode, when_stopped)
+            if !hotkey.check_conditions() {
+                return;
+            }
+
             // Clone action data before dropping lock
             let action = hotkey.action;
             let params = hotkey.params.clone();
             let notify = hotkey.notify;
+            let silent = hotkey.silent;
+            let restore_focus = hotkey.restore_focus;
             let action_name = hotkey.action_name.clone();
+            let binding = analytics::binding_key(&hotkey.trigger.describe(), &action_name);
             drop(hotkeys);
 
             log::info!("Triggering hotkey '{}' by index {}", action_name, index);
 
-            // Execute action with panic protection
-            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| action(&params)));
-
-            // Show notification if requested
-            if notify {
-                match result {
-                    Ok(Ok(_)) => OS::show_notification(&format!("✅ {}", action_name)),
-                    Ok(Err(e)) => OS::show_notification(&format!("❌ {}: {}", action_name, e)),
-                    Err(_) => {
-                        log::error!("Action '{}' panicked!", action_name);
-                        OS::show_notification(&format!("💥 {}: action panicked", action_name));
-                    }
-                }
-            } else {
-                // Log panics even if notify is false
-                if result.is_err() {
+            dispatch_action(
+                action,
+                params,
+                notify,
+                silent,
+                restore_focus,
+                action_name,
+                binding,
+            );
+        } else {
+            log::error!("Hotkey index {} out of bounds", index);
+        }
+    }
+}
+
+/// Run a hotkey's action with panic protection and the optional notification
+///
+/// For `silent` hotkeys every notification the action posts is dropped. With
+/// `restore_focus` the frontmost app/window is recorded first and brought back
+/// once the action returns, so macros don't strand the user in another app.
+///
+/// The action is queued on the worker pool so the callback thread returns at
+/// once; the few actions that must stay ordered run inline (see `workers`).
+/// Its run is counted under `binding` for the usage report.
+fn dispatch_action(
+    action: fn(&config::Params) -> anyhow::Result<()>,
+    params: config::Params,
+    notify: bool,
+    silent: bool,
+    restore_focus: bool,
+    action_name: String,
+    binding: String,
+) {
+    let inline = workers::is_inline(&action_name);
+    let run = action_job(
+        action,
+        params,
+        notify,
+        silent,
+        restore_focus,
+        action_name,
+        binding,
+    );
+    if inline {
+        run();
+    } else {
+        workers::submit(run);
+    }
+}
+
+/// The run of one action as `dispatch_action` describes it, ready to queue
+fn action_job(
+    action: fn(&config::Params) -> anyhow::Result<()>,
+    params: config::Params,
+    notify: bool,
+    silent: bool,
+    restore_focus: bool,
+    action_name: String,
+    binding: String,
+) -> impl FnOnce() + Send + 'static {
+    move || {
+        let _silenced = notify::silence(silent);
+        let focus = restore_focus
+            .then(platform::FocusSnapshot::capture)
+            .flatten();
+
+        // Built-in multi-step actions undo as one, like config macros (a macro
+        // records its own steps, which take over from this)
+        let _recording = undo::record(&action_name);
+
+        // Catch panics to prevent killing the worker (or callback) thread
+        let started = std::time::Instant::now();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            platform::current()
+                .check_action_permissions(&action_name)
+                .and_then(|_| action(&params))
+        }));
+        analytics::record(&binding, started.elapsed(), matches!(result, Ok(Ok(()))));
+        // The action may have switched apps - don't filter the next key on stale focus
+        input::frontmost::invalidate();
+
+        if let Some(focus) = focus
+            && let Err(e) = focus.restore()
+        {
+            log::warn!("Could not restore focus after '{}': {:#}", action_name, e);
+        }
+
+        broadcast::action_finished(
+            &action_name,
+            match &result {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => Some(format!("{:#}", e)),
+                Err(_) => Some("action panicked".to_string()),
+            },
+        );
+
+        // Show notification if requested
+        if notify {
+            match result {
+                Ok(Ok(_)) => platform::current()
+                    .show_notification(&notify::text("action_done", &[("action", &action_name)])),
+                Ok(Err(e)) => platform::current().show_notification(&notify::text(
+                    "action_failed",
+                    &[("action", &action_name), ("error", &e.to_string())],
+                )),
+                Err(_) => {
                     log::error!("Action '{}' panicked!", action_name);
+                    platform::current().show_notification(&notify::text(
+                        "action_panicked",
+                        &[("action", &action_name)],
+                    ));
                 }
             }
         } else {
-            log::error!("Hotkey index {} out of bounds", index);
+            // Log panics even if notify is false
+            if result.is_err() {
+                log::error!("Action '{}' panicked!", action_name);
+            }
         }
     }
 }
@@ -234,70 +397,100 @@ pub fn trigger_hotkey_by_index(index: usize) {
 ///
 /// Returns true if a hotkey was matched and the event should be consumed
 fn check_and_trigger_hotkey(pressed_keys: &Arc<std::collections::HashSet<u16>>) -> bool {
-    if let Some(hotkeys_mutex) = HOTKEYS.get() {
-        let hotkeys = hotkeys_mutex.lock().unwrap();
+    if let Some(table) = HOTKEYS.get() {
+        let hotkeys = table.load();
+
+        // Key sequences come first - mid-sequence, every key belongs to the sequence
+        match input::sequence::on_key_down(pressed_keys, &hotkeys) {
+            input::sequence::SequenceEvent::Ignored => {}
+            input::sequence::SequenceEvent::Waiting => return false,
+            input::sequence::SequenceEvent::Consumed => return true,
+            input::sequence::SequenceEvent::Completed(index) => {
+                drop(hotkeys);
+                trigger_hotkey_by_index(index);
+                return true;
+            }
+        }
 
-        for (index, hotkey) in hotkeys.iter().enumerate() {
-            if hotkey.matches_keyboard(pressed_keys) {
-                // Check if in text field (if enabled for this hotkey)
-                if hotkey.check_for_text_field && OS::is_in_text_field() {
-                    log::debug!(
-                        "Hotkey '{}' not triggered - cursor is in a text field",
-                        hotkey.action_name
-                    );
+        let mut matching: Vec<(usize, &input::Hotkey)> = hotkeys
+            .iter()
+            .enumerate()
+            .filter(|(_, hotkey)| hotkey.matches_keyboard(pressed_keys))
+            .collect();
+
+        // A chord with double/multi-tap bindings counts taps before running anything
+        if input::taps::is_multi_tap(&matching) {
+            // Text field check up front - otherwise typing the key is swallowed
+            // while the taps are counted
+            if matching.iter().any(|(_, hotkey)| hotkey.check_for_text_field)
+                && input::frontmost::in_text_field()
+            {
+                matching.retain(|(_, hotkey)| !hotkey.check_for_text_field);
+                if matching.is_empty() {
+                    log::debug!("Multi-tap hotkey not counted - cursor is in a text field");
                     return false; // Don't consume event - let it pass through
                 }
+            }
+            if let input::taps::TapEvent::Fire(index) =
+                input::taps::on_tap(&matching, trigger_hotkey_by_index)
+            {
+                drop(hotkeys);
+                trigger_hotkey_by_index(index);
+            }
+            return true;
+        }
 
-                if hotkey.trigger_on_release {
-                    // Mark as pending, trigger on key release
-                    let pending = PENDING_HOTKEY
-                        .get()
-                        .expect("PENDING_HOTKEY not initialized");
-                    *pending.lock().unwrap() = Some(PendingHotkey {
-                        hotkey_index: index,
-                        chord_keys: Arc::clone(pressed_keys),
-                    });
-                    return true; // Consume event
-                } else {
-                    // Clone action, params, notify, and action_name before dropping lock to avoid deadlock
-                    let action = hotkey.action;
-                    let params = hotkey.params.clone();
-                    let notify = hotkey.notify;
-                    let action_name = hotkey.action_name.clone();
-                    drop(hotkeys); // Explicitly drop the lock before calling action
-
-                    // Trigger immediately (lock is now released)
-                    // Catch panics to prevent crashing the event loop thread
-                    let result =
-                        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| action(&params)));
+        // The same chord can be bound per app: a binding limited to the
+        // frontmost app/window wins over a global one
+        let matched = input::pick_binding(matching.into_iter());
+        if let Some((index, hotkey)) = matched {
+            // Check if in text field (if enabled for this hotkey)
+            if hotkey.check_for_text_field && input::frontmost::in_text_field() {
+                log::debug!(
+                    "Hotkey '{}' not triggered - cursor is in a text field",
+                    hotkey.action_name
+                );
+                return false; // Don't consume event - let it pass through
+            }
 
-                    // Show notification if requested
-                    if notify {
-                        match result {
-                            Ok(Ok(_)) => {
-                                OS::show_notification(&format!("✅ {}", action_name));
-                            }
-                            Ok(Err(e)) => {
-                                OS::show_notification(&format!("❌ {}: {}", action_name, e));
-                            }
+            // Check runtime conditions (review mode, when_stopped)
+            if !hotkey.check_conditions() {
+                return true; // Consume event - the key is bound, just not right now
+            }
 
-                            Err(_) => {
-                                log::error!("Action '{}' panicked!", action_name);
-                                OS::show_notification(&format!(
-                                    "💥 {}: action panicked",
-                                    action_name
-                                ));
-                            }
-                        }
-                    } else {
-                        // Log panics even if notify is false
-                        if result.is_err() {
-                            log::error!("Action '{}' panicked!", action_name);
-                        }
-                    }
+            if hotkey.trigger_on_release {
+                // Mark as pending, trigger on key release
+                let pending = PENDING_HOTKEY
+                    .get()
+                    .expect("PENDING_HOTKEY not initialized");
+                *pending.lock().unwrap() = Some(PendingHotkey {
+                    hotkey_index: index,
+                    chord_keys: Arc::clone(pressed_keys),
+                });
+                return true; // Consume event
+            } else {
+                // Clone action, params, notify flags, and action_name before dropping lock to avoid deadlock
+                let action = hotkey.action;
+                let params = hotkey.params.clone();
+                let notify = hotkey.notify;
+                let silent = hotkey.silent;
+                let restore_focus = hotkey.restore_focus;
+                let action_name = hotkey.action_name.clone();
+                let binding = analytics::binding_key(&hotkey.trigger.describe(), &action_name);
+                drop(hotkeys); // Explicitly drop the lock before calling action
 
-                    return true; // Consume event
-                }
+                // Trigger immediately (lock is now released)
+                dispatch_action(
+                    action,
+                    params,
+                    notify,
+                    silent,
+                    restore_focus,
+                    action_name,
+                    binding,
+                );
+
+                return true; // Consume event
             }
         }
     }
@@ -320,14 +513,15 @@ fn check_pending_hotkey_release(pressed_keys: &Arc<std::collections::HashSet<u16
         if !any_chord_key_pressed {
             // All chord keys released - trigger the action!
             // Small delay to let the system fully process key releases
-            std::thread::sleep(std::time::Duration::from_millis(50));
+            #[cfg(target_os = "macos")]
+            macos::waits::settle(macos::waits::Wait::KeyRelease);
 
             // Clone action data and check text field before dropping lock to avoid deadlock
-            let action_data = if let Some(hotkeys_mutex) = HOTKEYS.get() {
-                let hotkeys = hotkeys_mutex.lock().unwrap();
+            let action_data = if let Some(table) = HOTKEYS.get() {
+                let hotkeys = table.load();
                 hotkeys.get(pending.hotkey_index).and_then(|hotkey| {
                     // Check if in text field (if enabled for this hotkey)
-                    if hotkey.check_for_text_field && OS::is_in_text_field() {
+                    if hotkey.check_for_text_field && input::frontmost::in_text_field() {
                         log::debug!(
                             "Pending hotkey '{}' not triggered - cursor is in a text field",
                             hotkey.action_name
@@ -335,11 +529,19 @@ fn check_pending_hotkey_release(pressed_keys: &Arc<std::collections::HashSet<u16
                         return None; // Skip triggering
                     }
 
+                    // Check runtime conditions (review mode, when_stopped)
+                    if !hotkey.check_conditions() {
+                        return None;
+                    }
+
                     Some((
                         hotkey.action,
                         hotkey.params.clone(),
                         hotkey.notify,
+                        hotkey.silent,
+                        hotkey.restore_focus,
                         hotkey.action_name.clone(),
+                        analytics::binding_key(&hotkey.trigger.describe(), &hotkey.action_name),
                     ))
                 })
             } else {
@@ -350,31 +552,18 @@ fn check_pending_hotkey_release(pressed_keys: &Arc<std::collections::HashSet<u16
             *pending_hotkey_guard.lock().unwrap() = None;
 
             // Now call the action with all locks released
-            if let Some((action, params, notify, action_name)) = action_data {
-                // Catch panics to prevent crashing the event loop thread
-                let result =
-                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| action(&params)));
-
-                // Show notification if requested
-                if notify {
-                    match result {
-                        Ok(Ok(_)) => {
-                            OS::show_notification(&format!("✅ {}", action_name));
-                        }
-                        Ok(Err(e)) => {
-                            OS::show_notification(&format!("❌ {}: {}", action_name, e));
-                        }
-                        Err(_) => {
-                            log::error!("Action '{}' panicked!", action_name);
-                            OS::show_notification(&format!("💥 {}: action panicked", action_name));
-                        }
-                    }
-                } else {
-                    // Log panics even if notify is false
-                    if result.is_err() {
-                        log::error!("Action '{}' panicked!", action_name);
-                    }
-                }
+            if let Some((action, params, notify, silent, restore_focus, action_name, binding)) =
+                action_data
+            {
+                dispatch_action(
+                    action,
+                    params,
+                    notify,
+                    silent,
+                    restore_focus,
+                    action_name,
+                    binding,
+                );
             }
 
             return true;
@@ -394,78 +583,66 @@ fn check_and_trigger_midi_hotkey(
     device: &str,
     channel: u8,
     active_midi: &Arc<std::collections::HashSet<input::midi::MidiMessage>>,
+    velocity: Option<u8>,
 ) -> bool {
-    if let Some(hotkeys_mutex) = HOTKEYS.get() {
-        let hotkeys = hotkeys_mutex.lock().unwrap();
+    if let Some(table) = HOTKEYS.get() {
+        let hotkeys = table.load();
+
+        // Bindings that need held keys or a layer go first, so shift + pad (or
+        // the pad in the current layer) beats the bare pad
+        let (specific, plain): (Vec<_>, Vec<_>) = hotkeys.iter().partition(|hk| {
+            matches!(hk.trigger, input::TriggerPattern::Hybrid { .. }) || !hk.layers.is_empty()
+        });
 
-        for hotkey in hotkeys.iter() {
+        for hotkey in specific.into_iter().chain(plain) {
             // Check if hotkey matches the MIDI pattern
             if !hotkey.matches_midi(active_midi) {
                 continue;
             }
 
-            // Filter by device if specified
-            if let Some(ref devices) = hotkey.midi_device {
-                if !devices.is_empty() && !devices.iter().any(|d| device.contains(d)) {
-                    log::debug!(
-                        "Hotkey '{}' not triggered - device filter '{}' doesn't match '{}'",
-                        hotkey.action_name,
-                        devices.join(", "),
-                        device
-                    );
-                    continue;
-                }
+            if !passes_midi_filters(hotkey, device, channel) {
+                continue;
             }
 
-            // Filter by channel if specified
-            if let Some(filter_channel) = hotkey.midi_channel {
-                if filter_channel != channel {
-                    log::debug!(
-                        "Hotkey '{}' not triggered - channel filter {} doesn't match {}",
-                        hotkey.action_name,
-                        filter_channel,
-                        channel
-                    );
-                    continue;
-                }
+            // Check runtime conditions (review mode, when_stopped)
+            if !hotkey.check_conditions() {
+                return true; // Matched, but gated
             }
 
             // All filters passed - trigger the hotkey
             {
-                // Clone action data before dropping lock
-                let action = hotkey.action;
-                let params = hotkey.params.clone();
+                // Clone action data before dropping lock; a velocity layer
+                // swaps in its own action and params
+                let layer =
+                    velocity.and_then(|v| input::midi::select_layer(&hotkey.velocity_layers, v));
+                let (action, params, action_name) = match layer {
+                    Some(layer) => (
+                        layer.action,
+                        layer.params.clone(),
+                        layer.action_name.clone(),
+                    ),
+                    None => (
+                        hotkey.action,
+                        hotkey.params.clone(),
+                        hotkey.action_name.clone(),
+                    ),
+                };
                 let notify = hotkey.notify;
-                let action_name = hotkey.action_name.clone();
+                let silent = hotkey.silent;
+                let restore_focus = hotkey.restore_focus;
+                let binding = analytics::binding_key(&hotkey.trigger.describe(), &action_name);
                 drop(hotkeys); // Explicitly drop the lock before calling action
 
                 log::info!("Triggering MIDI hotkey '{}'", action_name);
-
-                // Trigger immediately (lock is now released)
-                // Catch panics to prevent crashing the MIDI thread
-                let result =
-                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| action(&params)));
-
-                // Show notification if requested
-                if notify {
-                    match result {
-                        Ok(Ok(_)) => {
-                            OS::show_notification(&format!("✅ {}", action_name));
-                        }
-                        Ok(Err(e)) => {
-                            OS::show_notification(&format!("❌ {}: {}", action_name, e));
-                        }
-                        Err(_) => {
-                            log::error!("MIDI action '{}' panicked!", action_name);
-                            OS::show_notification(&format!("💥 {}: action panicked", action_name));
-                        }
-                    }
-                } else {
-                    // Log panics even if notify is false
-                    if result.is_err() {
-                        log::error!("MIDI action '{}' panicked!", action_name);
-                    }
-                }
+                dispatch_action(
+                    action,
+                    params,
+                    notify,
+                    silent,
+                    restore_focus,
+                    action_name,
+                    binding,
+                );
 
                 return true; // Matched
             }
@@ -474,11 +651,145 @@ fn check_and_trigger_midi_hotkey(
     false
 }
 
+/// Check a MIDI hotkey's device and channel filters
+fn passes_midi_filters(hotkey: &input::Hotkey, device: &str, channel: u8) -> bool {
+    // Filter by device if specified
+    if let Some(ref devices) = hotkey.midi_device
+        && !devices.is_empty()
+        && !devices.iter().any(|d| soft_match(device, d))
+    {
+        log::debug!(
+            "Hotkey '{}' not triggered - device filter '{}' doesn't match '{}'",
+            hotkey.action_name,
+            devices.join(", "),
+            device
+        );
+        return false;
+    }
+
+    // Filter by channel if specified
+    if let Some(filter_channel) = hotkey.midi_channel
+        && filter_channel != channel
+    {
+        log::debug!(
+            "Hotkey '{}' not triggered - channel filter {} doesn't match {}",
+            hotkey.action_name,
+            filter_channel,
+            channel
+        );
+        return false;
+    }
+    true
+}
+
+/// Run the fader bound to `cc`, if any, with the new value
+///
+/// Returns true if a fader matched. Runs are throttled per binding (see
+/// `input::fader`), so only some values of a fast move reach the action.
+fn check_and_run_fader(device: &str, channel: u8, cc: u8, value: u8) -> bool {
+    let Some(table) = HOTKEYS.get() else {
+        return false;
+    };
+    let hotkeys = table.load();
+    let Some((_, hotkey)) = input::pick_binding(
+        hotkeys
+            .iter()
+            .enumerate()
+            .filter(|(_, hk)| hk.matches_fader(cc) && passes_midi_filters(hk, device, channel)),
+    ) else {
+        return false;
+    };
+    let Some(fader) = &hotkey.fader else {
+        return false;
+    };
+
+    // Check runtime conditions (review mode, when_stopped)
+    if !hotkey.check_conditions() {
+        return true; // Matched, but gated
+    }
+
+    let params = hotkey.params.merged(std::collections::HashMap::from([(
+        fader.param.clone(),
+        fader.scale(value),
+    )]));
+    let binding = analytics::binding_key(&hotkey.trigger.describe(), &hotkey.action_name);
+    log::debug!("Fader '{}' {} = {}", hotkey.action_name, fader.param, value);
+    input::fader::submit(
+        &binding,
+        fader.interval,
+        action_job(
+            hotkey.action,
+            params,
+            hotkey.notify,
+            hotkey.silent,
+            hotkey.restore_focus,
+            hotkey.action_name.clone(),
+            binding.clone(),
+        ),
+    );
+    true
+}
+
+/// Run the wheel binding whose chord is held, if any, with the scrolled amount
+///
+/// Returns true if a wheel binding matched (the scroll is consumed). Amounts
+/// add up between runs, which are throttled per binding like faders.
+fn check_and_run_wheel(pressed_keys: &std::collections::HashSet<u16>, lines: f64) -> bool {
+    let Some(table) = HOTKEYS.get() else {
+        return false;
+    };
+    let hotkeys = table.load();
+    let Some((_, hotkey)) = input::pick_binding(
+        hotkeys
+            .iter()
+            .enumerate()
+            .filter(|(_, hk)| hk.matches_wheel(pressed_keys)),
+    ) else {
+        return false;
+    };
+    let Some(wheel) = &hotkey.wheel else {
+        return false;
+    };
+
+    // Check runtime conditions (review mode, when_stopped)
+    if !hotkey.check_conditions() {
+        return true; // Matched, but gated
+    }
+
+    let binding = analytics::binding_key(
+        &format!("{} + wheel", hotkey.trigger.describe()),
+        &hotkey.action_name,
+    );
+    input::wheel::add(&binding, wheel.amount(lines));
+
+    let (hotkey, wheel, key) = (hotkey.clone(), wheel.clone(), binding.clone());
+    input::fader::submit(&key, wheel.interval, move || {
+        let Some(amount) = input::wheel::take(&binding, wheel.integer) else {
+            return;
+        };
+        log::debug!("Wheel '{}' {} = {}", hotkey.action_name, wheel.param, amount);
+        let params = hotkey
+            .params
+            .merged(std::collections::HashMap::from([(wheel.param.clone(), amount)]));
+        action_job(
+            hotkey.action,
+            params,
+            hotkey.notify,
+            hotkey.silent,
+            hotkey.restore_focus,
+            hotkey.action_name.clone(),
+            binding,
+        )();
+    });
+    true
+}
+
 /// MIDI callback - updates MIDI state and checks registered MIDI hotkeys
 fn midi_callback(device: &str, channel: u8, message: input::midi::MidiMessage) {
     use input::midi::MIDI_STATE;
 
     let state = MIDI_STATE.get().expect("MIDI_STATE not initialized");
+    let mut cc_changed = false;
     let active = {
         let mut s = state.lock().unwrap();
         match message {
@@ -492,134 +803,176 @@ fn midi_callback(device: &str, channel: u8, message: input::midi::MidiMessage) {
             }
             input::midi::MidiMessage::ControlChange { cc, value } => {
                 log::debug!("MIDI: [{}] ch{} CC {} value={}", device, channel, cc, value);
-                s.cc(cc, value);
+                cc_changed = s.cc(cc, value);
             }
         }
         s.get_active_messages()
     };
 
-    check_and_trigger_midi_hotkey(device, channel, &active);
+    // A CC mapped to a fader follows its value instead of triggering
+    if let input::midi::MidiMessage::ControlChange { cc, value } = message
+        && cc_changed
+        && check_and_run_fader(device, channel, cc, value)
+    {
+        return;
+    }
+
+    // Note velocity (or CC value) picks the velocity layer
+    let velocity = match message {
+        input::midi::MidiMessage::NoteOn { velocity, .. } => Some(velocity),
+        input::midi::MidiMessage::ControlChange { value, .. } => Some(value),
+        input::midi::MidiMessage::NoteOff { .. } => None,
+    };
+    check_and_trigger_midi_hotkey(device, channel, &active, velocity);
 }
 
 // ============================================================================
-// Event Tap Callback
+// OSC Callback
 // ============================================================================
 
-// Event tap callback - tracks key state and checks registered hotkeys
-unsafe extern "C" fn key_event_callback(
-    _proxy: *mut c_void,
-    event_type: u32,
-    event: *mut c_void,
-    _user_info: *mut c_void,
-) -> *mut c_void {
-    // Check if this event was created by our app - if so, pass it through
-    const APP_EVENT_MARKER: i64 = 0x5054484B44;
-    const CG_EVENT_FIELD_EVENT_SOURCE_USER_DATA: u32 = 127;
-
-    let user_data =
-        unsafe { macos::CGEventGetIntegerValueField(event, CG_EVENT_FIELD_EVENT_SOURCE_USER_DATA) };
-    if user_data == APP_EVENT_MARKER {
-        return event; // Pass through events we created
+/// OSC callback - runs the hotkey bound to the address, or the action it routes to
+fn osc_callback(message: input::osc::OscMessage) {
+    log::debug!("OSC: {} {:?}", message.address, message.args);
+
+    if let Some(table) = HOTKEYS.get() {
+        let hotkeys = table.load();
+        let matching = hotkeys
+            .iter()
+            .enumerate()
+            .filter(|(_, hk)| hk.matches_osc(&message.address));
+        if let Some((_, hotkey)) = input::pick_binding(matching) {
+            // Check runtime conditions (review mode, when_stopped)
+            if !hotkey.check_conditions() {
+                return;
+            }
+            let names = match &hotkey.trigger {
+                input::TriggerPattern::Osc { args, .. } => args.as_slice(),
+                _ => &[],
+            };
+            let params = hotkey
+                .params
+                .merged(input::osc::named_params(names, &message.args));
+            let action = hotkey.action;
+            let notify = hotkey.notify;
+            let silent = hotkey.silent;
+            let restore_focus = hotkey.restore_focus;
+            let action_name = hotkey.action_name.clone();
+            let binding = analytics::binding_key(&hotkey.trigger.describe(), &action_name);
+            drop(hotkeys);
+
+            log::info!("Triggering OSC hotkey '{}'", action_name);
+            dispatch_action(
+                action,
+                params,
+                notify,
+                silent,
+                restore_focus,
+                action_name,
+                binding,
+            );
+            return;
+        }
     }
 
-    // Handle event tap disable events (types 14/15)
-    if event_type == macos::CG_EVENT_TAP_DISABLED_BY_TIMEOUT
-        || event_type == macos::CG_EVENT_TAP_DISABLED_BY_USER_INPUT
-    {
+    let Some(action_name) = input::osc::route(&message.address) else {
+        log::debug!("OSC: nothing bound to {}", message.address);
+        return;
+    };
+    let Some(action) = config::get_action(&action_name) else {
         log::warn!(
-            "Event tap disabled by macOS (type {}). Attempting recovery...",
-            event_type
+            "OSC: {} names unknown action '{}'",
+            message.address,
+            action_name
         );
-
-        if let Err(e) = macos::recreate_event_tap_if_needed() {
-            log::error!("Failed to recreate event tap: {}", e);
-        } else {
-            log::info!("Event tap successfully recovered");
+        return;
+    };
+    // Same gate as a hotkey (tags, review mode)
+    if let Err(e) = input::tags::check_action(&action_name) {
+        log::info!("OSC: {:#}", e);
+        return;
+    }
+    let params = match input::osc::paired_params(&message.args) {
+        Ok(params) => config::Params::new(params),
+        Err(e) => {
+            log::warn!("OSC: ignoring {} - {:#}", message.address, e);
+            return;
         }
+    };
+    log::info!("Triggering '{}' from OSC {}", action_name, message.address);
+    let binding = analytics::binding_key(&format!("osc {}", message.address), &action_name);
+    dispatch_action(action, params, false, false, false, action_name, binding);
+}
 
-        return event;
-    }
+// ============================================================================
+// Key Event Handling
+// ============================================================================
 
+/// Shared key handler for the platform event loop - tracks key state and
+/// checks registered hotkeys. Returns true if the event should be consumed.
+fn handle_key_event(event: platform::KeyEvent) -> bool {
     // Get key state (should always be initialized by this point)
     let key_state = KEY_STATE.get().expect("KEY_STATE not initialized");
 
-    if event_type == macos::CG_EVENT_KEY_DOWN {
-        let key_code = unsafe {
-            macos::CGEventGetIntegerValueField(event, macos::CG_EVENT_FIELD_KEYBOARD_EVENT_KEYCODE)
-        } as u16;
-
-        // Update key state
-        let mut state = key_state.lock().unwrap();
-        state.key_down(key_code);
-        let pressed_keys = state.get_pressed_keys();
-        drop(state);
+    match event {
+        platform::KeyEvent::KeyDown(key_code) => {
+            // Update key state
+            let mut state = key_state.lock().unwrap();
+            state.key_down(key_code);
+            let pressed_keys = state.get_pressed_keys();
+            drop(state);
 
-        // Check all registered hotkeys against current key state
-        if check_and_trigger_hotkey(&pressed_keys) {
-            return ptr::null_mut(); // Consume event
+            // Check all registered hotkeys against current key state
+            check_and_trigger_hotkey(&pressed_keys)
         }
-    } else if event_type == macos::CG_EVENT_KEY_UP {
-        let key_code = unsafe {
-            macos::CGEventGetIntegerValueField(event, macos::CG_EVENT_FIELD_KEYBOARD_EVENT_KEYCODE)
-        } as u16;
-
-        // Update key state
-        let mut state = key_state.lock().unwrap();
-        state.key_up(key_code);
-        let pressed_keys = state.get_pressed_keys();
-        drop(state);
-
-        // Check if pending hotkey should be triggered
-        check_pending_hotkey_release(&pressed_keys);
-    } else if event_type == macos::CG_EVENT_FLAGS_CHANGED {
-        // Modifier key pressed or released
-        let key_code = unsafe {
-            macos::CGEventGetIntegerValueField(event, macos::CG_EVENT_FIELD_KEYBOARD_EVENT_KEYCODE)
-        } as u16;
-
-        let flags = unsafe { macos::CGEventGetFlags(event) };
-
-        // Determine if this is a press or release based on the flags
-        use crate::input::*;
-        let is_pressed = match key_code {
-            KEY_CMD_LEFT | KEY_CMD_RIGHT => (flags & macos::CG_EVENT_FLAG_MASK_COMMAND) != 0,
-            KEY_SHIFT_LEFT | KEY_SHIFT_RIGHT => (flags & macos::CG_EVENT_FLAG_MASK_SHIFT) != 0,
-            KEY_OPTION_LEFT | KEY_OPTION_RIGHT => {
-                (flags & macos::CG_EVENT_FLAG_MASK_ALTERNATE) != 0
-            }
-            KEY_CONTROL_LEFT | KEY_CONTROL_RIGHT => {
-                (flags & macos::CG_EVENT_FLAG_MASK_CONTROL) != 0
-            }
-            _ => return event, // Unknown modifier
-        };
-
-        // Update key state
-        let mut state = key_state.lock().unwrap();
-        if is_pressed {
-            state.key_down(key_code);
-        } else {
+        platform::KeyEvent::KeyUp(key_code) => {
+            // Update key state
+            let mut state = key_state.lock().unwrap();
             state.key_up(key_code);
-        }
-        let pressed_keys = state.get_pressed_keys();
-        drop(state);
-
-        // Check hotkeys after modifier change
-        if is_pressed {
-            // Only check for new matches on key down, not release
-            if check_and_trigger_hotkey(&pressed_keys) {
-                return ptr::null_mut(); // Consume event
-            }
-        } else {
-            // Modifier released - check for pending hotkey trigger
+            let pressed_keys = state.get_pressed_keys();
+            drop(state);
+
+            // Check if pending hotkey should be triggered
             check_pending_hotkey_release(&pressed_keys);
+            false
+        }
+        platform::KeyEvent::Scroll(lines) => {
+            let pressed_keys = key_state.lock().unwrap().get_pressed_keys();
+            !pressed_keys.is_empty() && check_and_run_wheel(&pressed_keys, lines)
         }
     }
-
-    // Pass through other events
-    event
 }
 
 fn main() {
+    startup::begin();
+
+    // `pthkd init --role mix` writes a starter config.toml and exits
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if config::roles::is_init_command(&args) {
+        if let Err(e) = config::roles::run_init(&args) {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // `pthkd simulate --press ...` reports which binding a key press would run
+    if config::simulate::is_simulate_command(&args) {
+        if let Err(e) = config::simulate::run_simulate(&args) {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // `pthkd bind ...` etc. talk to the running daemon instead of starting one
+    if ipc::is_cli_command(&args) {
+        if let Err(e) = ipc::run_cli(&args) {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     if let Err(e) = run() {
         eprintln!("Error: {:?}", e);
         std::process::exit(1);
@@ -627,6 +980,8 @@ fn main() {
 }
 
 fn run() -> anyhow::Result<()> {
+    let mut timer = startup::StageTimer::new();
+
     // Initialize logging with file clearing on startup
     let log_path = init_logging()?;
 
@@ -634,14 +989,19 @@ fn run() -> anyhow::Result<()> {
     log::info!("Starting pthkd hotkey daemon...");
     log::info!("Log file: {}", log_path);
     log::info!("===========================================");
+    timer.mark("Logging");
 
     // BLOCKING permission check - will not return until all permissions granted or user quits
     log::info!("Checking required permissions...");
-    macos::permissions::ensure_permissions_granted().context("Failed to verify permissions")?;
+    platform::current()
+        .ensure_permissions()
+        .context("Failed to verify permissions")?;
 
     log::info!("✅ All permissions granted, starting daemon...");
+    timer.mark("Permissions check");
 
     // Initialize ProTools tokio runtime
+    #[cfg(target_os = "macos")]
     protools::init_runtime();
 
     // Initialize key state tracker
@@ -655,11 +1015,25 @@ fn run() -> anyhow::Result<()> {
     PENDING_HOTKEY.set(Mutex::new(None)).map_err(|_| {
         anyhow::anyhow!("Failed to initialize PENDING_HOTKEY - already initialized")
     })?;
+    timer.mark("Runtime and key state");
 
     // Load configuration from config.toml
     let config = load_config("config.toml")
         .context("Failed to load config.toml - make sure it exists in the current directory")?;
 
+    // Apply notification policy (quiet mode, coalescing)
+    notify::set_config(config.notifications.as_ref(), true);
+
+    // Apply review mode settings
+    match &config.review_mode {
+        Some(review) => {
+            input::review::set_blocked_tags(review.block_tags.clone());
+            input::review::set_enabled(review.enabled);
+        }
+        None => input::review::set_blocked_tags(input::review::default_blocked_tags()),
+    }
+    timer.mark("Config load");
+
     // Convert config to hotkeys
     let hotkeys = config_to_hotkeys(config.clone()).context("Failed to parse config")?;
 
@@ -675,17 +1049,105 @@ fn run() -> anyhow::Result<()> {
 
     // Initialize hotkey registry
     HOTKEYS
-        .set(Mutex::new(hotkeys))
+        .set(arc_swap::ArcSwap::from_pointee(hotkeys))
         .map_err(|_| anyhow::anyhow!("Failed to initialize hotkeys - already initialized"))?;
+    timer.mark("Hotkey table");
+
+    // Everything below is off the startup path - hotkeys don't wait for it
+
+    // Accept `pthkd bind` and other CLI requests
+    startup::defer("IPC socket", || {
+        ipc::start();
+        Ok(())
+    });
+
+    // HTTP/WebSocket API for remotes (no-op until enabled in config)
+    let server_config = config.server.clone();
+    startup::defer("HTTP server", move || {
+        server::start(server_config);
+        Ok(())
+    });
+
+    // Interval / time-of-day actions (no-op until set in config)
+    schedule::set_config(
+        config
+            .trigger
+            .as_ref()
+            .map(|trigger| trigger.schedule.clone())
+            .unwrap_or_default(),
+    );
+    schedule::start();
+
+    #[cfg(target_os = "macos")]
+    {
+        let auto_save = config.auto_save.clone();
+        let resource_meter = config.resource_meter.clone();
+        let busy_guard = config.busy_guard.clone();
+        let tally = config.tally.clone();
+        startup::defer("Pro Tools services", move || {
+            // Start idle-aware auto-save (no-op until enabled in config)
+            protools::autosave::set_config(auto_save);
+            protools::autosave::start();
+
+            // Menu bar resource meter for long renders (no-op until enabled in config)
+            protools::jobs::set_config(resource_meter);
+
+            // Hold Pro Tools actions while a progress dialog is up
+            protools::transport::set_busy_config(busy_guard);
+
+            // Light the recording tally while Pro Tools records (os.tally works regardless)
+            protools::tally::set_config(tally);
+            protools::tally::start();
+
+            // Watch for Pro Tools crashes and offer to relaunch
+            protools::recovery::start();
+            Ok(())
+        });
+
+        // Resolve English menu paths on localized apps (no-op until set in config)
+        macos::menu_locale::set_config(config.menu_locale.clone());
+
+        // Config-defined menu bar items (added when the menu is created)
+        macos::menubar::set_custom_items(config.menubar.clone());
+
+        // Run actions when apps launch or quit (no-op until set in config)
+        macos::app_events::set_config(config.trigger.clone());
+        macos::app_events::start();
+
+        // Scale UI settle times to this machine (unless fixed in config)
+        macos::waits::set_config(config.waits.clone());
+        macos::clicks::set_config(config.clicks.clone());
+        macos::layouts::set_config(config.layouts.clone());
+        startup::defer("Wait calibration", || {
+            macos::waits::calibrate();
+            Ok(())
+        });
+
+        // Reload hotkeys whenever config.toml is saved
+        startup::defer("Config watcher", || {
+            config::watch::start(macos::commands::reload_config_on_save);
+            Ok(())
+        });
+
+        // Load the Swift UI library now rather than on the first UI action
+        startup::defer("Swift UI library", || {
+            if !OS::swift_available() {
+                log::warn!("Swift UI library not loaded - using native fallbacks");
+            }
+            Ok(())
+        });
+    }
+
+    // Port for os.midi_send feedback when an action doesn't name one
+    input::midi_out::set_default_device(config.midi.as_ref().and_then(|midi| midi.output.clone()));
 
     // Initialize MIDI if any hotkeys use MIDI or if MIDI is enabled in config
     let has_midi_hotkeys = HOTKEYS
         .get()
         .unwrap()
-        .lock()
-        .unwrap()
+        .load()
         .iter()
-        .any(|hk| matches!(hk.trigger, hotkey::TriggerPattern::Midi(_)));
+        .any(|hk| hk.trigger.uses_midi());
 
     // MIDI is enabled if:
     // - [midi] section doesn't exist AND there are MIDI hotkeys (default: auto-enable)
@@ -694,8 +1156,6 @@ fn run() -> anyhow::Result<()> {
     let midi_enabled = config.midi.as_ref().map_or(has_midi_hotkeys, |m| m.enabled);
 
     if midi_enabled {
-        log::info!("Initializing MIDI input...");
-
         // Initialize MIDI state
         use input::midi::MidiState;
         input::midi::MIDI_STATE
@@ -704,107 +1164,54 @@ fn run() -> anyhow::Result<()> {
                 anyhow::anyhow!("Failed to initialize MIDI_STATE - already initialized")
             })?;
 
-        // Initialize MIDI input with callback
-        if let Err(e) = input::midi::init_midi_input(midi_callback) {
-            log::error!("Failed to initialize MIDI: {:#}", e);
-            log::warn!("MIDI hotkeys will not be available");
-        } else {
-            log::info!("✅ MIDI input initialized successfully");
-        }
+        // Scanning and connecting MIDI ports can take a while - do it in the background
+        log::info!("Initializing MIDI input...");
+        startup::defer("MIDI input", || {
+            input::midi::init_midi_input(midi_callback)
+                .context("MIDI hotkeys will not be available")
+        });
     } else {
         log::info!("No MIDI hotkeys configured, skipping MIDI initialization");
     }
 
-    // Register Carbon hotkeys for those marked with carbon=true
-    // These will work during secure input when CGEventTap is disabled
-    log::info!("Registering Carbon hotkeys...");
-    if let Err(e) = macos::carbon_hotkeys::register_carbon_hotkeys() {
-        log::error!("Failed to register Carbon hotkeys: {:#}", e);
-        log::warn!("Carbon hotkeys will not be available during secure input");
-    }
-
-    // Initialize NSApplication for menu bar (must be done before event loop)
-    // Create and install event tap for keyboard events
-    unsafe {
-        use objc2::runtime::AnyObject;
-        use objc2::{class, msg_send};
-
-        log::info!("Initializing NSApplication for menu bar...");
-
-        let ns_app_class = class!(NSApplication);
-        let ns_app: *mut AnyObject = msg_send![ns_app_class, sharedApplication];
-
-        if ns_app.is_null() {
-            anyhow::bail!("Failed to get NSApplication");
-        }
-
-        // Set activation policy to Accessory (menu bar only, no dock icon)
-        // NSApplicationActivationPolicyAccessory = 1
-        let policy: isize = 1;
-        let success: bool = msg_send![ns_app, setActivationPolicy: policy];
-
-        if !success {
-            log::warn!("Failed to set activation policy - menu bar may not work correctly");
-        }
-
-        log::info!("NSApplication initialized as menu bar app (no dock icon)");
-
-        // Create menu bar status item with reload callback
-        // Keep it alive for the duration of the program by not dropping it
-        let _menu_bar = macos::menubar::create_menu_bar(None, || {
-            log::info!("Reload Config triggered from menu");
-            // Call the reload_config command
-            if let Err(e) = macos::commands::reload_config(&crate::config::Params::new(
-                std::collections::HashMap::new(),
-            )) {
-                log::error!("Failed to reload config: {}", e);
-                OS::show_notification(&format!("❌ Failed to reload config: {}", e));
-            } else {
-                OS::show_notification("✅ Config reloaded successfully!");
-            }
-        })
-        .context("Failed to create menu bar")?;
-
-        log::info!("Menu bar icon created successfully");
-
-        // Create event tap
-        let event_tap = macos::create_keyboard_event_tap(key_event_callback)
-            .context("Failed to create event tap")?;
-
-        macos::install_event_tap_on_run_loop(event_tap, key_event_callback);
-
-        log::info!("Hotkey daemon is running. Listening for hotkeys...");
-
-        // Activate the application so it can receive events
-        let _: () = msg_send![ns_app, activateIgnoringOtherApps: true];
-
-        // Run NSApplication's event loop (blocks forever)
-        // This is required for menu bar items to work properly
-        // The _menu_bar variable stays in scope and won't be dropped
-        let _: () = msg_send![ns_app, run];
+    // OSC from control surfaces, if configured or any hotkey has an `osc` address
+    let has_osc_hotkeys = HOTKEYS
+        .get()
+        .unwrap()
+        .load()
+        .iter()
+        .any(|hk| matches!(hk.trigger, input::TriggerPattern::Osc { .. }));
+    let osc_config = match config.osc.clone() {
+        Some(osc) => osc.enabled.then_some(osc),
+        None => has_osc_hotkeys.then(config::OscConfig::default),
+    };
+    input::osc::set_config(config.osc.as_ref());
+    if let Some(osc) = osc_config {
+        startup::defer("OSC input", move || {
+            input::osc::start(&osc.bind, osc.port, osc_callback);
+            Ok(())
+        });
     }
 
-    Ok(())
+    platform::current().run_event_loop(handle_key_event)
 }
 
 /// Initialize logging system
 /// Note: Log file is cleared on recompile (in build.rs), not on each run
 /// Returns the absolute path to the log file
 fn init_logging() -> anyhow::Result<String> {
-    use std::env;
     use std::fs::{self, OpenOptions};
-    use std::path::PathBuf;
 
     // Use proper macOS log location: ~/Library/Logs/pthkd.log
-    let home = env::var("HOME").context("HOME environment variable not set")?;
-    let log_dir = PathBuf::from(home).join("Library").join("Logs");
+    let log_file_path = config::get_log_path()?;
 
     // Create Logs directory if it doesn't exist
-    if !log_dir.exists() {
-        fs::create_dir_all(&log_dir).context("Failed to create Logs directory")?;
+    if let Some(log_dir) = log_file_path.parent()
+        && !log_dir.exists()
+    {
+        fs::create_dir_all(log_dir).context("Failed to create Logs directory")?;
     }
 
-    let log_file_path = log_dir.join("pthkd.log");
     let absolute_path = log_file_path.to_string_lossy().to_string();
 
     // Configure env_logger to write to the file (append mode)
@@ -822,6 +1229,9 @@ fn init_logging() -> anyhow::Result<String> {
         .format(|buf, record| {
             use std::time::{SystemTime, UNIX_EPOCH};
 
+            // Feed WebSocket log subscribers
+            broadcast::log_record(record);
+
             // Simple timestamp using std lib only - format as Unix timestamp for now
             // This avoids the chrono dependency while still providing timing info
             let timestamp = SystemTime::now()
@@ -888,6 +1298,6 @@ impl MessageLog {
         self.message.push('\n');
     }
     pub fn display(&self) -> anyhow::Result<()> {
-        unsafe { crate::macos::MacOSSession::global().show_text_window(&self.message) }
+        platform::current().show_text_window(&self.message)
     }
 }
@@ -0,0 +1,311 @@
+//! Notification policy
+//!
+//! Every notification passes through here on its way to the platform:
+//! - quiet mode (menu bar toggle, or `quiet = true` in `[notifications]`)
+//!   drops them all
+//! - hotkeys with `silent = true` drop whatever their action posts
+//! - bursts are coalesced: the first notification in a window is shown at
+//!   once and the rest become a single summary when the window closes
+//!
+//! Notification Center throttles and reorders floods (20 MIDI-triggered
+//! actions in a second), so a summary is more useful than a partial stream.
+//! Dropped and coalesced notifications are still logged.
+//!
+//! Texts come from a template table (`text`): the shipped English, then the
+//! shipped `language` if one is set, then the config's own `templates`. The
+//! shipped translations only cover the daemon's own messages - anything else
+//! stays English unless the config overrides it. With `emoji = false` the
+//! leading emoji is dropped on the way out, after coalescing has counted
+//! failures by it.
+
+use crate::config::NotificationConfig;
+use crate::prelude::*;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Delivers a notification to the user (platform specific)
+pub type Deliver = fn(&str);
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Keep the emoji at the start of each notification
+static EMOJI: AtomicBool = AtomicBool::new(true);
+
+/// Template text by id, once the config has been applied (before that, the shipped English)
+static TEMPLATES: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
+
+lazy_static::lazy_static! {
+    static ref ENGLISH: HashMap<String, String> =
+        toml::from_str(include_str!("notify/en.toml")).expect("notify/en.toml is valid");
+}
+
+/// Coalescing window in milliseconds (0 = show everything)
+static COALESCE_MS: AtomicU64 = AtomicU64::new(DEFAULT_COALESCE_MS);
+
+pub const DEFAULT_COALESCE_MS: u64 = 1000;
+
+/// Notifications held back during the current window
+static BURST: Mutex<Burst> = Mutex::new(Burst {
+    window_start: None,
+    held: Vec::new(),
+    flush_scheduled: false,
+});
+
+struct Burst {
+    window_start: Option<Instant>,
+    held: Vec<String>,
+    flush_scheduled: bool,
+}
+
+thread_local! {
+    /// Set while a `silent` hotkey's action runs on this thread
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Apply `[notifications]` settings
+///
+/// The quiet state is only taken from the config at startup - on reload the
+/// menu bar toggle wins, the same way review mode survives reloads.
+pub fn set_config(config: Option<&NotificationConfig>, startup: bool) {
+    let coalesce_ms = config.map_or(DEFAULT_COALESCE_MS, |c| c.coalesce_ms);
+    COALESCE_MS.store(coalesce_ms, Ordering::SeqCst);
+    EMOJI.store(config.is_none_or(|c| c.emoji), Ordering::SeqCst);
+    if startup {
+        set_quiet(config.is_some_and(|c| c.quiet));
+    }
+
+    let mut templates = ENGLISH.clone();
+    if let Some(language) = config.and_then(|c| c.language.as_deref()) {
+        match shipped_templates(language) {
+            Ok(translated) => templates.extend(translated),
+            Err(e) => log::warn!("Notifications: {:#}", e),
+        }
+    }
+    if let Some(config) = config {
+        for id in config.templates.keys() {
+            if !ENGLISH.contains_key(id) {
+                log::warn!("Notifications: unknown template '{}'", id);
+            }
+        }
+        templates.extend(config.templates.clone());
+    }
+    *TEMPLATES.lock().unwrap() = Some(templates);
+}
+
+/// Shipped translated templates for a language code
+fn shipped_templates(language: &str) -> R<HashMap<String, String>> {
+    let text = match language.to_lowercase().as_str() {
+        "en" => return Ok(HashMap::new()),
+        "de" => include_str!("notify/de.toml"),
+        "es" => include_str!("notify/es.toml"),
+        "fr" => include_str!("notify/fr.toml"),
+        "ja" => include_str!("notify/ja.toml"),
+        other => anyhow::bail!(
+            "no shipped notification texts for '{}' (de, es, fr, ja) - use [notifications.templates]",
+            other
+        ),
+    };
+    Ok(toml::from_str(text)?)
+}
+
+/// A notification's text: template `id` with its `{name}` placeholders filled in
+pub fn text(id: &str, args: &[(&str, &str)]) -> String {
+    let template = TEMPLATES
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|templates| templates.get(id).cloned())
+        .or_else(|| ENGLISH.get(id).cloned())
+        .unwrap_or_else(|| {
+            log::warn!("No notification template '{}'", id);
+            id.to_string()
+        });
+    fill(&template, args)
+}
+
+fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+/// The message without its leading emoji, when `emoji = false`
+fn styled(message: &str) -> &str {
+    if EMOJI.load(Ordering::SeqCst) {
+        return message;
+    }
+    message
+        .trim_start_matches(|c: char| !c.is_ascii() && !c.is_alphanumeric())
+        .trim_start()
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::SeqCst)
+}
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::SeqCst);
+    log::info!("Quiet mode {}", if quiet { "ENABLED" } else { "DISABLED" });
+}
+
+/// Toggle quiet mode, returning the new state
+pub fn toggle_quiet() -> bool {
+    let quiet = !QUIET.fetch_xor(true, Ordering::SeqCst);
+    log::info!("Quiet mode {}", if quiet { "ENABLED" } else { "DISABLED" });
+    quiet
+}
+
+/// True if notifications from the current thread are being dropped
+pub fn is_silenced() -> bool {
+    SILENCED.with(Cell::get)
+}
+
+/// Drop this thread's notifications until the guard goes out of scope
+///
+/// Does nothing when `silent` is false, so callers can pass the hotkey's flag.
+pub fn silence(silent: bool) -> Silenced {
+    Silenced {
+        previous: SILENCED.with(|s| s.replace(silent || s.get())),
+    }
+}
+
+/// Restores the thread's previous silence state on drop
+pub struct Silenced {
+    previous: bool,
+}
+
+impl Drop for Silenced {
+    fn drop(&mut self) {
+        SILENCED.with(|s| s.set(self.previous));
+    }
+}
+
+/// Post a notification through the policy
+pub fn post(message: &str, deliver: Deliver) {
+    if is_quiet() {
+        log::info!("[quiet] {}", message);
+        return;
+    }
+    if is_silenced() {
+        log::info!("[silent] {}", message);
+        return;
+    }
+
+    let window = Duration::from_millis(COALESCE_MS.load(Ordering::SeqCst));
+    if window.is_zero() {
+        deliver(styled(message));
+        return;
+    }
+
+    let now = Instant::now();
+    let mut burst = BURST.lock().unwrap();
+    match burst.window_start {
+        Some(start) if now - start < window => {
+            log::debug!("Coalescing notification: {}", message);
+            burst.held.push(message.to_string());
+            if !burst.flush_scheduled {
+                burst.flush_scheduled = true;
+                let delay = window - (now - start);
+                std::thread::spawn(move || {
+                    std::thread::sleep(delay);
+                    flush(deliver);
+                });
+            }
+        }
+        _ => {
+            burst.window_start = Some(now);
+            drop(burst);
+            deliver(styled(message));
+        }
+    }
+}
+
+/// Show whatever was held back during the window as one notification
+fn flush(deliver: Deliver) {
+    let held = {
+        let mut burst = BURST.lock().unwrap();
+        burst.flush_scheduled = false;
+        // The summary opens a new window so a continuing flood stays coalesced
+        burst.window_start = Some(Instant::now());
+        std::mem::take(&mut burst.held)
+    };
+    if held.is_empty() {
+        return;
+    }
+    let message = summarize(&held);
+    if is_quiet() {
+        log::info!("[quiet] {}", message);
+        return;
+    }
+    deliver(styled(&message));
+}
+
+fn summarize(held: &[String]) -> String {
+    let [.., latest] = held else {
+        return String::new();
+    };
+    if held.len() == 1 {
+        return latest.clone();
+    }
+    let failed = held
+        .iter()
+        .filter(|m| m.starts_with('❌') || m.starts_with('💥'))
+        .count();
+    let (count, latest) = (held.len().to_string(), styled(latest));
+    if failed == 0 {
+        text("coalesced", &[("count", &count), ("latest", latest)])
+    } else {
+        text(
+            "coalesced_failed",
+            &[
+                ("count", &count),
+                ("failed", &failed.to_string()),
+                ("latest", latest),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_templates() {
+        assert_eq!(
+            fill(
+                &ENGLISH["rx_batch_stopped"],
+                &[("count", "3"), ("total", "8")]
+            ),
+            "⏹ RX batch stopped after 3 of 8 clips"
+        );
+        // Translations only use ids (and placeholders) the English table has
+        for language in ["de", "es", "fr", "ja"] {
+            for (id, template) in shipped_templates(language).unwrap() {
+                let english = ENGLISH
+                    .get(&id)
+                    .unwrap_or_else(|| panic!("{}: unknown id {}", language, id));
+                let placeholders = |text: &str| {
+                    let mut names: Vec<String> = text
+                        .split('{')
+                        .skip(1)
+                        .filter_map(|part| part.split_once('}').map(|(name, _)| name.to_string()))
+                        .collect();
+                    names.sort();
+                    names
+                };
+                assert_eq!(
+                    placeholders(&template),
+                    placeholders(english),
+                    "{} {}",
+                    language,
+                    id
+                );
+            }
+        }
+    }
+}
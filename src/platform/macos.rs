@@ -0,0 +1,276 @@
+//! macOS backend
+//!
+//! Thin wrapper over `crate::macos`: CGEventTap + Carbon hotkeys for capture,
+//! NSApplication for the menu bar and run loop, and the Swift bridge for
+//! notifications and UI automation.
+
+use super::{KeyEvent, KeyHandler, Platform};
+use crate::macos;
+use crate::prelude::*;
+use libc::c_void;
+use std::ptr;
+use std::sync::OnceLock;
+
+pub struct MacPlatform;
+
+/// Handler the event tap callback forwards decoded key events to
+static KEY_HANDLER: OnceLock<KeyHandler> = OnceLock::new();
+
+impl Platform for MacPlatform {
+    fn name(&self) -> &'static str {
+        "macos"
+    }
+
+    fn ensure_permissions(&self) -> R<()> {
+        macos::permissions::ensure_permissions_granted()
+    }
+
+    fn check_action_permissions(&self, action_name: &str) -> R<()> {
+        macos::permissions::check_action(action_name)
+    }
+
+    fn run_event_loop(&self, handler: KeyHandler) -> R<()> {
+        KEY_HANDLER
+            .set(handler)
+            .map_err(|_| anyhow::anyhow!("Event loop already running"))?;
+
+        let mut timer = crate::startup::StageTimer::new();
+
+        // Register Carbon hotkeys for those marked with carbon=true
+        // These will work during secure input when CGEventTap is disabled
+        log::info!("Registering Carbon hotkeys...");
+        if let Err(e) = macos::carbon_hotkeys::register_carbon_hotkeys() {
+            log::error!("Failed to register Carbon hotkeys: {:#}", e);
+            log::warn!("Carbon hotkeys will not be available during secure input");
+        }
+        timer.mark("Carbon hotkeys");
+
+        // Initialize NSApplication for menu bar (must be done before event loop)
+        // Create and install event tap for keyboard events
+        unsafe {
+            use objc2::runtime::AnyObject;
+            use objc2::{class, msg_send};
+
+            log::info!("Initializing NSApplication for menu bar...");
+
+            let ns_app_class = class!(NSApplication);
+            let ns_app: *mut AnyObject = msg_send![ns_app_class, sharedApplication];
+
+            if ns_app.is_null() {
+                anyhow::bail!("Failed to get NSApplication");
+            }
+
+            // Set activation policy to Accessory (menu bar only, no dock icon)
+            // NSApplicationActivationPolicyAccessory = 1
+            let policy: isize = 1;
+            let success: bool = msg_send![ns_app, setActivationPolicy: policy];
+
+            if !success {
+                log::warn!("Failed to set activation policy - menu bar may not work correctly");
+            }
+
+            log::info!("NSApplication initialized as menu bar app (no dock icon)");
+
+            // Create menu bar status item with reload callback
+            // Keep it alive for the duration of the program by not dropping it
+            let _menu_bar = macos::menubar::create_menu_bar(None, || {
+                log::info!("Reload Config triggered from menu");
+                // Call the reload_config command (it notifies on failure)
+                if macos::commands::reload_config(&crate::config::Params::new(
+                    std::collections::HashMap::new(),
+                ))
+                .is_ok()
+                {
+                    OS::show_notification(&crate::notify::text("config_reloaded", &[]));
+                }
+            })
+            .context("Failed to create menu bar")?;
+
+            log::info!("Menu bar icon created successfully");
+            timer.mark("Menu bar");
+
+            // Create event tap
+            let event_tap = macos::create_keyboard_event_tap(key_event_callback)
+                .context("Failed to create event tap")?;
+
+            macos::install_event_tap_on_run_loop(event_tap, key_event_callback);
+            timer.mark("Event tap");
+
+            log::info!("Hotkey daemon is running. Listening for hotkeys...");
+            crate::startup::ready();
+
+            // Activate the application so it can receive events
+            let _: () = msg_send![ns_app, activateIgnoringOtherApps: true];
+
+            // Run NSApplication's event loop (blocks forever)
+            // This is required for menu bar items to work properly
+            // The _menu_bar variable stays in scope and won't be dropped
+            let _: () = msg_send![ns_app, run];
+        }
+
+        Ok(())
+    }
+
+    fn show_notification(&self, message: &str) {
+        macos::show_notification(message);
+    }
+
+    fn show_text_window(&self, text: &str) -> R<()> {
+        // AppKit windows must be created on the main thread, and actions run
+        // on worker threads - hop over if needed
+        if is_main_thread() {
+            return unsafe { macos::MacOSSession::global().show_text_window(text) };
+        }
+        let text = text.to_string();
+        unsafe {
+            macos::dispatch_to_main_queue(move || {
+                if let Err(e) = macos::MacOSSession::global().show_text_window(&text) {
+                    log::error!("Text window error: {:#}", e);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn keystroke(&self, keys: &[&str]) -> R<()> {
+        macos::keystroke(keys)
+    }
+
+    fn type_text(&self, text: &str) -> R<()> {
+        macos::type_text(text, true)
+    }
+
+    fn current_app(&self) -> R<String> {
+        macos::get_current_app()
+    }
+
+    fn app_window(&self) -> R<String> {
+        macos::get_app_window()
+    }
+
+    fn is_in_text_field(&self) -> bool {
+        macos::is_in_text_field()
+    }
+
+    fn focus_app(&self, app_name: &str) -> R<()> {
+        macos::focus_app(app_name, "", true, false, 500)
+    }
+
+    fn focus_window(&self, app_name: &str, window_title: &str) -> R<()> {
+        macos::focus_app(app_name, window_title, true, false, 1000)
+    }
+
+    fn menu_click(&self, app_name: &str, menu_path: &[&str]) -> R<()> {
+        macos::menu_click(app_name, menu_path)
+    }
+}
+
+fn is_main_thread() -> bool {
+    use objc2::runtime::AnyClass;
+    use objc2::{class, msg_send};
+
+    let thread_class: &AnyClass = class!(NSThread);
+    unsafe { msg_send![thread_class, isMainThread] }
+}
+
+// ============================================================================
+// Event Tap Callback
+// ============================================================================
+
+/// Forward a decoded key event, returning true if it should be consumed
+fn dispatch(event: KeyEvent) -> bool {
+    KEY_HANDLER.get().is_some_and(|handler| handler(event))
+}
+
+// Event tap callback - decodes CGEvents into KeyEvents for the shared handler
+unsafe extern "C" fn key_event_callback(
+    _proxy: *mut c_void,
+    event_type: u32,
+    event: *mut c_void,
+    _user_info: *mut c_void,
+) -> *mut c_void {
+    // Check if this event was created by our app - if so, pass it through
+    const APP_EVENT_MARKER: i64 = 0x5054484B44;
+    const CG_EVENT_FIELD_EVENT_SOURCE_USER_DATA: u32 = 127;
+
+    let user_data =
+        unsafe { macos::CGEventGetIntegerValueField(event, CG_EVENT_FIELD_EVENT_SOURCE_USER_DATA) };
+    if user_data == APP_EVENT_MARKER {
+        return event; // Pass through events we created
+    }
+
+    // Handle event tap disable events (types 14/15)
+    if event_type == macos::CG_EVENT_TAP_DISABLED_BY_TIMEOUT
+        || event_type == macos::CG_EVENT_TAP_DISABLED_BY_USER_INPUT
+    {
+        log::warn!(
+            "Event tap disabled by macOS (type {}). Attempting recovery...",
+            event_type
+        );
+
+        if let Err(e) = macos::recreate_event_tap_if_needed() {
+            log::error!("Failed to recreate event tap: {}", e);
+        } else {
+            log::info!("Event tap successfully recovered");
+        }
+
+        return event;
+    }
+
+    if event_type == macos::CG_EVENT_SCROLL_WHEEL {
+        let lines = unsafe {
+            macos::CGEventGetDoubleValueField(
+                event,
+                macos::CG_EVENT_FIELD_SCROLL_WHEEL_FIXED_PT_DELTA_AXIS_1,
+            )
+        };
+        if lines == 0.0 {
+            return event; // Horizontal only
+        }
+        return if dispatch(KeyEvent::Scroll(lines)) {
+            ptr::null_mut()
+        } else {
+            event
+        };
+    }
+
+    let key_code = unsafe {
+        macos::CGEventGetIntegerValueField(event, macos::CG_EVENT_FIELD_KEYBOARD_EVENT_KEYCODE)
+    } as u16;
+
+    let key_event = if event_type == macos::CG_EVENT_KEY_DOWN {
+        KeyEvent::KeyDown(key_code)
+    } else if event_type == macos::CG_EVENT_KEY_UP {
+        KeyEvent::KeyUp(key_code)
+    } else if event_type == macos::CG_EVENT_FLAGS_CHANGED {
+        // Modifier key pressed or released
+        let flags = unsafe { macos::CGEventGetFlags(event) };
+
+        // Determine if this is a press or release based on the flags
+        let is_pressed = match key_code {
+            KEY_CMD_LEFT | KEY_CMD_RIGHT => (flags & macos::CG_EVENT_FLAG_MASK_COMMAND) != 0,
+            KEY_SHIFT_LEFT | KEY_SHIFT_RIGHT => (flags & macos::CG_EVENT_FLAG_MASK_SHIFT) != 0,
+            KEY_OPTION_LEFT | KEY_OPTION_RIGHT => {
+                (flags & macos::CG_EVENT_FLAG_MASK_ALTERNATE) != 0
+            }
+            KEY_CONTROL_LEFT | KEY_CONTROL_RIGHT => {
+                (flags & macos::CG_EVENT_FLAG_MASK_CONTROL) != 0
+            }
+            _ => return event, // Unknown modifier
+        };
+        if is_pressed {
+            KeyEvent::KeyDown(key_code)
+        } else {
+            KeyEvent::KeyUp(key_code)
+        }
+    } else {
+        // Pass through other events
+        return event;
+    };
+
+    if dispatch(key_event) {
+        ptr::null_mut() // Consume event
+    } else {
+        event
+    }
+}
@@ -0,0 +1,122 @@
+//! Platform abstraction layer
+//!
+//! Everything the shared daemon code needs from the host OS goes through the
+//! `Platform` trait: event capture, notifications, keystroke injection and UI
+//! automation. macOS gets the real backend; every other target gets a stub so
+//! the config, matcher and MIDI logic can be built and tested on Linux CI.
+
+use crate::prelude::*;
+
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(not(target_os = "macos"))]
+mod stub;
+
+/// A keyboard (or scroll) event in platform-neutral form (macOS virtual key codes)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyEvent {
+    KeyDown(u16),
+    KeyUp(u16),
+    /// Vertical scroll in lines, positive = up (fractional from trackpads)
+    Scroll(f64),
+}
+
+/// Called for every captured key or scroll event; return true to consume the event
+pub type KeyHandler = fn(KeyEvent) -> bool;
+
+pub trait Platform: Send + Sync {
+    /// Short backend name for logging
+    fn name(&self) -> &'static str;
+
+    // ------------------------------------------------------------------------
+    // Event Capture
+    // ------------------------------------------------------------------------
+
+    /// Block until the permissions needed for capture and automation are granted
+    fn ensure_permissions(&self) -> R<()>;
+
+    /// Check the permissions an action needs before running it
+    fn check_action_permissions(&self, action_name: &str) -> R<()>;
+
+    /// Install keyboard and scroll capture and run the platform event loop (blocks forever)
+    fn run_event_loop(&self, handler: KeyHandler) -> R<()>;
+
+    // ------------------------------------------------------------------------
+    // Notifications
+    // ------------------------------------------------------------------------
+
+    fn show_notification(&self, message: &str);
+
+    /// Show a block of text in a window (used by MessageLog)
+    fn show_text_window(&self, text: &str) -> R<()>;
+
+    // ------------------------------------------------------------------------
+    // Keystroke Injection
+    // ------------------------------------------------------------------------
+
+    /// Send a key combination, e.g. `["cmd", "shift", "s"]`
+    fn keystroke(&self, keys: &[&str]) -> R<()>;
+
+    fn type_text(&self, text: &str) -> R<()>;
+
+    // ------------------------------------------------------------------------
+    // UI Automation
+    // ------------------------------------------------------------------------
+
+    /// Name of the frontmost application
+    fn current_app(&self) -> R<String>;
+
+    /// Title of the frontmost application's focused window
+    fn app_window(&self) -> R<String>;
+
+    /// True if keyboard focus is in an editable text field
+    fn is_in_text_field(&self) -> bool;
+
+    fn focus_app(&self, app_name: &str) -> R<()>;
+
+    /// Bring an app to the front and wait until the given window (empty = any) is focused
+    fn focus_window(&self, app_name: &str, window_title: &str) -> R<()>;
+
+    fn menu_click(&self, app_name: &str, menu_path: &[&str]) -> R<()>;
+}
+
+/// The backend for the target this binary was built for
+pub fn current() -> &'static dyn Platform {
+    #[cfg(target_os = "macos")]
+    {
+        &macos::MacPlatform
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        &stub::StubPlatform
+    }
+}
+
+/// Frontmost app and window, recorded so they can be brought back later
+#[derive(Debug, Clone)]
+pub struct FocusSnapshot {
+    pub app: String,
+    pub window: String,
+}
+
+impl FocusSnapshot {
+    /// Record what's frontmost now (None if it can't be determined)
+    pub fn capture() -> Option<Self> {
+        let platform = current();
+        let app = platform.current_app().ok()?;
+        let window = platform.app_window().unwrap_or_default();
+        Some(Self { app, window })
+    }
+
+    /// Bring the recorded app and window back to the front if focus moved
+    pub fn restore(&self) -> R<()> {
+        let platform = current();
+        let app = platform.current_app().unwrap_or_default();
+        let window = platform.app_window().unwrap_or_default();
+        if app == self.app && window == self.window {
+            return Ok(());
+        }
+        log::info!("Restoring focus to {} ({})", self.app, self.window);
+        platform.focus_window(&self.app, &self.window)
+    }
+}
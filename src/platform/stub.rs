@@ -0,0 +1,80 @@
+//! Stub backend for non-macOS targets
+//!
+//! Lets the daemon build and run its config, matcher and MIDI logic anywhere.
+//! There is no keyboard capture; notifications and text windows go to the
+//! log, and automation calls fail with a clear error.
+
+use super::{KeyHandler, Platform};
+use crate::prelude::*;
+
+pub struct StubPlatform;
+
+fn unsupported<T>(what: &str) -> R<T> {
+    anyhow::bail!("{} is not supported on {}", what, std::env::consts::OS)
+}
+
+impl Platform for StubPlatform {
+    fn name(&self) -> &'static str {
+        "stub"
+    }
+
+    fn ensure_permissions(&self) -> R<()> {
+        Ok(())
+    }
+
+    fn check_action_permissions(&self, _action_name: &str) -> R<()> {
+        Ok(())
+    }
+
+    fn run_event_loop(&self, _handler: KeyHandler) -> R<()> {
+        log::warn!(
+            "No keyboard capture on {} - only MIDI hotkeys will fire",
+            std::env::consts::OS
+        );
+        crate::startup::ready();
+        loop {
+            std::thread::park();
+        }
+    }
+
+    fn show_notification(&self, message: &str) {
+        crate::notify::post(message, |message| log::info!("[notification] {}", message));
+    }
+
+    fn show_text_window(&self, text: &str) -> R<()> {
+        log::info!("[text window]\n{}", text);
+        Ok(())
+    }
+
+    fn keystroke(&self, _keys: &[&str]) -> R<()> {
+        unsupported("Keystroke injection")
+    }
+
+    fn type_text(&self, _text: &str) -> R<()> {
+        unsupported("Typing text")
+    }
+
+    fn current_app(&self) -> R<String> {
+        unsupported("Frontmost app lookup")
+    }
+
+    fn app_window(&self) -> R<String> {
+        unsupported("Window lookup")
+    }
+
+    fn is_in_text_field(&self) -> bool {
+        false
+    }
+
+    fn focus_app(&self, _app_name: &str) -> R<()> {
+        unsupported("Focusing apps")
+    }
+
+    fn focus_window(&self, _app_name: &str, _window_title: &str) -> R<()> {
+        unsupported("Focusing windows")
+    }
+
+    fn menu_click(&self, _app_name: &str, _menu_path: &[&str]) -> R<()> {
+        unsupported("Menu automation")
+    }
+}
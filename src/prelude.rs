@@ -1,5 +1,6 @@
 pub use crate::config::Params;
 pub use crate::input::*;
+#[cfg(target_os = "macos")]
 pub use crate::macos as OS;
 pub use anyhow::Context;
 pub use anyhow::Result as R;
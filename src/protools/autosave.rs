@@ -0,0 +1,99 @@
+//! Idle-aware auto-save
+//!
+//! Pro Tools' native auto-save fires on a timer, which can hiccup playback.
+//! This saves the session via PTSL only when the user has been idle for a
+//! while AND the transport is stopped.
+
+use super::TOKIO_RT;
+use super::client::ProtoolsSession;
+use crate::config::AutoSaveConfig;
+use crate::prelude::*;
+use std::sync::{Mutex, Once};
+use std::time::{Duration, Instant};
+
+/// Active auto-save settings (None = disabled). Replaced on config reload.
+static AUTO_SAVE_CONFIG: Mutex<Option<AutoSaveConfig>> = Mutex::new(None);
+
+static START: Once = Once::new();
+
+/// How often the background loop wakes up
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Back-off after a failed save (e.g. Pro Tools not running)
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Apply auto-save settings from config (None or enabled = false disables it)
+pub fn set_config(config: Option<AutoSaveConfig>) {
+    let config = config.filter(|c| c.enabled);
+    match &config {
+        Some(c) => log::info!(
+            "Auto-save enabled: every {} min when idle for {}s and transport stopped",
+            c.interval_minutes,
+            c.idle_seconds
+        ),
+        None => log::info!("Auto-save disabled"),
+    }
+    *AUTO_SAVE_CONFIG.lock().unwrap() = config;
+}
+
+/// Start the background auto-save loop (safe to call more than once)
+///
+/// Requires `init_runtime()` to have been called first.
+pub fn start() {
+    START.call_once(|| {
+        TOKIO_RT.get().unwrap().spawn(auto_save_loop());
+    });
+}
+
+async fn auto_save_loop() {
+    let mut last_save = Instant::now();
+    let mut retry_at: Option<Instant> = None;
+
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let config = AUTO_SAVE_CONFIG.lock().unwrap().clone();
+        let Some(config) = config else {
+            continue;
+        };
+
+        if last_save.elapsed() < Duration::from_secs(config.interval_minutes * 60) {
+            continue;
+        }
+        if retry_at.is_some_and(|t| Instant::now() < t) {
+            continue;
+        }
+        if OS::seconds_since_last_input() < config.idle_seconds as f64 {
+            continue;
+        }
+
+        match try_save().await {
+            Ok(true) => {
+                log::info!("✅ Auto-saved Pro Tools session");
+                last_save = Instant::now();
+                retry_at = None;
+            }
+            Ok(false) => {
+                // Transport is rolling - check again after another idle period
+                retry_at = Some(Instant::now() + Duration::from_secs(config.idle_seconds));
+            }
+            Err(e) => {
+                log::warn!("Auto-save failed: {:#}", e);
+                retry_at = Some(Instant::now() + RETRY_DELAY);
+            }
+        }
+    }
+}
+
+/// Save the session if the transport is stopped
+///
+/// Returns Ok(false) if the save was skipped because the transport is running
+async fn try_save() -> R<bool> {
+    let mut pt = ProtoolsSession::shared().await?;
+    if !pt.is_transport_stopped().await? {
+        log::debug!("Auto-save skipped - transport is running");
+        return Ok(false);
+    }
+    pt.save_session().await?;
+    Ok(true)
+}
@@ -1,17 +1,88 @@
 use super::ptsl;
 use anyhow::{Context, Result};
 use ptsl::*;
+use std::sync::Mutex;
 use tonic::Request as TonicRequest;
 use tonic::transport::Channel;
 
+/// Registered connection shared by every action (None until first use)
+///
+/// The gRPC channel multiplexes requests, so concurrent actions can each
+/// hold a clone. Two actions racing to make the first connection may both
+/// register; the later one is kept.
+static SHARED: Mutex<Option<ProtoolsSession>> = Mutex::new(None);
+
+#[derive(Clone)]
 pub struct ProtoolsSession {
     client: ptsl::ptsl_client::PtslClient<Channel>,
     session_id: String,
 }
 
+/// Forget the shared connection so the next action registers a fresh one
+///
+/// Call when Pro Tools quits - its session id dies with it.
+pub fn disconnect() {
+    if SHARED.lock().unwrap().take().is_some() {
+        log::info!("Dropped shared Pro Tools connection");
+    }
+}
+
+/// True while a registered connection is held
+pub fn is_connected() -> bool {
+    SHARED.lock().unwrap().is_some()
+}
+
+/// Commands the background loops send several times a second (transport
+/// cache, tally); their requests are only logged at trace level
+fn is_polled(command_id: CommandId) -> bool {
+    matches!(
+        command_id,
+        CommandId::GetTransportState | CommandId::GetTransportArmed
+    )
+}
+
+/// Commands whose effect goes onto Pro Tools' undo queue
+fn is_undoable(command_id: CommandId) -> bool {
+    use CommandId::*;
+    matches!(
+        command_id,
+        TrimToSelection
+            | CreateFadesBasedOnPreset
+            | RenameTargetTrack
+            | ConsolidateClip
+            | Cut
+            | Paste
+            | Clear
+            | CutSpecial
+            | ClearSpecial
+            | PasteSpecial
+            | Spot
+            | RenameSelectedClip
+            | RenameTargetClip
+            | CreateNewTracks
+            | GroupClips
+            | UngroupClips
+            | UngroupAllClips
+            | RegroupClips
+            | RepeatSelection
+            | DuplicateSelection
+            | SetSessionStartTime
+    )
+}
+
 impl ProtoolsSession {
+    /// The shared connection, connecting and registering if there isn't one
+    pub async fn shared() -> Result<Self> {
+        if let Some(pt) = SHARED.lock().unwrap().clone() {
+            return Ok(pt);
+        }
+        let pt = Self::new().await?;
+        *SHARED.lock().unwrap() = Some(pt.clone());
+        Ok(pt)
+    }
+
     pub async fn new() -> Result<Self> {
-        println!("Connecting to Pro Tools...");
+        log::debug!("Connecting to Pro Tools...");
 
         let channel = Channel::from_static("http://localhost:31416")
             .connect()
@@ -23,19 +94,23 @@ impl ProtoolsSession {
             session_id: String::new(),
         };
 
-        let session_data: ptsl::RegisterConnectionResponseBody = s
-            .cmd(
-                CommandId::RegisterConnection,
-                ptsl::RegisterConnectionRequestBody {
-                    company_name: "Feral Frequencies".to_string(),
-                    application_name: "pt-cli".to_string(),
-                },
-            )
-            .await?;
+        // Sent directly: `cmd` reconnects through here on failure
+        let body = serde_json::to_string(&ptsl::RegisterConnectionRequestBody {
+            company_name: "Feral Frequencies".to_string(),
+            application_name: "pt-cli".to_string(),
+        })?;
+        let response = s.send(CommandId::RegisterConnection, &body).await?;
+        let session_data: ptsl::RegisterConnectionResponseBody =
+            serde_json::from_str(&response.response_body_json).with_context(|| {
+                format!(
+                    "Pro Tools refused the connection: {}",
+                    response.response_error_json
+                )
+            })?;
 
         s.session_id = session_data.session_id;
 
-        println!("\n✅ Connected! Session ID: {}", s.session_id);
+        log::info!("✅ Connected to Pro Tools (session {})", s.session_id);
 
         Ok(s)
     }
@@ -46,8 +121,65 @@ impl ProtoolsSession {
         TResp: serde::de::DeserializeOwned,
     {
         let body_json = serde_json::to_string(&body)?;
-        eprintln!("Request body JSON: {}", body_json);
+        let level = if is_polled(command_id) {
+            log::Level::Trace
+        } else {
+            log::Level::Debug
+        };
+        log::log!(level, "{} request: {}", command_id.as_str_name(), body_json);
+
+        let response = match self.send(command_id, &body_json).await {
+            Ok(response) => response,
+            // The shared connection went stale (Pro Tools restarted) - the
+            // request never reached it, so reconnect and send it once more
+            Err(status) if status.code() == tonic::Code::Unavailable => {
+                log::warn!(
+                    "Pro Tools connection lost ({}), reconnecting",
+                    status.message()
+                );
+                disconnect();
+                *self = Self::shared().await?;
+                self.send(command_id, &body_json).await?
+            }
+            Err(status) => return Err(status.into()),
+        };
+
+        // Check for errors
+        if !response.response_error_json.is_empty() {
+            // The polling loops log their own failures, at a rate they choose
+            let level = if is_polled(command_id) {
+                log::Level::Debug
+            } else {
+                log::Level::Warn
+            };
+            log::log!(
+                level,
+                "{} error: {}",
+                command_id.as_str_name(),
+                response.response_error_json
+            );
+            anyhow::bail!(
+                "{}: {}",
+                command_id.as_str_name(),
+                response.response_error_json
+            );
+        }
+        if is_undoable(command_id) {
+            crate::undo::operation(command_id.as_str_name());
+        }
+
+        if response.response_body_json.is_empty() {
+            Ok(serde_json::from_str("{}")?)
+        } else {
+            Ok(serde_json::from_str(&response.response_body_json)?)
+        }
+    }
 
+    async fn send(
+        &mut self,
+        command_id: CommandId,
+        body_json: &str,
+    ) -> std::result::Result<Response, tonic::Status> {
         let request = Request {
             header: Some(RequestHeader {
                 task_id: String::new(),
@@ -58,25 +190,13 @@ impl ProtoolsSession {
                 version_revision: 0,
                 versioned_request_header_json: String::new(),
             }),
-            request_body_json: body_json,
+            request_body_json: body_json.to_string(),
         };
-
-        let response = self
+        Ok(self
             .client
             .send_grpc_request(TonicRequest::new(request))
             .await?
-            .into_inner();
-
-        // Check for errors
-        if !response.response_error_json.is_empty() {
-            eprintln!("Pro Tools Error: {}", response.response_error_json);
-        }
-
-        if response.response_body_json.is_empty() {
-            Ok(serde_json::from_str("{}")?)
-        } else {
-            Ok(serde_json::from_str(&response.response_body_json)?)
-        }
+            .into_inner())
     }
 
     pub async fn get_session_name(&mut self) -> Result<String> {
@@ -99,6 +219,31 @@ impl ProtoolsSession {
 
         Ok(std::path::PathBuf::from(path))
     }
+    /// Session info text (File > Export > Session Info as Text) with track
+    /// EDLs and plug-in lists, returned as a string instead of a file
+    pub async fn export_session_info_text(&mut self) -> Result<String> {
+        let response: serde_json::Value = self
+            .cmd(
+                CommandId::ExportSessionInfoAsText,
+                serde_json::json!({
+                    "include_file_list": false,
+                    "include_clip_list": false,
+                    "include_markers": false,
+                    "include_plugin_list": true,
+                    "include_track_edls": true,
+                    "track_list_type": "TListType_AllTracks",
+                    "fade_handling_type": "FHType_DontShowCrossfades",
+                    "text_as_file_format": "TFFormat_UTF8",
+                    "output_type": "ESIOType_String"
+                }),
+            )
+            .await?;
+
+        response["session_info"]
+            .as_str()
+            .map(str::to_string)
+            .context("Pro Tools returned no session info")
+    }
     pub async fn save_session(&mut self) -> Result<()> {
         let _response: serde_json::Value = self
             .cmd(CommandId::SaveSession, serde_json::json!({}))
@@ -121,6 +266,15 @@ impl ProtoolsSession {
         println!("Session Saved");
         Ok(())
     }
+    pub async fn open_session(&mut self, path: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .cmd(
+                CommandId::OpenSession,
+                serde_json::json!({ "session_path": path }),
+            )
+            .await?;
+        Ok(())
+    }
     pub async fn clear(&mut self) -> Result<()> {
         let _response: serde_json::Value =
             self.cmd(CommandId::Clear, serde_json::json!({})).await?;
@@ -203,6 +357,260 @@ impl ProtoolsSession {
 
         response["memory_locations"].as_array().cloned()
     }
+    /// Names of all explicitly selected tracks, in track order
+    pub async fn get_selected_track_names(&mut self) -> Result<Vec<String>> {
+        let tracks = self
+            .get_all_tracks()
+            .await
+            .context("Failed to get track list")?;
+
+        Ok(tracks
+            .iter()
+            .filter(|track| {
+                track["track_attributes"]["is_selected"].as_str() == Some("SetExplicitly")
+            })
+            .filter_map(|track| track["name"].as_str().map(|s| s.to_string()))
+            .collect())
+    }
+
+    /// Names of all tracks, in track order
+    pub async fn get_all_track_names(&mut self) -> Result<Vec<String>> {
+        let tracks = self
+            .get_all_tracks()
+            .await
+            .context("Failed to get track list")?;
+
+        Ok(tracks
+            .iter()
+            .filter_map(|track| track["name"].as_str().map(|s| s.to_string()))
+            .collect())
+    }
+
+    /// Undo the last `levels` operations, returning their names (Pro Tools 2024.06+)
+    pub async fn undo(&mut self, levels: i32) -> Result<Vec<String>> {
+        let response: serde_json::Value = self
+            .cmd(CommandId::Undo, ptsl::UndoRequestBody { levels })
+            .await?;
+        Ok(response["operations"]
+            .as_array()
+            .map(|operations| {
+                operations
+                    .iter()
+                    .filter_map(|op| op["operation"].as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Color tracks with a color palette index
+    pub async fn set_track_color(&mut self, names: Vec<String>, color_index: i32) -> Result<()> {
+        let _: serde_json::Value = self
+            .cmd(
+                CommandId::CIdSetTrackColor,
+                ptsl::SetTrackColorRequestBody {
+                    track_names: names,
+                    color_index,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Hex colors of the track color palette, by color index
+    ///
+    /// Empty before Pro Tools 2025.10, which also can't color tracks over PTSL.
+    pub async fn get_track_color_palette(&mut self) -> Result<Vec<String>> {
+        let response: serde_json::Value = self
+            .cmd(
+                CommandId::CIdGetColorPalette,
+                serde_json::json!({ "color_palette_target": "CPTarget_Tracks" }),
+            )
+            .await?;
+        Ok(response["color_list"]
+            .as_array()
+            .map(|colors| {
+                colors
+                    .iter()
+                    .filter_map(|color| color.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    pub async fn rename_track(&mut self, current_name: &str, new_name: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .cmd(
+                CommandId::RenameTargetTrack,
+                ptsl::RenameTargetTrackRequestBody {
+                    current_name: current_name.to_string(),
+                    new_name: new_name.to_string(),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Select tracks by name, replacing the current track selection
+    pub async fn select_tracks_by_name(&mut self, names: Vec<String>) -> Result<()> {
+        let _: serde_json::Value = self
+            .cmd(
+                CommandId::SelectTracksByName,
+                serde_json::json!({
+                    "track_names": names,
+                    "selection_mode": "SM_Replace",
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_clip_list(&mut self) -> Option<Vec<serde_json::Value>> {
+        let response: serde_json::Value = self
+            .cmd(
+                CommandId::CIdGetClipList,
+                serde_json::json!({
+                    "pagination_request": {
+                        "limit": 0,
+                        "offset": 0,
+                    },
+                }),
+            )
+            .await
+            .ok()?;
+
+        response["clip_list"].as_array().cloned()
+    }
+
+    /// Spot clips onto a track, placing the clip start at `start_samples`
+    pub async fn spot_clips_by_id(
+        &mut self,
+        clip_ids: Vec<String>,
+        track_name: &str,
+        start_samples: i64,
+    ) -> Result<()> {
+        let _: serde_json::Value = self
+            .cmd(
+                CommandId::CIdSpotClipsById,
+                serde_json::json!({
+                    "src_clips": clip_ids,
+                    "dst_track_name": track_name,
+                    "dst_location_data": {
+                        "location_type": "SLType_Start",
+                        "location": {
+                            "location": start_samples.to_string(),
+                            "time_type": "TLType_Samples",
+                        },
+                    },
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Clip boundaries (start, end) in samples on a track's target playlist
+    pub async fn get_clip_boundaries(&mut self, track_name: &str) -> Result<Vec<(i64, i64)>> {
+        let clips = self.get_playlist_clips(track_name).await?;
+        Ok(clips
+            .into_iter()
+            .map(|(start, end, _)| (start, end))
+            .collect())
+    }
+
+    /// Clips (start, end, clip ID) in samples on a track's target playlist, in time order
+    ///
+    /// The ID is the first channel's clip; look names up with `get_clip_list`.
+    pub async fn get_playlist_clips(
+        &mut self,
+        track_name: &str,
+    ) -> Result<Vec<(i64, i64, String)>> {
+        let response: serde_json::Value = self
+            .cmd(
+                CommandId::CIdGetTrackPlaylists,
+                serde_json::json!({ "track_name": track_name }),
+            )
+            .await?;
+        let playlist = response["playlists"]
+            .as_array()
+            .and_then(|playlists| {
+                playlists
+                    .iter()
+                    .find(|p| p["is_target"].as_bool().unwrap_or(false))
+                    .or_else(|| playlists.first())
+            })
+            .and_then(|p| p["playlist_name"].as_str())
+            .with_context(|| format!("No playlist on '{}'", track_name))?
+            .to_string();
+
+        let response: serde_json::Value = self
+            .cmd(
+                CommandId::CIdGetPlaylistElements,
+                serde_json::json!({
+                    "playlist_name": playlist,
+                    "time_format": "TLType_Samples",
+                    "pagination_request": {
+                        "limit": 0,
+                        "offset": 0,
+                    },
+                }),
+            )
+            .await?;
+
+        let sample = |element: &serde_json::Value, key: &str| {
+            element[key]["location"]
+                .as_str()
+                .and_then(|s| s.parse::<i64>().ok())
+        };
+        let mut clips: Vec<(i64, i64, String)> = response["elements_list"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|element| {
+                let clip_id = element["channel_clips"][0]["clip_id"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                Some((
+                    sample(element, "start_time")?,
+                    sample(element, "end_time")?,
+                    clip_id,
+                ))
+            })
+            .collect();
+        clips.sort();
+        Ok(clips)
+    }
+
+    /// Import audio files into the Clips list, returning the new clip IDs
+    pub async fn import_audio_to_clip_list(&mut self, files: Vec<String>) -> Result<Vec<String>> {
+        let response: serde_json::Value = self
+            .cmd(
+                CommandId::CIdImportAudioToClipList,
+                serde_json::json!({
+                    "file_list": files,
+                    "audio_operations": "AOperations_Default",
+                }),
+            )
+            .await?;
+
+        if let Some(failure) = response["failure_list"].as_array().and_then(|f| f.first()) {
+            return Err(anyhow::anyhow!("Import failed: {}", failure));
+        }
+
+        let clip_ids = response["file_list"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry["destination_file_list"].as_array())
+            .flatten()
+            .filter_map(|file| file["clip_id_list"].as_array())
+            .flatten()
+            .filter_map(|id| id.as_str().map(String::from))
+            .collect();
+        Ok(clip_ids)
+    }
+
     pub async fn get_used_marker_ruler_names(&mut self) -> Option<Vec<String>> {
         let markers = self.get_all_markers().await?;
 
@@ -238,6 +646,66 @@ impl ProtoolsSession {
         }
         Ok(())
     }
+    /// Mute (or unmute) tracks
+    pub async fn mute_tracks(&mut self, tracks: Vec<String>, state: bool) -> Result<()> {
+        if !tracks.is_empty() {
+            let _: serde_json::Value = self
+                .cmd(
+                    CommandId::SetTrackMuteState,
+                    ptsl::SetTrackMuteStateRequestBody {
+                        track_names: tracks,
+                        enabled: state,
+                    },
+                )
+                .await?;
+        }
+        Ok(())
+    }
+    /// Record-arm (or disarm) tracks
+    pub async fn record_arm_tracks(&mut self, tracks: Vec<String>, state: bool) -> Result<()> {
+        if !tracks.is_empty() {
+            let _: serde_json::Value = self
+                .cmd(
+                    CommandId::SetTrackRecordEnableState,
+                    ptsl::SetTrackRecordEnableStateRequestBody {
+                        track_names: tracks,
+                        enabled: state,
+                    },
+                )
+                .await?;
+        }
+        Ok(())
+    }
+    /// Turn input monitoring on (or off) for tracks
+    pub async fn input_monitor_tracks(&mut self, tracks: Vec<String>, state: bool) -> Result<()> {
+        if !tracks.is_empty() {
+            let _: serde_json::Value = self
+                .cmd(
+                    CommandId::SetTrackInputMonitorState,
+                    ptsl::SetTrackInputMonitorStateRequestBody {
+                        track_names: tracks,
+                        enabled: state,
+                    },
+                )
+                .await?;
+        }
+        Ok(())
+    }
+    /// Hide (or show) tracks in the Edit and Mix windows
+    pub async fn hide_tracks(&mut self, tracks: Vec<String>, hidden: bool) -> Result<()> {
+        if !tracks.is_empty() {
+            let _: serde_json::Value = self
+                .cmd(
+                    CommandId::SetTrackHiddenState,
+                    ptsl::SetTrackHiddenStateRequestBody {
+                        track_names: tracks,
+                        enabled: hidden,
+                    },
+                )
+                .await?;
+        }
+        Ok(())
+    }
     pub async fn get_samplerate(&mut self) -> Result<i64> {
         let response: serde_json::Value = self
             .cmd(CommandId::GetSessionSampleRate, serde_json::json!({}))
@@ -273,6 +741,31 @@ impl ProtoolsSession {
         Ok(rate)
     }
 
+    pub async fn get_transport_state(&mut self) -> Result<String> {
+        let response: serde_json::Value = self
+            .cmd(CommandId::GetTransportState, serde_json::json!({}))
+            .await?;
+
+        let state = response["current_setting"]
+            .as_str()
+            .context("Transport state missing from response")?;
+        Ok(state.to_string())
+    }
+
+    /// True when the transport's record button is armed
+    pub async fn get_transport_armed(&mut self) -> Result<bool> {
+        let response: serde_json::Value = self
+            .cmd(CommandId::GetTransportArmed, serde_json::json!({}))
+            .await?;
+        Ok(response["is_transport_armed"].as_bool().unwrap_or(false))
+    }
+
+    /// True when the transport is fully stopped (not playing, recording, cueing, etc.)
+    pub async fn is_transport_stopped(&mut self) -> Result<bool> {
+        let state = self.get_transport_state().await?;
+        Ok(state.ends_with("TransportStopped"))
+    }
+
     pub async fn get_edit_mode(&mut self) -> Result<String> {
         let response: serde_json::Value = self
             .cmd(CommandId::GetEditMode, serde_json::json!({}))
@@ -326,25 +819,7 @@ impl ProtoolsSession {
         destination_name: &str,
         color: &str,
     ) -> Result<()> {
-        let color_index = match color.to_lowercase().as_str() {
-            "dark purple" => 1,
-            "purple" => 2,
-            "pink" => 3,
-            "magenta" => 4,
-            "red" => 5,
-            "orange" => 6,
-            "dark yellow" => 7,
-            "yellow" => 8,
-            "light green" => 9,
-            "green" => 10,
-            "light blue" => 11,
-            "blue" => 12,
-            "dark blue" => 13,
-            "white" => 14,
-            "grey" => 15,
-            "black" => 16,
-            _ => 1,
-        };
+        let color_index = marker_color_index(color);
         let _: serde_json::Value = self
             .cmd(
                 CommandId::EditMemoryLocation,
@@ -377,6 +852,74 @@ impl ProtoolsSession {
         Ok(())
     }
 
+    /// Create a new marker; `number` must not be in use
+    pub async fn create_marker(
+        &mut self,
+        number: u32,
+        name: &str,
+        start_time: i64,
+        destination: MarkerLocation,
+        destination_name: &str,
+        color: &str,
+    ) -> Result<()> {
+        let _: serde_json::Value = self
+            .cmd(
+                CommandId::CreateMemoryLocation,
+                serde_json::json!({
+                    "number": number,
+                    "name": name,
+                    "start_time": start_time.to_string(),
+                    "end_time": start_time.to_string(),
+                    "time_properties": "TProperties_Marker",
+                    "reference": "MLReference_FollowTrackTimebase",
+                    "general_properties": {
+                        "zoom_settings": false,
+                        "pre_post_roll_times": false,
+                        "track_visibility": false,
+                        "track_heights": false,
+                        "group_enables": false,
+                        "window_configuration": false,
+                        "window_configuration_index": 1,
+                        "venue_snapshot_index": 1
+                    },
+                    "comments": "",
+                    "color_index": marker_color_index(color),
+                    "location": destination.as_str(),
+                    "track_name": destination_name
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Delete memory locations by number
+    pub async fn clear_memory_locations(&mut self, numbers: &[i64]) -> Result<()> {
+        let _: serde_json::Value = self
+            .cmd(
+                CommandId::ClearMemoryLocation,
+                ptsl::ClearMemoryLocationRequestBody {
+                    location_list: numbers.iter().map(|&number| number as i32).collect(),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Rename a clip in the Clips list (the audio file keeps its name)
+    pub async fn rename_clip(&mut self, clip_name: &str, new_name: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .cmd(
+                CommandId::RenameTargetClip,
+                ptsl::RenameTargetClipRequestBody {
+                    clip_name: clip_name.to_string(),
+                    new_name: new_name.to_string(),
+                    rename_file: false,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
     pub async fn go_to_next_marker(&mut self, location: &str, reverse: bool) -> Result<()> {
         let mut selection = PtSelectionSamples::new(self).await?;
         let (selection_time, _) = selection.get_io();
@@ -482,14 +1025,8 @@ impl PtSelectionSamples {
             .as_str()
             .and_then(|s| s.parse::<i64>().ok())
             .unwrap_or(0);
-        self.pre_roll_enabled = response["pre_roll_start_time"]
-            .as_str()
-            .and_then(|s| s.parse::<bool>().ok())
-            .unwrap_or(false);
-        self.post_roll_enabled = response["post_roll_stop_time"]
-            .as_str()
-            .and_then(|s| s.parse::<bool>().ok())
-            .unwrap_or(false);
+        self.pre_roll_enabled = response["pre_roll_enabled"].as_bool().unwrap_or(false);
+        self.post_roll_enabled = response["post_roll_enabled"].as_bool().unwrap_or(false);
 
         println!("{:?}", self);
         Ok(())
@@ -505,8 +1042,8 @@ impl PtSelectionSamples {
                           "out_time": self.out_time.to_string(),
                           "pre_roll_start_time": self.pre_roll_start_time.to_string(),
                           "post_roll_stop_time": self.post_roll_stop_time.to_string(),
-                          "pre_roll_enabled": self.pre_roll_enabled.to_string(),
-                          "post_roll_enable": self.post_roll_enabled.to_string(),
+                          "pre_roll_enabled": triple_bool(self.pre_roll_enabled),
+                          "post_roll_enabled": triple_bool(self.post_roll_enabled),
 
                 }),
             )
@@ -530,6 +1067,20 @@ impl PtSelectionSamples {
     pub fn get_io(&self) -> (i64, i64) {
         (self.in_time, self.out_time)
     }
+    /// Whether pre-roll and post-roll are on
+    pub fn pre_post_roll(&self) -> (bool, bool) {
+        (self.pre_roll_enabled, self.post_roll_enabled)
+    }
+    pub async fn set_pre_post_roll(
+        &mut self,
+        pt: &mut ProtoolsSession,
+        pre_roll: bool,
+        post_roll: bool,
+    ) -> Result<()> {
+        self.pre_roll_enabled = pre_roll;
+        self.post_roll_enabled = post_roll;
+        self.set(pt).await
+    }
     pub async fn slide(&mut self, pt: &mut ProtoolsSession, value: i64) -> Result<()> {
         self.in_time += value;
         self.out_time += value;
@@ -540,6 +1091,35 @@ impl PtSelectionSamples {
     }
 }
 
+/// PTSL TripleBool name for a bool
+fn triple_bool(value: bool) -> &'static str {
+    if value { "TB_True" } else { "TB_False" }
+}
+
+/// Pro Tools' marker color index for a color name (unknown names = 1)
+pub fn marker_color_index(color: &str) -> i32 {
+    match color.to_lowercase().as_str() {
+        "dark purple" => 1,
+        "purple" => 2,
+        "pink" => 3,
+        "magenta" => 4,
+        "red" => 5,
+        "orange" => 6,
+        "dark yellow" => 7,
+        "yellow" => 8,
+        "light green" => 9,
+        "green" => 10,
+        "light blue" => 11,
+        "blue" => 12,
+        "dark blue" => 13,
+        "white" => 14,
+        "grey" => 15,
+        "black" => 16,
+        _ => 1,
+    }
+}
+
+#[derive(Clone, Copy)]
 pub enum MarkerLocation {
     Track,
     NamedRuler,
@@ -556,7 +1136,7 @@ impl MarkerLocation {
     }
 }
 pub async fn save_protools_session() -> Result<()> {
-    let mut pt = ProtoolsSession::new().await?;
+    let mut pt = ProtoolsSession::shared().await?;
     pt.save_session().await?;
     Ok(())
 }
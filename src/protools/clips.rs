@@ -0,0 +1,502 @@
+//! ProTools clip actions (namespace: "pt")
+use super::client::*;
+use crate::actions_async;
+use crate::prelude::*;
+
+actions_async!("pt", clips, {
+    find,
+    extend_to_next_boundary,
+    extend_to_previous_boundary,
+    select_under_cursor,
+    #[destructive]
+    gain_stage,
+    #[destructive]
+    scene_take,
+    #[destructive]
+    ambience_bed,
+});
+
+// ============================================================================
+// Command Implementations
+// ============================================================================
+
+/// Find a clip by name and select it in the Clips list
+///
+/// Looks the name up via PTSL, then gives the Clips list keyboard focus and
+/// types the full clip name so Pro Tools selects it. The Clips list must be
+/// visible in the Edit window.
+///
+/// Params:
+/// - name: text to search for (prompts if omitted)
+/// - spot: also spot the clip to the first selected track at the selection start
+pub async fn find(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let mut search = params.get_string("name", "");
+    if search.is_empty() {
+        match crate::macos::window::show_input_dialog("Find Clip", Some("Clip name:"), None)? {
+            Some(text) if !text.trim().is_empty() => search = text.trim().to_string(),
+            _ => return Ok(()), // Cancelled
+        }
+    }
+
+    let clips = pt
+        .get_clip_list()
+        .await
+        .context("Failed to get clip list")?;
+
+    // Prefer an exact (case/space-insensitive) match, then the first partial match
+    let clip_name = |clip: &serde_json::Value| {
+        clip["clip_full_name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+    };
+    let clip = clips
+        .iter()
+        .find(|clip| crate::normalize(&clip_name(clip)) == crate::normalize(&search))
+        .or_else(|| {
+            clips
+                .iter()
+                .find(|clip| crate::soft_match(&clip_name(clip), &search))
+        })
+        .with_context(|| format!("No clip matching '{}'", search))?;
+    let name = clip_name(clip);
+    log::info!("Found clip '{}' for search '{}'", name, search);
+
+    // Clip List keyboard focus (Cmd+Option+2), then type-to-select
+    OS::focus_app("Pro Tools", "", true, false, 500)?;
+    OS::keystroke(&["cmd", "option", "2"])?;
+    OS::waits::settle(OS::waits::Wait::Focus);
+    OS::type_text(&name, true)?;
+
+    if params.get_bool("spot", false) {
+        let clip_id = clip["clip_id"]
+            .as_str()
+            .context("Clip has no ID")?
+            .to_string();
+        let track = pt
+            .get_selected_track_names()
+            .await?
+            .into_iter()
+            .next()
+            .context("Select a track to spot to")?;
+        let selection = PtSelectionSamples::new(pt).await?;
+        let (start, _) = selection.get_io();
+        pt.spot_clips_by_id(vec![clip_id], &track, start).await?;
+        log::info!("Spotted '{}' to '{}' at {}", name, track, start);
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Clip Boundary Navigation
+// ============================================================================
+
+/// Clip boundaries on the focused (first selected) track, plus the current selection
+async fn focused_track_boundaries(
+    pt: &mut ProtoolsSession,
+) -> R<(Vec<(i64, i64)>, PtSelectionSamples)> {
+    let track = pt
+        .get_selected_track_names()
+        .await?
+        .into_iter()
+        .next()
+        .context("Select a track first")?;
+    let clips = pt.get_clip_boundaries(&track).await?;
+    let selection = PtSelectionSamples::new(pt).await?;
+    Ok((clips, selection))
+}
+
+/// Extend the selection end to the next clip start or end on the focused track
+pub async fn extend_to_next_boundary(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    let (clips, mut selection) = focused_track_boundaries(pt).await?;
+    let (start, end) = selection.get_io();
+    let next = clips
+        .iter()
+        .flat_map(|&(s, e)| [s, e])
+        .filter(|&boundary| boundary > end)
+        .min()
+        .context("No clip boundary after the selection")?;
+    selection.set_io(pt, start, next).await?;
+    Ok(())
+}
+
+/// Extend the selection start to the previous clip start or end on the focused track
+pub async fn extend_to_previous_boundary(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    let (clips, mut selection) = focused_track_boundaries(pt).await?;
+    let (start, end) = selection.get_io();
+    let previous = clips
+        .iter()
+        .flat_map(|&(s, e)| [s, e])
+        .filter(|&boundary| boundary < start)
+        .max()
+        .context("No clip boundary before the selection")?;
+    selection.set_io(pt, previous, end).await?;
+    Ok(())
+}
+
+/// Select the whole clip under the edit cursor on the focused track
+pub async fn select_under_cursor(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    let (clips, mut selection) = focused_track_boundaries(pt).await?;
+    let (cursor, _) = selection.get_io();
+    let &(start, end) = clips
+        .iter()
+        .find(|&&(s, e)| s <= cursor && cursor < e)
+        .context("No clip under the edit cursor")?;
+    selection.set_io(pt, start, end).await?;
+    Ok(())
+}
+
+/// Bring the selected clips to a target loudness (or peak) with clip gain
+///
+/// Exports the clips to a temporary folder over PTSL, measures them (see
+/// `protools::loudness`) and nudges clip gain by the difference. Clip gain is
+/// nudged with Pro Tools' Control-Shift-Up/Down shortcuts, so the change is
+/// rounded to `step_db`, which must match Pro Tools' clip gain nudge value.
+///
+/// Params:
+/// - target_lufs: integrated loudness to aim for (default -27)
+/// - target_peak: aim for this sample peak in dBFS instead of loudness
+/// - max_peak: never raise the peak above this, in dBFS (default -1)
+/// - max_change: largest change applied, in dB (default 20)
+/// - step_db: Pro Tools' clip gain nudge value (default 0.5)
+pub async fn gain_stage(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let target_peak = params.get_onumber("target_peak");
+    let target_lufs = params.get_number("target_lufs", -27.0);
+    let max_peak = params.get_number("max_peak", -1.0);
+    let max_change = params.get_number("max_change", 20.0).abs();
+    let step = params.get_number("step_db", 0.5);
+    if step <= 0.0 {
+        anyhow::bail!("step_db must be greater than 0");
+    }
+
+    let measurement = measure_selected_clips(pt).await?;
+    let (measured, target, unit) = match target_peak {
+        Some(peak) => (measurement.peak_db, peak, "dBFS"),
+        None => (
+            measurement
+                .lufs
+                .context("Selection is too short or too quiet to measure")?,
+            target_lufs,
+            "LUFS",
+        ),
+    };
+    let change = (target - measured)
+        .min(max_peak - measurement.peak_db)
+        .clamp(-max_change, max_change);
+    let steps = (change / step).round() as i64;
+    log::info!(
+        "Gain staging: {:.1} {} (peak {:.1} dBFS), target {:.1} - {} step(s) of {} dB",
+        measured,
+        unit,
+        measurement.peak_db,
+        target,
+        steps,
+        step
+    );
+    if steps == 0 {
+        OS::show_notification(&crate::notify::text(
+            "clip_gain_unchanged",
+            &[("level", &format!("{:.1}", measured)), ("unit", unit)],
+        ));
+        return Ok(());
+    }
+
+    nudge_clip_gain(steps)?;
+    OS::show_notification(&crate::notify::text(
+        "clip_gain_changed",
+        &[
+            ("change", &format!("{:+.1}", steps as f64 * step)),
+            ("level", &format!("{:.1}", measured)),
+            ("unit", unit),
+            ("target", &format!("{:.1}", measured + steps as f64 * step)),
+        ],
+    ));
+    Ok(())
+}
+
+/// Nudge the selected clips' gain by `steps` of Pro Tools' clip gain nudge value
+///
+/// PTSL can't set clip gain, so this presses Control-Shift-Up/Down.
+pub fn nudge_clip_gain(steps: i64) -> R<()> {
+    let key = if steps > 0 { "up" } else { "down" };
+    for _ in 0..steps.abs() {
+        OS::keystroke(&["control", "shift", key])?;
+    }
+    Ok(())
+}
+
+/// Export the selected clips to a temporary folder and measure them together
+async fn measure_selected_clips(pt: &mut ProtoolsSession) -> R<super::loudness::Measurement> {
+    use super::ptsl;
+
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let dir = std::env::temp_dir().join(format!("pthkd-gain-{}-{}", std::process::id(), stamp));
+    std::fs::create_dir_all(&dir)?;
+    let result = async {
+        let _: serde_json::Value = pt
+            .cmd(
+                ptsl::CommandId::ExportClipsAsFiles,
+                ptsl::ExportClipsAsFilesRequestBody {
+                    file_path: dir.to_string_lossy().to_string(),
+                    format: ptsl::ExportFormat::EfInterleaved.into(),
+                    file_type: ptsl::ExportFileType::Wav.into(),
+                    bit_depth: ptsl::BitDepth::Bit32Float.into(),
+                    duplicate_names: ptsl::ResolveDuplicateNamesBy::AutoRenaming.into(),
+                    enforce_avid_compatibility: false,
+                    sample_rate_custom: 0,
+                },
+            )
+            .await
+            .context("Failed to export the selected clips")?;
+        let files: Vec<_> = std::fs::read_dir(&dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"))
+            })
+            .collect();
+        if files.is_empty() {
+            anyhow::bail!("No clips selected");
+        }
+        super::loudness::measure_files(&files)
+    }
+    .await;
+    std::fs::remove_dir_all(&dir).ok();
+    result
+}
+
+/// The clips on the selected tracks that overlap the timeline selection, as
+/// (clip start, track, clip name) in timeline order, tracks top to bottom
+pub(super) async fn selected_clips(pt: &mut ProtoolsSession) -> R<Vec<(i64, String, String)>> {
+    let (start, end) = PtSelectionSamples::new(pt).await?.get_io();
+    let names: std::collections::HashMap<String, String> = pt
+        .get_clip_list()
+        .await
+        .context("Failed to get clip list")?
+        .iter()
+        .filter_map(|clip| {
+            Some((
+                clip["clip_id"].as_str()?.to_string(),
+                clip["clip_full_name"].as_str()?.to_string(),
+            ))
+        })
+        .collect();
+    let mut clips = Vec::new();
+    for track in pt.get_selected_track_names().await? {
+        for (clip_start, clip_end, id) in pt.get_playlist_clips(&track).await? {
+            if clip_start < end.max(start + 1)
+                && clip_end > start
+                && let Some(name) = names.get(&id)
+            {
+                clips.push((clip_start, track.clone(), name.clone()));
+            }
+        }
+    }
+    // Stable, so clips starting together keep their tracks' order
+    clips.sort_by_key(|(clip_start, _, _)| *clip_start);
+    if clips.is_empty() {
+        anyhow::bail!("No clips selected");
+    }
+    Ok(clips)
+}
+
+/// Default scene/take pattern: "12A-3", "12A_T3", "SC12A TK3", "12a take 3"...
+const SCENE_TAKE_PATTERN: &str =
+    r"(?i)(?:sc(?:ene)?[ _-]*)?(?P<scene>\d+[a-z]*)[ _-]+(?:t(?:a?ke?|k)?[ _-]*)?(?P<take>\d+)";
+
+/// Read scene and take from the selected clips' names and mark or rename them
+///
+/// Covers the clips on the selected tracks that overlap the timeline
+/// selection. A preview of every change is shown before anything is applied.
+///
+/// Params:
+/// - pattern: regex with `scene` and `take` groups (default handles "12A-3",
+///   "SC12A_TK3", "12a take 3"...)
+/// - format: how the marker/new name is written (default "{scene}-{take}")
+/// - markers: add a marker at each clip start (default true)
+/// - rename: rename the clips (default false)
+/// - ruler: marker ruler for the markers (default: the main ruler)
+/// - color: marker color (default "yellow")
+/// - preview: confirm before applying (default true)
+pub async fn scene_take(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let pattern = params.get_string("pattern", SCENE_TAKE_PATTERN);
+    let pattern = regex::Regex::new(&pattern).context("Invalid scene/take pattern")?;
+    let format = params.get_string("format", "{scene}-{take}");
+    let markers = params.get_bool("markers", true);
+    let rename = params.get_bool("rename", false);
+    let ruler = params.get_string("ruler", "");
+    let color = params.get_string("color", "yellow");
+    if !markers && !rename {
+        anyhow::bail!("Nothing to do: set markers and/or rename");
+    }
+
+    // (clip start, clip name) for every selected clip, once per clip
+    let mut clips: Vec<(i64, String)> = selected_clips(pt)
+        .await?
+        .into_iter()
+        .map(|(clip_start, _, name)| (clip_start, name))
+        .collect();
+    clips.sort();
+    clips.dedup();
+
+    // (clip start, clip name, scene/take label) for the names that match
+    let changes: Vec<(i64, String, String)> = clips
+        .into_iter()
+        .filter_map(|(clip_start, name)| {
+            let caps = pattern.captures(&name)?;
+            let label = format
+                .replace("{scene}", &caps.name("scene")?.as_str().to_uppercase())
+                .replace("{take}", caps.name("take")?.as_str());
+            Some((clip_start, name, label))
+        })
+        .collect();
+    if changes.is_empty() {
+        anyhow::bail!("No scene/take found in the selected clip names");
+    }
+
+    if params.get_bool("preview", true) {
+        let list = changes
+            .iter()
+            .map(|(_, name, label)| format!("{} → {}", name, label))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let what = match (markers, rename) {
+            (true, true) => "Add markers and rename",
+            (true, false) => "Add markers for",
+            _ => "Rename",
+        };
+        let message = format!("{} {} clip(s)?\n\n{}", what, changes.len(), list);
+        if !crate::macos::window::show_confirm_dialog("Scene / Take", &message, "Apply")? {
+            return Ok(());
+        }
+    }
+
+    let mut next_number = pt
+        .get_all_markers()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|marker| marker["number"].as_u64())
+        .max()
+        .unwrap_or(0) as u32
+        + 1;
+    let (location, ruler_name) = if ruler.is_empty() {
+        (MarkerLocation::MainRuler, "")
+    } else {
+        (MarkerLocation::NamedRuler, ruler.as_str())
+    };
+    let mut renamed = std::collections::HashSet::new();
+    for (clip_start, name, label) in &changes {
+        if markers {
+            pt.create_marker(
+                next_number,
+                label,
+                *clip_start,
+                location,
+                ruler_name,
+                &color,
+            )
+            .await
+            .with_context(|| format!("Failed to add marker '{}'", label))?;
+            next_number += 1;
+        }
+        // The same clip can be used more than once in the selection
+        if rename && name != label && renamed.insert(name.clone()) {
+            pt.rename_clip(name, label)
+                .await
+                .with_context(|| format!("Failed to rename '{}'", name))?;
+        }
+    }
+    log::info!("Scene/take: {} clip(s) processed", changes.len());
+    OS::show_notification(&crate::notify::text(
+        "scene_take_done",
+        &[("count", &changes.len().to_string())],
+    ));
+    Ok(())
+}
+
+// ============================================================================
+// Ambience Beds
+// ============================================================================
+
+/// Spread the selected clips over ambience tracks and fill the selection with them
+///
+/// Every clip that overlaps the timeline selection on the selected tracks is
+/// spotted to the selection start on the next of `tracks`, then filled to
+/// the selection end with `edit_fill_selection_with_clip` (or trimmed, if
+/// it's already longer). The bed tracks are left selected.
+///
+/// Params:
+/// - tracks: ambience tracks to build the bed on, one per clip
+/// - color: palette index or hex color for the bed tracks (Pro Tools 2025.10+)
+/// - mode / crossfade / crossfade_ms / fade_preset ...: passed on to
+///   `edit_fill_selection_with_clip`
+pub async fn ambience_bed(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let bed_tracks = params.get_string_vec("tracks");
+    if bed_tracks.is_empty() {
+        anyhow::bail!("Provide the ambience tracks (tracks = [\"AMB 1\", \"AMB 2\"])");
+    }
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    let (start, end) = selection.get_io();
+    if end <= start {
+        anyhow::bail!("Select the stretch the bed should cover");
+    }
+
+    // Source clips: whatever the selection touches, track by track
+    let mut sources = Vec::new();
+    for track in pt.get_selected_track_names().await? {
+        sources.extend(
+            pt.get_playlist_clips(&track)
+                .await?
+                .into_iter()
+                .filter(|&(clip_start, clip_end, _)| clip_start < end && clip_end > start),
+        );
+    }
+    if sources.is_empty() {
+        anyhow::bail!("No clips in the selection");
+    }
+    if sources.len() > bed_tracks.len() {
+        anyhow::bail!(
+            "{} clips selected but only {} ambience track(s)",
+            sources.len(),
+            bed_tracks.len()
+        );
+    }
+
+    let used = &bed_tracks[..sources.len()];
+    for ((clip_start, clip_end, clip_id), track) in sources.into_iter().zip(used) {
+        pt.spot_clips_by_id(vec![clip_id], track, start).await?;
+        pt.select_tracks_by_name(vec![track.clone()]).await?;
+        selection.set_io(pt, start, end).await?;
+        if start + (clip_end - clip_start) >= end {
+            OS::menu_click("Pro Tools", &["Edit", "Trim Clip", "To Selection"])?;
+            if let Some(preset) = params.get_ostring("fade_preset") {
+                super::edit::fade_with_preset(pt, preset).await?;
+            }
+        } else {
+            super::edit::fill_selection_with_clip(pt, params).await?;
+        }
+        log::info!("Ambience bed: '{}' filled", track);
+    }
+
+    pt.select_tracks_by_name(used.to_vec()).await?;
+    selection.set_io(pt, start, end).await?;
+    if params.get_ostr("color").is_some() || params.get_oint("color").is_some() {
+        let palette = pt.get_track_color_palette().await.unwrap_or_default();
+        if palette.is_empty() {
+            log::warn!("Ambience bed: no PTSL track colors, tracks left uncolored");
+        } else {
+            let index = super::tracks::palette_index(&palette, params)?;
+            pt.set_track_color(used.to_vec(), index as i32).await?;
+        }
+    }
+    OS::show_notification(&crate::notify::text(
+        "ambience_bed_built",
+        &[("count", &used.len().to_string())],
+    ));
+    Ok(())
+}
@@ -4,21 +4,62 @@ use crate::actions_async;
 // Define all ProTools actions using the async macro
 // Actions are automatically registered with the "pt" namespace
 actions_async!("pt", edit, {
+    #[destructive]
     crossfade,
+    #[destructive]
+    batch_fades,
+    #[destructive]
+    fill_selection_with_clip,
+    #[destructive]
     adjust_clip_to_match_selection,
+    #[destructive]
     conform_delete,
+    #[destructive]
     conform_insert,
+    #[destructive]
+    insert_section,
+    nudge_selection,
+    extend_selection,
+    goto_timecode,
+    store_selection,
+    recall_selection,
     toggle_mode,
     toggle_tool,
+    #[destructive]
     reset_clip,
+    #[destructive]
+    clip_gain_nudge,
+    #[destructive]
+    clip_gain_set,
     click_a_button,
+    #[destructive]
     bg_paste_selection,
+    #[destructive]
     bg_clear_selection,
+    #[destructive]
+    clip_effects,
+    zoom_preset,
+    zoom_to_selection,
+    #[destructive]
+    rename_clip,
 });
 use super::client::*;
 use super::ptsl;
+use super::restore::RestoreGuard;
 use super::timecode::*;
 use ptsl::CommandId;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// A stored timeline selection (samples) and the tracks selected with it
+struct SelectionRegister {
+    in_time: i64,
+    out_time: i64,
+    tracks: Vec<String>,
+}
+
+/// Selection registers by slot, kept for the daemon's lifetime
+static SELECTION_REGISTERS: Mutex<Option<HashMap<i64, SelectionRegister>>> = Mutex::new(None);
 
 // ============================================================================
 // Command Implementations
@@ -30,62 +71,288 @@ pub async fn crossfade(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
     let fill = params.get_bool("fill_selection", false);
     let adjust = params.get_float("adjust_selection_frames", 0.0);
     let snap = params.get_bool("snap_to_grid", false);
-    let mut sel = PtSelectionTimecode::new(pt).await?;
-    if snap {
-        let mut io = sel.get_io(pt).await?;
-        io.0.snap_to_grid();
-        io.1.snap_to_grid();
-        sel.set_io(pt, &io.0, &io.1).await?;
+    // A failure part way leaves the snapped/widened selection - put the original back
+    let mut guard = RestoreGuard::new();
+    guard.save_selection_on_error(pt).await?;
+    let result = async {
+        let mut sel = PtSelectionTimecode::new(pt).await?;
+        if snap {
+            let mut io = sel.get_io(pt).await?;
+            io.0.snap_to_grid();
+            io.1.snap_to_grid();
+            sel.set_io(pt, &io.0, &io.1).await?;
+        }
+        if adjust > 0.0 {
+            let mut io = sel.get_io(pt).await?;
+            io.0.sub_hmsf(0, 0, 0, adjust);
+            io.1.add_hmsf(0, 0, 0, adjust);
+            sel.set_io(pt, &io.0, &io.1).await?;
+        }
+        if fill {
+            OS::menu_click(
+                "Pro Tools",
+                &["Edit", "Trim Clip", "Start to Fill Selection"],
+            )
+            .ok();
+            OS::menu_click("Pro Tools", &["Edit", "Trim Clip", "End to Fill Selection"]).ok();
+        }
+        let result = pt
+            .cmd::<_, serde_json::Value>(
+                CommandId::CreateFadesBasedOnPreset,
+                ptsl::CreateFadesBasedOnPresetRequestBody {
+                    fade_preset_name: preset,
+                    auto_adjust_bounds: true,
+                },
+            )
+            .await;
+
+        if result.is_err() {
+            pt.cmd::<_, serde_json::Value>(
+                CommandId::CreateFadesBasedOnPreset,
+                ptsl::CreateFadesBasedOnPresetRequestBody {
+                    fade_preset_name: String::new(), // Last used
+                    auto_adjust_bounds: true,
+                },
+            )
+            .await?;
+        }
+        if crossfade {
+            OS::menu_click("Pro Tools", &["Edit", "Automation", "Write to All Enabled"]).ok();
+
+            let _: serde_json::Value = pt
+                .cmd(
+                    CommandId::ClearSpecial,
+                    ptsl::ClearSpecialRequestBody {
+                        automation_data_option: ptsl::AutomationDataOptions::AllAutomation.into(),
+                    },
+                )
+                .await?;
+        }
+        sel.set(pt).await?;
+        Ok(())
     }
-    if adjust > 0.0 {
-        let mut io = sel.get_io(pt).await?;
-        io.0.sub_hmsf(0, 0, 0, adjust);
-        io.1.add_hmsf(0, 0, 0, adjust);
-        sel.set_io(pt, &io.0, &io.1).await?;
+    .await;
+    guard.finish(pt, result).await
+}
+
+/// Radio buttons for a fade shape in the Batch Fades dialog
+fn fade_shape_buttons(shape: &str) -> R<&'static [&'static str]> {
+    let buttons: &[&str] = match shape.to_lowercase().replace([' ', '-'], "_").as_str() {
+        "equal_power" => &["Equal Power"],
+        "equal_gain" => &["Equal Gain"],
+        // An equal gain fade with the standard (unbent) curve
+        "linear" => &["Equal Gain", "Standard"],
+        other => anyhow::bail!(
+            "Unknown fade shape '{}' (equal_power, equal_gain or linear)",
+            other
+        ),
+    };
+    Ok(buttons)
+}
+
+/// Fade the selected clips with explicit lengths and shapes
+///
+/// PTSL can only apply saved fade presets, so lengths and shapes go through
+/// the Batch Fades dialog, once per kind of fade - the dialog has a single
+/// length for all three. With only `preset` it's applied over PTSL instead.
+///
+/// Params:
+/// - in_ms / out_ms / crossfade_ms: fade lengths; kinds left out aren't created
+/// - shape: "equal_power", "equal_gain" or "linear" (default "equal_power")
+/// - in_shape / out_shape / crossfade_shape: per kind, over `shape`
+/// - adjust_existing: change fades already there too (default true)
+/// - preset: saved fade preset, when no lengths are given
+/// - window / length_field / in_checkbox / out_checkbox / crossfade_checkbox /
+///   adjust_checkbox: dialog names, for localized or older Pro Tools versions
+pub async fn batch_fades(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let kinds = [
+        ("in", "Create new Fade Ins"),
+        ("out", "Create new Fade Outs"),
+        ("crossfade", "Create new Crossfades"),
+    ];
+    let fades: Vec<_> = kinds
+        .iter()
+        .filter_map(|&(kind, _)| Some((kind, params.get_onumber(&format!("{}_ms", kind))?)))
+        .collect();
+    if fades.is_empty() {
+        let Some(preset) = params.get_ostring("preset") else {
+            anyhow::bail!("Provide in_ms, out_ms, crossfade_ms or a preset");
+        };
+        let _: serde_json::Value = pt
+            .cmd(
+                CommandId::CreateFadesBasedOnPreset,
+                ptsl::CreateFadesBasedOnPresetRequestBody {
+                    fade_preset_name: preset,
+                    auto_adjust_bounds: true,
+                },
+            )
+            .await?;
+        return Ok(());
     }
-    if fill {
-        OS::menu_click(
-            "Pro Tools",
-            &["Edit", "Trim Clip", "Start to Fill Selection"],
-        )
-        .ok();
-        OS::menu_click("Pro Tools", &["Edit", "Trim Clip", "End to Fill Selection"]).ok();
+    // Check every shape before opening anything
+    let shape = params.get_str("shape", "equal_power");
+    let shapes = fades
+        .iter()
+        .map(|&(kind, _)| fade_shape_buttons(params.get_str(&format!("{}_shape", kind), shape)))
+        .collect::<R<Vec<_>>>()?;
+
+    let window = params.get_string("window", "Batch Fades");
+    let adjust = params.get_bool("adjust_existing", true);
+    for (&(kind, ms), buttons) in fades.iter().zip(shapes) {
+        OS::menu_click("Pro Tools", &["Edit", "Fades", "Create..."])?;
+        if !OS::wait_for_window("Pro Tools", &window, OS::WindowCondition::Exists, 3000)? {
+            anyhow::bail!(
+                "The {} dialog didn't open (select more than one clip)",
+                window
+            );
+        }
+        for &(other, default_name) in &kinds {
+            let checkbox = params.get_string(&format!("{}_checkbox", other), default_name);
+            OS::set_checkbox_value("Pro Tools", &window, &checkbox, (other == kind) as i32)
+                .with_context(|| format!("Batch Fades checkbox '{}' not found", checkbox))?;
+        }
+        let checkbox = params.get_string("adjust_checkbox", "Adjust existing Fades");
+        OS::set_checkbox_value("Pro Tools", &window, &checkbox, adjust as i32)
+            .with_context(|| format!("Batch Fades checkbox '{}' not found", checkbox))?;
+        for button in buttons {
+            OS::click_button("Pro Tools", &window, button)
+                .with_context(|| format!("Batch Fades shape '{}' not found", button))?;
+        }
+        let field = params.get_string("length_field", "Fade Length");
+        OS::set_text_field_value("Pro Tools", &window, &field, &format!("{}", ms))
+            .with_context(|| format!("Batch Fades field '{}' not found", field))?;
+        // Commit the length without pressing OK early
+        OS::keystroke(&["tab"])?;
+        OS::click_button("Pro Tools", &window, "OK")?;
+        OS::wait_for_window("Pro Tools", &window, OS::WindowCondition::Closed, 3000)?;
+        log::info!("Batch fades: {} {} ms ({})", kind, ms, buttons.join(" + "));
+    }
+    Ok(())
+}
+/// Loop or repeat the clip at the selection start until it fills the selection
+///
+/// The clip under the selection start on the first selected track is the
+/// source, and the selection end is where the fill stops - an ambience bed in
+/// one key. `mode = "loop"` makes a looped clip through the Clip Looping
+/// dialog and trims it to the selection end; `mode = "duplicate"` copies the
+/// clip and pastes it with Repeat to Fill Selection.
+///
+/// Params:
+/// - mode: "loop" or "duplicate" (default "loop")
+/// - crossfade: loop mode - crossfade each loop into the next (default false)
+/// - crossfade_ms: loop mode - crossfade length (default: the dialog's last setting)
+/// - fade_preset: saved fade preset applied across the fill
+/// - window / loops_button / loops_field / crossfade_checkbox / crossfade_window /
+///   crossfade_field: dialog names, for localized or older Pro Tools versions
+pub async fn fill_selection_with_clip(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let mode = params.get_string("mode", "loop");
+    if mode != "loop" && mode != "duplicate" {
+        anyhow::bail!("Unknown mode '{}' (loop or duplicate)", mode);
+    }
+    let track = pt
+        .get_selected_track_names()
+        .await?
+        .into_iter()
+        .next()
+        .context("Select a track first")?;
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    let (start, end) = selection.get_io();
+    let (clip_start, clip_end) = pt
+        .get_clip_boundaries(&track)
+        .await?
+        .into_iter()
+        .find(|&(clip_start, clip_end)| clip_start <= start && start < clip_end)
+        .with_context(|| format!("No clip at the selection start on '{}'", track))?;
+    if end <= clip_end {
+        anyhow::bail!("The selection ends inside the clip - nothing to fill");
     }
-    let result = pt
-        .cmd::<_, serde_json::Value>(
-            CommandId::CreateFadesBasedOnPreset,
-            ptsl::CreateFadesBasedOnPresetRequestBody {
-                fade_preset_name: preset,
-                auto_adjust_bounds: true,
-            },
-        )
-        .await;
 
-    if result.is_err() {
-        pt.cmd::<_, serde_json::Value>(
+    let mut guard = RestoreGuard::new();
+    guard.save_selection_on_error(pt).await?;
+    let result = async {
+        selection.set_io(pt, clip_start, clip_end).await?;
+        if mode == "loop" {
+            let length = clip_end - clip_start;
+            let loops = (end - clip_start + length - 1) / length;
+            loop_clip(params, loops)?;
+            // The last loop runs past the selection end
+            selection.set_io(pt, clip_start, end).await?;
+            OS::menu_click("Pro Tools", &["Edit", "Trim Clip", "To Selection"])?;
+        } else {
+            pt.copy().await?;
+            selection.set_io(pt, clip_end, end).await?;
+            pt.paste_to_fill_selection().await?;
+            selection.set_io(pt, clip_start, end).await?;
+        }
+        if let Some(preset) = params.get_ostring("fade_preset") {
+            fade_with_preset(pt, preset).await?;
+        }
+        log::info!(
+            "Filled {}-{} on '{}' with the clip at {} ({})",
+            start,
+            end,
+            track,
+            clip_start,
+            mode
+        );
+        Ok(())
+    }
+    .await;
+    guard.finish(pt, result).await
+}
+
+/// Fade the selected clips with a saved fade preset
+pub(super) async fn fade_with_preset(pt: &mut ProtoolsSession, preset: String) -> R<()> {
+    let _: serde_json::Value = pt
+        .cmd(
             CommandId::CreateFadesBasedOnPreset,
             ptsl::CreateFadesBasedOnPresetRequestBody {
-                fade_preset_name: String::new(), // Last used
+                fade_preset_name: preset,
                 auto_adjust_bounds: true,
             },
         )
         .await?;
+    Ok(())
+}
+
+/// Loop the selected clip `loops` times through the Clip Looping dialog
+fn loop_clip(params: &Params, loops: i64) -> R<()> {
+    let window = params.get_string("window", "Clip Looping");
+    OS::menu_click("Pro Tools", &["Clip", "Loop..."])?;
+    if !OS::wait_for_window("Pro Tools", &window, OS::WindowCondition::Exists, 3000)? {
+        anyhow::bail!("The {} dialog didn't open", window);
     }
-    if crossfade {
-        OS::menu_click("Pro Tools", &["Edit", "Automation", "Write to All Enabled"]).ok();
+    let button = params.get_string("loops_button", "Number of Loops");
+    OS::click_button("Pro Tools", &window, &button)
+        .with_context(|| format!("Clip Looping option '{}' not found", button))?;
+    let field = params.get_string("loops_field", "Number of Loops");
+    OS::set_text_field_value("Pro Tools", &window, &field, &loops.to_string())
+        .with_context(|| format!("Clip Looping field '{}' not found", field))?;
+    OS::keystroke(&["tab"])?;
 
-        let _: serde_json::Value = pt
-            .cmd(
-                CommandId::ClearSpecial,
-                ptsl::ClearSpecialRequestBody {
-                    automation_data_option: ptsl::AutomationDataOptions::AllAutomation.into(),
-                },
-            )
-            .await?;
+    let crossfade = params.get_bool("crossfade", false);
+    let checkbox = params.get_string("crossfade_checkbox", "Enable Crossfade");
+    OS::set_checkbox_value("Pro Tools", &window, &checkbox, crossfade as i32)
+        .with_context(|| format!("Clip Looping checkbox '{}' not found", checkbox))?;
+    if let (true, Some(ms)) = (crossfade, params.get_onumber("crossfade_ms")) {
+        let settings = params.get_string("crossfade_window", "Loop Crossfade");
+        OS::click_button("Pro Tools", &window, "Settings...")?;
+        if !OS::wait_for_window("Pro Tools", &settings, OS::WindowCondition::Exists, 3000)? {
+            anyhow::bail!("The {} dialog didn't open", settings);
+        }
+        let field = params.get_string("crossfade_field", "Length");
+        OS::set_text_field_value("Pro Tools", &settings, &field, &format!("{}", ms))
+            .with_context(|| format!("Loop Crossfade field '{}' not found", field))?;
+        OS::keystroke(&["tab"])?;
+        OS::click_button("Pro Tools", &settings, "OK")?;
+        OS::wait_for_window("Pro Tools", &settings, OS::WindowCondition::Closed, 3000)?;
     }
-    sel.set(pt).await?;
+
+    OS::click_button("Pro Tools", &window, "OK")?;
+    OS::wait_for_window("Pro Tools", &window, OS::WindowCondition::Closed, 3000)?;
     Ok(())
 }
+
 pub async fn bg_paste_selection(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
     let preset = params.get_string("fade_preset", "");
     let adjust = params.get_float("adjust_selection_frames", 0.0);
@@ -155,7 +422,63 @@ pub async fn reset_clip(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
     OS::menu_click("Pro Tools", &["Edit", "Fades", "Delete"]).ok();
     OS::menu_click("Pro Tools", &["Edit", "Clear Special", "Clip Gain"]).ok();
     OS::menu_click("Pro Tools", &["Edit", "Clear Special", "Clip Effects"]).ok();
-    let result: serde_json::Value = pt
+    // Best effort, like the menu clicks: Pro Tools refuses to clear what
+    // the menus already cleared
+    for option in [
+        ptsl::AutomationDataOptions::ClipGain,
+        ptsl::AutomationDataOptions::ClipEffects,
+    ] {
+        let cleared: R<serde_json::Value> = pt
+            .cmd(
+                CommandId::ClearSpecial,
+                ptsl::ClearSpecialRequestBody {
+                    automation_data_option: option.into(),
+                },
+            )
+            .await;
+        if let Err(e) = cleared {
+            log::debug!("Clearing {:?} skipped: {:#}", option, e);
+        }
+    }
+    Ok(())
+}
+/// Whole nudge steps closest to `db`, from the `step_db` param
+fn clip_gain_steps(db: f64, params: &Params) -> R<i64> {
+    let step = params.get_number("step_db", 0.5);
+    if step <= 0.0 {
+        anyhow::bail!("step_db must be greater than 0");
+    }
+    Ok((db / step).round() as i64)
+}
+
+/// Raise or lower the selected clips' gain
+///
+/// Rounded to Pro Tools' clip gain nudge value, which `step_db` must match.
+/// Bound to a MIDI encoder (`db` from a fader, or one binding per direction)
+/// it rides clip gain.
+///
+/// Params:
+/// - db: change in dB, e.g. 1.5 or -0.5 (default 0.5)
+/// - step_db: Pro Tools' clip gain nudge value (default 0.5)
+pub async fn clip_gain_nudge(_pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let steps = clip_gain_steps(params.get_number("db", 0.5), params)?;
+    if steps == 0 {
+        anyhow::bail!("db is smaller than one nudge step");
+    }
+    super::clips::nudge_clip_gain(steps)
+}
+
+/// Set the selected clips' gain to a fixed value
+///
+/// Pro Tools can't report clip gain, so it's cleared to 0 dB first (which
+/// also flattens any clip gain line) and then nudged to `db`.
+///
+/// Params:
+/// - db: clip gain in dB (default 0)
+/// - step_db: Pro Tools' clip gain nudge value (default 0.5)
+pub async fn clip_gain_set(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let steps = clip_gain_steps(params.get_number("db", 0.0), params)?;
+    let _: serde_json::Value = pt
         .cmd(
             CommandId::ClearSpecial,
             ptsl::ClearSpecialRequestBody {
@@ -163,59 +486,387 @@ pub async fn reset_clip(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
             },
         )
         .await?;
-    println!("clip gain: {:?}", result);
-    let result: serde_json::Value = pt
-        .cmd(
-            CommandId::ClearSpecial,
-            ptsl::ClearSpecialRequestBody {
-                automation_data_option: ptsl::AutomationDataOptions::ClipEffects.into(),
-            },
-        )
-        .await?;
-    println!("clip effects: {:?}", result);
+    super::clips::nudge_clip_gain(steps)
+}
+
+/// Open the Clip Effects display and type values into its controls (Ultimate only)
+///
+/// `hpf_hz` and `gain_db` cover the usual dialogue pre-balance; `values` sets any
+/// other control by its accessibility name, e.g. `[["LPF Frequency", "12000"]]`.
+/// The control names can be overridden with `hpf_field` / `gain_field` for
+/// localized or older Pro Tools versions.
+pub async fn clip_effects(_pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let window = params.get_string("window", "Edit");
+    let show = params.get_bool("show", true);
+    let menu = ["View", "Other Displays", "Clip Effects"];
+    if show && !OS::menu_item_checked("Pro Tools", &menu).unwrap_or(false) {
+        OS::menu_click("Pro Tools", &menu)?;
+        OS::waits::settle(OS::waits::Wait::Display);
+    }
+
+    let mut values = Vec::new();
+    if let Some(hz) = params.get_onumber("hpf_hz") {
+        values.push((
+            params.get_string("hpf_field", "HPF Frequency"),
+            hz.to_string(),
+        ));
+    }
+    if let Some(db) = params.get_onumber("gain_db") {
+        values.push((
+            params.get_string("gain_field", "Input Gain"),
+            db.to_string(),
+        ));
+    }
+    values.extend(params.get_string_pairs("values"));
+
+    for (field, value) in values {
+        OS::set_text_field_value("Pro Tools", &window, &field, &value)
+            .with_context(|| format!("Clip Effects control '{}' not found", field))?;
+        // Commit the typed value before moving to the next control
+        OS::keystroke(&["return"])?;
+        log::info!("Clip Effects: {} = {}", field, value);
+    }
     Ok(())
 }
+
 pub async fn conform_delete(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
     println!("Running Conform Delete");
-    let mut flag = false;
-    let original_mode = pt.get_edit_mode().await?;
+    let mut guard = RestoreGuard::new();
+    let result = async {
+        enter_shuffle(pt, &mut guard).await?;
+        let _: serde_json::Value = pt.cmd(CommandId::Clear, serde_json::json!({})).await?;
+        Ok(())
+    }
+    .await;
+    guard.finish(pt, result).await
+}
+pub async fn conform_insert(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    println!("Running Conform Insert");
+    let mut guard = RestoreGuard::new();
+    let result = async {
+        enter_shuffle(pt, &mut guard).await?;
+        OS::menu_click("Pro Tools", &["Edit", "Insert Silence"])?;
+        // OS::keystroke(&["cmd", "shift", "e"]).await?;
+        OS::waits::settle(OS::waits::Wait::Menu);
+        Ok(())
+    }
+    .await;
+    guard.finish(pt, result).await
+}
+/// Switch to Shuffle, toggling Shuffle lock (cmd+F1) if it's in the way
+///
+/// The guard puts the edit mode back, then the lock.
+async fn enter_shuffle(pt: &mut ProtoolsSession, guard: &mut RestoreGuard) -> R<()> {
+    let original_mode = guard.save_edit_mode(pt).await?;
     pt.set_edit_mode("EMO_Shuffle").await?;
 
     if pt.get_edit_mode().await? != "EMO_Shuffle" {
         OS::keystroke(&["cmd", "f1"])?;
+        guard.keystroke(&["cmd", "f1"]);
+        // Restored first, so the lock goes back on outside Shuffle
+        guard.restore_edit_mode_to(original_mode);
         // std::thread::sleep(std::time::Duration::from_millis(35)); // Wait 50ms
         pt.set_edit_mode("EMO_Shuffle").await?;
-        flag = true;
     }
-    let _: serde_json::Value = pt.cmd(CommandId::Clear, serde_json::json!({})).await?;
-    // std::thread::sleep(std::time::Duration::from_millis(25)); // Wait 50ms
-    pt.set_edit_mode(&original_mode).await?;
-    if flag {
-        OS::keystroke(&["cmd", "f1"])?;
+    Ok(())
+}
+/// Insert a section of silence across every track (ripple-safe conform insert)
+///
+/// Selects all tracks, extends the selection from its start by the given
+/// duration, inserts silence in Shuffle mode, then restores the original
+/// track selection, timeline selection and edit mode. Refuses to run unless
+/// the transport is stopped.
+///
+/// Params:
+/// - duration: timecode length to insert (e.g. "00:00:05:00"); read from the clipboard if omitted
+/// - all_tracks: insert on every track rather than the current selection (default: true)
+pub async fn insert_section(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    if !pt.is_transport_stopped().await? {
+        anyhow::bail!("Stop the transport before inserting a section");
     }
+
+    let duration_str = match params.get_ostring("duration") {
+        Some(duration) => duration,
+        None => crate::macos::helpers::clipboard_text()?,
+    };
+    let duration_str = duration_str.trim().replace(';', ":");
+    if duration_str.split(':').count() != 4
+        || duration_str
+            .split(':')
+            .any(|part| part.parse::<f64>().is_err())
+    {
+        anyhow::bail!("'{}' is not a timecode (HH:MM:SS:FF)", duration_str);
+    }
+    let duration = Timecode::from_string(&duration_str, pt).await?;
+    if duration.is_zero() {
+        anyhow::bail!("Duration is zero");
+    }
+
+    // Remember everything we're about to change
+    let mut guard = RestoreGuard::new();
+    if params.get_bool("all_tracks", true) {
+        guard.save_track_selection(pt).await?;
+    }
+    guard.save_selection(pt).await?;
+    guard.save_edit_mode(pt).await?;
+    let mut selection = PtSelectionTimecode::new(pt).await?;
+    let (original_in, _) = selection.get_io(pt).await?;
+
+    let result = insert_section_inner(pt, params, &mut selection, &original_in, &duration).await;
+    // Restore, even if the insert failed
+    guard.finish(pt, result).await?;
+    log::info!("Inserted {} at {}", duration, original_in);
     Ok(())
 }
-pub async fn conform_insert(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
-    println!("Running Conform Insert");
-    let mut flag = false;
-    let original_mode = pt.get_edit_mode().await?;
-    pt.set_edit_mode("EMO_Shuffle").await?;
 
+async fn insert_section_inner(
+    pt: &mut ProtoolsSession,
+    params: &Params,
+    selection: &mut PtSelectionTimecode,
+    start: &Timecode,
+    duration: &Timecode,
+) -> R<()> {
+    if params.get_bool("all_tracks", true) {
+        let all = pt.get_all_track_names().await?;
+        pt.select_tracks_by_name(all).await?;
+    }
+
+    let mut end = Timecode::from_string(&start.to_string(), pt).await?;
+    end.add(duration);
+    selection.set_io(pt, start, &end).await?;
+
+    pt.set_edit_mode("EMO_Shuffle").await?;
     if pt.get_edit_mode().await? != "EMO_Shuffle" {
-        OS::keystroke(&["cmd", "f1"])?;
-        // std::thread::sleep(std::time::Duration::from_millis(35)); // Wait 50ms
-        pt.set_edit_mode("EMO_Shuffle").await?;
-        flag = true;
+        anyhow::bail!("Could not switch to Shuffle mode (is Shuffle locked?)");
     }
     OS::menu_click("Pro Tools", &["Edit", "Insert Silence"])?;
-    // OS::keystroke(&["cmd", "shift", "e"]).await?;
-    std::thread::sleep(std::time::Duration::from_millis(35)); // Wait 50ms
-    pt.set_edit_mode(&original_mode).await?;
-    if flag {
-        OS::keystroke(&["cmd", "f1"])?;
+    OS::waits::settle(OS::waits::Wait::Menu);
+    Ok(())
+}
+
+/// The `amount` param in samples, and whether `direction` is "left"
+fn selection_amount(clock: &SampleClock, params: &Params) -> R<(i64, bool)> {
+    // A bare number (from a wheel or fader binding) counts `unit`s
+    let samples = match params.get_onumber("amount") {
+        Some(amount) => {
+            clock.duration_to_samples(&format!("{}{}", amount, params.get_str("unit", "ms")))?
+        }
+        None => clock.duration_to_samples(params.get_str("amount", "1s"))?,
+    };
+    match params.get_str("direction", "right") {
+        "right" => Ok((samples, false)),
+        "left" => Ok((samples, true)),
+        other => anyhow::bail!("Unknown direction '{}' (use \"left\" or \"right\")", other),
+    }
+}
+
+/// Move the timeline selection (or cursor) by an amount
+///
+/// Params:
+/// - amount: "480smp", "10ms", "1s" or a timecode duration like "00:00:01:00" (default "1s")
+/// - unit: "smp", "ms" or "s" when `amount` is a number (default "ms")
+/// - direction: "right" (later) or "left" (earlier) (default "right")
+pub async fn nudge_selection(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let clock = SampleClock::new(pt).await?;
+    let (amount, left) = selection_amount(&clock, params)?;
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    let (start, _) = selection.get_io();
+    // Stop at the session start
+    let amount = if left { -amount } else { amount }.max(-start);
+    selection.slide(pt, amount).await?;
+    log::info!("Nudged the selection {} samples", amount);
+    Ok(())
+}
+
+/// Grow one edge of the timeline selection by an amount
+///
+/// `direction = "right"` moves the end later, `"left"` moves the start
+/// earlier. A negative amount ("-10ms") pulls that edge in instead, down to
+/// an empty selection.
+///
+/// Params:
+/// - amount: "480smp", "10ms", "1s" or a timecode duration like "00:00:01:00" (default "1s")
+/// - unit: "smp", "ms" or "s" when `amount` is a number (default "ms")
+/// - direction: "right" or "left" (default "right")
+pub async fn extend_selection(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let clock = SampleClock::new(pt).await?;
+    let (amount, left) = selection_amount(&clock, params)?;
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    let (start, end) = selection.get_io();
+    let (start, end) = if left {
+        ((start - amount).clamp(0, end), end)
+    } else {
+        (start, (end + amount).max(start))
+    };
+    selection.set_io(pt, start, end).await?;
+    log::info!("Selection is now {}-{}", start, end);
+    Ok(())
+}
+
+/// Move the selection to a typed location
+///
+/// Takes a timecode (or just its digits, "1000000" = 01:00:00:00),
+/// feet+frames ("90+08"), samples ("48000smp"), or an offset from the
+/// selection start ("+00:00:10:00", "-2s"). The selection keeps its length.
+///
+/// Params:
+/// - timecode: the location (default: ask, showing the current start)
+pub async fn goto_timecode(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let clock = SampleClock::new(pt).await?;
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    let (start, _) = selection.get_io();
+    let location = match params.get_ostring("timecode") {
+        Some(location) => location,
+        None => match crate::macos::window::show_input_dialog(
+            "Go to Timecode",
+            Some("Timecode, feet+frames, samples (smp) or +/- offset:"),
+            Some(&clock.to_timecode(start)),
+        )? {
+            Some(text) => text,
+            None => return Ok(()), // Cancelled
+        },
+    };
+    let position = clock.parse_position(&location, start)?;
+    selection.slide(pt, position - start).await?;
+    log::info!("Moved the selection to {}", clock.to_timecode(position));
+    Ok(())
+}
+
+/// Rename the selected clips from a template
+///
+/// Renames each clip that overlaps the timeline selection on the selected
+/// tracks, in timeline order, through PTSL. The template can use `{name}`
+/// (the current name), `{track}`, `{tc}` (the clip's start timecode) and
+/// `{counter}` (numbered from `start`; `{counter:03}` zero-pads). A clip
+/// used more than once is renamed once, numbered where it first appears.
+///
+/// Params:
+/// - name: the template, e.g. "{track}_{tc}_{counter:02}" (default: ask)
+/// - start: number of the first clip (default 1)
+/// - tc_separator: what goes between the timecode's fields (default "",
+///   so 01:00:10:00 reads "01001000")
+/// - preview: show the new names before renaming (default true)
+pub async fn rename_clip(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let template = match params.get_ostring("name") {
+        Some(template) => template,
+        None => match crate::macos::window::show_input_dialog(
+            "Rename Clips",
+            Some("New name ({name}, {track}, {tc}, {counter}):"),
+            Some("{name}"),
+        )? {
+            Some(text) if !text.trim().is_empty() => text.trim().to_string(),
+            _ => return Ok(()), // Cancelled
+        },
+    };
+    let separator = params.get_string("tc_separator", "");
+    let clock = SampleClock::new(pt).await?;
+    let clips = super::clips::selected_clips(pt).await?;
+
+    let mut seen = std::collections::HashSet::new();
+    let renames: Vec<(String, String)> = clips
+        .into_iter()
+        .filter(|(_, _, name)| seen.insert(name.clone()))
+        .zip(params.get_int("start", 1)..)
+        .map(|((clip_start, track, name), n)| {
+            let template = template.replace("{track}", &track).replace(
+                "{tc}",
+                &clock.to_timecode(clip_start).replace(':', &separator),
+            );
+            let new_name = super::tracks::expand_name_tokens(&template, &name, n);
+            (name, new_name)
+        })
+        .filter(|(name, new_name)| name != new_name)
+        .collect();
+    if renames.is_empty() {
+        anyhow::bail!("The selected clips already have those names");
+    }
+
+    if params.get_bool("preview", true) {
+        let list = renames
+            .iter()
+            .map(|(name, new_name)| format!("{} → {}", name, new_name))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let message = format!("Rename {} clip(s)?\n\n{}", renames.len(), list);
+        if !crate::macos::window::show_confirm_dialog("Rename Clips", &message, "Rename")? {
+            return Ok(());
+        }
     }
+    for (name, new_name) in &renames {
+        pt.rename_clip(name, new_name)
+            .await
+            .with_context(|| format!("Failed to rename '{}' to '{}'", name, new_name))?;
+    }
+    log::info!("Renamed {} clip(s)", renames.len());
+    OS::show_notification(&crate::notify::text(
+        "clips_renamed",
+        &[("count", &renames.len().to_string())],
+    ));
+    Ok(())
+}
+
+/// Remember the timeline selection and selected tracks in a register
+///
+/// Registers live until the daemon quits; storing to a slot replaces it.
+///
+/// Params:
+/// - slot: register number (default 1)
+pub async fn store_selection(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let slot = params.get_int("slot", 1);
+    let (in_time, out_time) = PtSelectionSamples::new(pt).await?.get_io();
+    let tracks = pt.get_selected_track_names().await?;
+    log::info!(
+        "Stored selection {}-{} on {} track(s) in slot {}",
+        in_time,
+        out_time,
+        tracks.len(),
+        slot
+    );
+    SELECTION_REGISTERS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(
+            slot,
+            SelectionRegister {
+                in_time,
+                out_time,
+                tracks,
+            },
+        );
+    Ok(())
+}
+
+/// Put back a selection stored with `store_selection`
+///
+/// Params:
+/// - slot: register number (default 1)
+/// - tracks: reselect the stored tracks too (default true)
+pub async fn recall_selection(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let slot = params.get_int("slot", 1);
+    let (in_time, out_time, tracks) = SELECTION_REGISTERS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|registers| registers.get(&slot))
+        .map(|register| (register.in_time, register.out_time, register.tracks.clone()))
+        .with_context(|| format!("Nothing stored in selection slot {}", slot))?;
+    if params.get_bool("tracks", true) && !tracks.is_empty() {
+        pt.select_tracks_by_name(tracks).await?;
+    }
+    PtSelectionSamples::new(pt)
+        .await?
+        .set_io(pt, in_time, out_time)
+        .await?;
+    log::info!(
+        "Recalled selection {}-{} from slot {}",
+        in_time,
+        out_time,
+        slot
+    );
     Ok(())
 }
+
 pub async fn toggle_mode(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
     let mode = pt.get_edit_mode().await?;
     if mode != "EMO_GridAbsolute" {
@@ -234,6 +885,34 @@ pub async fn toggle_tool(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
     }
     Ok(())
 }
+/// Recall one of the five Edit window zoom presets
+///
+/// Params:
+/// - preset: 1-5 (default 1)
+pub async fn zoom_preset(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let preset = params.get_int("preset", 1);
+    if !(1..=5).contains(&preset) {
+        anyhow::bail!("Zoom preset must be 1-5, got {}", preset);
+    }
+    let _: serde_json::Value = pt
+        .cmd(
+            CommandId::RecallZoomPreset,
+            serde_json::json!({ "zoom_preset": preset }),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Zoom the Edit window to the timeline selection
+///
+/// PTSL has no zoom other than presets, so this is Zoom Toggle (option-F),
+/// which zooms to the selection with Pro Tools' default Zoom Toggle
+/// preferences. A second press zooms back out.
+pub async fn zoom_to_selection(_pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    OS::focus_app("Pro Tools", "", true, false, 500)?;
+    OS::keystroke(&["option", "f"])
+}
+
 pub async fn click_a_button(_pt: &mut ProtoolsSession, params: &Params) -> R<()> {
     let button = params.get_string("button", "");
     if button.is_empty() {
@@ -0,0 +1,180 @@
+//! Tracked long-running jobs with a resource meter
+//!
+//! Renders (AudioSuite, commit, bounce) can take a while, and a hung render
+//! looks just like a slow one. While a tracked job runs, the menu bar shows
+//! system CPU plus Pro Tools CPU/memory so the user can tell the difference.
+
+use crate::config::ResourceMeterConfig;
+use crate::macos::sysmon;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Active meter settings (None = disabled). Replaced on config reload.
+static METER_CONFIG: Mutex<Option<ResourceMeterConfig>> = Mutex::new(None);
+
+/// Running jobs: (id, name, started)
+static ACTIVE_JOBS: Mutex<Vec<(u64, String, Instant)>> = Mutex::new(Vec::new());
+
+/// A meter thread is running. Only set or cleared while holding `ACTIVE_JOBS`,
+/// so a job starting as the meter winds down can't spawn a second one.
+static METER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Running batches: (action, stop notification id, stop requested)
+static BATCHES: Mutex<Vec<(&'static str, &'static str, Arc<AtomicBool>)>> = Mutex::new(Vec::new());
+
+/// Progress text of the running batch; the meter shows it ahead of its readout
+static BATCH_STATUS: Mutex<String> = Mutex::new(String::new());
+
+/// Apply resource meter settings from config (None or enabled = false disables it)
+pub fn set_config(config: Option<ResourceMeterConfig>) {
+    let config = config.filter(|c| c.enabled);
+    log::info!(
+        "Resource meter {}",
+        if config.is_some() {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    *METER_CONFIG.lock().unwrap() = config;
+}
+
+/// Marks a job as running until dropped
+pub struct JobGuard {
+    id: u64,
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        let mut jobs = ACTIVE_JOBS.lock().unwrap();
+        if let Some(pos) = jobs.iter().position(|(id, _, _)| *id == self.id) {
+            let (_, name, started) = jobs.remove(pos);
+            log::info!(
+                "Job '{}' finished after {:.1}s",
+                name,
+                started.elapsed().as_secs_f64()
+            );
+        }
+    }
+}
+
+/// Start tracking a job; the meter runs while any guard is alive
+pub fn track(name: &str) -> JobGuard {
+    let id = NEXT_JOB_ID.fetch_add(1, Ordering::SeqCst);
+    let interval = METER_CONFIG
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|c| Duration::from_millis(c.interval_ms));
+    let start_meter = {
+        let mut jobs = ACTIVE_JOBS.lock().unwrap();
+        jobs.push((id, name.to_string(), Instant::now()));
+        interval.is_some() && !METER_RUNNING.swap(true, Ordering::SeqCst)
+    };
+    log::info!("Job '{}' started", name);
+
+    if start_meter && let Some(interval) = interval {
+        std::thread::spawn(move || meter_loop(interval));
+    }
+    JobGuard { id }
+}
+
+/// A batch action running clip by clip until dropped
+///
+/// Pressing the action's hotkey again asks it to stop after the current
+/// item (see `request_stop`).
+pub struct Batch {
+    action: &'static str,
+    stop: Arc<AtomicBool>,
+}
+
+impl Batch {
+    /// Has the user asked the batch to stop?
+    pub fn stopped(&self) -> bool {
+        self.stop.load(Ordering::SeqCst)
+    }
+
+    /// Show the batch's progress in the menu bar
+    pub fn set_status(&self, text: &str) {
+        *BATCH_STATUS.lock().unwrap() = text.to_string();
+        crate::macos::menubar::set_status_text(text);
+    }
+}
+
+impl Drop for Batch {
+    fn drop(&mut self) {
+        BATCHES
+            .lock()
+            .unwrap()
+            .retain(|(action, _, _)| *action != self.action);
+        BATCH_STATUS.lock().unwrap().clear();
+        crate::macos::menubar::set_status_text("");
+    }
+}
+
+/// Start a batch for `action` (its registry name); `stopping` is the
+/// notification shown when a second press asks it to stop
+pub fn start_batch(action: &'static str, stopping: &'static str) -> Batch {
+    let stop = Arc::new(AtomicBool::new(false));
+    BATCHES
+        .lock()
+        .unwrap()
+        .push((action, stopping, Arc::clone(&stop)));
+    Batch { action, stop }
+}
+
+/// Ask `action`'s running batch to stop; false if it isn't running
+///
+/// Action wrappers call this before anything else, so the second press gets
+/// through while the batch holds Pro Tools busy.
+pub fn request_stop(action: &str) -> bool {
+    let batches = BATCHES.lock().unwrap();
+    let Some((_, stopping, stop)) = batches.iter().find(|(name, _, _)| *name == action) else {
+        return false;
+    };
+    stop.store(true, Ordering::SeqCst);
+    log::info!("Stopping '{}' after the current item", action);
+    crate::macos::show_notification(&crate::notify::text(stopping, &[]));
+    true
+}
+
+/// The menu bar text when nothing else is showing: the batch's progress, if any
+pub fn idle_status() -> String {
+    BATCH_STATUS.lock().unwrap().clone()
+}
+
+/// Update the menu bar readout until no jobs are left
+fn meter_loop(interval: Duration) {
+    loop {
+        let (count, elapsed) = {
+            let jobs = ACTIVE_JOBS.lock().unwrap();
+            let oldest = jobs.iter().map(|(_, _, started)| started.elapsed()).max();
+            if jobs.is_empty() {
+                METER_RUNNING.store(false, Ordering::SeqCst);
+            }
+            (jobs.len(), oldest.unwrap_or_default())
+        };
+        if count == 0 {
+            break;
+        }
+
+        let system = sysmon::system_cpu_percent().unwrap_or(0.0);
+        let readout = match sysmon::process_stats("Pro Tools") {
+            Ok(pt) => format!(
+                " {}s  CPU {:.0}%  PT {:.0}% {:.0}MB",
+                elapsed.as_secs(),
+                system,
+                pt.cpu_percent,
+                pt.mem_mb
+            ),
+            Err(_) => format!(" {}s  CPU {:.0}%", elapsed.as_secs(), system),
+        };
+        log::debug!("Resource meter:{}", readout);
+        crate::macos::menubar::set_status_text(&format!("{}{}", idle_status(), readout));
+        std::thread::sleep(interval);
+    }
+    crate::macos::menubar::set_status_text(&idle_status());
+}
@@ -0,0 +1,349 @@
+//! Peak and loudness measurement of exported audio files
+//!
+//! Reads PCM/float WAV files and measures sample peak and integrated loudness
+//! per ITU-R BS.1770 (K-weighting, 400 ms blocks with 75% overlap, absolute
+//! gate at -70 LUFS and relative gate 10 LU below). Used by
+//! `pt.clips_gain_stage`; Pro Tools has no analysis command over PTSL.
+
+use crate::prelude::*;
+use std::path::Path;
+
+/// Peak and integrated loudness of one or more files
+#[derive(Debug, Clone, Copy)]
+pub struct Measurement {
+    /// Highest sample, in dBFS
+    pub peak_db: f64,
+    /// Integrated loudness, in LUFS (None = too short or silent)
+    pub lufs: Option<f64>,
+}
+
+/// Decoded audio: one Vec of samples per channel
+struct Audio {
+    sample_rate: u32,
+    channels: Vec<Vec<f64>>,
+}
+
+/// Measure several files as if they were one programme (e.g. all selected clips)
+pub fn measure_files(paths: &[impl AsRef<Path>]) -> R<Measurement> {
+    let mut peak = 0.0f64;
+    let mut blocks = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        let audio = read_wav(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        peak = peak.max(sample_peak(&audio.channels));
+        blocks.extend(block_powers(&audio));
+    }
+    Ok(Measurement {
+        peak_db: to_db(peak),
+        lufs: integrate(&blocks),
+    })
+}
+
+fn to_db(amplitude: f64) -> f64 {
+    20.0 * amplitude.max(1e-10).log10()
+}
+
+fn sample_peak(channels: &[Vec<f64>]) -> f64 {
+    channels
+        .iter()
+        .flatten()
+        .fold(0.0, |peak, sample| peak.max(sample.abs()))
+}
+
+// ============================================================================
+// BS.1770 loudness
+// ============================================================================
+
+/// Block length and hop, in seconds
+const BLOCK_SECONDS: f64 = 0.4;
+const HOP_SECONDS: f64 = 0.1;
+
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+fn block_loudness(power: f64) -> f64 {
+    -0.691 + 10.0 * power.max(1e-20).log10()
+}
+
+/// Gated integrated loudness of a list of weighted block powers
+fn integrate(blocks: &[f64]) -> Option<f64> {
+    let above_absolute: Vec<f64> = blocks
+        .iter()
+        .copied()
+        .filter(|&power| block_loudness(power) > ABSOLUTE_GATE_LUFS)
+        .collect();
+    if above_absolute.is_empty() {
+        return None;
+    }
+    let mean = |powers: &[f64]| powers.iter().sum::<f64>() / powers.len() as f64;
+    let relative_gate = block_loudness(mean(&above_absolute)) + RELATIVE_GATE_LU;
+    let gated: Vec<f64> = above_absolute
+        .into_iter()
+        .filter(|&power| block_loudness(power) > relative_gate)
+        .collect();
+    (!gated.is_empty()).then(|| block_loudness(mean(&gated)))
+}
+
+/// Channel weighting: 1.0 for front channels, 1.41 for surrounds, LFE ignored
+/// (5.1 in Pro Tools' L C R Ls Rs LFE order)
+fn channel_weight(channel: usize, count: usize) -> f64 {
+    match (count, channel) {
+        (6, 5) => 0.0,
+        (6, 3 | 4) => 1.41,
+        _ => 1.0,
+    }
+}
+
+/// Weighted mean-square power of each 400 ms block
+fn block_powers(audio: &Audio) -> Vec<f64> {
+    let rate = audio.sample_rate as f64;
+    let block = (BLOCK_SECONDS * rate).round() as usize;
+    let hop = (HOP_SECONDS * rate).round() as usize;
+    let length = audio.channels.first().map_or(0, Vec::len);
+    if block == 0 || hop == 0 || length < block {
+        return Vec::new();
+    }
+
+    // Running sums of the squared K-weighted signal, per channel
+    let count = audio.channels.len();
+    let cumulative: Vec<(f64, Vec<f64>)> = audio
+        .channels
+        .iter()
+        .enumerate()
+        .map(|(index, samples)| {
+            let mut sums = Vec::with_capacity(samples.len() + 1);
+            sums.push(0.0);
+            let mut total = 0.0;
+            for sample in k_weight(samples, rate) {
+                total += sample * sample;
+                sums.push(total);
+            }
+            (channel_weight(index, count), sums)
+        })
+        .collect();
+
+    (0..=(length - block) / hop)
+        .map(|n| {
+            let (start, end) = (n * hop, n * hop + block);
+            cumulative
+                .iter()
+                .map(|(weight, sums)| weight * (sums[end] - sums[start]) / block as f64)
+                .sum()
+        })
+        .collect()
+}
+
+/// Apply the BS.1770 pre-filter (high shelf) and RLB high-pass at `rate`
+fn k_weight(samples: &[f64], rate: f64) -> Vec<f64> {
+    use std::f64::consts::PI;
+
+    // Shelf
+    let k = (PI * 1681.974450955533 / rate).tan();
+    let q = 0.7071752369554196;
+    let vh = 10f64.powf(3.999843853973347 / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b: [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    };
+
+    // High-pass
+    let k = (PI * 38.13547087602444 / rate).tan();
+    let q = 0.5003270373238773;
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad {
+        b: [1.0, -2.0, 1.0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    };
+
+    high_pass.run(&shelf.run(samples))
+}
+
+/// Direct form I biquad (a0 normalized to 1)
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+}
+
+impl Biquad {
+    fn run(&self, input: &[f64]) -> Vec<f64> {
+        let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+        input
+            .iter()
+            .map(|&x| {
+                let y = self.b[0] * x + self.b[1] * x1 + self.b[2] * x2
+                    - self.a[0] * y1
+                    - self.a[1] * y2;
+                (x2, x1, y2, y1) = (x1, x, y1, y);
+                y
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// WAV reading
+// ============================================================================
+
+const FORMAT_PCM: u16 = 1;
+const FORMAT_FLOAT: u16 = 3;
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+fn read_wav(path: &Path) -> R<Audio> {
+    parse_wav(&std::fs::read(path)?)
+}
+
+fn parse_wav(bytes: &[u8]) -> R<Audio> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        anyhow::bail!("Not a WAV file");
+    }
+    let u16_at = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+    let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+
+    let mut format = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = u32_at(pos + 4) as usize;
+        let body = pos + 8;
+        let end = (body + size).min(bytes.len());
+        match id {
+            b"fmt " if size >= 16 => {
+                // A file still being written can end inside its format chunk
+                if body + 16 > bytes.len() {
+                    anyhow::bail!("WAV format chunk is cut short");
+                }
+                let mut tag = u16_at(body);
+                if tag == FORMAT_EXTENSIBLE && size >= 26 {
+                    if body + 26 > bytes.len() {
+                        anyhow::bail!("WAV format chunk is cut short");
+                    }
+                    // The sub-format GUID starts with the real format tag
+                    tag = u16_at(body + 24);
+                }
+                let channels = u16_at(body + 2) as usize;
+                let sample_rate = u32_at(body + 4);
+                let bits = u16_at(body + 14);
+                format = Some((tag, channels, sample_rate, bits));
+            }
+            b"data" => {
+                let (tag, channels, sample_rate, bits) =
+                    format.context("WAV data chunk before its format chunk")?;
+                return decode(&bytes[body..end], tag, channels, sample_rate, bits);
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even length
+        pos = body + size + (size & 1);
+    }
+    anyhow::bail!("WAV file has no audio data")
+}
+
+fn decode(data: &[u8], tag: u16, channels: usize, sample_rate: u32, bits: u16) -> R<Audio> {
+    if channels == 0 || sample_rate == 0 {
+        anyhow::bail!("Invalid WAV format");
+    }
+    let width = bits as usize / 8;
+    let sample = |chunk: &[u8]| -> f64 {
+        match (tag, bits) {
+            (FORMAT_PCM, 16) => i16::from_le_bytes([chunk[0], chunk[1]]) as f64 / 32768.0,
+            (FORMAT_PCM, 24) => {
+                i32::from_le_bytes([0, chunk[0], chunk[1], chunk[2]]) as f64 / 2147483648.0
+            }
+            (FORMAT_PCM, 32) => i32::from_le_bytes(chunk.try_into().unwrap()) as f64 / 2147483648.0,
+            (FORMAT_FLOAT, 32) => f32::from_le_bytes(chunk.try_into().unwrap()) as f64,
+            _ => f64::from_le_bytes(chunk.try_into().unwrap()),
+        }
+    };
+    match (tag, bits) {
+        (FORMAT_PCM, 16 | 24 | 32) | (FORMAT_FLOAT, 32 | 64) => {}
+        _ => anyhow::bail!("Unsupported WAV format {} at {} bits", tag, bits),
+    }
+
+    let mut decoded = vec![Vec::with_capacity(data.len() / width / channels); channels];
+    for frame in data.chunks_exact(width * channels) {
+        for (channel, chunk) in frame.chunks_exact(width).enumerate() {
+            decoded[channel].push(sample(chunk));
+        }
+    }
+    Ok(Audio {
+        sample_rate,
+        channels: decoded,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(amplitude: f64, frequency: f64, rate: u32, seconds: f64) -> Vec<f64> {
+        (0..(rate as f64 * seconds) as usize)
+            .map(|n| {
+                amplitude * (2.0 * std::f64::consts::PI * frequency * n as f64 / rate as f64).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_full_scale_sine_loudness() {
+        // BS.1770: a 0 dBFS 997 Hz sine in one front channel reads -3.01 LUFS
+        for rate in [44100, 48000, 96000] {
+            let audio = Audio {
+                sample_rate: rate,
+                channels: vec![sine(1.0, 997.0, rate, 5.0)],
+            };
+            let lufs = integrate(&block_powers(&audio)).unwrap();
+            assert!((lufs + 3.01).abs() < 0.05, "{} Hz: {} LUFS", rate, lufs);
+        }
+    }
+
+    #[test]
+    fn test_parse_wav_and_gate_silence() {
+        let mut data = Vec::new();
+        for sample in sine(0.5, 997.0, 48000, 1.0) {
+            data.extend(((sample * 32767.0) as i16).to_le_bytes());
+        }
+        let mut wav = b"RIFF\0\0\0\0WAVEfmt ".to_vec();
+        wav.extend(16u32.to_le_bytes());
+        wav.extend(FORMAT_PCM.to_le_bytes());
+        wav.extend(1u16.to_le_bytes());
+        wav.extend(48000u32.to_le_bytes());
+        wav.extend((48000u32 * 2).to_le_bytes());
+        wav.extend(2u16.to_le_bytes());
+        wav.extend(16u16.to_le_bytes());
+        wav.extend(b"data");
+        wav.extend((data.len() as u32).to_le_bytes());
+        wav.extend(data);
+
+        let audio = parse_wav(&wav).unwrap();
+        assert_eq!(audio.sample_rate, 48000);
+        assert!((to_db(sample_peak(&audio.channels)) + 6.02).abs() < 0.01);
+
+        let silence = Audio {
+            sample_rate: 48000,
+            channels: vec![vec![0.0; 48000]],
+        };
+        assert_eq!(integrate(&block_powers(&silence)), None);
+    }
+
+    #[test]
+    fn test_parse_truncated_wav() {
+        let mut wav = b"RIFF\0\0\0\0WAVEfmt ".to_vec();
+        wav.extend(16u32.to_le_bytes());
+        wav.extend(FORMAT_PCM.to_le_bytes());
+        wav.extend(1u16.to_le_bytes());
+        assert!(parse_wav(&wav).is_err());
+
+        // Extensible format, cut off before the sub-format GUID
+        let mut wav = b"RIFF\0\0\0\0WAVEfmt ".to_vec();
+        wav.extend(40u32.to_le_bytes());
+        wav.extend(FORMAT_EXTENSIBLE.to_le_bytes());
+        wav.extend([0; 18]);
+        assert!(parse_wav(&wav).is_err());
+    }
+}
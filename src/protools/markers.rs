@@ -1,4 +1,6 @@
 use super::client::*;
+use super::ptsl::CommandId;
+use super::timecode::SampleClock;
 use crate::actions_async;
 use crate::prelude::*;
 
@@ -6,14 +8,25 @@ actions_async!("pt", markers, {
     go_to_next_marker,
     go_to_quick_marker,
     update_quick_marker,
+    palette,
+    create,
+    #[destructive]
+    delete,
+    renumber,
+    recall,
+    export,
+    import,
 });
 
+/// Quick markers live at 31000 + their number
+const QUICK_MARKER_BASE: i64 = 31000;
+
 pub async fn update_quick_marker(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
     let mut number = params.get_int("number", 0);
     let default_text = format!("QM {}", number);
     let text = params.get_string("name", &default_text);
     let color = params.get_string("color", "magenta");
-    number += 31000;
+    number += QUICK_MARKER_BASE;
     let selection = PtSelectionSamples::new(pt).await?;
     let (st, et) = selection.get_io();
     pt.edit_marker(
@@ -31,7 +44,7 @@ pub async fn update_quick_marker(pt: &mut ProtoolsSession, params: &Params) -> R
 
 pub async fn go_to_quick_marker(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
     let mut number = params.get_int("number", 0);
-    number += 31000;
+    number += QUICK_MARKER_BASE;
     let mut selection = PtSelectionSamples::new(pt).await?;
     let (st, et) = selection.get_io();
     let markers = pt.get_all_markers().await.unwrap_or(Vec::new());
@@ -71,3 +84,674 @@ pub async fn go_to_next_marker(pt: &mut ProtoolsSession, params: &Params) -> R<(
     OS::keystroke(&["left"])?;
     Ok(())
 }
+
+/// Pick a memory location from a list and recall it
+///
+/// Faster than the Memory Locations window on long reels: search by name or
+/// comment, pick from what matches (a single match is recalled at once).
+/// Markers move the cursor there, selections recall their range.
+///
+/// Parameters:
+/// - `search`: string - only locations whose name or comment contains this
+///   (default: ask, leave the answer empty to list them all)
+/// - `ask`: boolean - false lists every location without asking for a search (default: true)
+/// - `ruler`: string - only markers on this ruler (default: all)
+pub async fn palette(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let ruler = params.get_string("ruler", "");
+    let search = match params.get_ostring("search") {
+        Some(search) => search,
+        None if params.get_bool("ask", true) => {
+            match crate::macos::window::show_input_dialog(
+                "Memory Locations",
+                Some("Search (empty for all):"),
+                None,
+            )? {
+                Some(text) => text.trim().to_string(),
+                None => return Ok(()), // Cancelled
+            }
+        }
+        None => String::new(),
+    };
+
+    let mut locations: Vec<serde_json::Value> = pt
+        .get_all_markers()
+        .await
+        .context("Failed to get memory locations")?
+        .into_iter()
+        .filter(|location| {
+            ruler.is_empty() || location["track_name"].as_str().unwrap_or("") == ruler
+        })
+        .filter(|location| {
+            search.is_empty()
+                || ["name", "comments"].iter().any(|field| {
+                    crate::soft_match(location[*field].as_str().unwrap_or(""), &search)
+                })
+        })
+        .collect();
+    if locations.is_empty() {
+        OS::show_notification(&crate::notify::text(
+            "markers_not_found",
+            &[("search", &search)],
+        ));
+        return Ok(());
+    }
+    locations.sort_by_key(|location| location["number"].as_i64().unwrap_or(0));
+
+    let chosen = if locations.len() == 1 {
+        0
+    } else {
+        let labels: Vec<String> = locations.iter().map(palette_label).collect();
+        let prompt = format!("{} memory locations - type a number to jump:", labels.len());
+        match crate::macos::window::show_list_dialog("Memory Locations", &prompt, &labels)? {
+            Some(index) => index,
+            None => return Ok(()), // Cancelled
+        }
+    };
+    let location = &locations[chosen];
+
+    let time = |key: &str| {
+        location[key]
+            .as_str()
+            .and_then(|time| time.parse::<i64>().ok())
+    };
+    let start = time("start_time").with_context(|| {
+        format!(
+            "Memory location '{}' has no time to go to",
+            location["name"].as_str().unwrap_or("")
+        )
+    })?;
+    let end = match location["time_properties"].as_str() {
+        Some("TP_Selection") => time("end_time").unwrap_or(start),
+        _ => start,
+    };
+    log::info!("Recalling memory location {}", palette_label(location));
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    selection.set_io(pt, start, end).await?;
+    Ok(())
+}
+
+/// "12  Verse 2 - comment" for the palette list
+fn palette_label(location: &serde_json::Value) -> String {
+    let mut label = format!(
+        "{}  {}",
+        location["number"].as_i64().unwrap_or(0),
+        location["name"].as_str().unwrap_or("")
+    );
+    if let Some(comments) = location["comments"].as_str().filter(|c| !c.is_empty()) {
+        label.push_str(" - ");
+        label.push_str(comments);
+    }
+    label
+}
+
+// ============================================================================
+// Memory location management
+// ============================================================================
+
+/// Create a memory location at the timeline selection
+///
+/// Parameters:
+/// - `number`: location number (default: the lowest free one)
+/// - `name`, `comments`, `color` (marker color name, default "magenta")
+/// - `kind`: "marker", "selection" or "none" (default: selection if there is
+///   one, otherwise marker)
+/// - `ruler`: named marker ruler, or `track`: track to put the marker on
+///   (default: the main ruler)
+/// - `zoom`, `pre_post_roll`, `track_visibility`, `track_heights`,
+///   `group_enables`: store those settings with the location (default: false)
+/// - `window_configuration`: window configuration number to store (default: none)
+pub async fn create(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let locations = pt
+        .get_all_markers()
+        .await
+        .context("Failed to get memory locations")?;
+    let used: Vec<i64> = locations.iter().map(location_number).collect();
+    let number = match params.get_int("number", 0) {
+        0 => (1..).find(|n| !used.contains(n)).unwrap_or(1),
+        number if used.contains(&number) => {
+            anyhow::bail!("Memory location {} already exists", number)
+        }
+        number => number,
+    };
+
+    let selection = PtSelectionSamples::new(pt).await?;
+    let (start, end) = selection.get_io();
+    let time_properties = match params.get_string("kind", "").as_str() {
+        "" if end > start => "TProperties_Selection",
+        "" | "marker" => "TProperties_Marker",
+        "selection" => "TProperties_Selection",
+        "none" => "TProperties_None",
+        other => anyhow::bail!("Unknown memory location kind '{}'", other),
+    };
+    let (location, track_name) = match (params.get_ostring("track"), params.get_ostring("ruler")) {
+        (Some(track), _) => (MarkerLocation::Track, track),
+        (None, Some(ruler)) => (MarkerLocation::NamedRuler, ruler),
+        (None, None) => (MarkerLocation::MainRuler, String::new()),
+    };
+    let window_configuration = params.get_int("window_configuration", 0);
+    let name = params.get_string("name", &format!("Marker {}", number));
+
+    let _: serde_json::Value = pt
+        .cmd(
+            CommandId::CreateMemoryLocation,
+            serde_json::json!({
+                "number": number,
+                "name": name,
+                "start_time": start.to_string(),
+                "end_time": end.to_string(),
+                "time_properties": time_properties,
+                "reference": "MLReference_FollowTrackTimebase",
+                "general_properties": {
+                    "zoom_settings": params.get_bool("zoom", false),
+                    "pre_post_roll_times": params.get_bool("pre_post_roll", false),
+                    "track_visibility": params.get_bool("track_visibility", false),
+                    "track_heights": params.get_bool("track_heights", false),
+                    "group_enables": params.get_bool("group_enables", false),
+                    "window_configuration": window_configuration > 0,
+                    "window_configuration_index": window_configuration.max(1),
+                    "venue_snapshot_index": 1
+                },
+                "comments": params.get_string("comments", ""),
+                "color_index": marker_color_index(&params.get_string("color", "magenta")),
+                "location": location.as_str(),
+                "track_name": track_name
+            }),
+        )
+        .await?;
+    log::info!("Created memory location {} '{}'", number, name);
+    Ok(())
+}
+
+/// Delete memory locations
+///
+/// Parameters:
+/// - `number`: one location, or `numbers`: a list of them
+/// - `from` / `to`: every location numbered in that range (inclusive)
+pub async fn delete(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let mut numbers = params.get_int_vec("numbers");
+    if let Some(number) = params.get_oint("number") {
+        numbers.push(number);
+    }
+    if let (Some(from), Some(to)) = (params.get_oint("from"), params.get_oint("to")) {
+        let locations = pt
+            .get_all_markers()
+            .await
+            .context("Failed to get memory locations")?;
+        numbers.extend(
+            locations
+                .iter()
+                .map(location_number)
+                .filter(|number| (from..=to).contains(number)),
+        );
+    }
+    if numbers.is_empty() {
+        anyhow::bail!("No memory locations to delete (set number, numbers or from/to)");
+    }
+    numbers.sort();
+    numbers.dedup();
+    pt.clear_memory_locations(&numbers).await?;
+    log::info!("Deleted memory location(s) {:?}", numbers);
+    Ok(())
+}
+
+/// Renumber memory locations 1, 2, 3... in timeline order
+///
+/// Pro Tools can't renumber in place, so each location that moves is
+/// recreated under its new number (name, comments, color and stored settings
+/// carry over). Quick markers are left alone.
+///
+/// Parameters:
+/// - `start`: first number (default: 1)
+/// - `ruler`: only renumber markers on this ruler (default: all)
+pub async fn renumber(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let start = params.get_int("start", 1).max(1);
+    let ruler = params.get_string("ruler", "");
+    let mut locations: Vec<serde_json::Value> = pt
+        .get_all_markers()
+        .await
+        .context("Failed to get memory locations")?
+        .into_iter()
+        .filter(|location| location_number(location) < QUICK_MARKER_BASE)
+        .filter(|location| {
+            ruler.is_empty() || location["track_name"].as_str().unwrap_or("") == ruler
+        })
+        .collect();
+    locations.sort_by_key(|location| {
+        (
+            location["start_time"]
+                .as_str()
+                .and_then(|time| time.parse::<i64>().ok())
+                .unwrap_or(i64::MAX),
+            location_number(location),
+        )
+    });
+
+    let moves: Vec<(serde_json::Value, i64)> = locations
+        .into_iter()
+        .zip(start..)
+        .filter(|(location, number)| location_number(location) != *number)
+        .collect();
+    if moves.is_empty() {
+        OS::show_notification(&crate::notify::text("markers_in_order", &[]));
+        return Ok(());
+    }
+
+    // Park every moving location out of the way first, so no new number
+    // collides with one that hasn't moved yet
+    let park = QUICK_MARKER_BASE - 1000;
+    let mut parked = Vec::new();
+    for (index, (location, number)) in moves.iter().enumerate() {
+        let temporary = park + index as i64;
+        move_location(pt, location, temporary).await?;
+        parked.push((location, temporary, *number));
+    }
+    for (location, temporary, number) in parked {
+        let mut location = location.clone();
+        location["number"] = temporary.into();
+        move_location(pt, &location, number).await?;
+    }
+    OS::show_notification(&crate::notify::text(
+        "markers_renumbered",
+        &[("count", &moves.len().to_string())],
+    ));
+    Ok(())
+}
+
+/// Recreate a memory location under a new number
+async fn move_location(
+    pt: &mut ProtoolsSession,
+    location: &serde_json::Value,
+    number: i64,
+) -> R<()> {
+    let mut body = location.clone();
+    body["number"] = number.into();
+    let _: serde_json::Value = pt
+        .cmd(CommandId::CreateMemoryLocation, body)
+        .await
+        .with_context(|| format!("Failed to create memory location {}", number))?;
+    pt.clear_memory_locations(&[location_number(location)])
+        .await
+}
+
+/// Recall a memory location by number, applying its stored zoom, track and
+/// window settings like clicking it in the Memory Locations window
+///
+/// Parameters:
+/// - `number`: location number
+pub async fn recall(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let number = params
+        .get_oint("number")
+        .context("Set the memory location number to recall")?;
+    let _: serde_json::Value = pt
+        .cmd(
+            CommandId::SelectMemoryLocation,
+            serde_json::json!({ "number": number }),
+        )
+        .await
+        .with_context(|| format!("Failed to recall memory location {}", number))?;
+    Ok(())
+}
+
+fn location_number(location: &serde_json::Value) -> i64 {
+    location["number"].as_i64().unwrap_or(0)
+}
+
+// ============================================================================
+// Marker lists
+// ============================================================================
+
+/// Marker list file layouts
+#[derive(Clone, Copy, PartialEq)]
+enum ListFormat {
+    Csv,
+    Tsv,
+    /// Media Composer marker list: user, timecode, track, color, comment, duration
+    Avid,
+}
+
+impl ListFormat {
+    fn parse(name: &str) -> R<Self> {
+        match name.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "tsv" | "txt" => Ok(Self::Tsv),
+            "avid" => Ok(Self::Avid),
+            other => anyhow::bail!("Unknown marker list format '{}' (csv, tsv or avid)", other),
+        }
+    }
+
+    /// From a file's extension (.csv, else tab-separated)
+    fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Self::Csv,
+            _ => Self::Tsv,
+        }
+    }
+
+    fn separator(self) -> char {
+        if self == Self::Csv { ',' } else { '\t' }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Avid => "txt",
+        }
+    }
+}
+
+const LIST_HEADER: [&str; 7] = [
+    "Number", "Name", "Start", "End", "Ruler", "Color", "Comments",
+];
+
+/// Write every memory location to a marker list with timecodes
+///
+/// Parameters:
+/// - `format`: "csv" (default), "tsv" or "avid" (Media Composer marker import)
+/// - `path`: output file (default: "<session> Markers.csv/.tsv/.txt" next to the session)
+/// - `ruler`: only markers on this ruler (default: all)
+/// - `avid_track`: track column for the avid format (default "V1")
+pub async fn export(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let format = ListFormat::parse(params.get_str("format", "csv"))?;
+    let ruler = params.get_string("ruler", "");
+    let clock = SampleClock::new(pt).await?;
+    let mut locations: Vec<serde_json::Value> = pt
+        .get_all_markers()
+        .await
+        .context("Failed to get memory locations")?
+        .into_iter()
+        .filter(|location| location_number(location) < QUICK_MARKER_BASE)
+        .filter(|location| {
+            ruler.is_empty() || location["track_name"].as_str().unwrap_or("") == ruler
+        })
+        .collect();
+    locations.sort_by_key(|location| location_time(location, "start_time"));
+
+    let session = pt.get_session_path().await?;
+    let path = match params.get_ostring("path") {
+        Some(path) => std::path::PathBuf::from(path),
+        None => session.with_file_name(format!(
+            "{} Markers.{}",
+            session.file_stem().unwrap_or_default().to_string_lossy(),
+            format.extension()
+        )),
+    };
+
+    let avid_track = params.get_string("avid_track", "V1");
+    let mut out = String::new();
+    if format != ListFormat::Avid {
+        out.push_str(&LIST_HEADER.join(&format.separator().to_string()));
+        out.push('\n');
+    }
+    for location in &locations {
+        let text = |key: &str| location[key].as_str().unwrap_or("").to_string();
+        let start = location_time(location, "start_time").unwrap_or(0);
+        let end = location_time(location, "end_time").filter(|&end| end > start);
+        let color = color_name(location["color_index"].as_i64().unwrap_or(0));
+        let fields: Vec<String> = if format == ListFormat::Avid {
+            // Duration in frames; markers are one frame long
+            let frames = end.map_or(1, |end| {
+                (clock.to_frames(end) - clock.to_frames(start)).max(1)
+            });
+            vec![
+                "pthkd".to_string(),
+                clock.to_timecode(start),
+                avid_track.clone(),
+                avid_color(color).to_string(),
+                [text("name"), text("comments")]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" - "),
+                frames.to_string(),
+            ]
+        } else {
+            vec![
+                location_number(location).to_string(),
+                text("name"),
+                clock.to_timecode(start),
+                end.map(|end| clock.to_timecode(end)).unwrap_or_default(),
+                text("track_name"),
+                color.to_string(),
+                text("comments"),
+            ]
+        };
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|field| list_field(field, format.separator()))
+            .collect();
+        out.push_str(&fields.join(&format.separator().to_string()));
+        out.push('\n');
+    }
+    std::fs::write(&path, out).with_context(|| format!("Failed to write {}", path.display()))?;
+    log::info!("Wrote {} markers to {}", locations.len(), path.display());
+    OS::show_notification(&crate::notify::text(
+        "markers_exported",
+        &[("count", &locations.len().to_string())],
+    ));
+    Ok(())
+}
+
+/// Create markers from a marker list (as written by `export`)
+///
+/// Reads the Name, Start, End, Ruler, Color and Comments columns by their
+/// header; Number is kept when it's free. Media Composer marker lists
+/// (`format = "avid"`) are read by position instead.
+///
+/// Parameters:
+/// - `path`: the list (required)
+/// - `format`: "csv", "tsv" or "avid" (default: from the extension)
+/// - `ruler`: put every marker on this ruler (default: the list's Ruler column)
+/// - `color`: marker color when the list has none (default "magenta")
+pub async fn import(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let path = std::path::PathBuf::from(
+        params
+            .get_ostring("path")
+            .context("Set the marker list path")?,
+    );
+    let format = match params.get_ostring("format") {
+        Some(format) => ListFormat::parse(&format)?,
+        None => ListFormat::from_path(&path),
+    };
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let rows = read_list(&text, format)?;
+    if rows.is_empty() {
+        anyhow::bail!("No markers in {}", path.display());
+    }
+
+    let clock = SampleClock::new(pt).await?;
+    let mut used: Vec<i64> = pt
+        .get_all_markers()
+        .await
+        .context("Failed to get memory locations")?
+        .iter()
+        .map(location_number)
+        .collect();
+    let ruler = params.get_ostring("ruler");
+    let default_color = params.get_string("color", "magenta");
+
+    let mut created = 0;
+    for row in rows {
+        let start = clock
+            .to_samples(&row.start)
+            .with_context(|| format!("Marker '{}'", row.name))?;
+        let end = match row.end.as_deref().filter(|end| !end.trim().is_empty()) {
+            Some(end) => clock.to_samples(end)?.max(start),
+            None => start,
+        };
+        let number = match row.number.filter(|number| !used.contains(number)) {
+            Some(number) => number,
+            None => (1..).find(|n| !used.contains(n)).unwrap_or(1),
+        };
+        used.push(number);
+        let ruler = ruler
+            .clone()
+            .or(row.ruler)
+            .filter(|ruler| !ruler.is_empty());
+        let (location, track_name) = match ruler {
+            Some(ruler) => (MarkerLocation::NamedRuler, ruler),
+            None => (MarkerLocation::MainRuler, String::new()),
+        };
+        let color = row.color.unwrap_or_else(|| default_color.clone());
+        let _: serde_json::Value = pt
+            .cmd(
+                CommandId::CreateMemoryLocation,
+                serde_json::json!({
+                    "number": number,
+                    "name": row.name,
+                    "start_time": start.to_string(),
+                    "end_time": end.to_string(),
+                    "time_properties": if end > start { "TProperties_Selection" } else { "TProperties_Marker" },
+                    "reference": "MLReference_FollowTrackTimebase",
+                    "general_properties": {
+                        "zoom_settings": false,
+                        "pre_post_roll_times": false,
+                        "track_visibility": false,
+                        "track_heights": false,
+                        "group_enables": false,
+                        "window_configuration": false,
+                        "window_configuration_index": 1,
+                        "venue_snapshot_index": 1
+                    },
+                    "comments": row.comments,
+                    "color_index": marker_color_index(&color),
+                    "location": location.as_str(),
+                    "track_name": track_name
+                }),
+            )
+            .await
+            .with_context(|| format!("Failed to create marker '{}'", row.name))?;
+        created += 1;
+    }
+    log::info!("Imported {} markers from {}", created, path.display());
+    OS::show_notification(&crate::notify::text(
+        "markers_imported",
+        &[("count", &created.to_string())],
+    ));
+    Ok(())
+}
+
+/// One marker read from a list
+struct ListRow {
+    number: Option<i64>,
+    name: String,
+    start: String,
+    end: Option<String>,
+    ruler: Option<String>,
+    color: Option<String>,
+    comments: String,
+}
+
+fn read_list(text: &str, format: ListFormat) -> R<Vec<ListRow>> {
+    let records = super::session::parse_csv(text, format.separator());
+    let records = records
+        .into_iter()
+        .filter(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+    if format == ListFormat::Avid {
+        // user, timecode, track, color, comment[, duration]
+        return Ok(records
+            .filter(|record| record.len() >= 5)
+            .map(|record| ListRow {
+                number: None,
+                name: record[4].trim().to_string(),
+                start: record[1].trim().to_string(),
+                end: None,
+                ruler: None,
+                color: Some(record[3].trim().to_lowercase()).filter(|c| !c.is_empty()),
+                comments: String::new(),
+            })
+            .collect());
+    }
+
+    let mut records = records.peekable();
+    let header: Vec<String> = records
+        .next()
+        .context("Marker list is empty")?
+        .iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|column| column == name);
+    let start_column = column("start")
+        .or_else(|| column("timecode"))
+        .context("Marker list has no Start column")?;
+    let (number, name, end, ruler, color, comments) = (
+        column("number"),
+        column("name"),
+        column("end"),
+        column("ruler"),
+        column("color"),
+        column("comments"),
+    );
+    let field = |record: &[String], index: Option<usize>| {
+        index
+            .and_then(|index| record.get(index))
+            .map(|field| field.trim().to_string())
+            .filter(|field| !field.is_empty())
+    };
+    Ok(records
+        .map(|record| ListRow {
+            number: field(&record, number).and_then(|number| number.parse().ok()),
+            name: field(&record, name).unwrap_or_default(),
+            start: field(&record, Some(start_column)).unwrap_or_default(),
+            end: field(&record, end),
+            ruler: field(&record, ruler),
+            color: field(&record, color),
+            comments: field(&record, comments).unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Quote a field if it holds the separator, a quote or a newline
+fn list_field(text: &str, separator: char) -> String {
+    if text.contains([separator, '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn location_time(location: &serde_json::Value, key: &str) -> Option<i64> {
+    location[key].as_str().and_then(|time| time.parse().ok())
+}
+
+/// Marker color name for a Pro Tools color index (see `marker_color_index`)
+fn color_name(index: i64) -> &'static str {
+    const NAMES: [&str; 16] = [
+        "dark purple",
+        "purple",
+        "pink",
+        "magenta",
+        "red",
+        "orange",
+        "dark yellow",
+        "yellow",
+        "light green",
+        "green",
+        "light blue",
+        "blue",
+        "dark blue",
+        "white",
+        "grey",
+        "black",
+    ];
+    NAMES
+        .get((index - 1).max(0) as usize)
+        .copied()
+        .unwrap_or("magenta")
+}
+
+/// Nearest Media Composer marker color
+fn avid_color(color: &str) -> &'static str {
+    match color {
+        "red" | "orange" => "red",
+        "green" | "light green" => "green",
+        "blue" | "dark blue" => "blue",
+        "light blue" => "cyan",
+        "pink" | "magenta" | "purple" | "dark purple" => "magenta",
+        "yellow" | "dark yellow" => "yellow",
+        "black" | "grey" => "black",
+        _ => "white",
+    }
+}
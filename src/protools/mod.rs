@@ -1,5 +1,6 @@
 use crate::prelude::*;
-use std::sync::OnceLock;
+use std::sync::{OnceLock, mpsc};
+use std::time::Duration;
 
 // Generated protobuf module
 #[allow(dead_code)]
@@ -8,13 +9,23 @@ pub mod ptsl {
 }
 
 // Module declarations
+pub mod autosave;
 pub mod client;
+pub mod clips;
 pub mod edit;
+pub mod jobs;
+pub mod loudness;
 pub mod markers;
 pub mod plugins;
+pub mod recovery;
+pub mod restore;
 pub mod session;
+pub mod tally;
 pub mod timecode;
 pub mod tracks;
+pub mod transport;
+pub mod window_layout;
+pub mod workspace;
 
 // Re-exports
 pub use client::ProtoolsSession;
@@ -28,15 +39,77 @@ pub fn init_runtime() {
     TOKIO_RT.set(rt).unwrap();
 }
 
+/// Longest an action waits for its Pro Tools command (`command_timeout_ms` param)
+pub const COMMAND_TIMEOUT_MS: u64 = 120_000;
+
+/// Batch actions run as long as their clips take, so they wait a day instead
+/// (`command_timeout_ms` still overrides it). Freeze and commit renders wait
+/// on their own `timeout_ms`.
+pub const BATCH_ACTIONS: &[&str] = &[
+    "plugins_rx_batch",
+    "session_bounce_stems",
+    "plugins_audiosuite_batch",
+    "tracks_freeze_selected",
+    "tracks_unfreeze_selected",
+    "tracks_commit_selected",
+];
+pub const BATCH_TIMEOUT_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Default `command_timeout_ms` for an action
+pub fn default_timeout_ms(action_name: &str) -> u64 {
+    if BATCH_ACTIONS.contains(&action_name) {
+        BATCH_TIMEOUT_MS
+    } else {
+        COMMAND_TIMEOUT_MS
+    }
+}
+
+/// A Pro Tools command running on its own thread
+pub struct CommandHandle {
+    receiver: mpsc::Receiver<R<()>>,
+}
+
+impl CommandHandle {
+    /// Wait for the command to finish and return its result
+    ///
+    /// Gives up after `timeout`; the command itself keeps running (a thread
+    /// can't be cancelled), but the caller gets an error instead of a guess.
+    pub fn wait(self, timeout: Duration) -> R<()> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(mpsc::RecvTimeoutError::Timeout) => anyhow::bail!(
+                "Pro Tools command still running after {:.1}s",
+                timeout.as_secs_f64()
+            ),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                anyhow::bail!("Pro Tools command panicked")
+            }
+        }
+    }
+}
+
 /// Run an async ProTools command from a sync context
-pub fn run_command<F, Fut>(f: F)
+///
+/// The command runs on its own thread; the returned handle reports when it
+/// finishes and whether it succeeded.
+pub fn run_command<F, Fut>(f: F) -> CommandHandle
 where
     F: FnOnce() -> Fut + Send + 'static,
-    Fut: std::future::Future<Output = ()> + Send + 'static,
+    Fut: std::future::Future<Output = R<()>> + Send + 'static,
 {
+    // Carry a silent hotkey's setting over to the command thread
+    let silent = crate::notify::is_silenced();
+    // ...and a macro's undo count
+    let recording = crate::undo::current();
+    let (sender, receiver) = mpsc::channel();
     std::thread::spawn(move || {
-        TOKIO_RT.get().unwrap().block_on(f());
+        let _silenced = crate::notify::silence(silent);
+        let _recording = crate::undo::attach(recording);
+        let result = TOKIO_RT.get().unwrap().block_on(f());
+        // The caller may have stopped waiting
+        sender.send(result).ok();
     });
+    CommandHandle { receiver }
 }
 
 /// Combine all module registries into one
@@ -48,5 +121,31 @@ pub fn get_action_registry()
     registry.extend(edit::get_edit_registry());
     registry.extend(session::get_session_registry());
     registry.extend(plugins::get_plugins_registry());
+    registry.extend(clips::get_clips_registry());
+    registry.extend(workspace::get_workspace_registry());
+    registry.extend(window_layout::get_window_layout_registry());
+    registry.extend(transport::get_transport_registry());
     registry
 }
+
+/// Registry names of the actions marked `#[destructive]`, from all modules
+pub fn destructive_actions() -> &'static std::collections::HashSet<&'static str> {
+    static DESTRUCTIVE: std::sync::OnceLock<std::collections::HashSet<&'static str>> =
+        std::sync::OnceLock::new();
+    DESTRUCTIVE.get_or_init(|| {
+        [
+            tracks::get_tracks_destructive(),
+            markers::get_markers_destructive(),
+            edit::get_edit_destructive(),
+            session::get_session_destructive(),
+            plugins::get_plugins_destructive(),
+            clips::get_clips_destructive(),
+            workspace::get_workspace_destructive(),
+            window_layout::get_window_layout_destructive(),
+            transport::get_transport_destructive(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    })
+}
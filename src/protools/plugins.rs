@@ -7,9 +7,22 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 actions_async!("pt", plugins, {
+    #[destructive]
     audiosuite,
+    #[destructive]
+    audiosuite_batch,
     multitap_selector,
+    #[destructive]
     send_receive_rx,
+    #[destructive]
+    rx_process,
+    #[destructive]
+    rx_batch,
+    list_audiosuite,
+    insert,
+    #[destructive]
+    remove,
+    bypass_slot,
 });
 
 // ============================================================================
@@ -19,13 +32,13 @@ actions_async!("pt", plugins, {
 /// Minimal MenuItem struct for parsing AudioSuite menu structure
 /// Only contains fields needed for plugin discovery
 #[derive(Debug, Deserialize)]
-struct MenuItem {
-    title: String,
-    children: Option<Vec<MenuItem>>,
+pub(super) struct MenuItem {
+    pub(super) title: String,
+    pub(super) children: Option<Vec<MenuItem>>,
 }
 
 /// Parse menu JSON from Swift
-fn parse_menus(json: &str) -> R<Vec<MenuItem>> {
+pub(super) fn parse_menus(json: &str) -> R<Vec<MenuItem>> {
     #[derive(Deserialize)]
     struct MenuResponse {
         menus: Vec<MenuItem>,
@@ -37,17 +50,325 @@ fn parse_menus(json: &str) -> R<Vec<MenuItem>> {
 // Command Implementations
 // ============================================================================
 
+/// Open an AudioSuite plugin, optionally load a preset, and press a button
+///
+/// Params:
+/// - plugin: AudioSuite plugin (soft matched against the menu)
+/// - preset: preset to load before pressing `button` (default: leave it as it is)
+/// - preset_popup / settings_popup: the plugin window's preset popups, searched
+///   in that order (default "Librarian Menu" / "Settings Menu")
+/// - button: button to press, e.g. "Render" (default: none)
+/// - close: close the window afterwards (default false)
+/// - save: save the session afterwards (default true)
 pub async fn audiosuite(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
     let plugin = params.get_string("plugin", "");
     let button = params.get_string("button", "");
     let close = params.get_bool("close", false);
     let save = params.get_bool("save", true);
+    if let Some(preset) = params.get_ostr("preset") {
+        let (category, exact_name) = find_plugin_category(&plugin)?;
+        activate_plugin_internal(&category, &exact_name).await?;
+        load_preset(&exact_name, preset, params)?;
+    }
     call_plugin(&plugin, &button, close).await?;
     if save {
         pt.save_session().await?;
     }
     Ok(())
 }
+/// Render an AudioSuite plugin on every clip in the selection, one at a time
+///
+/// Works through the clips that overlap the timeline selection on each
+/// selected track, selecting and rendering one clip per pass - the same as
+/// clicking Render 200 times, without the clicking. The menu bar shows
+/// progress and a notification comes every `notify_every` clips; press the
+/// same hotkey again to stop after the current clip. The selection is put
+/// back afterwards.
+///
+/// Params:
+/// - plugin: AudioSuite plugin, as in pt.plugins_audiosuite (required)
+/// - preset / preset_popup / settings_popup: preset to load first, as in
+///   pt.plugins_audiosuite
+/// - stop_on_error: stop at the first failed clip (default true)
+/// - notify_every: clips between progress notifications (default 25, 0 = none)
+/// - timeout_ms: how long one clip may take to render (default 60000)
+pub async fn audiosuite_batch(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let plugin = params.get_string("plugin", "");
+    if plugin.is_empty() {
+        anyhow::bail!("audiosuite_batch needs a plugin, e.g. plugin = \"RX 11 De-click\"");
+    }
+    let tracks = pt.get_selected_track_names().await?;
+    if tracks.is_empty() {
+        anyhow::bail!("Select a track first");
+    }
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    let (sel_start, sel_end) = selection.get_io();
+    let mut clips = Vec::new();
+    for track in &tracks {
+        clips.extend(
+            pt.get_clip_boundaries(track)
+                .await?
+                .into_iter()
+                .filter(|&(start, end)| start < sel_end && end > sel_start)
+                .map(|(start, end)| (track.clone(), start, end)),
+        );
+    }
+    if clips.is_empty() {
+        anyhow::bail!("No clips in the selection");
+    }
+
+    // Open the plugin once and leave it open for every clip
+    let (category, exact_name) = find_plugin_category(&plugin)?;
+    activate_plugin_internal(&category, &exact_name).await?;
+    if let Some(preset) = params.get_ostr("preset") {
+        load_preset(&exact_name, preset, params)?;
+    }
+
+    let batch = jobs::start_batch("plugins_audiosuite_batch", "audiosuite_batch_stopping");
+    let result =
+        audiosuite_batch_clips(pt, &batch, params, &exact_name, &clips, &mut selection).await;
+    drop(batch);
+
+    // Put the original selection back
+    pt.select_tracks_by_name(tracks).await.ok();
+    selection.set_io(pt, sel_start, sel_end).await.ok();
+
+    let (done, failed) = result?;
+    let (count, total) = (done.to_string(), clips.len().to_string());
+    let text = if done + failed < clips.len() {
+        crate::notify::text(
+            "audiosuite_batch_stopped",
+            &[
+                ("plugin", &exact_name),
+                ("count", &count),
+                ("total", &total),
+            ],
+        )
+    } else if failed > 0 {
+        crate::notify::text(
+            "audiosuite_batch_done_failures",
+            &[
+                ("plugin", &exact_name),
+                ("count", &count),
+                ("failed", &failed.to_string()),
+            ],
+        )
+    } else {
+        crate::notify::text(
+            "audiosuite_batch_done",
+            &[("plugin", &exact_name), ("count", &count)],
+        )
+    };
+    OS::show_notification(&text);
+    Ok(())
+}
+
+/// Render each clip in turn, returning (rendered, failed) - fewer than all
+/// of them if the batch was stopped
+async fn audiosuite_batch_clips(
+    pt: &mut ProtoolsSession,
+    batch: &jobs::Batch,
+    params: &Params,
+    plugin: &str,
+    clips: &[(String, i64, i64)],
+    selection: &mut PtSelectionSamples,
+) -> R<(usize, usize)> {
+    let stop_on_error = params.get_bool("stop_on_error", true);
+    let notify_every = params.get_int("notify_every", 25).max(0) as usize;
+    let timeout_ms = params.get_timeout_ms("timeout_ms", 60000);
+
+    let (mut done, mut failed) = (0, 0);
+    let mut current_track = None;
+    for (index, (track, start, end)) in clips.iter().enumerate() {
+        if batch.stopped() {
+            log::info!("AudioSuite batch aborted after {} clips", index);
+            break;
+        }
+        batch.set_status(&format!(" AS {}/{}", index + 1, clips.len()));
+        if notify_every > 0 && index > 0 && index % notify_every == 0 {
+            OS::show_notification(&crate::notify::text(
+                "audiosuite_batch_progress",
+                &[
+                    ("plugin", plugin),
+                    ("done", &index.to_string()),
+                    ("total", &clips.len().to_string()),
+                ],
+            ));
+        }
+        log::info!(
+            "AudioSuite batch: clip {}/{} on '{}' ({}-{})",
+            index + 1,
+            clips.len(),
+            track,
+            start,
+            end
+        );
+
+        let rendered = async {
+            if current_track != Some(track) {
+                pt.select_tracks_by_name(vec![track.clone()]).await?;
+                current_track = Some(track);
+            }
+            selection.set_io(pt, *start, *end).await?;
+            call_plugin(plugin, "Render", false).await?;
+            wait_for_pro_tools(pt, timeout_ms).await
+        }
+        .await
+        .with_context(|| {
+            format!(
+                "AudioSuite batch failed on clip {}/{}",
+                index + 1,
+                clips.len()
+            )
+        });
+        match rendered {
+            Ok(()) => done += 1,
+            Err(e) if stop_on_error => return Err(e),
+            Err(e) => {
+                log::warn!("{:#}", e);
+                failed += 1;
+            }
+        }
+    }
+    Ok((done, failed))
+}
+
+/// Re-read the AudioSuite menu and pick a plugin to open from the list
+///
+/// Refreshes the cached plugin list (after installing plugins) that config
+/// reloads check `plugin` names against; `pthkd actions` lists it too.
+pub async fn list_audiosuite(_pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    *PLUGIN_MAP.lock().unwrap() = None;
+    get_plugin_map()?;
+    let plugins = known_plugins().unwrap_or_default();
+    if plugins.is_empty() {
+        anyhow::bail!("No AudioSuite plugins found");
+    }
+    let prompt = format!(
+        "{} AudioSuite plugins - type a number to open:",
+        plugins.len()
+    );
+    let Some(index) = crate::macos::window::show_list_dialog("AudioSuite", &prompt, &plugins)?
+    else {
+        return Ok(()); // Cancelled
+    };
+    call_plugin(&plugins[index], "", false).await
+}
+/// The selected tracks' insert slot, from the `slot` param ("a"-"j" or 1-10)
+///
+/// `selector` / `insert_button` are the slot's accessibility names, with
+/// `{slot}` standing for its letter; `window` overrides the Mix-then-Edit
+/// window lookup.
+async fn insert_slots(
+    pt: &mut ProtoolsSession,
+    params: &Params,
+) -> R<(Vec<String>, String, String, String)> {
+    let letter = OS::inserts::slot_letter(&params.get_string("slot", "a"))?
+        .to_ascii_uppercase()
+        .to_string();
+    let selector = params
+        .get_string("selector", "Insert selector {slot}")
+        .replace("{slot}", &letter);
+    let insert_button = params
+        .get_string("insert_button", "Insert {slot}")
+        .replace("{slot}", &letter);
+    let tracks = pt.get_selected_track_names().await?;
+    if tracks.is_empty() {
+        anyhow::bail!("No tracks selected");
+    }
+    OS::focus_app("Pro Tools", "", true, false, 500)?;
+    Ok((tracks, letter, selector, insert_button))
+}
+
+/// Put a plugin in an insert slot on each selected track
+///
+/// PTSL has no command for inserts, so this picks the plugin from the
+/// slot's insert selector in the Mix window (the Edit window if Mix isn't
+/// open), which has to show inserts. Pair with `remove` or `bypass_slot` for
+/// a reference EQ that comes and goes on one key.
+///
+/// Params:
+/// - plugin: plugin as the insert menu names it (required, soft matched)
+/// - slot: insert slot, "a"-"j" or 1-10 (default "a")
+/// - format: "stereo"/"multichannel" or "mono"/"multi-mono" (default stereo)
+/// - window / selector / insert_button: see `insert_slots`
+pub async fn insert(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let plugin = params.get_string("plugin", "");
+    if plugin.is_empty() {
+        anyhow::bail!("'plugin' is required");
+    }
+    let format = params.get_string("format", "stereo");
+    let window = params.get_string("window", "");
+    let (tracks, letter, selector, insert_button) = insert_slots(pt, params).await?;
+    let mut inserted = plugin.clone();
+    for track in &tracks {
+        let slot = OS::inserts::InsertSlot {
+            window: &window,
+            track,
+            selector: &selector,
+            insert: &insert_button,
+        };
+        inserted = OS::inserts::insert(&slot, &plugin, &format)
+            .with_context(|| format!("Failed to insert {} on '{}'", plugin, track))?;
+    }
+    OS::show_notification(&crate::notify::text(
+        "plugin_inserted",
+        &[
+            ("plugin", &inserted),
+            ("slot", &letter),
+            ("count", &tracks.len().to_string()),
+        ],
+    ));
+    Ok(())
+}
+
+/// Empty an insert slot on each selected track
+///
+/// Params: slot, window, selector, insert_button - as in `insert`
+pub async fn remove(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let window = params.get_string("window", "");
+    let (tracks, letter, selector, insert_button) = insert_slots(pt, params).await?;
+    for track in &tracks {
+        let slot = OS::inserts::InsertSlot {
+            window: &window,
+            track,
+            selector: &selector,
+            insert: &insert_button,
+        };
+        OS::inserts::remove(&slot)
+            .with_context(|| format!("Failed to clear insert {} on '{}'", letter, track))?;
+    }
+    OS::show_notification(&crate::notify::text(
+        "plugin_removed",
+        &[("slot", &letter), ("count", &tracks.len().to_string())],
+    ));
+    Ok(())
+}
+
+/// Toggle the bypass of an insert slot on each selected track
+///
+/// Command-clicks the slot's insert, as you would by hand.
+///
+/// Params: slot, window, selector, insert_button - as in `insert`
+pub async fn bypass_slot(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let window = params.get_string("window", "");
+    let (tracks, letter, selector, insert_button) = insert_slots(pt, params).await?;
+    for track in &tracks {
+        let slot = OS::inserts::InsertSlot {
+            window: &window,
+            track,
+            selector: &selector,
+            insert: &insert_button,
+        };
+        OS::inserts::toggle_bypass(&slot)
+            .with_context(|| format!("Failed to bypass insert {} on '{}'", letter, track))?;
+    }
+    OS::show_notification(&crate::notify::text(
+        "plugin_bypassed",
+        &[("slot", &letter), ("count", &tracks.len().to_string())],
+    ));
+    Ok(())
+}
 pub async fn send_receive_rx(_pt: &mut ProtoolsSession, params: &Params) -> R<()> {
     let version = params.get_int("version", 11);
     let plugin = format!("RX {} Connect", version);
@@ -58,35 +379,248 @@ pub async fn send_receive_rx(_pt: &mut ProtoolsSession, params: &Params) -> R<()
         // Send to RX for analysis
         call_plugin(&plugin, "Analyze", false).await?;
     } else if crate::soft_match(&app, &rx_app) {
-        // Send back to Pro Tools - Cmd+Enter returns to DAW
-        OS::keystroke(&["cmd", "enter"])?;
-        let mut windows = 2;
-        // while windows == 1 {
-        //     let app_windows = crate::macos::ui_elements::get_window_titles(&app)?;
-        //     windows = app_windows
-        //         .into_iter()
-        //         .filter(|w| w == "Pro Tools 1")
-        //         .count();
-        // }
-        // println!("Multiple windows detected");
-        while windows > 1 {
-            std::thread::sleep(std::time::Duration::from_millis(100)); // Wait 50ms
-            let app_windows = OS::get_window_titles(&app)?;
-            windows = app_windows
-                .into_iter()
-                .filter(|w| w == "Pro Tools 1")
-                .count();
+        return_from_rx(&app, &plugin).await?;
+    }
+
+    Ok(())
+}
+
+/// Hands-off RX round trip: send the selection to RX, run a module preset, render back
+///
+/// Params:
+/// - `version`: RX version (default 11)
+/// - `module`: RX module as named in its menu, e.g. "De-click"
+/// - `preset`: module preset to apply (empty = module defaults)
+/// - `chain`: several modules instead, e.g. [["De-click", "Medium clicks"], ["De-hum", ""]]
+/// - `module_menu`: RX menu that lists the modules (default "Modules")
+/// - `preset_popup`: name of the module's preset popup (default "Preset")
+/// - `timeout_ms`: how long to wait for RX and for processing (default 30000)
+pub async fn rx_process(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let trip = RxRoundTrip::from_params(params)?;
+    if OS::get_current_app()? != "Pro Tools" {
+        anyhow::bail!("rx_process must start from Pro Tools");
+    }
+    trip.run(pt).await
+}
+
+/// RX round trip (see `rx_process`) for every clip in the selection, one at a time
+///
+/// Works through the clips on the first selected track that overlap the
+/// timeline selection. The menu bar shows progress; press the same hotkey
+/// again to stop after the current clip. Takes the same params as
+/// `rx_process`.
+pub async fn rx_batch(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let trip = RxRoundTrip::from_params(params)?;
+    let track = pt
+        .get_selected_track_names()
+        .await?
+        .into_iter()
+        .next()
+        .context("Select a track first")?;
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    let (sel_start, sel_end) = selection.get_io();
+    let clips: Vec<(i64, i64)> = pt
+        .get_clip_boundaries(&track)
+        .await?
+        .into_iter()
+        .filter(|&(start, end)| start < sel_end && end > sel_start)
+        .collect();
+    if clips.is_empty() {
+        anyhow::bail!("No clips in the selection on '{}'", track);
+    }
+
+    let batch = jobs::start_batch("plugins_rx_batch", "rx_batch_stopping");
+    let result = rx_batch_clips(pt, &batch, &trip, &track, &clips, &mut selection).await;
+    drop(batch);
+
+    // Put the original selection back
+    selection.set_io(pt, sel_start, sel_end).await.ok();
+
+    match result {
+        Ok(done) if done == clips.len() => {
+            OS::show_notification(&crate::notify::text(
+                "rx_batch_done",
+                &[("count", &done.to_string())],
+            ));
+            Ok(())
+        }
+        Ok(done) => {
+            OS::show_notification(&crate::notify::text(
+                "rx_batch_stopped",
+                &[
+                    ("count", &done.to_string()),
+                    ("total", &clips.len().to_string()),
+                ],
+            ));
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Process each clip in turn, returning how many were done before any abort
+async fn rx_batch_clips(
+    pt: &mut ProtoolsSession,
+    batch: &jobs::Batch,
+    trip: &RxRoundTrip,
+    track: &str,
+    clips: &[(i64, i64)],
+    selection: &mut PtSelectionSamples,
+) -> R<usize> {
+    pt.select_tracks_by_name(vec![track.to_string()]).await?;
+    for (index, &(start, end)) in clips.iter().enumerate() {
+        if batch.stopped() {
+            log::info!("RX batch aborted after {} clips", index);
+            return Ok(index);
         }
-        println!("one window detected");
-        OS::focus_app("Pro Tools", "", true, false, 50).ok();
-        OS::wait_for_window("Pro Tools", &plugin, OS::WindowCondition::Focused, 50).ok();
-        // Focus Pro Tools and wait for confirmation (switch but don't launch)
+        batch.set_status(&format!(" RX {}/{}", index + 1, clips.len()));
+        log::info!(
+            "RX batch: clip {}/{} ({}-{})",
+            index + 1,
+            clips.len(),
+            start,
+            end
+        );
 
-        // Now render the changes back
-        call_plugin(&plugin, "Render", false).await?;
+        OS::focus_app("Pro Tools", "", true, false, 1000)?;
+        selection.set_io(pt, start, end).await?;
+        trip.run(pt)
+            .await
+            .with_context(|| format!("RX batch failed on clip {}/{}", index + 1, clips.len()))?;
     }
+    Ok(clips.len())
+}
 
-    Ok(())
+/// An RX Connect round trip through a chain of module presets
+struct RxRoundTrip {
+    plugin: String,
+    rx_app: String,
+    /// (module, preset) pairs, preset empty = module defaults
+    chain: Vec<(String, String)>,
+    module_menu: String,
+    preset_popup: String,
+    timeout_ms: u64,
+}
+
+impl RxRoundTrip {
+    fn from_params(params: &Params) -> R<Self> {
+        let version = params.get_int("version", 11);
+        let mut chain = params.get_string_pairs("chain");
+        if chain.is_empty() {
+            let module = params.get_string("module", "");
+            if module.is_empty() {
+                anyhow::bail!("RX round trip needs a module, e.g. module = \"De-click\"");
+            }
+            chain.push((module, params.get_string("preset", "")));
+        }
+        Ok(Self {
+            plugin: format!("RX {} Connect", version),
+            rx_app: format!("RX {}", version),
+            chain,
+            module_menu: params.get_string("module_menu", "Modules"),
+            preset_popup: params.get_string("preset_popup", "Preset"),
+            timeout_ms: params.get_timeout_ms("timeout_ms", 30000),
+        })
+    }
+
+    /// Send the Pro Tools selection to RX, process it and render it back
+    async fn run(&self, pt: &mut ProtoolsSession) -> R<()> {
+        // Send the selection over and wait for RX to come up with it
+        call_plugin(&self.plugin, "Analyze", false).await?;
+        OS::focus_app(&self.rx_app, "", true, false, self.timeout_ms as i32)
+            .with_context(|| format!("{} did not come to the front", self.rx_app))?;
+        let rx = OS::get_current_app()?;
+
+        for (module, preset) in &self.chain {
+            self.apply_module(&rx, module, preset)?;
+        }
+
+        return_from_rx(&rx, &self.plugin).await?;
+        wait_for_pro_tools(pt, self.timeout_ms).await
+    }
+
+    /// Open a module, apply the preset and process
+    fn apply_module(&self, rx: &str, module: &str, preset: &str) -> R<()> {
+        OS::menu_click(rx, &[&self.module_menu, module])
+            .with_context(|| format!("Could not open RX module '{}'", module))?;
+        if !OS::wait_for_window(rx, module, OS::WindowCondition::Exists, 5000)? {
+            anyhow::bail!("RX module window '{}' did not appear", module);
+        }
+        if !preset.is_empty() {
+            OS::select_popup_menu_item(rx, module, &self.preset_popup, preset)
+                .with_context(|| format!("Could not select RX preset '{}'", preset))?;
+        }
+        {
+            let _job = jobs::track(&format!("RX: {}", module));
+            OS::click_button(rx, module, "Render")?;
+            wait_for_rx_render(rx, module, self.timeout_ms)?;
+        }
+        OS::close_window(rx, module, Some(2000)).ok();
+        Ok(())
+    }
+}
+
+/// Wait for Pro Tools to finish the AudioSuite render (PTSL answers again once it's idle)
+async fn wait_for_pro_tools(pt: &mut ProtoolsSession, timeout_ms: u64) -> R<()> {
+    let started = std::time::Instant::now();
+    let timeout = std::time::Duration::from_millis(timeout_ms);
+
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    while started.elapsed() < timeout {
+        if pt.get_transport_state().await.is_ok() {
+            return Ok(());
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    }
+    anyhow::bail!(
+        "Pro Tools did not finish rendering within {} ms",
+        timeout_ms
+    )
+}
+
+/// Send the RX result back to Pro Tools and render it into the clip
+async fn return_from_rx(rx_app: &str, plugin: &str) -> R<()> {
+    // Send back to Pro Tools - Cmd+Enter returns to DAW
+    OS::keystroke(&["cmd", "enter"])?;
+    let mut windows = 2;
+    while windows > 1 {
+        std::thread::sleep(std::time::Duration::from_millis(100)); // Wait 50ms
+        let app_windows = OS::get_window_titles(rx_app)?;
+        windows = app_windows
+            .into_iter()
+            .filter(|w| w == "Pro Tools 1")
+            .count();
+    }
+    log::debug!("RX returned the audio to Pro Tools");
+
+    // Focus Pro Tools and wait for confirmation (switch but don't launch)
+    OS::focus_app("Pro Tools", "", true, false, 50).ok();
+    OS::wait_for_window("Pro Tools", plugin, OS::WindowCondition::Focused, 50).ok();
+
+    // Now render the changes back
+    call_plugin(plugin, "Render", false).await
+}
+
+/// Wait for RX to finish processing (the module shows a Cancel button while it runs)
+fn wait_for_rx_render(rx_app: &str, module: &str, timeout_ms: u64) -> R<()> {
+    let started = std::time::Instant::now();
+    let timeout = std::time::Duration::from_millis(timeout_ms);
+
+    // Give processing a moment to start
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    while started.elapsed() < timeout {
+        let buttons = OS::get_window_buttons(rx_app, module).unwrap_or_default();
+        if !buttons.iter().any(|b| b == "Cancel") {
+            log::info!(
+                "RX '{}' finished in {} ms",
+                module,
+                started.elapsed().as_millis()
+            );
+            return Ok(());
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    anyhow::bail!("RX '{}' did not finish within {} ms", module, timeout_ms)
 }
 pub async fn multitap_selector(_pt: &mut ProtoolsSession, params: &Params) -> R<()> {
     let plugins = params.get_string_vec("plugins");
@@ -169,12 +703,72 @@ fn get_plugin_map() -> R<HashMap<String, (String, String)>> {
         log::info!("Building AudioSuite plugin map cache...");
         let map = build_plugin_map()?;
         log::info!("Plugin map cached with {} entries", map.len());
+        if let Err(e) = save_plugin_names(&map) {
+            log::warn!("Failed to save the AudioSuite plugin list: {:#}", e);
+        }
         *cache = Some(map);
     }
 
     Ok(cache.as_ref().unwrap().clone())
 }
 
+/// audiosuite_plugins.json, next to config.toml: the menu as last read, so
+/// config reloads can check plugin names without asking Pro Tools
+fn plugin_list_path() -> R<std::path::PathBuf> {
+    Ok(crate::config::get_config_path()?.with_file_name("audiosuite_plugins.json"))
+}
+
+fn save_plugin_names(map: &HashMap<String, (String, String)>) -> R<()> {
+    let mut names: Vec<&String> = map.values().map(|(_, name)| name).collect();
+    names.sort();
+    std::fs::write(plugin_list_path()?, serde_json::to_string_pretty(&names)?)?;
+    Ok(())
+}
+
+/// AudioSuite menu items from this run's menu scan, or the last saved one
+/// (None = the menu has never been read)
+pub fn known_plugins() -> Option<Vec<String>> {
+    if let Some(map) = PLUGIN_MAP.lock().unwrap().as_ref() {
+        let mut names: Vec<String> = map.values().map(|(_, name)| name.clone()).collect();
+        names.sort();
+        return Some(names);
+    }
+    let json = std::fs::read_to_string(plugin_list_path().ok()?).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Check the plugin names in an AudioSuite action's params against the known
+/// menu, so a typo shows up at reload rather than when the key is pressed.
+/// Passes when the menu has never been read.
+pub fn check_params(action: &str, params: &HashMap<String, toml::Value>) -> R<()> {
+    let key = match action.strip_prefix("pt.").unwrap_or(action) {
+        "plugins_audiosuite" | "plugins_audiosuite_batch" => "plugin",
+        "plugins_multitap_selector" => "plugins",
+        _ => return Ok(()),
+    };
+    let Some(known) = known_plugins() else {
+        return Ok(());
+    };
+    let names: Vec<&str> = match params.get(key) {
+        Some(toml::Value::String(name)) => vec![name.as_str()],
+        Some(toml::Value::Array(names)) => names.iter().filter_map(|v| v.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    for name in names.into_iter().filter(|name| !name.is_empty()) {
+        // Same matching as find_plugin_category
+        let found = known
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(name) || crate::soft_match(known, name));
+        if !found {
+            anyhow::bail!(
+                "unknown AudioSuite plugin '{}' (pt.plugins_list_audiosuite lists them)",
+                name
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Find the category for a plugin in the AudioSuite menu using cached HashMap
 /// Returns (category_name, exact_plugin_name)
 fn find_plugin_category(plugin_name: &str) -> R<(String, String)> {
@@ -227,6 +821,50 @@ async fn activate_plugin(plugin_name: &str) -> R<()> {
     activate_plugin_internal(&category, &exact_name).await
 }
 
+/// Load a preset in an open AudioSuite window by name
+///
+/// Looks through the preset (librarian) popup first, then the settings
+/// popup, matching the name loosely; the error lists what the popup offers.
+fn load_preset(exact_name: &str, preset: &str, params: &Params) -> R<()> {
+    let window = format!("AudioSuite: {}", exact_name);
+    let popups = [
+        params.get_string("preset_popup", "Librarian Menu"),
+        params.get_string("settings_popup", "Settings Menu"),
+    ];
+    let mut offered = Vec::new();
+    for popup in &popups {
+        let items = match OS::get_popup_menu_items("Pro Tools", &window, popup) {
+            Ok(items) => items,
+            Err(e) => {
+                log::debug!("No popup '{}' in '{}': {:#}", popup, window, e);
+                continue;
+            }
+        };
+        let found = items
+            .iter()
+            .find(|item| item.eq_ignore_ascii_case(preset))
+            .or_else(|| items.iter().find(|item| crate::soft_match(item, preset)));
+        if let Some(item) = found {
+            log::info!("Loading {} preset '{}'", exact_name, item);
+            OS::select_popup_menu_item("Pro Tools", &window, popup, item)?;
+            OS::waits::settle(OS::waits::Wait::Menu);
+            return Ok(());
+        }
+        if offered.is_empty() {
+            offered = items;
+        }
+    }
+    anyhow::bail!(
+        "No {} preset '{}' (presets: {})",
+        exact_name,
+        preset,
+        offered.join(", ")
+    )
+}
+
+/// Longest an AudioSuite render's progress dialog is waited on
+const RENDER_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3600);
+
 pub async fn call_plugin(plugin: &str, button: &str, close: bool) -> R<()> {
     // Get the exact plugin name from the menu (search once)
     let exact_name = if !plugin.is_empty() {
@@ -237,10 +875,15 @@ pub async fn call_plugin(plugin: &str, button: &str, close: bool) -> R<()> {
         plugin.to_string()
     };
 
+    // Track the render so the resource meter shows while it runs
+    let _job = (!button.is_empty()).then(|| jobs::track(&format!("AudioSuite: {}", exact_name)));
+
     if !button.is_empty() {
         let window = format!("AudioSuite: {}", exact_name);
         OS::click_button("Pro Tools", &window, button)?;
-        std::thread::sleep(std::time::Duration::from_millis(35)); // Wait 50ms
+        OS::waits::settle(OS::waits::Wait::Click);
+        // Keep the meter up until the render's progress dialog closes
+        super::transport::wait_for_progress(super::transport::PROGRESS_APPEAR, RENDER_TIMEOUT);
     }
     if close {
         let window = format!("AudioSuite: {}", exact_name);
@@ -0,0 +1,237 @@
+//! Pro Tools crash detection and session recovery
+//!
+//! Hears about Pro Tools quitting from NSWorkspace's terminate notification
+//! (see `macos::app_events`). If a fresh crash report
+//! shows up in ~/Library/Logs/DiagnosticReports it was a crash rather than a
+//! normal quit: the crash is logged, the user is notified and offered a
+//! one-click relaunch that reopens the last session.
+
+use super::TOKIO_RT;
+use super::client::ProtoolsSession;
+use crate::prelude::*;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const APP_NAME: &str = "Pro Tools";
+
+/// Last session path seen while Pro Tools was running
+static LAST_SESSION: Mutex<Option<String>> = Mutex::new(None);
+
+/// Whether Pro Tools is running, kept current by the workspace notifications
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+static START: Once = Once::new();
+
+/// Slack for a crash report written just before the quit notification arrived
+const REPORT_SLACK: Duration = Duration::from_secs(3);
+
+/// How often to refresh the last session path over PTSL
+const SESSION_REFRESH: Duration = Duration::from_secs(30);
+
+/// Time allowed for ReportCrash to write the crash report
+const REPORT_DELAY: Duration = Duration::from_secs(5);
+
+/// How long to wait for PTSL to come up after a relaunch
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Start the background crash watcher (safe to call more than once)
+///
+/// Requires `init_runtime()` to have been called first. Launches and quits
+/// arrive through `app_launched` / `app_quit`, called by `macos::app_events`.
+pub fn start() {
+    START.call_once(|| {
+        let running = OS::get_running_apps()
+            .map(|apps| apps.iter().any(|app| app == APP_NAME))
+            .unwrap_or(false);
+        RUNNING.store(running, Ordering::SeqCst);
+        TOKIO_RT.get().unwrap().spawn(session_loop());
+    });
+}
+
+/// An app launched (workspace notification)
+pub fn app_launched(app: &str) {
+    if app == APP_NAME {
+        RUNNING.store(true, Ordering::SeqCst);
+    }
+}
+
+/// An app quit (workspace notification)
+pub fn app_quit(app: &str) {
+    if app != APP_NAME || !RUNNING.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let Some(rt) = TOKIO_RT.get() else {
+        return;
+    };
+    // Its PTSL session id is gone; the next action registers again
+    super::client::disconnect();
+    let quit_at = SystemTime::now();
+    rt.spawn(async move {
+        tokio::time::sleep(REPORT_DELAY).await;
+        if crash_report_since(quit_at - REPORT_SLACK) {
+            handle_crash();
+        } else {
+            log::info!("Pro Tools quit normally");
+        }
+    });
+}
+
+/// Keep the last session path current while Pro Tools runs
+async fn session_loop() {
+    loop {
+        if RUNNING.load(Ordering::SeqCst)
+            && let Ok(path) = current_session_path().await
+        {
+            *LAST_SESSION.lock().unwrap() = Some(path);
+        }
+        tokio::time::sleep(SESSION_REFRESH).await;
+    }
+}
+
+async fn current_session_path() -> R<String> {
+    let mut pt = ProtoolsSession::shared().await?;
+    let path = pt.get_session_path().await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+fn home_dir() -> PathBuf {
+    PathBuf::from(std::env::var("HOME").unwrap_or_default())
+}
+
+/// True if a Pro Tools crash report was written after `since`
+fn crash_report_since(since: SystemTime) -> bool {
+    let reports = home_dir()
+        .join("Library")
+        .join("Logs")
+        .join("DiagnosticReports");
+    let Ok(entries) = std::fs::read_dir(&reports) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        entry.file_name().to_string_lossy().starts_with(APP_NAME)
+            && entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified >= since)
+    })
+}
+
+/// Crash history log: one "unix_time<TAB>local_time<TAB>session" line per crash
+fn crash_log_path() -> PathBuf {
+    home_dir()
+        .join("Library")
+        .join("Logs")
+        .join("pthkd-crashes.log")
+}
+
+/// Append this crash to the history log, returning (total, last 30 days)
+fn record_crash(session: &str) -> (usize, usize) {
+    use std::io::Write;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = crash_log_path();
+    if let Ok(mut file) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+    {
+        writeln!(
+            file,
+            "{}\t{}\t{}",
+            now,
+            crate::macos::helpers::local_timestamp(),
+            session
+        )
+        .ok();
+    }
+
+    let history = std::fs::read_to_string(&path).unwrap_or_default();
+    let times: Vec<u64> = history
+        .lines()
+        .filter_map(|line| line.split('\t').next()?.parse().ok())
+        .collect();
+    let recent = times
+        .iter()
+        .filter(|&&t| now.saturating_sub(t) < 30 * 24 * 60 * 60)
+        .count();
+    (times.len(), recent)
+}
+
+fn handle_crash() {
+    let session = LAST_SESSION.lock().unwrap().clone().unwrap_or_default();
+    let (total, recent) = record_crash(&session);
+    log::error!(
+        "💥 Pro Tools crashed ({} in the last 30 days, {} total). Last session: {}",
+        recent,
+        total,
+        session
+    );
+    OS::show_notification(&crate::notify::text(
+        "pro_tools_crashed",
+        &[("count", &recent.to_string())],
+    ));
+
+    // Offer recovery without blocking the watcher
+    std::thread::spawn(move || {
+        let message = if session.is_empty() {
+            "Pro Tools quit unexpectedly. Relaunch it?".to_string()
+        } else {
+            format!(
+                "Pro Tools quit unexpectedly. Relaunch it and reopen\n{}?",
+                session
+            )
+        };
+        match crate::macos::window::show_confirm_dialog("Pro Tools Crashed", &message, "Relaunch") {
+            Ok(true) => {
+                if let Err(e) = relaunch() {
+                    log::error!("Relaunch failed: {:#}", e);
+                    OS::show_notification(&crate::notify::text(
+                        "relaunch_failed",
+                        &[("error", &e.to_string())],
+                    ));
+                }
+            }
+            Ok(false) => {}
+            Err(e) => log::error!("Recovery dialog failed: {:#}", e),
+        }
+    });
+}
+
+/// Relaunch Pro Tools and reopen the last known session
+///
+/// Blocks until PTSL answers (or times out), so call it off the main thread.
+pub fn relaunch() -> R<()> {
+    let session = LAST_SESSION.lock().unwrap().clone();
+    OS::launch_app(APP_NAME)?;
+
+    let Some(session) = session.filter(|s| !s.is_empty()) else {
+        log::info!("Relaunched Pro Tools (no session to reopen)");
+        return Ok(());
+    };
+
+    // Run on a separate thread so this is safe to call from inside the runtime
+    std::thread::spawn(move || {
+        TOKIO_RT.get().unwrap().block_on(async move {
+            let deadline = Instant::now() + LAUNCH_TIMEOUT;
+            let mut pt = loop {
+                match ProtoolsSession::shared().await {
+                    Ok(pt) => break pt,
+                    Err(_) if Instant::now() < deadline => {
+                        tokio::time::sleep(Duration::from_secs(2)).await
+                    }
+                    Err(e) => return Err(e.context("Pro Tools did not come up")),
+                }
+            };
+            pt.open_session(&session).await?;
+            log::info!("✅ Reopened {}", session);
+            Ok(())
+        })
+    })
+    .join()
+    .map_err(|_| anyhow::anyhow!("Relaunch panicked"))?
+}
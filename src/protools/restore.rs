@@ -0,0 +1,194 @@
+//! Putting Pro Tools back the way a macro found it
+//!
+//! Actions that change global state on the way to their real work (edit mode,
+//! solos, track or timeline selection) record it on a [`RestoreGuard`] before
+//! touching it, run their steps, then hand the result to [`RestoreGuard::finish`].
+//! The saved state is put back whether the steps succeeded, failed or hit a
+//! `?` early return, so a failed macro never leaves the user in Shuffle with
+//! odd solos. A command that times out still finishes on its own thread and
+//! restores when it does.
+//!
+//! ```ignore
+//! let mut guard = RestoreGuard::new();
+//! guard.save_edit_mode(pt).await?;
+//! let result = async {
+//!     pt.set_edit_mode("EMO_Shuffle").await?;
+//!     // ...
+//!     Ok(())
+//! }
+//! .await;
+//! guard.finish(pt, result).await
+//! ```
+
+use super::client::{ProtoolsSession, PtSelectionSamples};
+use crate::prelude::*;
+
+/// Saved state, put back in reverse order of saving
+pub struct RestoreGuard {
+    steps: Vec<Step>,
+    finished: bool,
+}
+
+struct Step {
+    saved: Saved,
+    /// Only put back if the action failed (its result is meant to stick)
+    on_error_only: bool,
+}
+
+enum Saved {
+    EditMode(String),
+    Solos {
+        all: Vec<String>,
+        soloed: Vec<String>,
+    },
+    TrackSelection(Vec<String>),
+    Selection(PtSelectionSamples),
+    Keystroke(Vec<&'static str>),
+}
+
+impl Default for RestoreGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RestoreGuard {
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            finished: false,
+        }
+    }
+
+    fn push(&mut self, saved: Saved, on_error_only: bool) {
+        self.steps.push(Step {
+            saved,
+            on_error_only,
+        });
+    }
+
+    /// Save the current edit mode, returning it
+    pub async fn save_edit_mode(&mut self, pt: &mut ProtoolsSession) -> R<String> {
+        let mode = pt.get_edit_mode().await?;
+        self.restore_edit_mode_to(mode.clone());
+        Ok(mode)
+    }
+
+    /// Put the edit mode back to `mode` (e.g. after a later keystroke step)
+    pub fn restore_edit_mode_to(&mut self, mode: String) {
+        self.push(Saved::EditMode(mode), false);
+    }
+
+    /// Save which tracks are soloed
+    pub async fn save_solos(&mut self, pt: &mut ProtoolsSession) -> R<()> {
+        let tracks = pt.get_all_tracks().await.context("Failed to get tracks")?;
+        let name = |track: &serde_json::Value| track["name"].as_str().map(str::to_string);
+        let soloed = tracks
+            .iter()
+            .filter(|track| track["track_attributes"]["is_soloed"].as_bool() == Some(true))
+            .filter_map(name)
+            .collect();
+        let all = tracks.iter().filter_map(name).collect();
+        self.push(Saved::Solos { all, soloed }, false);
+        Ok(())
+    }
+
+    /// Save the track selection
+    pub async fn save_track_selection(&mut self, pt: &mut ProtoolsSession) -> R<()> {
+        let tracks = pt.get_selected_track_names().await?;
+        self.push(Saved::TrackSelection(tracks), false);
+        Ok(())
+    }
+
+    /// Save the timeline selection (including pre/post-roll)
+    pub async fn save_selection(&mut self, pt: &mut ProtoolsSession) -> R<()> {
+        let selection = PtSelectionSamples::new(pt).await?;
+        self.push(Saved::Selection(selection), false);
+        Ok(())
+    }
+
+    /// Save the timeline selection, putting it back only if the action fails
+    pub async fn save_selection_on_error(&mut self, pt: &mut ProtoolsSession) -> R<()> {
+        let selection = PtSelectionSamples::new(pt).await?;
+        self.push(Saved::Selection(selection), true);
+        Ok(())
+    }
+
+    /// Press `keys` when restoring (undoes a toggle the action pressed)
+    pub fn keystroke(&mut self, keys: &[&'static str]) {
+        self.push(Saved::Keystroke(keys.to_vec()), false);
+    }
+
+    /// Put everything back and return the action's result
+    ///
+    /// A failed restore is logged; it only replaces the result if the action
+    /// itself succeeded.
+    pub async fn finish<T>(mut self, pt: &mut ProtoolsSession, result: R<T>) -> R<T> {
+        self.finished = true;
+        let steps = std::mem::take(&mut self.steps);
+        let restored = restore(pt, steps, result.is_err()).await;
+        let value = result?;
+        restored?;
+        Ok(value)
+    }
+}
+
+impl Drop for RestoreGuard {
+    fn drop(&mut self) {
+        if self.finished || self.steps.is_empty() {
+            return;
+        }
+        // Dropped without finish (early return or panic) - restore in the background
+        log::warn!("Action stopped early - restoring Pro Tools state");
+        let steps = std::mem::take(&mut self.steps);
+        if let Some(rt) = super::TOKIO_RT.get() {
+            rt.spawn(async move {
+                match ProtoolsSession::shared().await {
+                    Ok(mut pt) => {
+                        restore(&mut pt, steps, true).await.ok();
+                    }
+                    Err(e) => log::error!("Failed to restore Pro Tools state: {:#}", e),
+                }
+            });
+        }
+    }
+}
+
+/// Put `steps` back, newest first, carrying on past failures
+async fn restore(pt: &mut ProtoolsSession, steps: Vec<Step>, failed: bool) -> R<()> {
+    let mut first_error = None;
+    for step in steps.into_iter().rev() {
+        if step.on_error_only && !failed {
+            continue;
+        }
+        if let Err(e) = restore_step(pt, step.saved).await {
+            log::error!("Failed to restore Pro Tools state: {:#}", e);
+            first_error.get_or_insert(e);
+        }
+    }
+    match first_error {
+        Some(e) => Err(e.context("Failed to restore Pro Tools state")),
+        None => Ok(()),
+    }
+}
+
+async fn restore_step(pt: &mut ProtoolsSession, saved: Saved) -> R<()> {
+    match saved {
+        Saved::EditMode(mode) => pt.set_edit_mode(&mode).await?,
+        Saved::Solos { all, soloed } => {
+            pt.solo_tracks(all, false).await?;
+            pt.solo_tracks(soloed, true).await?;
+        }
+        Saved::TrackSelection(tracks) => {
+            if !tracks.is_empty() {
+                pt.select_tracks_by_name(tracks).await?;
+            }
+        }
+        Saved::Selection(mut selection) => {
+            let (in_time, out_time) = selection.get_io();
+            selection.set_io(pt, in_time, out_time).await?;
+        }
+        Saved::Keystroke(keys) => OS::keystroke(&keys)?,
+    }
+    Ok(())
+}
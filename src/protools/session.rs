@@ -1,12 +1,21 @@
 use super::client::*;
 use crate::actions_async;
 use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 actions_async!("pt", session, {
      export_selection,
     popups,
     version_up,
     save_as,
+    add_note,
+    view_notes,
+    report_session_recall,
+    compare_session_layout,
+    bounce_stems,
+    bounce,
+    screenshot,
 });
 pub async fn save_as(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
     let name = params.get_str("name", "");
@@ -113,3 +122,961 @@ pub async fn popups(_pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
     }
     Ok(())
 }
+
+// ============================================================================
+// Session Notes Sidecar
+// ============================================================================
+
+/// Path of the Markdown notes file that sits next to the session file
+async fn notes_path(pt: &mut ProtoolsSession) -> R<std::path::PathBuf> {
+    let session = pt.get_session_path().await?;
+    let stem = session
+        .file_stem()
+        .context("Session path has no file name")?
+        .to_string_lossy()
+        .to_string();
+    Ok(session.with_file_name(format!("{} Notes.md", stem)))
+}
+
+/// Append a timestamped note to the session's notes sidecar
+///
+/// Each note records the wall-clock time and the timeline selection.
+///
+/// Params:
+/// - text: note text (prompts if omitted)
+/// - from_clipboard: use the clipboard contents as the note (default: false)
+pub async fn add_note(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let mut text = params.get_string("text", "");
+    if params.get_bool("from_clipboard", false) {
+        text = crate::macos::helpers::clipboard_text()?;
+    }
+    if text.trim().is_empty() {
+        match crate::macos::window::show_input_dialog("Session Note", Some("Note:"), None)? {
+            Some(input) if !input.trim().is_empty() => text = input,
+            _ => return Ok(()), // Cancelled
+        }
+    }
+
+    let selection = super::timecode::PtSelectionTimecode::new(pt).await?;
+    let (start, end) = selection.get_io(pt).await?;
+    let location = if start.to_string() == end.to_string() {
+        format!("@ {}", start)
+    } else {
+        format!("{} - {}", start, end)
+    };
+
+    let path = notes_path(pt).await?;
+    let is_new = !path.exists();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    use std::io::Write;
+    if is_new {
+        let session = pt.get_session_name().await.unwrap_or_default();
+        writeln!(file, "# Session Notes: {}\n", session.trim_matches('"'))?;
+    }
+    writeln!(
+        file,
+        "## {} ({})\n\n{}\n",
+        crate::macos::helpers::local_timestamp(),
+        location,
+        text.trim()
+    )?;
+
+    log::info!("Added note to {}", path.display());
+    OS::show_notification(&crate::notify::text("note_added", &[]));
+    Ok(())
+}
+
+/// Show the session's notes sidecar in a text window
+pub async fn view_notes(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    let path = notes_path(pt).await?;
+    let notes = std::fs::read_to_string(&path).unwrap_or_else(|_| "No notes yet".to_string());
+    crate::MessageLog::new(&notes).display()
+}
+
+// ============================================================================
+// Recall Sheet
+// ============================================================================
+
+/// One track's row on the recall sheet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecallTrack {
+    name: String,
+    kind: String,
+    format: String,
+    folder: String,
+    state: Vec<String>,
+    plugins: Vec<String>,
+    user_delay: String,
+    comments: String,
+}
+
+/// Per-track details from the session info text, keyed by track name
+#[derive(Default)]
+struct SessionInfoTrack {
+    plugins: Vec<String>,
+    user_delay: String,
+    comments: String,
+}
+
+/// Recall sheet file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecallFormat {
+    Markdown,
+    Csv,
+    Json,
+}
+
+impl RecallFormat {
+    fn parse(name: &str) -> R<Self> {
+        match name {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            other => anyhow::bail!(
+                "Unknown recall sheet format '{}' (use markdown, csv or json)",
+                other
+            ),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Write a recall sheet for the mix: every track with its format, state and
+/// insert plug-ins, in place of mixer screenshots
+///
+/// Track details come from the PTSL track list and the plug-in lists of
+/// Session Info as Text. PTSL has no queries for I/O assignments, sends or
+/// automation modes yet, so those aren't on the sheet.
+///
+/// Params:
+/// - format: "markdown" (default), "csv" or "json" (for `compare_session_layout`)
+/// - path: output file (default: "<session> Recall.md/.csv/.json" next to the session)
+/// - show: open the sheet in a text window when done (default: true)
+pub async fn report_session_recall(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let format = RecallFormat::parse(params.get_str("format", "markdown"))?;
+    let rows = collect_recall(pt).await?;
+
+    let session = pt.get_session_path().await?;
+    let stem = session_stem(&session)?;
+    let path = match params.get_ostring("path") {
+        Some(path) => std::path::PathBuf::from(path),
+        None => session.with_file_name(format!("{} Recall.{}", stem, format.extension())),
+    };
+
+    let sheet = match format {
+        RecallFormat::Markdown => recall_markdown(&stem, &rows),
+        RecallFormat::Csv => recall_csv(&rows),
+        RecallFormat::Json => serde_json::to_string_pretty(&rows)?,
+    };
+    std::fs::write(&path, &sheet).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    log::info!(
+        "Wrote recall sheet for {} tracks to {}",
+        rows.len(),
+        path.display()
+    );
+    OS::show_notification(&crate::notify::text(
+        "recall_sheet_saved",
+        &[("count", &rows.len().to_string())],
+    ));
+    if params.get_bool("show", true) {
+        crate::MessageLog::new(&sheet).display()?;
+    }
+    Ok(())
+}
+
+/// Compare two track layouts and show what changed
+///
+/// Lists added, removed and renamed tracks, and tracks whose type, format,
+/// folder or inserts changed - the checklist for carrying a template change
+/// over to the other episodes. State (mute, solo...), delay and comments are
+/// ignored since they change during a mix.
+///
+/// Params:
+/// - from: recall sheet (.json or .csv from `report_session_recall`) to compare against
+/// - to: second recall sheet (default: the open session)
+/// - path: also write the report to this file
+pub async fn compare_session_layout(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let from_path = params
+        .get_ostring("from")
+        .context("compare_session_layout needs a 'from' recall sheet")?;
+    let from = load_recall(&from_path)?;
+    let (to, to_label) = match params.get_ostring("to") {
+        Some(path) => (load_recall(&path)?, path),
+        None => {
+            let session = pt.get_session_path().await?;
+            (collect_recall(pt).await?, session_stem(&session)?)
+        }
+    };
+
+    let changes = diff_layouts(&from, &to);
+    let report = if changes.is_empty() {
+        format!("{} and {}: track layouts match\n", from_path, to_label)
+    } else {
+        format!(
+            "Layout changes from {} to {}\n\n{}\n",
+            from_path,
+            to_label,
+            changes.join("\n")
+        )
+    };
+
+    if let Some(path) = params.get_ostring("path") {
+        std::fs::write(&path, &report).with_context(|| format!("Failed to write {}", path))?;
+        log::info!("Wrote layout comparison to {}", path);
+    }
+    log::info!("Layout comparison: {} changes", changes.len());
+    crate::MessageLog::new(&report).display()
+}
+
+/// Gather the recall sheet rows for the open session
+async fn collect_recall(pt: &mut ProtoolsSession) -> R<Vec<RecallTrack>> {
+    let tracks = pt
+        .get_all_tracks()
+        .await
+        .context("Failed to get the track list from Pro Tools")?;
+    let mut info = parse_session_info(&pt.export_session_info_text().await?);
+
+    Ok(tracks
+        .iter()
+        .filter_map(|track| {
+            let name = track["name"].as_str()?.to_string();
+            let details = info.remove(&name).unwrap_or_default();
+            Some(RecallTrack {
+                kind: enum_label(&track["type"]),
+                format: enum_label(&track["format"]),
+                folder: track["parent_folder_name"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                state: track_state(&track["track_attributes"]),
+                plugins: details.plugins,
+                user_delay: details.user_delay,
+                comments: details.comments,
+                name,
+            })
+        })
+        .collect())
+}
+
+fn session_stem(session: &std::path::Path) -> R<String> {
+    Ok(session
+        .file_stem()
+        .context("Session path has no file name")?
+        .to_string_lossy()
+        .to_string())
+}
+
+/// "TType_Audio" -> "Audio"
+fn enum_label(value: &serde_json::Value) -> String {
+    let raw = value.as_str().unwrap_or("");
+    raw.split_once('_')
+        .map_or(raw, |(_, label)| label)
+        .to_string()
+}
+
+fn track_state(attributes: &serde_json::Value) -> Vec<String> {
+    // Attribute states are "None", "SetExplicitly", "SetImplicitly"...
+    let set = |key: &str| match &attributes[key] {
+        serde_json::Value::Bool(b) => *b,
+        serde_json::Value::String(s) => s.contains("Set"),
+        _ => false,
+    };
+    [
+        ("is_inactive", "Inactive"),
+        ("is_hidden", "Hidden"),
+        ("is_muted", "Muted"),
+        ("is_soloed", "Soloed"),
+        ("is_record_enabled", "Rec"),
+        ("is_input_monitoring_on", "Input"),
+        ("is_frozen", "Frozen"),
+        ("is_locked", "Locked"),
+    ]
+    .into_iter()
+    .filter(|(key, _)| set(key))
+    .map(|(_, label)| label.to_string())
+    .collect()
+}
+
+/// Pull the per-track blocks out of Session Info as Text
+///
+/// Each track starts with a "TRACK NAME:" line followed by tab-separated
+/// "COMMENTS:", "USER DELAY:" and "PLUG-INS:" lines.
+fn parse_session_info(text: &str) -> HashMap<String, SessionInfoTrack> {
+    let mut tracks = HashMap::new();
+    let mut current: Option<(String, SessionInfoTrack)> = None;
+
+    for line in text.lines() {
+        let Some((label, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim_start_matches([' ', '\t']);
+        match label.trim() {
+            "TRACK NAME" => {
+                if let Some((name, track)) = current.take() {
+                    tracks.insert(name, track);
+                }
+                current = Some((value.trim().to_string(), SessionInfoTrack::default()));
+            }
+            "COMMENTS" => {
+                if let Some((_, track)) = current.as_mut() {
+                    track.comments = value.trim().to_string();
+                }
+            }
+            "USER DELAY" => {
+                if let Some((_, track)) = current.as_mut() {
+                    track.user_delay = value.trim().to_string();
+                }
+            }
+            "PLUG-INS" => {
+                if let Some((_, track)) = current.as_mut() {
+                    track.plugins = value
+                        .split('\t')
+                        .map(str::trim)
+                        .filter(|plugin| !plugin.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some((name, track)) = current {
+        tracks.insert(name, track);
+    }
+    tracks
+}
+
+fn recall_markdown(session: &str, rows: &[RecallTrack]) -> String {
+    let cell = |text: &str| text.replace('|', "\\|");
+    let mut out = format!(
+        "# Recall Sheet: {}\n\n{}\n\n| Track | Type | Format | Folder | State | Inserts | Delay | Comments |\n|---|---|---|---|---|---|---|---|\n",
+        session,
+        crate::macos::helpers::local_timestamp()
+    );
+    for row in rows {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            cell(&row.name),
+            row.kind,
+            row.format,
+            cell(&row.folder),
+            row.state.join(", "),
+            cell(&row.plugins.join("<br>")),
+            cell(&row.user_delay),
+            cell(&row.comments),
+        ));
+    }
+    out
+}
+
+fn recall_csv(rows: &[RecallTrack]) -> String {
+    let field = |text: &str| {
+        if text.contains([',', '"', '\n']) {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text.to_string()
+        }
+    };
+    let mut out = String::from("Track,Type,Format,Folder,State,Inserts,Delay,Comments\n");
+    for row in rows {
+        let fields = [
+            field(&row.name),
+            field(&row.kind),
+            field(&row.format),
+            field(&row.folder),
+            field(&row.state.join(" ")),
+            field(&row.plugins.join("; ")),
+            field(&row.user_delay),
+            field(&row.comments),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Load a recall sheet saved as JSON or CSV
+fn load_recall(path: &str) -> R<Vec<RecallTrack>> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+    if path.ends_with(".json") {
+        return serde_json::from_str(&text)
+            .with_context(|| format!("{} is not a recall sheet", path));
+    }
+    if !path.ends_with(".csv") {
+        anyhow::bail!("Can only compare .json or .csv recall sheets, not {}", path);
+    }
+
+    let mut records = parse_csv(&text, ',').into_iter();
+    records.next(); // Header
+    Ok(records
+        .filter(|record| record.len() >= 8)
+        .map(|record| {
+            let list = |text: &str, separator: &str| -> Vec<String> {
+                text.split(separator)
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            };
+            RecallTrack {
+                name: record[0].clone(),
+                kind: record[1].clone(),
+                format: record[2].clone(),
+                folder: record[3].clone(),
+                state: list(&record[4], " "),
+                plugins: list(&record[5], ";"),
+                user_delay: record[6].clone(),
+                comments: record[7].clone(),
+            }
+        })
+        .collect())
+}
+
+/// Split CSV text into records (quoted fields may contain separators and newlines)
+pub fn parse_csv(text: &str, separator: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => record.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            '\r' if !quoted => {}
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// Describe how the `to` layout differs from `from`, one line per change
+///
+/// A track missing from one side is paired up as a rename when a track of
+/// the same type and format appears at the same position on the other side.
+fn diff_layouts(from: &[RecallTrack], to: &[RecallTrack]) -> Vec<String> {
+    let from_names: HashMap<&str, &RecallTrack> =
+        from.iter().map(|t| (t.name.as_str(), t)).collect();
+    let to_names: HashMap<&str, &RecallTrack> = to.iter().map(|t| (t.name.as_str(), t)).collect();
+
+    let mut removed: Vec<(usize, &RecallTrack)> = from
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| !to_names.contains_key(t.name.as_str()))
+        .collect();
+    let mut added: Vec<(usize, &RecallTrack)> = to
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| !from_names.contains_key(t.name.as_str()))
+        .collect();
+
+    let mut changes = Vec::new();
+    let mut renamed = Vec::new();
+    removed.retain(|&(position, old)| {
+        let Some(found) = added.iter().position(|&(p, new)| {
+            p == position && new.kind == old.kind && new.format == old.format
+        }) else {
+            return true;
+        };
+        let (_, new) = added.remove(found);
+        changes.push(format!("~ Renamed: {} -> {}", old.name, new.name));
+        renamed.push((old, new));
+        false
+    });
+    for (_, track) in &removed {
+        changes.push(format!(
+            "- Removed: {} ({} {})",
+            track.name, track.format, track.kind
+        ));
+    }
+    for (_, track) in &added {
+        changes.push(format!(
+            "+ Added: {} ({} {})",
+            track.name, track.format, track.kind
+        ));
+    }
+
+    let kept = from
+        .iter()
+        .filter_map(|old| to_names.get(old.name.as_str()).map(|new| (old, *new)));
+    for (old, new) in kept.chain(renamed) {
+        let mut fields = Vec::new();
+        if old.kind != new.kind {
+            fields.push(format!("type {} -> {}", old.kind, new.kind));
+        }
+        if old.format != new.format {
+            fields.push(format!("format {} -> {}", old.format, new.format));
+        }
+        if old.folder != new.folder {
+            fields.push(format!("folder '{}' -> '{}'", old.folder, new.folder));
+        }
+        if old.plugins != new.plugins {
+            fields.push(format!(
+                "inserts [{}] -> [{}]",
+                old.plugins.join(", "),
+                new.plugins.join(", ")
+            ));
+        }
+        if !fields.is_empty() {
+            changes.push(format!("* Changed: {}: {}", new.name, fields.join("; ")));
+        }
+    }
+    changes
+}
+
+// ============================================================================
+// Stem Bouncing
+// ============================================================================
+
+/// One stem of a `bounce_stems` matrix
+struct Stem {
+    name: String,
+    /// Track/VCA name globs to solo
+    tracks: Vec<String>,
+    /// Mix source to bounce (bus, output or physical out name)
+    source: String,
+    source_type: String,
+}
+
+impl Stem {
+    fn from_table(table: &toml::Table, default_source: &str, default_type: &str) -> R<Self> {
+        let string = |key: &str| table.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        let name = string("name").context("Stem without a name")?;
+        let tracks = match table.get("tracks") {
+            Some(toml::Value::String(track)) => vec![track.clone()],
+            Some(toml::Value::Array(tracks)) => tracks
+                .iter()
+                .filter_map(|t| t.as_str().map(str::to_string))
+                .collect(),
+            _ => vec![name.clone()],
+        };
+        Ok(Self {
+            tracks,
+            source: string("source").unwrap_or_else(|| default_source.to_string()),
+            source_type: string("source_type").unwrap_or_else(|| default_type.to_string()),
+            name,
+        })
+    }
+}
+
+/// PTSL name of a mix source type ("bus", "output" or "physical")
+fn source_type_name(source_type: &str) -> R<&'static str> {
+    Ok(match source_type.to_lowercase().as_str() {
+        "bus" => "Bus",
+        "output" => "Output",
+        "physical" | "physical_out" => "PhysicalOut",
+        other => anyhow::bail!("Unknown source_type '{}'", other),
+    })
+}
+
+/// Bounce the timeline selection once per stem, soloing each stem's tracks
+///
+/// Runs the stems one after the other as a tracked job, with progress in the
+/// menu bar; press the same hotkey again to stop after the current stem. The
+/// solo state from before the run is restored at the end, also when a bounce
+/// fails. A failed stem is reported and skipped unless `stop_on_error` is set.
+///
+/// Params (big matrices are best kept in a `params_file`):
+/// - stems: array of tables: `name`, `tracks` (track/VCA name globs to solo,
+///   default the stem name), optional `source` / `source_type` overrides
+/// - source: mix source bounced for every stem (e.g. "Main Mix")
+/// - source_type: "bus", "output" or "physical" (default "bus")
+/// - file_name: naming pattern, `{session}` and `{stem}` (default "{session}_{stem}")
+/// - directory: absolute folder for the files (default: the session's Bounced Files)
+/// - file_type: "wav" or "aiff" (default "wav")
+/// - bit_depth: 16, 24 or 32 (default 24)
+/// - format: "interleaved" or "multiple_mono" (default "interleaved")
+/// - sample_rate: 44100, 48000, 88200, 96000, 176400 or 192000 (default: the session's)
+/// - offline: bounce offline (default true)
+/// - stop_on_error: stop at the first failed stem (default false)
+pub async fn bounce_stems(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let source = params.get_string("source", "");
+    let source_type = params.get_string("source_type", "bus");
+    let stems = params
+        .get_tables("stems")
+        .iter()
+        .map(|table| Stem::from_table(table, &source, &source_type))
+        .collect::<R<Vec<_>>>()?;
+    if stems.is_empty() {
+        anyhow::bail!("No stems configured");
+    }
+    if let Some(stem) = stems.iter().find(|stem| stem.source.is_empty()) {
+        anyhow::bail!("Stem '{}' has no source", stem.name);
+    }
+
+    let session = pt.get_session_name().await?;
+    let pattern = params.get_string("file_name", "{session}_{stem}");
+    let stop_on_error = params.get_bool("stop_on_error", false);
+
+    let all_tracks = pt.get_all_track_names().await?;
+    // Put the solos back the way they were, however the bounces go
+    let mut guard = super::restore::RestoreGuard::new();
+    guard.save_solos(pt).await?;
+
+    let _job = super::jobs::track("Bounce stems");
+    let batch = super::jobs::start_batch("session_bounce_stems", "stems_stopping");
+    let mut failed = Vec::new();
+    let mut bounced = 0;
+    let mut stopped = false;
+    for (number, stem) in (1..).zip(&stems) {
+        if batch.stopped() {
+            stopped = true;
+            break;
+        }
+        batch.set_status(&format!(" Stem {}/{}", number, stems.len()));
+        OS::show_notification(&crate::notify::text(
+            "stem_bouncing",
+            &[
+                ("number", &number.to_string()),
+                ("total", &stems.len().to_string()),
+                ("stem", &stem.name),
+            ],
+        ));
+        let file_name = pattern
+            .replace("{session}", &session)
+            .replace("{stem}", &stem.name);
+        let result = bounce_stem(pt, params, stem, &all_tracks, &file_name).await;
+        match result {
+            Ok(()) => bounced += 1,
+            Err(e) => {
+                log::error!("Stem '{}' failed: {:#}", stem.name, e);
+                failed.push(stem.name.clone());
+                if stop_on_error {
+                    break;
+                }
+            }
+        }
+    }
+
+    drop(batch);
+    guard.finish(pt, Ok(())).await?;
+
+    if stopped && failed.is_empty() {
+        OS::show_notification(&crate::notify::text(
+            "stems_stopped",
+            &[
+                ("count", &bounced.to_string()),
+                ("total", &stems.len().to_string()),
+            ],
+        ));
+        Ok(())
+    } else if failed.is_empty() {
+        OS::show_notification(&crate::notify::text(
+            "stems_bounced",
+            &[("count", &bounced.to_string())],
+        ));
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "Bounced {} of {} stem(s); failed: {}",
+            bounced,
+            stems.len(),
+            failed.join(", ")
+        )
+    }
+}
+
+/// Solo one stem's tracks and bounce it
+async fn bounce_stem(
+    pt: &mut ProtoolsSession,
+    params: &Params,
+    stem: &Stem,
+    all_tracks: &[String],
+    file_name: &str,
+) -> R<()> {
+    let matchers = stem
+        .tracks
+        .iter()
+        .map(|glob| super::tracks::glob_to_regex(glob))
+        .collect::<R<Vec<_>>>()?;
+    let solos: Vec<String> = all_tracks
+        .iter()
+        .filter(|track| matchers.iter().any(|m| m.is_match(track)))
+        .cloned()
+        .collect();
+    if solos.is_empty() {
+        anyhow::bail!("No tracks match {:?}", stem.tracks);
+    }
+    log::info!("Stem '{}': soloing {:?}", stem.name, solos);
+    pt.solo_tracks(all_tracks.to_vec(), false).await?;
+    pt.solo_tracks(solos, true).await?;
+
+    let _job = super::jobs::track(&format!("Bounce stem {}", stem.name));
+    export_mix(pt, params, &stem.source, &stem.source_type, file_name).await?;
+    log::info!("Stem '{}' bounced to '{}'", stem.name, file_name);
+    Ok(())
+}
+
+/// Bounce one mix source over PTSL with the file settings in `params`
+async fn export_mix(
+    pt: &mut ProtoolsSession,
+    params: &Params,
+    source: &str,
+    source_type: &str,
+    file_name: &str,
+) -> R<()> {
+    let directory = params.get_string("directory", "");
+    let (destination, directory) = if directory.is_empty() {
+        ("EM_FD_SessionFolder", "Bounced Files".to_string())
+    } else {
+        ("EM_FD_Directory", directory)
+    };
+    let file_type = match params.get_str("file_type", "wav").to_lowercase().as_str() {
+        "aiff" => "EM_AIFF",
+        _ => "EM_WAV",
+    };
+    let bit_depth = match params.get_int("bit_depth", 24) {
+        16 => "Bit16",
+        32 => "Bit32Float",
+        _ => "Bit24",
+    };
+    let format = match params.get_str("format", "interleaved") {
+        "multiple_mono" => "EF_MultipleMono",
+        _ => "EF_Interleaved",
+    };
+    let sample_rate = match params.get_oint("sample_rate") {
+        None => "SR_None".to_string(),
+        Some(rate @ (44100 | 48000 | 88200 | 96000 | 176400 | 192000)) => format!("SR_{}", rate),
+        Some(rate) => anyhow::bail!("Unsupported sample rate {}", rate),
+    };
+    let offline = if params.get_bool("offline", true) {
+        "TB_True"
+    } else {
+        "TB_False"
+    };
+
+    let _: serde_json::Value = pt
+        .cmd(
+            super::ptsl::CommandId::ExportMix,
+            serde_json::json!({
+                "file_name": file_name,
+                "file_type": file_type,
+                "mix_source_list": [{
+                    "source_type": source_type_name(source_type)?,
+                    "name": source,
+                }],
+                "audio_info": {
+                    "compression_type": "CT_PCM",
+                    "export_format": format,
+                    "bit_depth": bit_depth,
+                    "sample_rate": sample_rate,
+                    "pad_to_frame_boundary": "TB_False",
+                    "delivery_format": "EM_DF_FilePerMixSource",
+                },
+                "location_info": {
+                    "import_after_bounce": "TB_False",
+                    "file_destination": destination,
+                    "directory": directory,
+                },
+                "offline_bounce": offline,
+            }),
+        )
+        .await
+        .with_context(|| format!("Bounce of '{}' failed", file_name))?;
+    Ok(())
+}
+
+// ============================================================================
+// Mix Bouncing
+// ============================================================================
+
+/// Bounce the timeline selection to a file and say where it went
+///
+/// With a `source` the bounce runs over PTSL. Without one, the Bounce Mix
+/// dialog is driven instead, so the mix source it last used is kept: the
+/// settings given are picked in its popups, the destination folder is typed
+/// into the folder chooser, and the action waits for the bounce's progress
+/// window to close. A long realtime bounce needs a `command_timeout_ms` to
+/// match.
+///
+/// Params:
+/// - source / source_type: mix source for a PTSL bounce (e.g. "Main Mix", "bus")
+/// - file_name: `{session}` is the session name (default "{session}")
+/// - directory: absolute folder for the file (default: the session's Bounced Files)
+/// - file_type: "wav" or "aiff" (default "wav")
+/// - bit_depth: 16, 24 or 32 (default 24)
+/// - format: "interleaved" or "multiple_mono" (default "interleaved")
+/// - sample_rate: 44100, 48000, 88200, 96000, 176400 or 192000 (default: the session's)
+/// - offline: bounce offline (default true)
+/// - timeout_ms: dialog bounces - how long the bounce may take (default 3600000)
+/// - window / progress_window / file_name_field / choose_button: dialog names,
+///   for localized or older Pro Tools versions
+pub async fn bounce(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let session = pt.get_session_name().await?;
+    let file_name = params
+        .get_string("file_name", "{session}")
+        .replace("{session}", &session);
+    let directory = match params.get_ostring("directory") {
+        Some(directory) => std::path::PathBuf::from(directory),
+        None => pt
+            .get_session_path()
+            .await?
+            .parent()
+            .context("Session has no folder")?
+            .join("Bounced Files"),
+    };
+    let extension = match params.get_str("file_type", "wav").to_lowercase().as_str() {
+        "aiff" => "aif",
+        _ => "wav",
+    };
+
+    let _job = super::jobs::track("Bounce mix");
+    match params.get_ostring("source") {
+        Some(source) => {
+            let source_type = params.get_string("source_type", "bus");
+            export_mix(pt, params, &source, &source_type, &file_name).await?;
+        }
+        None => bounce_with_dialog(params, &file_name, &directory)?,
+    }
+
+    let path = directory.join(format!("{}.{}", file_name, extension));
+    log::info!("Bounced to {}", path.display());
+    OS::show_notification(&crate::notify::text(
+        "mix_bounced",
+        &[("path", &path.display().to_string())],
+    ));
+    Ok(())
+}
+
+/// Fill in the Bounce Mix dialog, bounce, and wait for it to finish
+fn bounce_with_dialog(params: &Params, file_name: &str, directory: &std::path::Path) -> R<()> {
+    let window = params.get_string("window", "Bounce Mix");
+    OS::menu_click("Pro Tools", &["File", "Bounce Mix..."])?;
+    if !OS::wait_for_window("Pro Tools", &window, OS::WindowCondition::Exists, 3000)? {
+        anyhow::bail!("The {} dialog didn't open", window);
+    }
+
+    let popups = [
+        (
+            "File Type",
+            params.get_ostr("file_type").map(str::to_uppercase),
+        ),
+        (
+            "Format",
+            params.get_ostr("format").map(|format| match format {
+                "multiple_mono" => "Multiple Mono".to_string(),
+                _ => "Interleaved".to_string(),
+            }),
+        ),
+        (
+            "Bit Depth",
+            params
+                .get_oint("bit_depth")
+                .map(|bits| format!("{} Bit", bits)),
+        ),
+        (
+            "Sample Rate",
+            params
+                .get_oint("sample_rate")
+                .map(|rate| format!("{} kHz", rate as f64 / 1000.0)),
+        ),
+    ];
+    for (popup, item) in popups {
+        if let Some(item) = item {
+            OS::select_popup_menu_item("Pro Tools", &window, popup, &item)
+                .with_context(|| format!("Failed to set {} to '{}'", popup, item))?;
+        }
+    }
+    if let Some(offline) = params.get_obool("offline") {
+        OS::set_checkbox_value("Pro Tools", &window, "Offline", offline as i32)?;
+    }
+    let field = params.get_string("file_name_field", "File Name");
+    OS::set_text_field_value("Pro Tools", &window, &field, file_name)
+        .with_context(|| format!("Bounce Mix field '{}' not found", field))?;
+    OS::keystroke(&["tab"])?;
+
+    if params.get_ostr("directory").is_some() {
+        // Go to the folder in the chooser with cmd+shift+G
+        let choose = params.get_string("choose_button", "Choose...");
+        OS::click_button("Pro Tools", &window, &choose)?;
+        OS::waits::settle(OS::waits::Wait::Display);
+        OS::keystroke(&["cmd", "shift", "g"])?;
+        OS::waits::settle(OS::waits::Wait::Display);
+        OS::type_text(&directory.to_string_lossy(), true)?;
+        OS::keystroke(&["return"])?;
+        OS::waits::settle(OS::waits::Wait::Display);
+        OS::keystroke(&["return"])?;
+        OS::waits::settle(OS::waits::Wait::Display);
+    }
+
+    OS::click_button("Pro Tools", &window, "Bounce")?;
+    OS::wait_for_window("Pro Tools", &window, OS::WindowCondition::Closed, 5000)?;
+    let progress = params.get_string("progress_window", "Bounce");
+    let timeout = params.get_int("timeout_ms", 3_600_000) as i32;
+    if OS::wait_for_window("Pro Tools", &progress, OS::WindowCondition::Exists, 3000)?
+        && !OS::wait_for_window("Pro Tools", &progress, OS::WindowCondition::Closed, timeout)?
+    {
+        anyhow::bail!("The bounce didn't finish within {} ms", timeout);
+    }
+    Ok(())
+}
+
+/// Capture the Pro Tools window for a "look at this edit" message
+///
+/// The frontmost Pro Tools window (Edit, Mix or a plugin) is captured with a
+/// caption bar showing the session name and the timecode at the start of the
+/// selection.
+///
+/// Params:
+/// - `annotate`: draw the caption (default: true)
+/// - `note`: extra text for the caption (e.g. "alt line at 2nd cut")
+/// - `path`: save a PNG here; a folder gets "<session> <date time>.png"
+/// - `clipboard`: copy it to the clipboard (default: true unless `path` is set)
+pub async fn screenshot(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let session = pt.get_session_name().await?;
+    let caption = if params.get_bool("annotate", true) {
+        let clock = super::timecode::SampleClock::new(pt).await?;
+        let (start, _) = PtSelectionSamples::new(pt).await?.get_io();
+        let mut caption = format!("{}   {}", session, clock.to_timecode(start));
+        if let Some(note) = params.get_ostring("note") {
+            caption = format!("{}   {}", caption, note);
+        }
+        Some(caption)
+    } else {
+        None
+    };
+    let shot = crate::macos::screenshot::capture_window("Pro Tools", caption.as_deref())?;
+
+    let path = params.get_ostring("path").map(std::path::PathBuf::from);
+    if let Some(path) = &path {
+        let path = if path.is_dir() {
+            let stamp = crate::macos::helpers::local_timestamp().replace(':', ".");
+            path.join(format!("{} {}.png", session, stamp))
+        } else {
+            path.clone()
+        };
+        shot.save(&path)?;
+        log::info!("Saved screenshot to {}", path.display());
+    }
+    if params.get_bool("clipboard", path.is_none()) {
+        shot.copy_to_clipboard()?;
+    }
+    OS::show_notification(&crate::notify::text("window_captured", &[]));
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! Recording tally light (`[tally]` section, `os.tally`)
+//!
+//! Polls the transport and lights the tally overlay (`macos::tally`) while
+//! Pro Tools records - and, with `armed = true`, while the transport's record
+//! button is armed. `os.tally` lights it by hand as well, e.g. for a VO booth
+//! that's live before the transport rolls.
+
+use super::TOKIO_RT;
+use super::client::ProtoolsSession;
+use crate::config::TallyConfig;
+use crate::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once};
+use std::time::Duration;
+
+/// Active tally settings (None = no `[tally]` section). Replaced on config reload.
+static TALLY_CONFIG: Mutex<Option<TallyConfig>> = Mutex::new(None);
+
+/// Lit by `os.tally`
+static MANUAL: AtomicBool = AtomicBool::new(false);
+
+/// Lit because Pro Tools is recording (or armed)
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+/// Whether the overlay is shown right now
+static LIT: Mutex<bool> = Mutex::new(false);
+
+static START: Once = Once::new();
+
+/// How often the loop checks whether the tally was turned on
+const IDLE_POLL: Duration = Duration::from_secs(1);
+
+/// Apply tally settings from config
+pub fn set_config(config: Option<TallyConfig>) {
+    match &config {
+        Some(c) if c.enabled => log::info!(
+            "Tally enabled: {} on display {:?}{}",
+            c.style,
+            c.display,
+            if c.armed { ", also when armed" } else { "" }
+        ),
+        _ => log::info!("Tally follows os.tally only"),
+    }
+    *TALLY_CONFIG.lock().unwrap() = config;
+    // Redraw in the new style, or go dark if automatic tally was turned off
+    if !is_automatic() {
+        RECORDING.store(false, Ordering::SeqCst);
+    }
+    *LIT.lock().unwrap() = false;
+    crate::macos::tally::hide();
+    update();
+}
+
+/// Start the background transport watcher (safe to call more than once)
+///
+/// Requires `init_runtime()` to have been called first.
+pub fn start() {
+    START.call_once(|| {
+        TOKIO_RT.get().unwrap().spawn(tally_loop());
+    });
+}
+
+/// Light or darken the tally by hand, whatever the transport is doing
+pub fn set_manual(lit: bool) {
+    MANUAL.store(lit, Ordering::SeqCst);
+    update();
+}
+
+pub fn is_manual() -> bool {
+    MANUAL.load(Ordering::SeqCst)
+}
+
+fn is_automatic() -> bool {
+    TALLY_CONFIG
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|c| c.enabled)
+}
+
+/// Show or hide the overlay to match the manual and recording states
+fn update() {
+    let lit = MANUAL.load(Ordering::SeqCst) || RECORDING.load(Ordering::SeqCst);
+    let mut shown = LIT.lock().unwrap();
+    if lit == *shown {
+        return;
+    }
+    *shown = lit;
+    if lit {
+        let config = TALLY_CONFIG.lock().unwrap().clone().unwrap_or_default();
+        crate::macos::tally::show(&config);
+    } else {
+        log::info!("Tally off");
+        crate::macos::tally::hide();
+    }
+}
+
+async fn tally_loop() {
+    loop {
+        let config = TALLY_CONFIG.lock().unwrap().clone();
+        let Some(config) = config.filter(|c| c.enabled) else {
+            tokio::time::sleep(IDLE_POLL).await;
+            continue;
+        };
+        tokio::time::sleep(Duration::from_millis(config.interval_ms.max(50))).await;
+
+        // Pro Tools not running or not answering = not recording
+        let recording = is_recording(config.armed).await.unwrap_or(false);
+        if recording != RECORDING.swap(recording, Ordering::SeqCst) {
+            log::info!(
+                "Pro Tools {}",
+                if recording {
+                    "recording"
+                } else {
+                    "stopped recording"
+                }
+            );
+            update();
+        }
+    }
+}
+
+async fn is_recording(armed: bool) -> R<bool> {
+    let mut pt = ProtoolsSession::shared().await?;
+    let state = super::transport::transport_state(&mut pt).await?;
+    if super::transport::is_recording(&state) {
+        return Ok(true);
+    }
+    Ok(armed && pt.get_transport_armed().await?)
+}
@@ -101,6 +101,14 @@ impl Timecode {
         self.normalize();
     }
 
+    pub fn add(&mut self, other: &Timecode) {
+        self.add_hmsf(other.hr, other.min, other.sec, other.fr);
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.hr == 0 && self.min == 0 && self.sec == 0 && self.fr == 0.0
+    }
+
     pub fn sub_hmsf(&mut self, hr: i64, min: i64, sec: i64, fr: f64) {
         self.hr -= hr;
         self.min -= min;
@@ -109,6 +117,166 @@ impl Timecode {
         self.normalize();
     }
 }
+/// Converts session sample positions to timecode and back
+///
+/// Sample 0 is the session start. Pulled-down rates (23.976, 29.97...) run
+/// 1000/1001 slow; drop-frame labels aren't skipped, so drop-frame sessions
+/// read as non-drop.
+#[derive(Debug, Clone)]
+pub struct SampleClock {
+    sample_rate: f64,
+    /// Frames per timecode second (30 for 29.97)
+    fps: i64,
+    /// Frames per real second
+    frame_rate: f64,
+    /// Session start, in frames
+    start_frames: i64,
+}
+
+impl SampleClock {
+    pub async fn new(pt: &mut ProtoolsSession) -> R<Self> {
+        let sample_rate = pt.get_samplerate().await? as f64;
+        let fps = pt.get_frames_per_second().await?;
+        let setting: serde_json::Value = pt
+            .cmd(CommandId::GetSessionTimeCodeRate, serde_json::json!({}))
+            .await?;
+        let setting = setting["current_setting"].as_str().unwrap_or("");
+        let pulled = ["23976", "2997", "47952", "5994", "11988"]
+            .iter()
+            .any(|rate| setting.contains(rate));
+        let frame_rate = if pulled {
+            fps as f64 * 1000.0 / 1001.0
+        } else {
+            fps as f64
+        };
+        let start: serde_json::Value = pt
+            .cmd(CommandId::GetSessionStartTime, serde_json::json!({}))
+            .await?;
+        let mut clock = Self {
+            sample_rate,
+            fps,
+            frame_rate,
+            start_frames: 0,
+        };
+        clock.start_frames = clock
+            .parse_frames(
+                start["session_start_time"]
+                    .as_str()
+                    .unwrap_or("00:00:00:00"),
+            )
+            .unwrap_or(0);
+        Ok(clock)
+    }
+
+    /// Timecode frame count (from 00:00:00:00) at a sample position
+    pub fn to_frames(&self, samples: i64) -> i64 {
+        // Half a sample on, so a frame start rounded to a sample stays in its frame
+        let frames = ((samples as f64 + 0.5) * self.frame_rate / self.sample_rate).floor() as i64;
+        (self.start_frames + frames).max(0)
+    }
+
+    /// "HH:MM:SS:FF" for a sample position
+    pub fn to_timecode(&self, samples: i64) -> String {
+        let frames = self.to_frames(samples);
+        let fps = self.fps.max(1);
+        format!(
+            "{:02}:{:02}:{:02}:{:02}",
+            frames / (fps * 3600),
+            frames / (fps * 60) % 60,
+            frames / fps % 60,
+            frames % fps
+        )
+    }
+
+    /// Sample position of a timecode ("HH:MM:SS:FF", ";" accepted)
+    pub fn to_samples(&self, timecode: &str) -> R<i64> {
+        let frames = self
+            .parse_frames(timecode)
+            .with_context(|| format!("'{}' is not a timecode (HH:MM:SS:FF)", timecode))?;
+        let samples = (frames - self.start_frames) as f64 * self.sample_rate / self.frame_rate;
+        Ok(samples.round().max(0.0) as i64)
+    }
+
+    /// Length in samples of an amount: "480smp", "10ms", "1.5s" or a timecode
+    /// duration ("00:00:01:00"); a leading "-" makes it negative
+    pub fn duration_to_samples(&self, amount: &str) -> R<i64> {
+        let amount = amount.trim();
+        let (sign, magnitude) = match amount.strip_prefix('-') {
+            Some(rest) => (-1.0, rest.trim()),
+            None => (1.0, amount),
+        };
+        let number = |value: &str| value.trim().parse::<f64>().ok();
+        let samples = if magnitude.contains([':', ';']) {
+            self.parse_frames(magnitude)
+                .map(|frames| frames as f64 * self.sample_rate / self.frame_rate)
+        } else if let Some(value) = magnitude.strip_suffix("smp") {
+            number(value)
+        } else if let Some(value) = magnitude.strip_suffix("ms") {
+            number(value).map(|ms| ms * self.sample_rate / 1000.0)
+        } else if let Some(value) = magnitude.strip_suffix('s') {
+            number(value).map(|seconds| seconds * self.sample_rate)
+        } else {
+            None
+        };
+        let samples = samples.with_context(|| {
+            format!(
+                "'{}' is not an amount (e.g. \"480smp\", \"10ms\", \"1s\", \"00:00:01:00\")",
+                amount
+            )
+        })?;
+        Ok((sign * samples).round() as i64)
+    }
+
+    /// Sample position of a typed location, the way a counter takes it:
+    /// - a timecode ("01:00:10:00", ";" or "." accepted), or just its digits,
+    ///   right-aligned like Pro Tools' counter ("1000" = 00:00:10:00)
+    /// - feet+frames from the session start ("90+08", 16 frames a foot)
+    /// - samples ("48000smp")
+    /// - an offset from `current`: "+" or "-" and any `duration_to_samples` amount
+    pub fn parse_position(&self, text: &str, current: i64) -> R<i64> {
+        let text = text.trim();
+        let position = if text.starts_with(['+', '-']) {
+            let amount = text.trim_start_matches('+');
+            current + self.duration_to_samples(amount)?
+        } else if let Some(samples) = text.strip_suffix("smp") {
+            samples
+                .trim()
+                .parse()
+                .with_context(|| format!("'{}' is not a sample count", text))?
+        } else if let Some((feet, frames)) = text.split_once('+') {
+            let (Ok(feet), Ok(frames)) = (feet.trim().parse::<i64>(), frames.trim().parse::<i64>())
+            else {
+                anyhow::bail!("'{}' is not feet+frames", text);
+            };
+            ((feet * 16 + frames) as f64 * self.sample_rate / self.frame_rate).round() as i64
+        } else if !text.is_empty() && text.len() <= 8 && text.chars().all(|c| c.is_ascii_digit()) {
+            let digits = format!("{:0>8}", text);
+            self.to_samples(&format!(
+                "{}:{}:{}:{}",
+                &digits[0..2],
+                &digits[2..4],
+                &digits[4..6],
+                &digits[6..8]
+            ))?
+        } else {
+            self.to_samples(text)?
+        };
+        Ok(position.max(0))
+    }
+
+    fn parse_frames(&self, timecode: &str) -> Option<i64> {
+        let parts: Vec<i64> = timecode
+            .trim()
+            .split([':', ';', '.'])
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()?;
+        let [hours, minutes, seconds, frames] = parts[..] else {
+            return None;
+        };
+        Some(((hours * 60 + minutes) * 60 + seconds) * self.fps + frames)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct PtSelectionTimecode {
     play_start_marker_time: String,
@@ -222,3 +390,64 @@ impl PtSelectionTimecode {
     //     Ok(())
     // }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_clock_round_trip() {
+        // 48 kHz, 29.97 non-drop, session start 01:00:00:00
+        let clock = SampleClock {
+            sample_rate: 48000.0,
+            fps: 30,
+            frame_rate: 30.0 * 1000.0 / 1001.0,
+            start_frames: 108_000,
+        };
+        assert_eq!(clock.to_timecode(0), "01:00:00:00");
+        // One timecode second lasts 1.001 real seconds
+        assert_eq!(clock.to_samples("01:00:01:00").unwrap(), 48048);
+        assert_eq!(clock.to_timecode(48048), "01:00:01:00");
+        assert_eq!(
+            clock.to_timecode(clock.to_samples("01:02:03;04").unwrap()),
+            "01:02:03:04"
+        );
+        assert!(clock.to_samples("1:00").is_err());
+    }
+
+    #[test]
+    fn test_duration_to_samples() {
+        let clock = SampleClock {
+            sample_rate: 48000.0,
+            fps: 25,
+            frame_rate: 25.0,
+            start_frames: 90_000,
+        };
+        assert_eq!(clock.duration_to_samples("480smp").unwrap(), 480);
+        assert_eq!(clock.duration_to_samples("10ms").unwrap(), 480);
+        assert_eq!(clock.duration_to_samples("1.5s").unwrap(), 72000);
+        // Durations ignore the session start
+        assert_eq!(clock.duration_to_samples("00:00:01:05").unwrap(), 57600);
+        assert_eq!(clock.duration_to_samples("-2 ms").unwrap(), -96);
+        assert!(clock.duration_to_samples("10").is_err());
+        assert!(clock.duration_to_samples("fast").is_err());
+    }
+
+    #[test]
+    fn test_parse_position() {
+        // 48 kHz, 24 fps, session start 01:00:00:00
+        let clock = SampleClock {
+            sample_rate: 48000.0,
+            fps: 24,
+            frame_rate: 24.0,
+            start_frames: 86_400,
+        };
+        assert_eq!(clock.parse_position("01:00:01:12", 0).unwrap(), 72000);
+        assert_eq!(clock.parse_position("1000112", 0).unwrap(), 72000);
+        assert_eq!(clock.parse_position("1+08", 0).unwrap(), 48000);
+        assert_eq!(clock.parse_position("96000smp", 0).unwrap(), 96000);
+        assert_eq!(clock.parse_position("+00:00:01:00", 1000).unwrap(), 49000);
+        assert_eq!(clock.parse_position("-1s", 1000).unwrap(), 0);
+        assert!(clock.parse_position("soon", 0).is_err());
+    }
+}
@@ -8,8 +8,25 @@ actions_async!("pt", tracks, {
     solo_clear,
     add_selected_to_solos,
     remove_selected_from_solos,
+    mute_selected,
+    mute_clear,
+    record_arm_selected,
+    input_monitor_selected,
     view_selector,
     lane_selector,
+    freeze_selected,
+    unfreeze_selected,
+    #[destructive]
+    commit_selected,
+    select_by_pattern,
+    select_matching,
+    show_matching,
+    hide_matching,
+    create_from_template,
+    recolor,
+    #[destructive]
+    rename,
+    set_height,
 });
 
 pub async fn solo_clear(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
@@ -144,6 +161,119 @@ pub async fn remove_selected_from_solos(pt: &mut ProtoolsSession, _params: &Para
 
     Ok(())
 }
+
+// ============================================================================
+// Mute / Record Arm / Input Monitor
+// ============================================================================
+
+/// Per-track switches that follow the track selection the way solo does
+#[derive(Debug, Clone, Copy)]
+enum TrackSwitch {
+    Mute,
+    RecordArm,
+    InputMonitor,
+}
+
+impl TrackSwitch {
+    fn is_on(self, attributes: &serde_json::Value) -> bool {
+        let value = &attributes[match self {
+            TrackSwitch::Mute => "is_muted",
+            TrackSwitch::RecordArm => "is_record_enabled",
+            TrackSwitch::InputMonitor => "is_input_monitoring_on",
+        }];
+        // Input monitoring is an attribute state ("None" / "SetExplicitly"...)
+        value
+            .as_bool()
+            .unwrap_or_else(|| value.as_str().is_some_and(|state| !state.ends_with("None")))
+    }
+
+    async fn set(self, pt: &mut ProtoolsSession, tracks: Vec<String>, state: bool) -> R<()> {
+        match self {
+            TrackSwitch::Mute => pt.mute_tracks(tracks, state).await,
+            TrackSwitch::RecordArm => pt.record_arm_tracks(tracks, state).await,
+            TrackSwitch::InputMonitor => pt.input_monitor_tracks(tracks, state).await,
+        }
+    }
+}
+
+/// Turn `switch` on for the selected tracks, and off for the rest if `exclusive`
+///
+/// Only tracks whose state changes are sent, as in `solo_selected`.
+async fn switch_selected(pt: &mut ProtoolsSession, switch: TrackSwitch, exclusive: bool) -> R<()> {
+    let tracks = pt
+        .get_all_tracks()
+        .await
+        .context("Failed to get track list")?;
+    let mut on = Vec::new();
+    let mut off = Vec::new();
+    for track in &tracks {
+        let Some(name) = track["name"].as_str() else {
+            continue;
+        };
+        let attributes = &track["track_attributes"];
+        let is_selected = attributes["is_selected"].as_str().unwrap_or("None") == "SetExplicitly";
+        let is_on = switch.is_on(attributes);
+        if is_selected && !is_on {
+            on.push(name.to_string());
+        } else if exclusive && !is_selected && is_on {
+            off.push(name.to_string());
+        }
+    }
+    log::info!("{:?}: {} on, {} off", switch, on.len(), off.len());
+    switch.set(pt, on, true).await?;
+    switch.set(pt, off, false).await?;
+    Ok(())
+}
+
+/// Mute the selected tracks
+///
+/// Params:
+/// - exclusive: also unmute every unselected track (default: true, like solo_selected)
+pub async fn mute_selected(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    switch_selected(pt, TrackSwitch::Mute, params.get_bool("exclusive", true)).await
+}
+
+/// Unmute every track
+pub async fn mute_clear(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    let tracks = pt
+        .get_all_tracks()
+        .await
+        .context("Failed to get track list")?;
+    let muted = tracks
+        .iter()
+        .filter(|track| TrackSwitch::Mute.is_on(&track["track_attributes"]))
+        .filter_map(|track| track["name"].as_str().map(str::to_string))
+        .collect();
+    pt.mute_tracks(muted, false).await?;
+    Ok(())
+}
+
+/// Record-arm the selected tracks
+///
+/// Params:
+/// - exclusive: also disarm every unselected track (default: true)
+pub async fn record_arm_selected(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    switch_selected(
+        pt,
+        TrackSwitch::RecordArm,
+        params.get_bool("exclusive", true),
+    )
+    .await
+}
+
+/// Turn input monitoring on for the selected tracks
+///
+/// Params:
+/// - exclusive: also turn it off for every unselected track (default: true)
+pub async fn input_monitor_selected(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    switch_selected(
+        pt,
+        TrackSwitch::InputMonitor,
+        params.get_bool("exclusive", true),
+    )
+    .await
+}
+
 lazy_static! {
     static ref KEY_COUNTER: Arc<Mutex<HotkeyCounter>> = Arc::new(Mutex::new(HotkeyCounter::new()));
 }
@@ -187,3 +317,775 @@ pub async fn lane_selector(_pt: &mut ProtoolsSession, params: &Params) -> R<()>
 
     Ok(())
 }
+
+// ============================================================================
+// Freeze / Commit
+// ============================================================================
+
+const COMMIT_WINDOW: &str = "Commit Tracks";
+
+/// Count the selected tracks, bailing if there are none
+async fn selected_track_count(pt: &mut ProtoolsSession) -> R<usize> {
+    let count = pt.get_selected_track_names().await?.len();
+    if count == 0 {
+        anyhow::bail!("No tracks selected");
+    }
+    Ok(count)
+}
+
+/// Log and notify how many tracks were processed (`id` = notification template)
+fn report_processed(id: &str, count: usize) {
+    let message = crate::notify::text(id, &[("count", &count.to_string())]);
+    log::info!("{}", message);
+    OS::show_notification(&message);
+}
+
+/// Run Track > Freeze or Unfreeze on the selected tracks and wait for it
+///
+/// Fails if the menu item is missing or disabled (e.g. the tracks are
+/// already frozen), and only reports the count once the progress dialog
+/// has closed.
+async fn freeze_menu(pt: &mut ProtoolsSession, params: &Params, item: &str, done: &str) -> R<()> {
+    let count = selected_track_count(pt).await?;
+    let menu = ["Track", item];
+    if !OS::menu_item_enabled("Pro Tools", &menu)? {
+        anyhow::bail!("Track > {} is not available for the selected tracks", item);
+    }
+    let _job = super::jobs::track(&format!("{} tracks", item));
+    OS::menu_click("Pro Tools", &menu)?;
+    let timeout = params.get_timeout_ms("timeout_ms", 3_600_000);
+    if !super::transport::wait_for_progress(
+        super::transport::PROGRESS_APPEAR,
+        std::time::Duration::from_millis(timeout),
+    ) {
+        anyhow::bail!("{} was still running after {} ms", item, timeout);
+    }
+    report_processed(done, count);
+    Ok(())
+}
+
+/// Freeze all selected tracks (Track > Freeze)
+///
+/// Params:
+/// - timeout_ms: longest to wait for the freeze to finish (default: 1 hour)
+pub async fn freeze_selected(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    freeze_menu(pt, params, "Freeze", "tracks_frozen").await
+}
+
+/// Unfreeze all selected tracks (Track > Unfreeze)
+///
+/// Params:
+/// - timeout_ms: longest to wait for the unfreeze to finish (default: 1 hour)
+pub async fn unfreeze_selected(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    freeze_menu(pt, params, "Unfreeze", "tracks_unfrozen").await
+}
+
+/// Commit all selected tracks (Track > Commit...)
+///
+/// Params:
+/// - after_commit: item for the "After Commit" popup (e.g. "Make Inactive", "Hide and Make Inactive")
+/// - consolidate_clips: set the "Consolidate Clips" checkbox
+/// - offline: set the "Offline" checkbox
+/// - copy_automation: set the "Copy Automation" checkbox
+/// - copy_sends: set the "Copy Sends" checkbox
+/// - confirm: press Commit to close the dialog (default: true)
+/// - timeout_ms: longest to wait for the commit to finish (default: 1 hour)
+pub async fn commit_selected(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let count = selected_track_count(pt).await?;
+    OS::menu_click("Pro Tools", &["Track", "Commit..."])?;
+    if !OS::wait_for_window(
+        "Pro Tools",
+        COMMIT_WINDOW,
+        OS::WindowCondition::Exists,
+        3000,
+    )? {
+        anyhow::bail!("'{}' dialog did not appear", COMMIT_WINDOW);
+    }
+
+    if let Some(after) = params.get_ostring("after_commit") {
+        OS::select_popup_menu_item("Pro Tools", COMMIT_WINDOW, "After Commit", &after)?;
+    }
+    for (key, checkbox) in [
+        ("consolidate_clips", "Consolidate Clips"),
+        ("offline", "Offline"),
+        ("copy_automation", "Copy Automation"),
+        ("copy_sends", "Copy Sends"),
+    ] {
+        if let Some(value) = params.get_obool(key) {
+            OS::set_checkbox_value("Pro Tools", COMMIT_WINDOW, checkbox, value as i32)?;
+        }
+    }
+
+    if !params.get_bool("confirm", true) {
+        return Ok(());
+    }
+    let _job = super::jobs::track("Commit tracks");
+    OS::click_button("Pro Tools", COMMIT_WINDOW, "Commit")?;
+    OS::wait_for_window(
+        "Pro Tools",
+        COMMIT_WINDOW,
+        OS::WindowCondition::Closed,
+        3000,
+    )
+    .ok();
+    // The meter covers the commit itself, not just the click
+    let timeout = params.get_timeout_ms("timeout_ms", 3_600_000);
+    if !super::transport::wait_for_progress(
+        super::transport::PROGRESS_APPEAR,
+        std::time::Duration::from_millis(timeout),
+    ) {
+        anyhow::bail!("The commit was still running after {} ms", timeout);
+    }
+    report_processed("tracks_committed", count);
+    Ok(())
+}
+
+// ============================================================================
+// Pattern Selection
+// ============================================================================
+
+/// Build a case-insensitive regex from a glob (`*` and `?` wildcards)
+pub fn glob_to_regex(glob: &str) -> R<regex::Regex> {
+    let mut pattern = String::from("(?i)^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    Ok(regex::Regex::new(&pattern)?)
+}
+
+/// Tracks whose names match the `pattern` / `regex` and `type` params,
+/// and the rest, both in session order
+async fn match_tracks(pt: &mut ProtoolsSession, params: &Params) -> R<(Vec<String>, Vec<String>)> {
+    let matcher = match params.get_ostring("regex") {
+        Some(re) => regex::Regex::new(&re)?,
+        None => {
+            let glob = params.get_string("pattern", "");
+            if glob.is_empty() {
+                anyhow::bail!("Provide a 'pattern' or 'regex' param");
+            }
+            glob_to_regex(&glob)?
+        }
+    };
+    // Accept a single type or a list, with or without the "TT_" prefix
+    let type_key = |t: &str| t.to_lowercase().trim_start_matches("tt_").to_string();
+    let types: Vec<String> = match params.get_ostring("type") {
+        Some(t) => vec![type_key(&t)],
+        None => params
+            .get_string_vec("type")
+            .iter()
+            .map(|t| type_key(t))
+            .collect(),
+    };
+
+    let tracks = pt
+        .get_all_tracks()
+        .await
+        .context("Failed to get track list")?;
+    let (matching, others): (Vec<_>, Vec<_>) = tracks
+        .iter()
+        .filter_map(|track| Some((track, track["name"].as_str()?)))
+        .partition(|(track, name)| {
+            (types.is_empty()
+                || types.contains(&type_key(track["type"].as_str().unwrap_or_default())))
+                && matcher.is_match(name)
+        });
+    let names = |tracks: Vec<(&serde_json::Value, &str)>| {
+        tracks
+            .into_iter()
+            .map(|(_, name)| name.to_string())
+            .collect::<Vec<_>>()
+    };
+    let matching = names(matching);
+    if matching.is_empty() {
+        anyhow::bail!("No tracks match");
+    }
+    Ok((matching, names(others)))
+}
+
+/// Select tracks whose names match a glob or regex
+///
+/// Replaces the current track selection, so it can be chained before
+/// solo/mute/record-arm actions.
+///
+/// Params:
+/// - pattern: glob over track names, case-insensitive (e.g. "DX*")
+/// - regex: regular expression over track names (used instead of pattern)
+/// - type: only match tracks of these types (e.g. "audio", "aux", ["midi", "instrument"])
+pub async fn select_matching(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let (names, _) = match_tracks(pt, params).await?;
+    log::info!("Selecting {} track(s): {:?}", names.len(), names);
+    pt.select_tracks_by_name(names).await?;
+    Ok(())
+}
+
+/// Same as `select_matching` (its earlier name)
+pub async fn select_by_pattern(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    select_matching(pt, params).await
+}
+
+/// Show tracks whose names match a glob or regex
+///
+/// Params: as `select_matching`, plus
+/// - hide_others: hide every other track (default: true)
+pub async fn show_matching(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let (names, others) = match_tracks(pt, params).await?;
+    log::info!("Showing {} track(s): {:?}", names.len(), names);
+    // Show first, so the Edit window is never left empty
+    pt.hide_tracks(names, false).await?;
+    if params.get_bool("hide_others", true) {
+        pt.hide_tracks(others, true).await?;
+    }
+    Ok(())
+}
+
+/// Hide tracks whose names match a glob or regex
+///
+/// Params: as `select_matching`, plus
+/// - show_others: show every other track (default: false)
+pub async fn hide_matching(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let (names, others) = match_tracks(pt, params).await?;
+    if params.get_bool("show_others", false) {
+        pt.hide_tracks(others, false).await?;
+    }
+    log::info!("Hiding {} track(s): {:?}", names.len(), names);
+    pt.hide_tracks(names, true).await?;
+    Ok(())
+}
+
+// ============================================================================
+// Track Color and Names
+// ============================================================================
+
+/// "#RRGGBB" / "RRGGBB" (or "#AARRGGBB", as the palette lists them) -> RGB
+fn parse_hex_color(hex: &str) -> Option<[u8; 3]> {
+    let hex = hex.trim().trim_start_matches('#');
+    if !matches!(hex.len(), 6 | 8) {
+        return None;
+    }
+    let rgb = u32::from_str_radix(&hex[hex.len() - 6..], 16).ok()?;
+    Some([(rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8])
+}
+
+/// Index of the palette color closest to `rgb`
+fn nearest_palette_color(palette: &[String], rgb: [u8; 3]) -> Option<usize> {
+    let distance = |other: [u8; 3]| {
+        rgb.iter()
+            .zip(other)
+            .map(|(&a, b)| (a as i32 - b as i32).pow(2))
+            .sum::<i32>()
+    };
+    palette
+        .iter()
+        .enumerate()
+        .filter_map(|(index, color)| Some((index, distance(parse_hex_color(color)?))))
+        .min_by_key(|&(_, distance)| distance)
+        .map(|(index, _)| index)
+}
+
+/// The palette index the `color` param asks for: an index, or the closest
+/// palette color to a hex color
+pub(super) fn palette_index(palette: &[String], params: &Params) -> R<usize> {
+    match (params.get_oint("color"), params.get_ostr("color")) {
+        (Some(index), _) => usize::try_from(index)
+            .ok()
+            .filter(|&index| index < palette.len())
+            .with_context(|| {
+                format!(
+                    "Color index {} out of range (0-{})",
+                    index,
+                    palette.len() - 1
+                )
+            }),
+        (None, Some(hex)) => {
+            let rgb = parse_hex_color(hex)
+                .with_context(|| format!("Invalid color '{}' (use \"#RRGGBB\")", hex))?;
+            nearest_palette_color(palette, rgb).context("Couldn't read the color palette")
+        }
+        (None, None) => anyhow::bail!("Provide a 'color' param"),
+    }
+}
+
+/// Color the selected tracks
+///
+/// Uses PTSL on Pro Tools 2025.10 and later. Earlier versions can't color
+/// tracks over PTSL, so the Color Palette window is opened on the
+/// selection to pick the color by hand.
+///
+/// Params:
+/// - color: palette index (e.g. 12), or a hex color (e.g. "#3A7BD5") for the
+///   closest palette color
+pub async fn recolor(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let names = pt.get_selected_track_names().await?;
+    if names.is_empty() {
+        anyhow::bail!("No tracks selected");
+    }
+    let palette = pt.get_track_color_palette().await.unwrap_or_default();
+    if palette.is_empty() {
+        log::info!("No PTSL track colors, opening the Color Palette");
+        OS::menu_click("Pro Tools", &["Window", "Color Palette"])?;
+        OS::show_notification(&crate::notify::text("pick_color", &[]));
+        return Ok(());
+    }
+
+    let index = palette_index(&palette, params)?;
+    log::info!("Coloring {} track(s) {}", names.len(), palette[index]);
+    pt.set_track_color(names, index as i32).await?;
+    Ok(())
+}
+
+lazy_static! {
+    static ref NAME_TOKEN: regex::Regex =
+        regex::Regex::new(r"\{(n|counter|name)(?::(0?)(\d+))?\}").unwrap();
+}
+
+/// Expand `{name}` (the current name) and `{n}` or `{counter}` (the
+/// number), padded like a format string: `{n:02}` -> "07", `{n:3}` -> "  7"
+pub fn expand_name_tokens(template: &str, name: &str, n: i64) -> String {
+    NAME_TOKEN
+        .replace_all(template, |caps: &regex::Captures| {
+            if &caps[1] == "name" {
+                return name.to_string();
+            }
+            let width = caps.get(3).map_or(0, |w| w.as_str().parse().unwrap_or(0));
+            if caps.get(2).is_some_and(|zero| !zero.is_empty()) {
+                format!("{:0width$}", n, width = width)
+            } else {
+                format!("{:width$}", n, width = width)
+            }
+        })
+        .into_owned()
+}
+
+/// Rename the selected tracks, top to bottom
+///
+/// Each name has `find` replaced, then `prefix` and `suffix` added. All of
+/// them can use `{n}` (numbered from `start`, `{n:02}` zero-pads) and
+/// `{name}` (the current name).
+///
+/// Params:
+/// - find / replace: replace text in the name (plain text, case-sensitive)
+/// - prefix / suffix: add to the start / end of the name
+/// - name: replace the whole name instead (e.g. "DX {n:02}")
+/// - start: number of the first track (default: 1)
+pub async fn rename(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let names = pt.get_selected_track_names().await?;
+    if names.is_empty() {
+        anyhow::bail!("No tracks selected");
+    }
+    let find = params.get_str("find", "");
+    let start = params.get_int("start", 1);
+    let renames: Vec<(String, String)> = names
+        .iter()
+        .zip(start..)
+        .map(|(name, n)| {
+            let expand = |template: &str| expand_name_tokens(template, name, n);
+            let base = match params.get_ostr("name") {
+                Some(template) => expand(template),
+                None if !find.is_empty() => {
+                    name.replace(find, &expand(params.get_str("replace", "")))
+                }
+                None => name.clone(),
+            };
+            let new_name = format!(
+                "{}{}{}",
+                expand(params.get_str("prefix", "")),
+                base,
+                expand(params.get_str("suffix", ""))
+            );
+            (name.clone(), new_name)
+        })
+        .filter(|(name, new_name)| name != new_name)
+        .collect();
+    if renames.is_empty() {
+        anyhow::bail!("Nothing to rename (set find, prefix, suffix or name)");
+    }
+    // Check before renaming anything - Pro Tools refuses duplicate names
+    let all_names = pt.get_all_track_names().await?;
+    for (index, (_, new_name)) in renames.iter().enumerate() {
+        if new_name.trim().is_empty() {
+            anyhow::bail!("A track would be left without a name");
+        }
+        if renames[..index].iter().any(|(_, other)| other == new_name) {
+            anyhow::bail!("Two tracks would be named '{}'", new_name);
+        }
+        let keeps_name = |track: &String| !renames.iter().any(|(name, _)| name == track);
+        if all_names
+            .iter()
+            .any(|track| track == new_name && keeps_name(track))
+        {
+            anyhow::bail!("There already is a track named '{}'", new_name);
+        }
+    }
+
+    let _job = super::jobs::track("Rename tracks");
+    for (name, new_name) in &rename_order(&renames, &all_names) {
+        log::info!("Renaming track '{}' -> '{}'", name, new_name);
+        pt.rename_track(name, new_name)
+            .await
+            .with_context(|| format!("Failed to rename '{}'", name))?;
+    }
+    OS::show_notification(&crate::notify::text(
+        "tracks_renamed",
+        &[("count", &renames.len().to_string())],
+    ));
+    Ok(())
+}
+
+/// The renames in an order Pro Tools accepts
+///
+/// A track is only renamed once no other track still has its new name, so
+/// chains like A -> B, B -> C rename B first. Swaps (A -> B, B -> A) go
+/// through a temporary name.
+fn rename_order(renames: &[(String, String)], all_names: &[String]) -> Vec<(String, String)> {
+    let mut pending = renames.to_vec();
+    let mut order = Vec::new();
+    while !pending.is_empty() {
+        let free = pending
+            .iter()
+            .position(|(_, new_name)| !pending.iter().any(|(name, _)| name == new_name));
+        match free {
+            Some(index) => order.push(pending.remove(index)),
+            None => {
+                let (name, new_name) = pending.remove(0);
+                let taken = |temp: &String| {
+                    all_names.contains(temp)
+                        || renames.iter().any(|(_, other)| other == temp)
+                        || order.iter().any(|(_, other)| other == temp)
+                };
+                let temp = (1..)
+                    .map(|n| format!("{}~{}", name, n))
+                    .find(|temp| !taken(temp))
+                    .unwrap();
+                order.push((name, temp.clone()));
+                pending.push((temp, new_name));
+            }
+        }
+    }
+    order
+}
+
+// ============================================================================
+// Track Templates
+// ============================================================================
+
+/// One entry of a track template
+#[derive(Debug, serde::Deserialize)]
+struct TrackTemplate {
+    name: String,
+    /// "mono", "stereo", "5.1", "7.1.2"...
+    #[serde(default = "default_track_format")]
+    format: String,
+    /// "audio", "aux", "midi", "instrument", "vca", "folder"...
+    #[serde(default = "default_track_type", rename = "type")]
+    kind: String,
+    /// "samples" or "ticks"
+    #[serde(default = "default_track_timebase")]
+    timebase: String,
+    /// How many tracks (Pro Tools numbers them "name 1", "name 2"...)
+    #[serde(default = "default_track_count")]
+    count: i32,
+    /// Color palette index
+    color: Option<i32>,
+    /// Group to put the tracks in
+    group: Option<String>,
+}
+
+fn default_track_format() -> String {
+    "mono".to_string()
+}
+fn default_track_type() -> String {
+    "audio".to_string()
+}
+fn default_track_timebase() -> String {
+    "samples".to_string()
+}
+fn default_track_count() -> i32 {
+    1
+}
+
+/// Track templates file: `[[track]]` entries (TOML) or `{"track": [...]}` / `[...]` (JSON)
+#[derive(serde::Deserialize)]
+struct TemplateFile {
+    track: Vec<TrackTemplate>,
+}
+
+fn load_track_template(path: &std::path::Path) -> R<Vec<TrackTemplate>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if is_json {
+        let value: serde_json::Value = serde_json::from_str(&text)?;
+        return Ok(match value {
+            serde_json::Value::Array(_) => serde_json::from_value(value)?,
+            _ => serde_json::from_value::<TemplateFile>(value)?.track,
+        });
+    }
+    Ok(toml::from_str::<TemplateFile>(&text)
+        .with_context(|| format!("Invalid track template {}", path.display()))?
+        .track)
+}
+
+/// PTSL track format name ("5.1" -> "TF_5_1")
+fn track_format(format: &str) -> R<String> {
+    let format = format.to_lowercase().replace(['.', ' '], "_");
+    Ok(match format.as_str() {
+        "mono" => "TF_Mono".to_string(),
+        "stereo" => "TF_Stereo".to_string(),
+        "lcr" => "TF_LCR".to_string(),
+        "lcrs" => "TF_LCRS".to_string(),
+        "quad" => "TF_Quad".to_string(),
+        _ if format.starts_with(|c: char| c.is_ascii_digit()) => format!("TF_{}", format),
+        _ => anyhow::bail!("Unknown track format '{}'", format),
+    })
+}
+
+/// PTSL track type name ("aux" -> "TT_Aux")
+fn track_type(kind: &str) -> R<&'static str> {
+    Ok(match kind.to_lowercase().as_str() {
+        "audio" => "TT_Audio",
+        "aux" | "aux input" => "TT_Aux",
+        "midi" => "TT_Midi",
+        "instrument" => "TT_Instrument",
+        "vca" => "TT_Vca",
+        "master" => "TT_Master",
+        "video" => "TT_Video",
+        "folder" | "basic folder" => "TT_BasicFolder",
+        "routing folder" => "TT_RoutingFolder",
+        other => anyhow::bail!("Unknown track type '{}'", other),
+    })
+}
+
+/// Create the tracks listed in a template, in order, at the end of the session
+///
+/// Each entry has a `name` and optionally `format` (default "mono"), `type`
+/// (default "audio"), `timebase`, `count`, `color` (palette index) and
+/// `group`. Tracks sharing a group name are grouped once all are created.
+///
+/// Params:
+/// - path: template file, TOML (`[[track]]` tables) or JSON; relative paths
+///   are next to config.toml
+/// - tracks: the entries inline instead, as an array of tables
+pub async fn create_from_template(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let templates = match params.get_ostring("path") {
+        Some(path) => {
+            let path = match std::path::PathBuf::from(&path) {
+                path if path.is_relative() => crate::config::get_config_path()?
+                    .parent()
+                    .context("Config path has no folder")?
+                    .join(path),
+                path => path,
+            };
+            load_track_template(&path)?
+        }
+        None => params
+            .get_tables("tracks")
+            .into_iter()
+            .map(|table| {
+                toml::Value::Table(table)
+                    .try_into::<TrackTemplate>()
+                    .context("Invalid track entry")
+            })
+            .collect::<R<Vec<_>>>()?,
+    };
+    if templates.is_empty() {
+        anyhow::bail!("No tracks in the template (set path or tracks)");
+    }
+    // Check every entry before creating anything
+    for template in &templates {
+        track_format(&template.format).with_context(|| format!("Track '{}'", template.name))?;
+        track_type(&template.kind).with_context(|| format!("Track '{}'", template.name))?;
+    }
+
+    let _job = super::jobs::track("Create tracks");
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    let mut created = 0;
+    for template in &templates {
+        let response: serde_json::Value = pt
+            .cmd(
+                super::ptsl::CommandId::CreateNewTracks,
+                serde_json::json!({
+                    "number_of_tracks": template.count.max(1),
+                    "track_name": template.name,
+                    "track_format": track_format(&template.format)?,
+                    "track_type": track_type(&template.kind)?,
+                    "track_timebase": format!("TTB_{}", capitalize(&template.timebase)),
+                    "insertion_point_position": "TIPoint_Last",
+                }),
+            )
+            .await
+            .with_context(|| format!("Failed to create '{}'", template.name))?;
+        let names: Vec<String> = response["created_track_names"]
+            .as_array()
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| name.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        created += names.len().max(1);
+
+        if let Some(color) = template.color
+            && !names.is_empty()
+        {
+            pt.set_track_color(names.clone(), color)
+                .await
+                .with_context(|| format!("Failed to color '{}'", template.name))?;
+        }
+        if let Some(group) = &template.group {
+            match groups.iter_mut().find(|(name, _)| name == group) {
+                Some((_, tracks)) => tracks.extend(names),
+                None => groups.push((group.clone(), names)),
+            }
+        }
+    }
+
+    for (group, tracks) in groups {
+        if let Err(e) = create_group(pt, &group, tracks).await {
+            log::warn!("Failed to create group '{}': {:#}", group, e);
+            OS::show_notification(&crate::notify::text(
+                "group_not_created",
+                &[("group", &group), ("error", &format!("{:#}", e))],
+            ));
+        }
+    }
+    OS::show_notification(&crate::notify::text(
+        "tracks_created",
+        &[("count", &created.to_string())],
+    ));
+    Ok(())
+}
+
+/// "samples" -> "Samples"
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| {
+            first
+                .to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Group tracks through the Create Group dialog (PTSL can't create groups)
+async fn create_group(pt: &mut ProtoolsSession, name: &str, tracks: Vec<String>) -> R<()> {
+    if tracks.is_empty() {
+        anyhow::bail!("no tracks were created for it");
+    }
+    pt.select_tracks_by_name(tracks).await?;
+    OS::menu_click("Pro Tools", &["Track", "Group..."])?;
+    if !OS::wait_for_window(
+        "Pro Tools",
+        "Create Group",
+        OS::WindowCondition::Exists,
+        3000,
+    )? {
+        anyhow::bail!("the Create Group dialog didn't open");
+    }
+    // The name field has focus when the dialog opens
+    OS::type_text_verified(name, true)?;
+    OS::keystroke(&["return"])?;
+    OS::wait_for_window(
+        "Pro Tools",
+        "Create Group",
+        OS::WindowCondition::Closed,
+        3000,
+    )?;
+    Ok(())
+}
+
+/// Track heights, smallest first, as Control-Up/Down steps through them
+const TRACK_HEIGHTS: [&str; 7] = [
+    "micro", "mini", "small", "medium", "large", "jumbo", "extreme",
+];
+
+/// Set the height of the selected tracks
+///
+/// PTSL can't set track heights, so this steps them with Control-Up/Down:
+/// down to micro and back up to `height`, or `step` steps from where they
+/// are - the one to give an encoder (e.g. a `wheel` binding with
+/// `param = "step"`).
+///
+/// Params:
+/// - height: micro, mini, small, medium, large, jumbo or extreme
+/// - step: steps up (positive) or down (negative) instead of `height`
+pub async fn set_height(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let (down, up) = match (params.get_ostr("height"), params.get_oint("step")) {
+        (Some(height), _) => {
+            let index = TRACK_HEIGHTS
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(height))
+                .with_context(|| {
+                    format!(
+                        "Unknown track height '{}' (one of {})",
+                        height,
+                        TRACK_HEIGHTS.join(", ")
+                    )
+                })?;
+            (TRACK_HEIGHTS.len() - 1, index)
+        }
+        (None, Some(step)) => {
+            let steps = (step.unsigned_abs() as usize).min(TRACK_HEIGHTS.len() - 1);
+            if step < 0 { (steps, 0) } else { (0, steps) }
+        }
+        (None, None) => anyhow::bail!("Set 'height' or 'step'"),
+    };
+    if pt.get_selected_track_names().await?.is_empty() {
+        anyhow::bail!("No tracks selected");
+    }
+    OS::focus_app("Pro Tools", "", true, false, 500)?;
+    for (key, count) in [("down", down), ("up", up)] {
+        for _ in 0..count {
+            OS::keystroke(&["control", key])?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_tokens() {
+        assert_eq!(expand_name_tokens("DX {n:02}", "A1", 7), "DX 07");
+        assert_eq!(expand_name_tokens("{name}_{n}", "FX", 12), "FX_12");
+        assert_eq!(expand_name_tokens("{n:3}|{x}", "", 7), "  7|{x}");
+        assert_eq!(expand_name_tokens("{name}-{counter:03}", "VO", 4), "VO-004");
+        assert_eq!(parse_hex_color("#3A7BD5"), Some([0x3a, 0x7b, 0xd5]));
+        assert_eq!(
+            nearest_palette_color(&["#000000".into(), "#FF0000".into()], [200, 20, 20]),
+            Some(1)
+        );
+    }
+    #[test]
+    fn test_rename_order() {
+        let pairs = |list: &[(&str, &str)]| -> Vec<(String, String)> {
+            list.iter()
+                .map(|(a, b)| (a.to_string(), b.to_string()))
+                .collect()
+        };
+        let names: Vec<String> = ["A", "B", "C"].iter().map(|s| s.to_string()).collect();
+
+        // B has to make way before A can take its name
+        assert_eq!(
+            rename_order(&pairs(&[("A", "B"), ("B", "C")]), &names),
+            pairs(&[("B", "C"), ("A", "B")])
+        );
+        // A swap goes through a temporary name
+        assert_eq!(
+            rename_order(&pairs(&[("A", "B"), ("B", "A")]), &names),
+            pairs(&[("A", "A~1"), ("B", "A"), ("A~1", "B")])
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! Transport control, state tracking and action gating
+//!
+//! Transport actions (play, stop, record, loop, pre/post-roll...) over PTSL,
+//! so MIDI pads and hotkeys can run the transport without HUI. Also provides
+//! a cached PTSL transport-state query so hotkeys can be gated on playback
+//! state (`when_stopped = true`), a guard that refuses destructive edits
+//! while Pro Tools is recording, and a guard that holds actions while Pro
+//! Tools is busy with a progress dialog.
+
+use super::TOKIO_RT;
+use super::client::{ProtoolsSession, PtSelectionSamples};
+use super::ptsl::CommandId;
+use crate::actions_async;
+use crate::config::BusyGuardConfig;
+use crate::prelude::*;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+actions_async!("pt", transport, {
+    play,
+    stop,
+    toggle_play,
+    record,
+    record_arm,
+    loop_playback,
+    return_to_zero,
+    shuttle,
+    pre_roll,
+    post_roll,
+});
+
+// ============================================================================
+// Transport Actions
+// ============================================================================
+
+/// Start playback (does nothing while already playing)
+pub async fn play(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    if is_stopped(&pt.get_transport_state().await?) {
+        toggle_play_state(pt).await?;
+    }
+    Ok(())
+}
+
+/// Stop playback or recording (does nothing while stopped)
+pub async fn stop(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    if !is_stopped(&pt.get_transport_state().await?) {
+        toggle_play_state(pt).await?;
+    }
+    Ok(())
+}
+
+/// Start or stop playback, like the space bar
+pub async fn toggle_play(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    toggle_play_state(pt).await
+}
+
+/// Arm the transport and start recording (does nothing while recording)
+pub async fn record(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    let state = pt.get_transport_state().await?;
+    if is_recording(&state) {
+        return Ok(());
+    }
+    if !pt.get_transport_armed().await? {
+        toggle_record_enable(pt).await?;
+    }
+    if is_stopped(&state) {
+        toggle_play_state(pt).await?;
+    }
+    Ok(())
+}
+
+/// Arm or disarm the transport's record button
+///
+/// Params:
+/// - enabled: arm (true) or disarm (false) instead of toggling
+pub async fn record_arm(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let armed = pt.get_transport_armed().await?;
+    if params.get_obool("enabled") != Some(armed) {
+        toggle_record_enable(pt).await?;
+    }
+    Ok(())
+}
+
+/// Turn loop playback on or off
+///
+/// Params:
+/// - enabled: turn it on (true) or off (false) instead of toggling
+pub async fn loop_playback(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let response: serde_json::Value = pt
+        .cmd(CommandId::GetPlaybackMode, serde_json::json!({}))
+        .await?;
+    let looping = response["current_settings"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|mode| mode.as_str().is_some_and(|mode| mode.ends_with("_Loop")));
+    let enabled = params.get_obool("enabled").unwrap_or(!looping);
+    if enabled != looping {
+        let _: serde_json::Value = pt
+            .cmd(
+                CommandId::SetPlaybackMode,
+                serde_json::json!({
+                    "playback_mode": if enabled { "PM_Loop" } else { "PM_Normal" },
+                }),
+            )
+            .await?;
+    }
+    log::info!("Loop playback {}", if enabled { "on" } else { "off" });
+    Ok(())
+}
+
+/// Move the cursor to the session start
+pub async fn return_to_zero(pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    selection.set_io(pt, 0, 0).await?;
+    Ok(())
+}
+
+/// Play (or record, with `record = true`) at a set speed
+///
+/// PTSL only offers half speed besides normal speed.
+///
+/// Params:
+/// - speed: 0.5 or 1 (default 0.5)
+/// - record: record instead of play (default false)
+pub async fn shuttle(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let speed = params.get_number("speed", 0.5);
+    let record = params.get_bool("record", false);
+    if speed == 1.0 {
+        return if record {
+            self::record(pt, params).await
+        } else {
+            play(pt, params).await
+        };
+    }
+    if speed != 0.5 {
+        anyhow::bail!("Pro Tools can only shuttle at half speed (speed = 0.5) or normal speed");
+    }
+    let command = if record {
+        CommandId::RecordHalfSpeed
+    } else {
+        CommandId::PlayHalfSpeed
+    };
+    let _: serde_json::Value = pt.cmd(command, serde_json::json!({})).await?;
+    invalidate_state();
+    Ok(())
+}
+
+/// Turn pre-roll on or off
+///
+/// Params:
+/// - enabled: turn it on (true) or off (false) instead of toggling
+pub async fn pre_roll(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    let (pre, post) = selection.pre_post_roll();
+    let pre = params.get_obool("enabled").unwrap_or(!pre);
+    selection.set_pre_post_roll(pt, pre, post).await?;
+    log::info!("Pre-roll {}", if pre { "on" } else { "off" });
+    Ok(())
+}
+
+/// Turn post-roll on or off
+///
+/// Params:
+/// - enabled: turn it on (true) or off (false) instead of toggling
+pub async fn post_roll(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let mut selection = PtSelectionSamples::new(pt).await?;
+    let (pre, post) = selection.pre_post_roll();
+    let post = params.get_obool("enabled").unwrap_or(!post);
+    selection.set_pre_post_roll(pt, pre, post).await?;
+    log::info!("Post-roll {}", if post { "on" } else { "off" });
+    Ok(())
+}
+
+async fn toggle_play_state(pt: &mut ProtoolsSession) -> R<()> {
+    let _: serde_json::Value = pt
+        .cmd(CommandId::TogglePlayState, serde_json::json!({}))
+        .await?;
+    invalidate_state();
+    Ok(())
+}
+
+async fn toggle_record_enable(pt: &mut ProtoolsSession) -> R<()> {
+    let _: serde_json::Value = pt
+        .cmd(CommandId::ToggleRecordEnable, serde_json::json!({}))
+        .await?;
+    invalidate_state();
+    Ok(())
+}
+
+// ============================================================================
+// Cached Transport State
+// ============================================================================
+
+/// Last known transport state and when it was fetched
+static TRANSPORT_CACHE: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+/// How long a cached transport state is trusted before querying PTSL again
+const CACHE_TTL: Duration = Duration::from_millis(500);
+
+/// Get the transport state, using the cached value if it is fresh
+///
+/// Returns the raw PTSL enum string (e.g. "TState_TransportStopped")
+pub async fn transport_state(pt: &mut ProtoolsSession) -> R<String> {
+    if let Some(state) = cached_state() {
+        return Ok(state);
+    }
+    let state = pt.get_transport_state().await?;
+    *TRANSPORT_CACHE.lock().unwrap() = Some((state.clone(), Instant::now()));
+    Ok(state)
+}
+
+/// Forget the cached state (the transport was just changed)
+fn invalidate_state() {
+    *TRANSPORT_CACHE.lock().unwrap() = None;
+}
+
+fn cached_state() -> Option<String> {
+    TRANSPORT_CACHE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .filter(|(_, fetched)| fetched.elapsed() < CACHE_TTL)
+        .map(|(state, _)| state.clone())
+}
+
+/// How often the background refresh polls PTSL for hotkey gating
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Back-off between refreshes while Pro Tools isn't answering
+const REFRESH_RETRY: Duration = Duration::from_secs(2);
+
+/// Longest a single refresh query may take
+const REFRESH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Oldest state hotkey gating still trusts
+const STALE_AFTER: Duration = Duration::from_secs(2);
+
+static REFRESH: std::sync::Once = std::sync::Once::new();
+
+/// Transport state for sync contexts (hotkey callbacks)
+///
+/// Never talks to PTSL, so it's safe on the event tap: it reads the cache
+/// that a background task keeps fresh, starting the task on first use.
+/// None means the state isn't known right now - callers fail open.
+pub fn transport_state_cached() -> Option<String> {
+    REFRESH.call_once(|| match TOKIO_RT.get() {
+        Some(rt) => {
+            rt.spawn(refresh_loop());
+        }
+        None => log::warn!("No runtime for the transport state refresh"),
+    });
+    TRANSPORT_CACHE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .filter(|(_, fetched)| fetched.elapsed() < STALE_AFTER)
+        .map(|(state, _)| state.clone())
+}
+
+/// Keep TRANSPORT_CACHE fresh for `transport_state_cached`
+async fn refresh_loop() {
+    loop {
+        let refreshed = tokio::time::timeout(REFRESH_TIMEOUT, async {
+            let mut pt = ProtoolsSession::shared().await?;
+            let state = pt.get_transport_state().await?;
+            *TRANSPORT_CACHE.lock().unwrap() = Some((state, Instant::now()));
+            R::<()>::Ok(())
+        })
+        .await;
+        let wait = match refreshed {
+            Ok(Ok(())) => REFRESH_INTERVAL,
+            Ok(Err(e)) => {
+                log::debug!("Transport state refresh failed: {:#}", e);
+                REFRESH_RETRY
+            }
+            Err(_) => {
+                log::debug!("Transport state refresh timed out");
+                REFRESH_RETRY
+            }
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+pub fn is_stopped(state: &str) -> bool {
+    state.ends_with("TransportStopped")
+}
+
+pub fn is_recording(state: &str) -> bool {
+    state.contains("Recording")
+}
+
+// ============================================================================
+// Destructive Action Guard
+// ============================================================================
+
+/// Pro Tools actions that remove or overwrite audio, clips or names
+///
+/// Marked `#[destructive]` in their module's `actions_async!` list; names
+/// are the registry keys (module_action). Actions that only add to the
+/// session, like `markers_import` and `tracks_create_from_template`, aren't
+/// marked: they leave existing audio and names alone.
+pub fn is_destructive(action_name: &str) -> bool {
+    super::destructive_actions().contains(action_name)
+}
+
+/// Refuse destructive actions while Pro Tools is recording
+///
+/// Called by the `actions_async!` wrapper before every Pro Tools action.
+pub async fn guard_destructive(pt: &mut ProtoolsSession, action_name: &str) -> R<()> {
+    if !is_destructive(action_name) {
+        return Ok(());
+    }
+    let state = transport_state(pt).await?;
+    if is_recording(&state) {
+        log::warn!("Refusing '{}' - Pro Tools is recording", action_name);
+        anyhow::bail!("Refusing destructive action during record");
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Busy Guard
+// ============================================================================
+
+/// Active busy guard settings. Replaced on config reload.
+static BUSY_CONFIG: Mutex<Option<BusyGuardConfig>> = Mutex::new(None);
+
+/// How often a held action re-checks the Pro Tools windows
+const BUSY_POLL: Duration = Duration::from_millis(250);
+
+/// Apply busy guard settings from config (no section = defaults, enabled)
+pub fn set_busy_config(config: Option<BusyGuardConfig>) {
+    let config = config.unwrap_or_default();
+    log::info!(
+        "Busy guard {}",
+        if config.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    *BUSY_CONFIG.lock().unwrap() = Some(config);
+}
+
+/// Title of a Pro Tools progress dialog that's open right now, if any
+fn busy_window(patterns: &[String]) -> Option<String> {
+    let titles = OS::get_window_titles("Pro Tools").ok()?;
+    titles
+        .into_iter()
+        // The session name is in these titles and could contain anything
+        .filter(|title| !title.starts_with("Edit:") && !title.starts_with("Mix:"))
+        .find(|title| patterns.iter().any(|p| crate::soft_match(title, p)))
+}
+
+/// Hold a Pro Tools action while Pro Tools shows a progress dialog
+///
+/// Saving, indexing or rendering puts up a modal that would swallow the
+/// action's clicks and keystrokes. Waits up to `wait_ms` for it to close
+/// (with a menu bar note), then refuses the action. Called by the
+/// `actions_async!` wrapper on the worker thread, before connecting.
+pub fn guard_busy(action_name: &str) -> R<()> {
+    let Some(config) = BUSY_CONFIG.lock().unwrap().clone().filter(|c| c.enabled) else {
+        return Ok(());
+    };
+    let Some(mut dialog) = busy_window(&config.windows) else {
+        return Ok(());
+    };
+
+    log::info!("Holding '{}' - Pro Tools is busy ({})", action_name, dialog);
+    crate::macos::menubar::set_status_text(" PT busy…");
+    let deadline = Instant::now() + Duration::from_millis(config.wait_ms);
+    let result = loop {
+        if Instant::now() >= deadline {
+            log::warn!("Refusing '{}' - '{}' is still open", action_name, dialog);
+            break Err(anyhow::anyhow!("Pro Tools is busy ({})", dialog));
+        }
+        std::thread::sleep(BUSY_POLL);
+        match busy_window(&config.windows) {
+            Some(title) => dialog = title,
+            None => break Ok(()),
+        }
+    };
+    crate::macos::menubar::set_status_text(&super::jobs::idle_status());
+    if result.is_ok() {
+        log::info!("Pro Tools is free - running '{}'", action_name);
+    }
+    result
+}
+
+/// How long a render's progress dialog gets to show up
+pub const PROGRESS_APPEAR: Duration = Duration::from_millis(1500);
+
+/// Wait for the progress dialog a render, commit or bounce puts up to close
+///
+/// Gives the dialog `appear` to show up and returns at once if it doesn't
+/// (a short render can finish first). Uses the busy guard's window titles,
+/// enabled or not. False if it was still open after `timeout`.
+pub fn wait_for_progress(appear: Duration, timeout: Duration) -> bool {
+    let patterns = BUSY_CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_default()
+        .windows;
+    let started = Instant::now();
+    let mut seen = false;
+    loop {
+        match busy_window(&patterns) {
+            Some(_) => seen = true,
+            None if seen || started.elapsed() >= appear => return true,
+            None => {}
+        }
+        if started.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(BUSY_POLL);
+    }
+}
@@ -0,0 +1,182 @@
+//! ProTools window layout actions (namespace: "pt")
+//!
+//! Recall Window Configurations from the Window menu, and save/restore whole
+//! layouts - a Window Configuration plus where each floating window sits -
+//! to clean the screen up after a client session.
+use super::client::*;
+use super::plugins::parse_menus;
+use crate::actions_async;
+use crate::prelude::*;
+
+actions_async!("pt", window_layout, {
+    recall,
+    save,
+    restore,
+});
+
+/// The `configuration` param, a number or a name
+fn configuration_param(params: &Params) -> Option<String> {
+    params
+        .get_oint("configuration")
+        .map(|number| number.to_string())
+        .or_else(|| params.get_ostring("configuration"))
+}
+
+/// Split a Window Configurations menu item ("1: Edit Big", "#2 Mix") into
+/// its number and name
+fn split_configuration(title: &str) -> Option<(u32, &str)> {
+    let rest = title.trim_start().trim_start_matches('#');
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let number = rest[..digits].parse().ok()?;
+    let name = rest[digits..].trim_start_matches([':', '.', ')', ' ']);
+    Some((number, name))
+}
+
+/// The menu item for `wanted` (a number, else a name matched exactly then
+/// loosely) among the Window Configurations submenu's items
+fn find_configuration<'a>(titles: &'a [String], wanted: &str) -> Option<&'a String> {
+    let configurations: Vec<(&String, u32, &str)> = titles
+        .iter()
+        .filter_map(|title| split_configuration(title).map(|(n, name)| (title, n, name)))
+        .collect();
+    if let Ok(number) = wanted.trim().parse::<u32>() {
+        return configurations
+            .iter()
+            .find(|(_, n, _)| *n == number)
+            .map(|(title, _, _)| *title);
+    }
+    configurations
+        .iter()
+        .find(|(_, _, name)| name.eq_ignore_ascii_case(wanted))
+        .or_else(|| {
+            configurations
+                .iter()
+                .find(|(_, _, name)| crate::soft_match(name, wanted))
+        })
+        .map(|(title, _, _)| *title)
+}
+
+/// Recall a Window Configuration through Window > Configurations
+fn recall_configuration(wanted: &str) -> R<()> {
+    let menus = parse_menus(&OS::get_app_menus("Pro Tools")?)?;
+    let titles: Vec<String> = menus
+        .iter()
+        .find(|menu| menu.title == "Window")
+        .and_then(|menu| menu.children.as_deref())
+        .and_then(|items| items.iter().find(|item| item.title == "Configurations"))
+        .and_then(|item| item.children.as_deref())
+        .map(|items| items.iter().map(|item| item.title.clone()).collect())
+        .context("No Window > Configurations menu in Pro Tools")?;
+    let title = find_configuration(&titles, wanted)
+        .with_context(|| format!("No Window Configuration '{}' in this session", wanted))?;
+    log::info!("Recalling Window Configuration '{}'", title);
+    OS::menu_click("Pro Tools", &["Window", "Configurations", title])?;
+    OS::waits::settle(OS::waits::Wait::Display);
+    Ok(())
+}
+
+// ============================================================================
+// Command Implementations
+// ============================================================================
+
+/// Recall a Window Configuration by number or name
+///
+/// Params:
+/// - configuration: number (1, 2...) or name, soft matched (required)
+pub async fn recall(_pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let Some(configuration) = configuration_param(params) else {
+        anyhow::bail!("'configuration' is required");
+    };
+    OS::focus_app("Pro Tools", "", true, false, 500)?;
+    recall_configuration(&configuration)
+}
+
+/// Save where every Pro Tools window is as a `[layouts.<name>]` entry
+///
+/// Params:
+/// - name: layout name (prompts if omitted)
+/// - configuration: Window Configuration `restore` recalls first (default: none)
+pub async fn save(_pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let mut name = params.get_string("name", "");
+    if name.is_empty() {
+        match crate::macos::window::show_input_dialog("Save Layout", Some("Layout name:"), None)? {
+            Some(input) if !input.trim().is_empty() => name = input.trim().to_string(),
+            _ => return Ok(()), // Cancelled
+        }
+    }
+    let windows = OS::layouts::capture("Pro Tools")?;
+    if windows.is_empty() {
+        anyhow::bail!("No Pro Tools windows open");
+    }
+    let count = windows.len();
+    crate::config::append_layout(
+        &name,
+        &crate::config::LayoutConfig {
+            configuration: configuration_param(params),
+            windows,
+        },
+    )?;
+    OS::show_notification(&crate::notify::text(
+        "layout_saved",
+        &[("name", &name), ("count", &count.to_string())],
+    ));
+    Ok(())
+}
+
+/// Restore a saved layout: its Window Configuration, then every window's
+/// position and size
+///
+/// Windows in the layout that aren't open are skipped and listed in the
+/// notification.
+///
+/// Params:
+/// - name: layout saved with `save` (required)
+/// - configuration: recall this Window Configuration instead of the layout's
+pub async fn restore(_pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let name = params.get_string("name", "");
+    if name.is_empty() {
+        anyhow::bail!("'name' is required");
+    }
+    let layout = OS::layouts::get(&name)?;
+    OS::focus_app("Pro Tools", "", true, false, 500)?;
+    if let Some(configuration) = configuration_param(params).or(layout.configuration) {
+        recall_configuration(&configuration)?;
+    }
+    let missing = OS::layouts::place("Pro Tools", &layout.windows)?;
+    if missing.is_empty() {
+        OS::show_notification(&crate::notify::text("layout_restored", &[("name", &name)]));
+    } else {
+        OS::show_notification(&crate::notify::text(
+            "layout_restored_missing",
+            &[("name", &name), ("windows", &missing.join(", "))],
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_configuration() {
+        let titles: Vec<String> = [
+            "Window Configuration List",
+            "New Configuration...",
+            "1: Edit Big",
+            "2. Mix + Video",
+            "#12 Client",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(find_configuration(&titles, "2").unwrap(), "2. Mix + Video");
+        assert_eq!(find_configuration(&titles, "12").unwrap(), "#12 Client");
+        assert_eq!(
+            find_configuration(&titles, "edit big").unwrap(),
+            "1: Edit Big"
+        );
+        assert_eq!(find_configuration(&titles, "client").unwrap(), "#12 Client");
+        assert!(find_configuration(&titles, "3").is_none());
+        assert!(find_configuration(&titles, "New Configuration").is_none());
+    }
+}
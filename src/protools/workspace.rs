@@ -0,0 +1,139 @@
+//! ProTools Workspace browser actions (namespace: "pt")
+//!
+//! A Soundminer-lite workflow built on the accessibility helpers: open the
+//! Workspace, run a search, and spot a result to the timeline.
+use super::client::*;
+use crate::actions_async;
+use crate::prelude::*;
+
+actions_async!("pt", workspace, {
+    open,
+    search,
+    spot,
+});
+
+const WORKSPACE_WINDOW: &str = "Workspace";
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Make sure the Workspace window is open, opening it from the Window menu if not
+fn ensure_workspace_open() -> R<()> {
+    if OS::window_exists("Pro Tools", WORKSPACE_WINDOW)? {
+        return Ok(());
+    }
+    OS::menu_click("Pro Tools", &["Window", "Workspace"])?;
+    if !OS::wait_for_window(
+        "Pro Tools",
+        WORKSPACE_WINDOW,
+        OS::WindowCondition::Exists,
+        2000,
+    )? {
+        anyhow::bail!("Workspace window did not open");
+    }
+    Ok(())
+}
+
+/// Pick the file path out of a Workspace row
+///
+/// Rows are read as plain cell text, so the Path column must be visible.
+fn row_path(row: &[String]) -> Option<String> {
+    row.iter().find(|cell| cell.starts_with('/')).cloned()
+}
+
+// ============================================================================
+// Command Implementations
+// ============================================================================
+
+/// Open (or bring forward) the Workspace window
+pub async fn open(_pt: &mut ProtoolsSession, _params: &Params) -> R<()> {
+    OS::focus_app("Pro Tools", "", true, false, 500)?;
+    ensure_workspace_open()
+}
+
+/// Run a Workspace search
+///
+/// Params:
+/// - text: search text (prompts if omitted)
+/// - kind: item to pick from the Kind popup (e.g. "Audio File")
+/// - sample_rate: item to pick from the Sample Rate popup (e.g. "48 kHz")
+/// - kind_popup: name of the Kind popup (default: "Kind")
+/// - sample_rate_popup: name of the Sample Rate popup (default: "Sample Rate")
+/// - field: name of the search field (default: first text field)
+pub async fn search(_pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    let mut text = params.get_string("text", "");
+    if text.is_empty() {
+        match crate::macos::window::show_input_dialog(
+            "Workspace Search",
+            Some("Search for:"),
+            None,
+        )? {
+            Some(input) if !input.trim().is_empty() => text = input.trim().to_string(),
+            _ => return Ok(()), // Cancelled
+        }
+    }
+
+    OS::focus_app("Pro Tools", "", true, false, 500)?;
+    ensure_workspace_open()?;
+
+    if let Some(kind) = params.get_ostring("kind") {
+        let popup = params.get_string("kind_popup", "Kind");
+        OS::select_popup_menu_item("Pro Tools", WORKSPACE_WINDOW, &popup, &kind)
+            .with_context(|| format!("Failed to set {} to '{}'", popup, kind))?;
+    }
+    if let Some(rate) = params.get_ostring("sample_rate") {
+        let popup = params.get_string("sample_rate_popup", "Sample Rate");
+        OS::select_popup_menu_item("Pro Tools", WORKSPACE_WINDOW, &popup, &rate)
+            .with_context(|| format!("Failed to set {} to '{}'", popup, rate))?;
+    }
+
+    let field = params.get_string("field", "");
+    OS::set_text_field_value("Pro Tools", WORKSPACE_WINDOW, &field, &text)
+        .context("Failed to set Workspace search text")?;
+    OS::keystroke(&["return"])?;
+    log::info!("Workspace search: '{}'", text);
+    Ok(())
+}
+
+/// Spot a Workspace result to the timeline
+///
+/// Imports the file to the Clips list and spots it to the first selected
+/// track at the selection start. Uses the selected row if there is one,
+/// otherwise the top result. The Workspace's Path column must be visible.
+///
+/// Params:
+/// - top: always use the top result, ignoring the selection (default: false)
+pub async fn spot(pt: &mut ProtoolsSession, params: &Params) -> R<()> {
+    ensure_workspace_open()?;
+
+    let selected = if params.get_bool("top", false) {
+        Vec::new()
+    } else {
+        OS::get_table_rows("Pro Tools", WORKSPACE_WINDOW, true)?
+    };
+    let rows = if selected.is_empty() {
+        OS::get_table_rows("Pro Tools", WORKSPACE_WINDOW, false)?
+    } else {
+        selected
+    };
+    let row = rows.first().context("No Workspace results")?;
+    let path = row_path(row).context("No file path in row - show the Path column")?;
+
+    let track = pt
+        .get_selected_track_names()
+        .await?
+        .into_iter()
+        .next()
+        .context("Select a track to spot to")?;
+    let selection = PtSelectionSamples::new(pt).await?;
+    let (start, _) = selection.get_io();
+
+    let clip_ids = pt.import_audio_to_clip_list(vec![path.clone()]).await?;
+    if clip_ids.is_empty() {
+        anyhow::bail!("Import returned no clips for '{}'", path);
+    }
+    pt.spot_clips_by_id(clip_ids, &track, start).await?;
+    log::info!("Spotted '{}' to '{}' at {}", path, track, start);
+    Ok(())
+}
@@ -0,0 +1,198 @@
+//! Scheduled actions (`[[trigger.schedule]]`)
+//!
+//! Runs actions on an interval (`every = "20m"`) or at times of day
+//! (`at = "18:30"`), e.g. a periodic session copy as a backup. A small thread
+//! checks the schedule once a second; intervals count from when the config
+//! was loaded. Scheduled actions are skipped while hotkeys are suspended.
+
+use crate::config::ScheduleTriggerConfig;
+use std::sync::{Mutex, Once};
+use std::time::{Duration, Instant};
+
+const TICK: Duration = Duration::from_secs(1);
+
+/// Active schedule (empty = nothing scheduled). Replaced on config reload.
+static SCHEDULE: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+static START: Once = Once::new();
+
+struct Entry {
+    config: ScheduleTriggerConfig,
+    every: Option<Duration>,
+    /// Minutes past midnight
+    at: Vec<u32>,
+    next: Option<Instant>,
+    /// Minute of the day (and day) it last ran at a time of day
+    last_at: Option<(i32, u32)>,
+}
+
+/// Apply the schedule from config, dropping entries that can't run
+pub fn set_config(schedule: Vec<ScheduleTriggerConfig>) {
+    let now = Instant::now();
+    let entries: Vec<Entry> = schedule
+        .into_iter()
+        .filter_map(|config| match parse_entry(&config) {
+            Ok((every, at)) => Some(Entry {
+                next: every.map(|every| now + every),
+                every,
+                at,
+                last_at: None,
+                config,
+            }),
+            Err(e) => {
+                log::error!("Skipping schedule for {}: {:#}", config.action, e);
+                None
+            }
+        })
+        .collect();
+    if !entries.is_empty() {
+        log::info!("{} scheduled action(s)", entries.len());
+    }
+    *SCHEDULE.lock().unwrap() = entries;
+}
+
+fn parse_entry(config: &ScheduleTriggerConfig) -> anyhow::Result<(Option<Duration>, Vec<u32>)> {
+    if crate::config::get_action(&config.action).is_none() {
+        anyhow::bail!("unknown action");
+    }
+    let every = config.every.as_deref().map(parse_interval).transpose()?;
+    let at = config
+        .at
+        .iter()
+        .map(|time| parse_time_of_day(time))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if every.is_none() && at.is_empty() {
+        anyhow::bail!("needs `every` or `at`");
+    }
+    Ok((every, at))
+}
+
+/// Start the scheduler thread (safe to call more than once)
+pub fn start() {
+    START.call_once(|| {
+        std::thread::spawn(|| {
+            loop {
+                std::thread::sleep(TICK);
+                tick();
+            }
+        });
+    });
+}
+
+fn tick() {
+    let now = Instant::now();
+    let today = local_minute_of_day();
+    let mut due = Vec::new();
+    for entry in SCHEDULE.lock().unwrap().iter_mut() {
+        let mut run = false;
+        if let (Some(every), Some(next)) = (entry.every, entry.next)
+            && now >= next
+        {
+            entry.next = Some(now + every);
+            run = true;
+        }
+        if let Some((day, minute)) = today
+            && entry.at.contains(&minute)
+            && entry.last_at != Some((day, minute))
+        {
+            entry.last_at = Some((day, minute));
+            run = true;
+        }
+        if run {
+            due.push((entry.config.action.clone(), entry.config.params.clone()));
+        }
+    }
+
+    for (action, params) in due {
+        if crate::input::suspend::is_suspended() {
+            log::info!("Hotkeys suspended - skipping scheduled {}", action);
+            continue;
+        }
+        log::info!("Running scheduled {}", action);
+        // Own thread - a slow action mustn't hold up the rest of the schedule
+        std::thread::spawn(move || {
+            let params = crate::config::Params::new(params);
+            if let Err(e) = crate::ipc::run_action(&action, params) {
+                log::error!("Scheduled {} failed: {:#}", action, e);
+                #[cfg(target_os = "macos")]
+                crate::macos::show_notification(&crate::notify::text(
+                    "action_failed",
+                    &[("action", &action), ("error", &format!("{:#}", e))],
+                ));
+            }
+        });
+    }
+}
+
+/// "90s", "20m", "1h30m", "2h" (a bare number is minutes)
+fn parse_interval(text: &str) -> anyhow::Result<Duration> {
+    let text = text.trim().to_lowercase();
+    if let Ok(minutes) = text.parse::<u64>() {
+        return Ok(Duration::from_secs(minutes * 60));
+    }
+    let mut seconds = 0;
+    let mut number = String::new();
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        match c {
+            '0'..='9' => number.push(c),
+            'h' | 'm' | 's' => {
+                let value: u64 = number
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("invalid interval '{}'", text))?;
+                number.clear();
+                seconds += value
+                    * match c {
+                        'h' => 3600,
+                        'm' => 60,
+                        _ => 1,
+                    };
+            }
+            _ => anyhow::bail!("invalid interval '{}'", text),
+        }
+    }
+    if !number.is_empty() || seconds == 0 {
+        anyhow::bail!("invalid interval '{}'", text);
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+/// "18:30" -> minutes past midnight
+fn parse_time_of_day(text: &str) -> anyhow::Result<u32> {
+    let (hours, minutes) = text
+        .trim()
+        .split_once(':')
+        .and_then(|(h, m)| Some((h.parse::<u32>().ok()?, m.parse::<u32>().ok()?)))
+        .filter(|&(h, m)| h < 24 && m < 60)
+        .ok_or_else(|| anyhow::anyhow!("invalid time '{}' (use HH:MM)", text))?;
+    Ok(hours * 60 + minutes)
+}
+
+/// (day of the year, minute of the day) in local time
+fn local_minute_of_day() -> Option<(i32, u32)> {
+    unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&now, &mut tm).is_null() {
+            return None;
+        }
+        Some((tm.tm_yday, (tm.tm_hour * 60 + tm.tm_min) as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval_and_time() {
+        assert_eq!(parse_interval("20m").unwrap(), Duration::from_secs(1200));
+        assert_eq!(parse_interval("1h30m").unwrap(), Duration::from_secs(5400));
+        assert_eq!(parse_interval("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_interval("15").unwrap(), Duration::from_secs(900));
+        assert!(parse_interval("0m").is_err());
+        assert!(parse_interval("5 minutes").is_err());
+
+        assert_eq!(parse_time_of_day("18:30").unwrap(), 1110);
+        assert!(parse_time_of_day("24:00").is_err());
+    }
+}
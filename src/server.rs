@@ -0,0 +1,399 @@
+//! HTTP + WebSocket API for remote triggering (`[server]` section)
+//!
+//! Lets tablet remotes, Companion/Bitfocus and scripts on other machines
+//! drive the same action registry the hotkeys use:
+//!
+//! ```text
+//! POST /action/pt.tracks_solo_selected          run an action, JSON body = params
+//! GET  /actions                                 registered action names
+//! GET  /status                                  one-line daemon status
+//! GET  /ws                                      WebSocket: action results and log lines
+//! GET  /logs                                    WebSocket: log lines as plain text
+//! ```
+//!
+//! Every response is `{"ok": bool, "message": ...}` (action list: a JSON
+//! array). With `token` set, requests need `Authorization: Bearer <token>`
+//! or `?token=<token>`; binding anywhere but loopback requires one.
+//!
+//! Web pages are refused unless their Origin is in `allowed_origins`, and
+//! action bodies must be sent as `application/json`, so a page can't post a
+//! "simple" cross-site request at the API.
+
+use crate::config::{Params, ServerConfig};
+use crate::ipc::Response;
+use crate::prelude::*;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream};
+use std::sync::mpsc::TryRecvError;
+use std::sync::{Arc, Once};
+use std::time::{Duration, Instant};
+use tungstenite::protocol::{Role, WebSocket};
+use tungstenite::{Message, http};
+
+static START: Once = Once::new();
+
+/// Largest request body accepted (action params)
+const MAX_BODY: usize = 64 * 1024;
+
+/// How often an idle WebSocket is pinged to notice clients that went away
+const WS_PING: Duration = Duration::from_secs(30);
+
+/// Longest a WebSocket read waits before checking for events to send
+const WS_POLL: Duration = Duration::from_millis(50);
+
+/// Start the server if enabled in config (safe to call more than once)
+///
+/// Settings are read once; changing `[server]` needs a restart.
+pub fn start(config: Option<ServerConfig>) {
+    let Some(config) = config.filter(|c| c.enabled) else {
+        return;
+    };
+    if config.token.is_none() && !is_loopback(&config.bind) {
+        log::error!(
+            "HTTP server disabled - listening on {} needs a token in [server]",
+            config.bind
+        );
+        return;
+    }
+    START.call_once(|| {
+        let address = format!("{}:{}", config.bind, config.port);
+        let listener = match TcpListener::bind(&address) {
+            Ok(listener) => listener,
+            Err(e) => {
+                log::error!("HTTP server disabled - can't listen on {}: {}", address, e);
+                return;
+            }
+        };
+        log::info!("HTTP server listening on http://{}", address);
+        let config = Arc::new(config);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let config = Arc::clone(&config);
+                std::thread::spawn(move || {
+                    if let Err(e) = serve(stream, &config) {
+                        log::warn!("HTTP connection failed: {:#}", e);
+                    }
+                });
+            }
+        });
+    });
+}
+
+/// A parsed request head plus body
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+}
+
+/// Is `bind` a loopback address (only this machine can connect)?
+fn is_loopback(bind: &str) -> bool {
+    bind.eq_ignore_ascii_case("localhost")
+        || bind.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+fn serve(stream: TcpStream, config: &ServerConfig) -> R<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let request = read_request(&stream)?;
+    let mut stream = stream;
+
+    // Browsers always send Origin; pages from unlisted origins get nothing
+    let origin = request.header("origin");
+    if let Some(origin) = origin
+        && !config
+            .allowed_origins
+            .iter()
+            .any(|allowed| allowed == origin)
+    {
+        log::warn!("HTTP: refused request from origin {}", origin);
+        return write_json(&mut stream, None, 403, false, "Origin not allowed");
+    }
+
+    if request.method == "OPTIONS" {
+        return write_response(&mut stream, origin, 204, "");
+    }
+    if !authorized(&request, config) {
+        return write_json(&mut stream, origin, 401, false, "Missing or wrong token");
+    }
+
+    let is_upgrade = request
+        .header("upgrade")
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/ws") if is_upgrade => stream_events(stream, &request, false),
+        ("GET", "/logs") if is_upgrade => stream_events(stream, &request, true),
+        ("GET", "/actions") => {
+            let names = serde_json::to_string(&crate::config::action_names())?;
+            write_response(&mut stream, origin, 200, &names)
+        }
+        ("GET", "/status") => write_json(&mut stream, origin, 200, true, &crate::ipc::status()),
+        ("POST", path) if path.starts_with("/action/") => {
+            if !is_json(&request) {
+                return write_json(
+                    &mut stream,
+                    origin,
+                    415,
+                    false,
+                    "Content-Type must be application/json",
+                );
+            }
+            let action = &path["/action/".len()..];
+            let (status, ok, message) = match run(action, &request.body) {
+                Ok(message) => (200, true, message),
+                Err(e) => (400, false, format!("{:#}", e)),
+            };
+            write_json(&mut stream, origin, status, ok, &message)
+        }
+        _ => write_json(&mut stream, origin, 404, false, "Not found"),
+    }
+}
+
+/// Was the body sent as JSON? (A page can only send other types without a
+/// CORS preflight.)
+fn is_json(request: &HttpRequest) -> bool {
+    request
+        .header("content-type")
+        .and_then(|v| v.split(';').next())
+        .is_some_and(|v| v.trim().eq_ignore_ascii_case("application/json"))
+}
+
+fn run(action: &str, body: &[u8]) -> R<String> {
+    let params: HashMap<String, toml::Value> = if body.iter().all(u8::is_ascii_whitespace) {
+        HashMap::new()
+    } else {
+        serde_json::from_slice(body).context("Body must be a JSON object of params")?
+    };
+    log::info!("HTTP: running {}", action);
+    crate::ipc::run_action(action, Params::new(params))
+}
+
+fn authorized(request: &HttpRequest, config: &ServerConfig) -> bool {
+    let Some(token) = config.token.as_deref() else {
+        return true;
+    };
+    let matches = |given: Option<&str>| given.is_some_and(|given| same_secret(given, token));
+    matches(
+        request
+            .header("authorization")
+            .and_then(|v| v.strip_prefix("Bearer ")),
+    ) || matches(request.query.get("token").map(String::as_str))
+}
+
+/// Compare a token without bailing out at the first wrong byte, so response
+/// times don't give away how much of a guess was right
+fn same_secret(given: &str, token: &str) -> bool {
+    let (given, token) = (given.as_bytes(), token.as_bytes());
+    let mut diff = given.len() ^ token.len();
+    for (i, &byte) in token.iter().enumerate() {
+        diff |= usize::from(byte ^ given.get(i).copied().unwrap_or(!byte));
+    }
+    diff == 0
+}
+
+/// Decode a query string key or value (`%XX` escapes, `+` for space)
+fn url_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let hex = |at: usize| bytes.get(at).and_then(|&b| (b as char).to_digit(16));
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], hex(i + 1), hex(i + 2)) {
+            (b'%', Some(high), Some(low)) => {
+                out.push((high * 16 + low) as u8);
+                i += 2;
+            }
+            (b'+', _, _) => out.push(b' '),
+            (byte, _, _) => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn read_request(stream: &TcpStream) -> R<HttpRequest> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        anyhow::bail!("Malformed request line");
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (url_decode(k), url_decode(v)))
+        .collect();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    if length > MAX_BODY {
+        anyhow::bail!("Request body too large ({} bytes)", length);
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(HttpRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        query,
+        headers,
+        body,
+    })
+}
+
+fn write_json(
+    stream: &mut TcpStream,
+    origin: Option<&str>,
+    status: u16,
+    ok: bool,
+    message: &str,
+) -> R<()> {
+    let body = serde_json::to_string(&Response {
+        ok,
+        message: message.to_string(),
+    })?;
+    write_response(stream, origin, status, &body)
+}
+
+/// Write a response; `origin` (an allowed page's) gets the CORS headers
+fn write_response(stream: &mut TcpStream, origin: Option<&str>, status: u16, body: &str) -> R<()> {
+    let reason = match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        415 => "Unsupported Media Type",
+        _ => "Not Found",
+    };
+    let cors = match origin {
+        Some(origin) => format!(
+            "Access-Control-Allow-Origin: {}\r\n\
+             Access-Control-Allow-Headers: Authorization, Content-Type\r\n\
+             Vary: Origin\r\n",
+            origin
+        ),
+        None => String::new(),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         {}\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        cors,
+        body
+    )?;
+    Ok(())
+}
+
+/// Upgrade to a WebSocket and forward broadcast events until the client leaves
+///
+/// With `logs_only`, only log events are sent, formatted as in the log file.
+fn stream_events(mut stream: TcpStream, request: &HttpRequest, logs_only: bool) -> R<()> {
+    let mut handshake = http::Request::builder().method("GET").uri(&request.path);
+    for (name, value) in &request.headers {
+        handshake = handshake.header(name, value);
+    }
+    let response = tungstenite::handshake::server::create_response(&handshake.body(())?)?;
+
+    write!(stream, "HTTP/1.1 101 Switching Protocols\r\n")?;
+    for (name, value) in response.headers() {
+        write!(stream, "{}: {}\r\n", name, value.to_str()?)?;
+    }
+    write!(stream, "\r\n")?;
+    // Reads give up quickly, so one thread can both read and push events
+    stream.set_read_timeout(Some(WS_POLL))?;
+
+    let events = crate::broadcast::subscribe();
+    let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+    log::info!("WebSocket client connected");
+    let mut last_sent = Instant::now();
+    'connection: loop {
+        // Pings are answered and Close is acknowledged by tungstenite on the
+        // next flush or send
+        match socket.read() {
+            Ok(Message::Close(_)) => {
+                socket.flush().ok();
+                break 'connection;
+            }
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) => {}
+            Err(_) => break 'connection,
+        }
+        match socket.flush() {
+            Ok(()) => {}
+            Err(tungstenite::Error::Io(e)) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(_) => break 'connection,
+        }
+
+        // Send everything that came in since the last read
+        loop {
+            let message = match events.try_recv() {
+                Ok(event) if logs_only => match event.log_line() {
+                    Some(line) => Message::text(line),
+                    None => continue,
+                },
+                Ok(event) => Message::text(event.to_json()),
+                Err(TryRecvError::Empty) if last_sent.elapsed() >= WS_PING => {
+                    Message::Ping(Default::default())
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => break 'connection,
+            };
+            if socket.send(message).is_err() {
+                break 'connection;
+            }
+            last_sent = Instant::now();
+        }
+    }
+    log::info!("WebSocket client disconnected");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_from_query() {
+        assert_eq!(url_decode("a%2Bb+c%26d"), "a+b c&d");
+        assert_eq!(url_decode("100%"), "100%");
+        assert_eq!(url_decode("%zz%4"), "%zz%4");
+        assert!(same_secret("s3cr+t", "s3cr+t"));
+        assert!(!same_secret("s3cr", "s3cr+t"));
+        assert!(!same_secret("s3cr+tt", "s3cr+t"));
+        assert!(!same_secret("", "x"));
+    }
+}
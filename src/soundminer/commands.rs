@@ -5,35 +5,122 @@ use crate::prelude::*;
 // Command Implementations
 // ============================================================================
 
+/// Send the selected Soundminer files to the DAW
+///
+/// Params:
+/// - `daw`: DAW menu entry to target (e.g. "Pro Tools") - verified via its checkmark
+/// - `command`: Transfer menu item (default "Bring into DAW")
+/// - `reference_original`, `original_sample_rate`, `spot_as_region`: transfer options
+/// - `launch`: launch the DAW first if it isn't running
+/// - `verify`: check the DAW target and Transfer item before sending (default true)
+/// - `focus_attempts`: tries to bring Soundminer to the front (default 3)
+/// - `timeout_ms`: how long to wait for the transfer to finish (default 10000)
+/// - `return_focus`: switch back to the app that was frontmost (default true)
 pub fn send_to_daw(params: &Params) -> R<()> {
     let daw = params.get_ostring("daw");
-    let command = params.get_str("command", "Bring into DAW");
-    let refo = params.get_obool("reference_original");
-    let orig = params.get_obool("original_sample_rate");
-    let sprn = params.get_obool("spot_as_region");
     let launch = params.get_bool("launch", false);
-    // let sm = crate::soft_match_vec("Soundminer", &OS::get_running_apps()?)
-    //     .unwrap_or("Soundminer".to_string());
-    // println!(
-    //     "Running Apps: {:?}",
-    //     OS::get_running_apps()
-    // );
+    let return_focus = params.get_bool("return_focus", true);
+
+    let original_app = OS::get_current_app().ok();
+
     if launch && let Some(ref d) = daw {
         OS::focus_app(d, "", false, true, 1000)?;
     }
-    let sm = focus_sm();
+
+    let result = spot(params, daw.as_deref());
+
+    // Return focus even if the transfer failed
+    if return_focus
+        && let Some(app) = original_app
+        && !crate::soft_match(&app, "Soundminer")
+        && let Err(e) = OS::focus_app(&app, "", true, false, 1000)
+    {
+        log::warn!("Could not return focus to {}: {:#}", app, e);
+    }
+
+    result
+}
+
+fn spot(params: &Params, daw: Option<&str>) -> R<()> {
+    let command = params.get_str("command", "Bring into DAW");
+    let refo = params.get_obool("reference_original");
+    let orig = params.get_obool("original_sample_rate");
+    let sprn = params.get_obool("spot_as_region");
+    let verify = params.get_bool("verify", true);
+    let focus_attempts = params.get_int("focus_attempts", 3).max(1) as usize;
+    let timeout_ms = params.get_timeout_ms("timeout_ms", 10000);
+
+    let sm = focus_sm_verified(focus_attempts)?;
 
     send_sm_event("refo", refo)?;
     send_sm_event("orig", orig)?;
     send_sm_event("sprn", sprn)?;
-    if let Some(ref d) = daw {
-        OS::menu_click(&sm, &["DAW", d])?;
+
+    if let Some(d) = daw {
+        set_daw_target(&sm, d, verify)?;
+    }
+
+    if verify && !OS::menu_item_enabled(&sm, &["Transfer", command])? {
+        anyhow::bail!(
+            "Soundminer '{}' is unavailable - is anything selected?",
+            command
+        );
     }
     OS::menu_click(&sm, &["Transfer", command])?;
-    log::info!("✅ Spot to DAW command sent");
+
+    wait_for_transfer(&sm, command, timeout_ms)?;
+    log::info!("✅ Spot to DAW complete");
     Ok(())
 }
 
+/// Select the DAW menu target and confirm it took (the menu shows a checkmark)
+fn set_daw_target(sm: &str, daw: &str, verify: bool) -> R<()> {
+    let path = ["DAW", daw];
+    if verify && OS::menu_item_checked(sm, &path)? {
+        return Ok(());
+    }
+    OS::menu_click(sm, &path)?;
+    if !verify {
+        return Ok(());
+    }
+
+    // The menu can take a moment to update after the click
+    for _ in 0..5 {
+        if OS::menu_item_checked(sm, &path)? {
+            log::info!("Soundminer DAW target set to {}", daw);
+            return Ok(());
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    anyhow::bail!("Soundminer DAW target is not set to '{}'", daw)
+}
+
+/// Wait until Soundminer answers menu queries again
+///
+/// Soundminer is unresponsive while it transfers, so the Transfer item reads
+/// as disabled (or the query fails) until the files have been handed over.
+fn wait_for_transfer(sm: &str, command: &str, timeout_ms: u64) -> R<()> {
+    let started = std::time::Instant::now();
+    let timeout = std::time::Duration::from_millis(timeout_ms);
+
+    // Give the transfer a moment to start
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    while started.elapsed() < timeout {
+        if OS::menu_item_enabled(sm, &["Transfer", command]).unwrap_or(false) {
+            log::info!(
+                "Soundminer transfer finished in {} ms",
+                started.elapsed().as_millis()
+            );
+            return Ok(());
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    anyhow::bail!(
+        "Soundminer transfer did not finish within {} ms",
+        timeout_ms
+    )
+}
+
 pub fn send_sm_event(id: &str, param: Option<bool>) -> R<()> {
     if let Some(param) = param {
         let p = if param { 1 } else { 0 };
@@ -85,6 +172,27 @@ fn focus_sm() -> String {
         "Soundminer_Intel".to_string()
     }
 }
+
+/// Bring Soundminer to the front, retrying until it's actually frontmost
+///
+/// Returns the running app name (e.g. "Soundminer_Intel") for menu calls.
+fn focus_sm_verified(attempts: usize) -> R<String> {
+    for attempt in 1..=attempts {
+        let sm = focus_sm();
+        if crate::soft_match(&sm, "Soundminer") {
+            return Ok(sm);
+        }
+        log::warn!(
+            "Soundminer not frontmost after focus attempt {}/{} ({} is)",
+            attempt,
+            attempts,
+            sm
+        );
+        std::thread::sleep(std::time::Duration::from_millis(100 * attempt as u64));
+    }
+    anyhow::bail!("Could not bring Soundminer to the front")
+}
+
 // SM Apple codes:
 //
 // refo - refernce original
@@ -0,0 +1,78 @@
+//! Startup timing and deferred initialization
+//!
+//! Only what hotkeys need to fire (config, hotkey table, event capture) runs
+//! on the startup path. Slower integrations are handed to `defer`, which runs
+//! them on background threads. Every stage logs how long it took so startup
+//! regressions show up in the log.
+
+use crate::prelude::*;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+/// When the process started (set by `begin`)
+static LAUNCHED: OnceLock<Instant> = OnceLock::new();
+
+/// Record the launch time. Call first thing in `main`.
+pub fn begin() {
+    LAUNCHED.get_or_init(Instant::now);
+}
+
+/// Milliseconds since launch
+pub fn elapsed_ms() -> f64 {
+    LAUNCHED.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+/// Times consecutive startup stages
+pub struct StageTimer {
+    stage_started: Instant,
+}
+
+impl StageTimer {
+    pub fn new() -> Self {
+        Self {
+            stage_started: Instant::now(),
+        }
+    }
+
+    /// Log how long the stage that just finished took
+    pub fn mark(&mut self, stage: &str) {
+        let now = Instant::now();
+        log::info!(
+            "⏱  {}: {:.1} ms (t+{:.1} ms)",
+            stage,
+            (now - self.stage_started).as_secs_f64() * 1000.0,
+            elapsed_ms()
+        );
+        self.stage_started = now;
+    }
+}
+
+impl Default for StageTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run a slow initialization step on a background thread, logging its timing
+pub fn defer<F>(name: &'static str, init: F)
+where
+    F: FnOnce() -> R<()> + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let started = Instant::now();
+        match init() {
+            Ok(()) => log::info!(
+                "⏱  {} ready: {:.1} ms (t+{:.1} ms, background)",
+                name,
+                started.elapsed().as_secs_f64() * 1000.0,
+                elapsed_ms()
+            ),
+            Err(e) => log::error!("{} failed to initialize: {:#}", name, e),
+        }
+    });
+}
+
+/// Log that hotkeys are live. Called by the platform once capture is installed.
+pub fn ready() {
+    log::info!("⏱  Responsive to hotkeys at t+{:.1} ms", elapsed_ms());
+}
@@ -0,0 +1,189 @@
+//! Support bundles (`os.export_support_bundle`)
+//!
+//! One zip for a bug report instead of a pasted log excerpt and a guess at
+//! the config: config.toml with its secrets blanked out, the end of the log,
+//! a diagnostics report (versions, permissions, status, bindings) and the
+//! recent action history with the usage stats.
+
+use crate::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// How much of the log goes in
+const LOG_LINES: usize = 5000;
+
+/// Config keys whose values are blanked out (matched anywhere in the key)
+const SECRET_KEYS: &str = "token|password|passwd|secret|api_key|apikey";
+
+lazy_static::lazy_static! {
+    /// `key = value` where the key looks secret, in a table or inline table
+    static ref SECRET_VALUE: regex::Regex = regex::Regex::new(&format!(
+        r#"(?i)(\b[\w-]*(?:{})[\w-]*\s*=\s*)("(?:[^"\\]|\\.)*"|'[^']*'|[^,}}\s]+)"#,
+        SECRET_KEYS
+    ))
+    .unwrap();
+}
+
+/// Blank out the values of secret-looking keys in TOML text
+pub fn redact(config: &str) -> String {
+    SECRET_VALUE
+        .replace_all(config, r#"${1}"<redacted>""#)
+        .into_owned()
+}
+
+/// Write a bundle into `directory`, returning the zip's path
+pub fn export_bundle(directory: &Path) -> R<PathBuf> {
+    let stamp = Command::new("date")
+        .arg("+%Y%m%d-%H%M%S")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    let name = format!("pthkd-support-{}", stamp);
+    let staging = std::env::temp_dir().join(&name);
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)
+        .with_context(|| format!("Failed to create {}", staging.display()))?;
+
+    let result = write_files(&staging).and_then(|_| {
+        let zip = directory.join(format!("{}.zip", name));
+        let output = Command::new("zip")
+            .arg("-qr")
+            .arg(&zip)
+            .arg(&name)
+            .current_dir(std::env::temp_dir())
+            .output()
+            .context("Failed to run zip")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "zip failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(zip)
+    });
+    std::fs::remove_dir_all(&staging).ok();
+    result
+}
+
+/// The bundle's files, each written even if the others fail
+fn write_files(staging: &Path) -> R<()> {
+    let config = crate::config::get_config_path()?;
+    let files: [(&str, R<String>); 4] = [
+        (
+            "config.toml",
+            std::fs::read_to_string(&config)
+                .map(|text| redact(&text))
+                .with_context(|| format!("Failed to read {}", config.display())),
+        ),
+        ("pthkd.log", crate::ipc::recent_log_lines(LOG_LINES)),
+        ("diagnostics.txt", Ok(diagnostics())),
+        ("actions.txt", Ok(action_history())),
+    ];
+    for (file, contents) in files {
+        let contents = contents.unwrap_or_else(|e| format!("Unavailable: {:#}", e));
+        std::fs::write(staging.join(file), contents)?;
+    }
+    let usage = config.with_file_name("usage.json");
+    if usage.exists() {
+        std::fs::copy(&usage, staging.join("usage.json"))?;
+    }
+    Ok(())
+}
+
+/// Versions, permissions, daemon status and the bindings in effect
+fn diagnostics() -> String {
+    let command = |program: &str, args: &[&str]| {
+        Command::new(program)
+            .args(args)
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .unwrap_or_else(|e| format!("unavailable ({})", e))
+    };
+
+    let mut report = vec![
+        format!("pthkd {}", env!("CARGO_PKG_VERSION")),
+        format!("Built for {}", std::env::consts::ARCH),
+        format!("System: {}", command("uname", &["-srm"])),
+    ];
+    #[cfg(target_os = "macos")]
+    {
+        report.push(format!(
+            "macOS: {}",
+            command("sw_vers", &["-productVersion"])
+        ));
+        let missing = crate::macos::permissions::check_all_permissions().missing_permissions();
+        report.push(format!(
+            "Missing permissions: {}",
+            if missing.is_empty() {
+                "none".to_string()
+            } else {
+                missing.join(", ")
+            }
+        ));
+    }
+    report.push(format!("Status: {}", crate::ipc::status()));
+
+    report.push(String::new());
+    report.push("Bindings:".to_string());
+    if let Some(table) = crate::input::HOTKEYS.get() {
+        for hotkey in table.load().iter() {
+            report.push(format!(
+                "  {} => {}",
+                hotkey.trigger.describe(),
+                hotkey.action_name
+            ));
+        }
+    }
+    report.join("\n") + "\n"
+}
+
+/// The recent actions, oldest first, with how long ago they finished
+fn action_history() -> String {
+    let actions = crate::broadcast::recent_actions();
+    if actions.is_empty() {
+        return "No actions run since the daemon started\n".to_string();
+    }
+    actions
+        .iter()
+        .map(|action| {
+            format!(
+                "{:>6}s ago  {}{}\n",
+                action.finished.elapsed().as_secs(),
+                action.action,
+                action
+                    .error
+                    .as_ref()
+                    .map(|e| format!(" - failed: {}", e))
+                    .unwrap_or_default()
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let config = r#"
+[server]
+token = "hunter2"
+port = 8765
+
+[[hotkey]]
+keys = ["cmd", "f1"]
+action = "os.rapid_pw"
+params = { account = "me", password = 'p,w}', Secret_Key = 42 }
+"#;
+        let redacted = redact(config);
+        assert!(redacted.contains(r#"token = "<redacted>""#));
+        assert!(redacted.contains(r#"password = "<redacted>","#));
+        assert!(redacted.contains(r#"Secret_Key = "<redacted>" }"#));
+        assert!(redacted.contains("port = 8765"));
+        assert!(redacted.contains(r#"account = "me""#));
+        assert!(!redacted.contains("hunter2"));
+    }
+}
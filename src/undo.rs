@@ -0,0 +1,165 @@
+//! Undo tracking for macros
+//!
+//! Every hotkey action is recorded, so built-in multi-step actions such as
+//! `pt.edit_crossfade` count as macros too. A macro hotkey (`actions =
+//! [...]`) records its steps under their names, taking over from the
+//! hotkey's recording.
+//!
+//! While a macro runs, every Pro Tools operation that lands on its undo
+//! queue (PTSL edits, edit menu clicks) is counted. `os.undo_last_macro`
+//! then undoes exactly that many levels, so backing out of a six step macro
+//! doesn't take guessing at Cmd+Z.
+//!
+//! The count lives on the macro's thread and is carried over to the threads
+//! its Pro Tools commands run on, the same way a hotkey's `silent` flag is.
+//! Keystrokes aren't counted: there's no telling whether Cmd+V changed
+//! anything. Edits made by hand after the macro are undone first, as Pro
+//! Tools' undo queue is a stack.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Operations counted for the running macro
+pub type Counter = Arc<AtomicUsize>;
+
+thread_local! {
+    /// Set while a macro (or a command it started) runs on this thread
+    static RECORDING: RefCell<Option<Counter>> = const { RefCell::new(None) };
+}
+
+/// The last macro that changed anything
+static LAST: Mutex<Option<LastMacro>> = Mutex::new(None);
+
+/// A finished macro's undoable operations
+pub struct LastMacro {
+    /// Its steps, for notifications
+    pub name: String,
+    /// Still counts commands that outlived the macro (timed out, then finished)
+    operations: Counter,
+}
+
+impl LastMacro {
+    pub fn operations(&self) -> usize {
+        self.operations.load(Ordering::SeqCst)
+    }
+}
+
+/// Count this thread's operations until the guard goes out of scope
+pub fn record(name: &str) -> Recording {
+    let counter = Counter::default();
+    Recording {
+        name: name.to_string(),
+        previous: RECORDING.with(|r| r.replace(Some(counter.clone()))),
+        counter,
+    }
+}
+
+/// Remembers the macro as the last one on drop, if it did anything
+pub struct Recording {
+    name: String,
+    counter: Counter,
+    previous: Option<Counter>,
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        RECORDING.with(|r| r.replace(self.previous.take()));
+        let operations = self.counter.load(Ordering::SeqCst);
+        // A macro that changed nothing leaves the last one on top of the queue
+        if operations > 0 {
+            log::info!("Macro made {} undoable operation(s)", operations);
+            *LAST.lock().unwrap() = Some(LastMacro {
+                name: std::mem::take(&mut self.name),
+                operations: self.counter.clone(),
+            });
+        }
+    }
+}
+
+/// This thread's counter, to carry over to a thread it starts
+pub fn current() -> Option<Counter> {
+    RECORDING.with(|r| r.borrow().clone())
+}
+
+/// Count this thread's operations on `counter` until the guard is dropped
+pub fn attach(counter: Option<Counter>) -> Attached {
+    Attached {
+        previous: RECORDING.with(|r| r.replace(counter)),
+    }
+}
+
+/// Restores the thread's previous counter on drop
+pub struct Attached {
+    previous: Option<Counter>,
+}
+
+impl Drop for Attached {
+    fn drop(&mut self) {
+        RECORDING.with(|r| r.replace(self.previous.take()));
+    }
+}
+
+/// Note an operation that went onto Pro Tools' undo queue
+pub fn operation(what: &str) {
+    RECORDING.with(|r| {
+        if let Some(counter) = r.borrow().as_ref() {
+            counter.fetch_add(1, Ordering::SeqCst);
+            log::debug!("Undoable operation: {}", what);
+        }
+    });
+}
+
+/// Take the last macro, so undoing it twice doesn't undo what came before
+pub fn take_last() -> Option<LastMacro> {
+    LAST.lock().unwrap().take()
+}
+
+/// True for Pro Tools menu items whose effect goes onto the undo queue
+pub fn is_undoable_menu(menu_path: &[&str]) -> bool {
+    match menu_path {
+        [
+            "Edit",
+            "Undo"
+            | "Redo"
+            | "Copy"
+            | "Select All"
+            | "Play Edit Selection"
+            | "Selection"
+            | "Undo History",
+            ..,
+        ] => false,
+        ["Edit" | "Track" | "Clip" | "Event", _, ..] => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_carry_over_to_command_threads() {
+        {
+            let _recording = record("pt.edit_crossfade");
+            operation("Cut");
+            let counter = current();
+            std::thread::spawn(move || {
+                let _attached = attach(counter);
+                operation("Paste");
+            })
+            .join()
+            .unwrap();
+        }
+        // Not recording any more
+        operation("Clear");
+        let last = take_last().unwrap();
+        assert_eq!(last.name, "pt.edit_crossfade");
+        assert_eq!(last.operations(), 2);
+        assert!(take_last().is_none());
+
+        assert!(is_undoable_menu(&["Edit", "Consolidate Clip"]));
+        assert!(!is_undoable_menu(&["Edit", "Undo"]));
+        assert!(!is_undoable_menu(&["View", "Rulers", "Markers"]));
+    }
+}
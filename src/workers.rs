@@ -0,0 +1,84 @@
+//! Worker pool for hotkey actions
+//!
+//! Actions run here instead of on the event tap (or MIDI) callback thread, so
+//! a slow `shell_script` or accessibility search can't freeze keyboard input.
+//! The triggering event is consumed immediately, the same way the Pro Tools
+//! async wrapper hands its work off to the tokio runtime.
+//!
+//! A few actions still run inline on the callback thread (`INLINE_ACTIONS`):
+//! - `reload_config` re-registers Carbon hotkeys, which must happen on the
+//!   main thread
+//! - `toggle_review_mode`, `enable_tag`, `disable_tag`, `set_layer`,
+//!   `toggle_layer` and `toggle_suspend` change which hotkeys fire, so the
+//!   very next keystroke has to see the new state
+//! - `rapid_pw` and `test_pw` can open AppKit or keychain dialogs (modal, so
+//!   main thread only) and recreate the event tap afterwards
+
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Number of worker threads
+const WORKER_THREADS: usize = 4;
+
+/// Actions (registry names, without namespace) that run on the callback thread
+pub const INLINE_ACTIONS: &[&str] = &[
+    "reload_config",
+    "toggle_review_mode",
+    "enable_tag",
+    "disable_tag",
+    "set_layer",
+    "toggle_layer",
+    "toggle_suspend",
+    "rapid_pw",
+    "test_pw",
+];
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+static QUEUE: OnceLock<Sender<Job>> = OnceLock::new();
+
+/// True if the action must run inline rather than on the pool
+///
+/// Accepts both "reload_config" and namespaced "os.reload_config".
+pub fn is_inline(action_name: &str) -> bool {
+    let name = action_name
+        .split_once('.')
+        .map_or(action_name, |(_, name)| name);
+    INLINE_ACTIONS.contains(&name)
+}
+
+/// Queue a job on the worker pool (starting the pool on first use)
+pub fn submit<F>(job: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    let queue = QUEUE.get_or_init(start_pool);
+    if queue.send(Box::new(job)).is_err() {
+        log::error!("Worker pool is gone - dropping job");
+    }
+}
+
+fn start_pool() -> Sender<Job> {
+    let (sender, receiver) = channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    for i in 0..WORKER_THREADS {
+        let receiver = Arc::clone(&receiver);
+        std::thread::Builder::new()
+            .name(format!("pthkd-worker-{}", i))
+            .spawn(move || worker_loop(&receiver))
+            .expect("Failed to spawn worker thread");
+    }
+    log::info!("Started {} action worker threads", WORKER_THREADS);
+    sender
+}
+
+fn worker_loop(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        // Hold the lock only while waiting, not while running the job
+        let job = receiver.lock().unwrap().recv();
+        match job {
+            Ok(job) => job(),
+            Err(_) => break, // Sender dropped
+        }
+    }
+}
@@ -286,6 +286,20 @@ class MenuOps {
         return false
     }
 
+    /// Check if a menu item shows a checkmark (AXMenuItemMarkChar is set)
+    static func menuItemChecked(appName: String, menuPath: [String]) -> Bool {
+        guard let item = try? navigateToMenuItem(appName: appName, menuPath: menuPath) else {
+            return false
+        }
+
+        var markRef: AnyObject?
+        guard AXUIElementCopyAttributeValue(item, kAXMenuItemMarkCharAttribute as CFString, &markRef) == .success,
+              let mark = markRef as? String else {
+            return false
+        }
+        return !mark.isEmpty
+    }
+
     /// Helper to navigate to a menu item and return the element
     /// Used by menuItemExists to avoid code duplication
     private static func navigateToMenuItem(appName: String, menuPath: [String]) throws -> AXUIElement {
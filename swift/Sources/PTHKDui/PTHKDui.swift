@@ -89,6 +89,26 @@ public func menuItemEnabled(
     return MenuOps.menuItemEnabled(appName: app, menuPath: path)
 }
 
+// C ABI: Check if a menu item is checked
+@_cdecl("pthkd_menu_item_checked")
+public func menuItemChecked(
+    appName: UnsafePointer<CChar>?,
+    menuPath: UnsafePointer<UnsafePointer<CChar>?>,
+    menuPathCount: Int32
+) -> Bool {
+    let app = appName != nil ? String(cString: appName!) : ""
+
+    // Convert menu path array to Swift [String]
+    var path: [String] = []
+    for i in 0..<Int(menuPathCount) {
+        if let itemPtr = menuPath[i] {
+            path.append(String(cString: itemPtr))
+        }
+    }
+
+    return MenuOps.menuItemChecked(appName: app, menuPath: path)
+}
+
 // C ABI: Send keystroke to application
 // modifiers: bit flags (shift=1, control=2, option=4, command=8)
 @_cdecl("pthkd_send_keystroke")
@@ -320,6 +340,71 @@ public func getPopupMenuItems(
     }
 }
 
+// C ABI: Select an item from a popup menu
+@_cdecl("pthkd_select_popup_menu_item")
+public func selectPopupMenuItem(
+    appName: UnsafePointer<CChar>?,
+    windowName: UnsafePointer<CChar>?,
+    popupName: UnsafePointer<CChar>,
+    itemName: UnsafePointer<CChar>
+) -> Bool {
+    do {
+        let app = appName != nil ? String(cString: appName!) : ""
+        let window = windowName != nil ? String(cString: windowName!) : ""
+        let popup = String(cString: popupName)
+        let item = String(cString: itemName)
+
+        try WindowOps.selectPopupMenuItem(appName: app, windowName: window, popupName: popup, itemName: item)
+        return true
+    } catch {
+        NSLog("pthkd_select_popup_menu_item error: \(error.localizedDescription)")
+        return false
+    }
+}
+
+// C ABI: Set the value of a text field
+@_cdecl("pthkd_set_text_field_value")
+public func setTextFieldValue(
+    appName: UnsafePointer<CChar>?,
+    windowName: UnsafePointer<CChar>?,
+    fieldName: UnsafePointer<CChar>,
+    value: UnsafePointer<CChar>
+) -> Bool {
+    do {
+        let app = appName != nil ? String(cString: appName!) : ""
+        let window = windowName != nil ? String(cString: windowName!) : ""
+        let field = String(cString: fieldName)
+        let text = String(cString: value)
+
+        try WindowOps.setTextFieldValue(appName: app, windowName: window, fieldName: field, value: text)
+        return true
+    } catch {
+        NSLog("pthkd_set_text_field_value error: \(error.localizedDescription)")
+        return false
+    }
+}
+
+// C ABI: Get table rows (returns JSON array of arrays)
+@_cdecl("pthkd_get_table_rows")
+public func getTableRows(
+    appName: UnsafePointer<CChar>?,
+    windowName: UnsafePointer<CChar>?,
+    selectedOnly: Bool
+) -> UnsafePointer<CChar>? {
+    do {
+        let app = appName != nil ? String(cString: appName!) : ""
+        let window = windowName != nil ? String(cString: windowName!) : ""
+
+        let rows = try WindowOps.getTableRows(appName: app, windowName: window, selectedOnly: selectedOnly)
+        let jsonData = try JSONSerialization.data(withJSONObject: rows)
+        let json = String(data: jsonData, encoding: .utf8) ?? "[]"
+        return UnsafePointer(strdup(json))  // Rust must free this
+    } catch {
+        let errorJSON = "{\"error\": \"\(error.localizedDescription)\"}"
+        return UnsafePointer(strdup(errorJSON))
+    }
+}
+
 // C ABI: Get all text from a window (returns JSON array)
 @_cdecl("pthkd_get_window_text")
 public func getWindowText(
@@ -197,6 +197,90 @@ class WindowOps {
         return textStrings
     }
 
+    /// Select an item from a popup menu
+    /// - Parameters:
+    ///   - appName: Name of the app (empty string for frontmost app)
+    ///   - windowName: Name of the window (empty string for frontmost window)
+    ///   - popupName: Name of the popup button
+    ///   - itemName: Menu item to select (soft match)
+    static func selectPopupMenuItem(appName: String, windowName: String, popupName: String, itemName: String) throws {
+        let app = try getApp(appName: appName)
+        let window = try getWindow(app: app, windowName: windowName)
+
+        guard let popup = findElement(in: window, role: kAXPopUpButtonRole as String, name: popupName) else {
+            throw WindowError.buttonNotFound(popupName)
+        }
+
+        // Open the menu, then look for the item inside the popup
+        _ = AXUIElementPerformAction(popup, kAXPressAction as CFString)
+        Thread.sleep(forTimeInterval: 0.05)  // 50ms
+
+        guard let item = findElement(in: popup, role: kAXMenuItemRole as String, name: itemName) else {
+            // Dismiss the open menu before bailing
+            _ = AXUIElementPerformAction(popup, kAXCancelAction as CFString)
+            throw WindowError.buttonNotFound(itemName)
+        }
+
+        guard AXUIElementPerformAction(item, kAXPressAction as CFString) == .success else {
+            throw WindowError.clickFailed
+        }
+    }
+
+    /// Set the value of a text field and give it focus
+    /// - Parameters:
+    ///   - appName: Name of the app (empty string for frontmost app)
+    ///   - windowName: Name of the window (empty string for frontmost window)
+    ///   - fieldName: Title, description or placeholder of the field (empty string for first field)
+    ///   - value: Text to put in the field
+    static func setTextFieldValue(appName: String, windowName: String, fieldName: String, value: String) throws {
+        let app = try getApp(appName: appName)
+        let window = try getWindow(app: app, windowName: windowName)
+
+        guard let field = findTextField(in: window, name: fieldName) else {
+            throw WindowError.buttonNotFound(fieldName.isEmpty ? "<text field>" : fieldName)
+        }
+
+        _ = AXUIElementSetAttributeValue(field, kAXFocusedAttribute as CFString, kCFBooleanTrue)
+        guard AXUIElementSetAttributeValue(field, kAXValueAttribute as CFString, value as CFString) == .success else {
+            throw WindowError.clickFailed
+        }
+    }
+
+    /// Get the cell text of each row in the first table/outline in a window
+    /// - Parameters:
+    ///   - appName: Name of the app (empty string for frontmost app)
+    ///   - windowName: Name of the window (empty string for frontmost window)
+    ///   - selectedOnly: Only return selected rows
+    /// - Returns: One array of cell strings per row
+    static func getTableRows(appName: String, windowName: String, selectedOnly: Bool) throws -> [[String]] {
+        let app = try getApp(appName: appName)
+        let window = try getWindow(app: app, windowName: windowName)
+
+        guard let table = findElementByRole(in: window, role: kAXOutlineRole as String)
+            ?? findElementByRole(in: window, role: kAXTableRole as String) else {
+            throw WindowError.buttonNotFound("<table>")
+        }
+
+        let attribute = selectedOnly ? kAXSelectedRowsAttribute : kAXRowsAttribute
+        var rowsRef: AnyObject?
+        guard AXUIElementCopyAttributeValue(table, attribute as CFString, &rowsRef) == .success,
+              rowsRef != nil else {
+            return []
+        }
+
+        let rowsCF = rowsRef as! CFArray
+        let count = min(CFArrayGetCount(rowsCF), 1000)
+        var rows: [[String]] = []
+        for i in 0..<count {
+            guard let rowPtr = CFArrayGetValueAtIndex(rowsCF, i) else { continue }
+            let row = unsafeBitCast(rowPtr, to: AXUIElement.self)
+            var cells: [String] = []
+            collectValues(from: row, into: &cells)
+            rows.append(cells)
+        }
+        return rows
+    }
+
     // MARK: - Helper Methods
 
     private static func getApp(appName: String) throws -> NSRunningApplication {
@@ -291,6 +375,10 @@ class WindowOps {
         if let button = findElement(in: window, role: kAXButtonRole as String, name: buttonName) {
             return button
         }
+        // Radio buttons (fade shapes, dialog options) press the same way
+        if let radio = findElement(in: window, role: kAXRadioButtonRole as String, name: buttonName) {
+            return radio
+        }
         throw WindowError.buttonNotFound(buttonName)
     }
 
@@ -401,6 +489,109 @@ class WindowOps {
         }
     }
 
+    /// Recursively find the first element with a role, regardless of title
+    private static func findElementByRole(in element: AXUIElement, role: String, depth: Int = 0) -> AXUIElement? {
+        guard depth < 50 else { return nil }
+
+        var roleRef: AnyObject?
+        if AXUIElementCopyAttributeValue(element, kAXRoleAttribute as CFString, &roleRef) == .success,
+           let elementRole = roleRef as? String,
+           elementRole == role {
+            return element
+        }
+
+        var childrenRef: AnyObject?
+        guard AXUIElementCopyAttributeValue(element, kAXChildrenAttribute as CFString, &childrenRef) == .success,
+              childrenRef != nil else {
+            return nil
+        }
+
+        let childrenCF = childrenRef as! CFArray
+        let count = CFArrayGetCount(childrenCF)
+        guard count > 0 && count < 10000 else { return nil } // Sanity check
+
+        for i in 0..<count {
+            guard let childPtr = CFArrayGetValueAtIndex(childrenCF, i) else { continue }
+            let child = unsafeBitCast(childPtr, to: AXUIElement.self)
+            if let found = findElementByRole(in: child, role: role, depth: depth + 1) {
+                return found
+            }
+        }
+
+        return nil
+    }
+
+    /// Recursively find a text field by title, description or placeholder
+    private static func findTextField(in element: AXUIElement, name: String, depth: Int = 0) -> AXUIElement? {
+        guard depth < 50 else { return nil }
+
+        var roleRef: AnyObject?
+        if AXUIElementCopyAttributeValue(element, kAXRoleAttribute as CFString, &roleRef) == .success,
+           let role = roleRef as? String,
+           role == kAXTextFieldRole as String {
+            if name.isEmpty {
+                return element
+            }
+            for attribute in [kAXTitleAttribute, kAXDescriptionAttribute, kAXPlaceholderValueAttribute] {
+                var labelRef: AnyObject?
+                if AXUIElementCopyAttributeValue(element, attribute as CFString, &labelRef) == .success,
+                   let label = labelRef as? String,
+                   !label.isEmpty,
+                   softMatch(label, name) {
+                    return element
+                }
+            }
+        }
+
+        var childrenRef: AnyObject?
+        guard AXUIElementCopyAttributeValue(element, kAXChildrenAttribute as CFString, &childrenRef) == .success,
+              childrenRef != nil else {
+            return nil
+        }
+
+        let childrenCF = childrenRef as! CFArray
+        let count = CFArrayGetCount(childrenCF)
+        guard count > 0 && count < 10000 else { return nil } // Sanity check
+
+        for i in 0..<count {
+            guard let childPtr = CFArrayGetValueAtIndex(childrenCF, i) else { continue }
+            let child = unsafeBitCast(childPtr, to: AXUIElement.self)
+            if let found = findTextField(in: child, name: name, depth: depth + 1) {
+                return found
+            }
+        }
+
+        return nil
+    }
+
+    /// Recursively collect non-empty string values (used for table cells)
+    private static func collectValues(from element: AXUIElement, into results: inout [String], depth: Int = 0) {
+        guard depth < 10 && results.count < 100 else { return }
+
+        var valueRef: AnyObject?
+        if AXUIElementCopyAttributeValue(element, kAXValueAttribute as CFString, &valueRef) == .success,
+           let text = valueRef as? String,
+           !text.isEmpty {
+            results.append(text)
+        }
+
+        var childrenRef: AnyObject?
+        guard AXUIElementCopyAttributeValue(element, kAXChildrenAttribute as CFString, &childrenRef) == .success,
+              childrenRef != nil else {
+            return
+        }
+
+        let childrenCF = childrenRef as! CFArray
+        let count = CFArrayGetCount(childrenCF)
+        guard count > 0 && count < 10000 else { return } // Sanity check
+
+        for i in 0..<count {
+            guard let childPtr = CFArrayGetValueAtIndex(childrenCF, i) else { continue }
+            let child = unsafeBitCast(childPtr, to: AXUIElement.self)
+            collectValues(from: child, into: &results, depth: depth + 1)
+        }
+    }
+
     /// Soft match (case-insensitive, whitespace-insensitive, partial matching)
     private static func softMatch(_ haystack: String, _ needle: String) -> Bool {
         let normalizedHaystack = haystack.lowercased().filter { !$0.isWhitespace }